@@ -0,0 +1,160 @@
+//! Benchmarks for the core classification paths
+//!
+//! Run with `cargo bench`. To check a change for regressions, save a
+//! baseline before it and compare after:
+//!
+//! ```text
+//! cargo bench -- --save-baseline before
+//! cargo bench -- --baseline before
+//! ```
+//!
+//! To measure your own system, add it to `single_point` alongside the
+//! reference payoffs below.
+
+use std::hint::black_box;
+
+use antifragile::aggregate::{Aggregation, BatchAnalysis, classify_all};
+use antifragile::monte_carlo::{MonteCarloConfig, monte_carlo};
+use antifragile::sweep::classify_grid;
+use antifragile::{Antifragile, TriadAnalysis, Verified};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+/// Convex floating-point payoff
+struct Quadratic;
+
+impl Antifragile for Quadratic {
+    type Stressor = f64;
+    type Payoff = f64;
+
+    fn payoff(&self, x: f64) -> f64 {
+        x * x
+    }
+}
+
+/// Linear integer payoff
+struct Linear;
+
+impl Antifragile for Linear {
+    type Stressor = i64;
+    type Payoff = i64;
+
+    fn payoff(&self, x: i64) -> i64 {
+        3 * x + 1
+    }
+}
+
+/// Concave payoff with a transcendental function, closer to a real model
+#[derive(Clone, Copy)]
+struct Saturating {
+    capacity: f64,
+}
+
+impl Antifragile for Saturating {
+    type Stressor = f64;
+    type Payoff = f64;
+
+    fn payoff(&self, load: f64) -> f64 {
+        self.capacity * (1.0 - (-load / self.capacity).exp())
+    }
+}
+
+/// The single-point tests on one system: `classify`, the tolerance variant,
+/// and `Verified::check`
+fn bench_system<T>(c: &mut Criterion, name: &str, system: &T, at: T::Stressor, delta: T::Stressor)
+where
+    T: Antifragile,
+    T::Payoff: core::ops::Sub<Output = T::Payoff> + Default + PartialOrd,
+{
+    let mut group = c.benchmark_group(name);
+    group.bench_function("classify", |b| {
+        b.iter(|| black_box(system).classify(black_box(at), black_box(delta)));
+    });
+    group.bench_function("classify_with_tolerance", |b| {
+        b.iter(|| {
+            black_box(system).classify_with_tolerance(
+                black_box(at),
+                black_box(delta),
+                T::Payoff::default(),
+            )
+        });
+    });
+    group.bench_function("verified_check", |b| {
+        b.iter(|| Verified::check(black_box(system), black_box(at), black_box(delta)));
+    });
+    group.finish();
+}
+
+fn single_point(c: &mut Criterion) {
+    bench_system(c, "quadratic_f64", &Quadratic, 10.0, 1.0);
+    bench_system(c, "linear_i64", &Linear, 10, 1);
+    bench_system(
+        c,
+        "saturating_f64",
+        &Saturating { capacity: 100.0 },
+        50.0,
+        5.0,
+    );
+}
+
+fn batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch");
+    for size in [100_u16, 10_000] {
+        let systems: Vec<Saturating> = (1..=size)
+            .map(|i| Saturating {
+                capacity: f64::from(i),
+            })
+            .collect();
+        group.bench_with_input(
+            BenchmarkId::new("classify_all", size),
+            &systems,
+            |b, systems| {
+                b.iter(|| classify_all(systems, 50.0, 5.0, Aggregation::WeakestLink));
+            },
+        );
+        let points: Vec<(f64, f64)> = (1..=size).map(|i| (f64::from(i), 1.0)).collect();
+        group.bench_with_input(
+            BenchmarkId::new("classify_batch", size),
+            &points,
+            |b, points| {
+                b.iter(|| Saturating { capacity: 100.0 }.classify_batch(points));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("classify_grid", size),
+            &size,
+            |b, &size| {
+                b.iter(|| {
+                    classify_grid(&Saturating { capacity: 100.0 }, 1.0, 1.0, usize::from(size))
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn monte_carlo_paths(c: &mut Criterion) {
+    let mut group = c.benchmark_group("monte_carlo");
+    for samples in [1_000, 100_000] {
+        let config = MonteCarloConfig {
+            samples,
+            ..MonteCarloConfig::default()
+        };
+        group.bench_with_input(
+            BenchmarkId::from_parameter(samples),
+            &config,
+            |b, config| {
+                b.iter(|| {
+                    monte_carlo(
+                        &Saturating { capacity: 100.0 },
+                        |noise| 50.0 + 5.0 * noise.normal(),
+                        config,
+                    )
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, single_point, batch, monte_carlo_paths);
+criterion_main!(benches);
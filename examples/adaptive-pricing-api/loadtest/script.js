@@ -5,10 +5,12 @@
  */
 
 import http from 'k6/http';
-import { sleep } from 'k6';
+import ws from 'k6/ws';
+import { check, sleep } from 'k6';
 import exec from 'k6/execution';
 
 const BASE_URL = __ENV.API_URL || 'http://api:3000';
+const WS_URL = BASE_URL.replace(/^http/, 'ws');
 
 export const options = {
   scenarios: {
@@ -33,6 +35,13 @@ export const options = {
       startTime: '130s',
       env: { PHASE: '3' },
     },
+    live_watcher: {
+      executor: 'per-vu-iterations',
+      vus: 1,
+      iterations: 1,
+      maxDuration: '200s',
+      exec: 'watch',
+    },
   },
 };
 
@@ -74,6 +83,30 @@ export default function () {
   sleep(0.05);
 }
 
+// Follow the live stream for the whole test and log each transition
+export function watch() {
+  let last = null;
+  let updates = 0;
+
+  const res = ws.connect(`${WS_URL}/antifragile/ws`, null, (socket) => {
+    socket.on('message', (message) => {
+      const update = JSON.parse(message);
+      updates += 1;
+      if (update.classification !== last) {
+        console.log(
+          `  [live] ${update.classification} (convexity ${update.convexity.toFixed(3)}, ` +
+            `hit rate ${(update.cache.hit_rate * 100).toFixed(0)}%)`
+        );
+        last = update.classification;
+      }
+    });
+    socket.setTimeout(() => socket.close(), 195000);
+  });
+
+  check(res, { 'live stream upgraded': (r) => r && r.status === 101 });
+  check(updates, { 'live stream sent updates': (n) => n > 0 });
+}
+
 export function handleSummary(data) {
   const res = http.get(`${BASE_URL}/antifragile/status`);
   let status = { classification: 'unknown', metrics: {}, analysis: {} };
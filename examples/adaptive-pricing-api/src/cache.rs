@@ -5,18 +5,47 @@
 //! and better overall performance.
 
 use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 
-use crate::pricing::{PriceQuery, PriceResult};
+use crate::pricing::{PriceQuery, PriceResult, calculate_price};
 
 /// A cached price entry with metadata
 #[derive(Debug, Clone)]
 struct CacheEntry {
     result: PriceResult,
     created_at: Instant,
+    last_access: Instant,
     hit_count: u64,
 }
 
+/// Which entry makes room when the cache is full of live entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EvictionPolicy {
+    /// Evict the least recently used entry
+    #[default]
+    Lru,
+    /// Evict the least frequently used entry, the least recently used among ties
+    Lfu,
+    /// Never evict a live entry; new entries are not cached until one expires
+    TtlOnly,
+}
+
+impl EvictionPolicy {
+    /// Every policy, in declaration order
+    pub const ALL: [Self; 3] = [Self::Lru, Self::Lfu, Self::TtlOnly];
+
+    /// Name used in requests and responses
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Lru => "lru",
+            Self::Lfu => "lfu",
+            Self::TtlOnly => "ttl-only",
+        }
+    }
+}
+
 /// Adaptive cache for pricing results
 ///
 /// This cache demonstrates antifragile behavior:
@@ -29,17 +58,19 @@ pub struct AdaptiveCache {
     entries: DashMap<PriceQuery, CacheEntry>,
     ttl: Duration,
     max_capacity: usize,
+    policy: EvictionPolicy,
 }
 
 impl AdaptiveCache {
     const DEFAULT_MAX_CAPACITY: usize = 10_000;
 
-    /// Create a new cache with default TTL of 5 minutes and 10k entry cap
+    /// Create a new LRU cache with default TTL of 5 minutes and 10k entry cap
     pub fn new() -> Self {
         Self {
             entries: DashMap::new(),
             ttl: Duration::from_secs(300),
             max_capacity: Self::DEFAULT_MAX_CAPACITY,
+            policy: EvictionPolicy::default(),
         }
     }
 
@@ -50,14 +81,27 @@ impl AdaptiveCache {
             entries: DashMap::new(),
             ttl,
             max_capacity,
+            policy: EvictionPolicy::default(),
         }
     }
 
+    /// Use `policy` to make room when the cache is full
+    pub fn with_policy(mut self, policy: EvictionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// The eviction policy in use
+    pub fn policy(&self) -> EvictionPolicy {
+        self.policy
+    }
+
     /// Get a cached result if it exists and hasn't expired
     pub fn get(&self, query: &PriceQuery) -> Option<PriceResult> {
         if let Some(mut entry) = self.entries.get_mut(query) {
             if entry.created_at.elapsed() < self.ttl {
                 entry.hit_count += 1;
+                entry.last_access = Instant::now();
                 return Some(entry.result.clone());
             } else {
                 // Entry expired, will be replaced
@@ -68,22 +112,45 @@ impl AdaptiveCache {
         None
     }
 
-    /// Insert a new entry into the cache, evicting stale or oldest entries if at capacity
+    /// Look up `query`, computing and caching its price on a miss
+    ///
+    /// Returns the price and whether it was served from the cache.
+    pub async fn get_or_compute(&self, query: PriceQuery) -> (PriceResult, bool) {
+        if let Some(cached) = self.get(&query) {
+            return (cached, true);
+        }
+        let result = calculate_price(&query).await;
+        self.insert(query, result.clone());
+        (result, false)
+    }
+
+    /// Insert a new entry into the cache, making room according to the policy if at capacity
+    ///
+    /// Stale entries are cleared first. Under [`EvictionPolicy::TtlOnly`] a
+    /// cache that is still full leaves `query` uncached.
     pub fn insert(&self, query: PriceQuery, result: PriceResult) {
-        if self.entries.len() >= self.max_capacity {
+        let is_new = !self.entries.contains_key(&query);
+        if is_new && self.entries.len() >= self.max_capacity {
             self.cleanup();
         }
 
-        // If still at capacity after cleanup, evict the oldest entry
-        if self.entries.len() >= self.max_capacity {
-            self.evict_oldest();
+        if is_new && self.entries.len() >= self.max_capacity {
+            match self.policy {
+                EvictionPolicy::Lru => self.evict_min_by_key(|entry| entry.last_access),
+                EvictionPolicy::Lfu => {
+                    self.evict_min_by_key(|entry| (entry.hit_count, entry.last_access))
+                }
+                EvictionPolicy::TtlOnly => return,
+            }
         }
 
+        let now = Instant::now();
         self.entries.insert(
             query,
             CacheEntry {
                 result,
-                created_at: Instant::now(),
+                created_at: now,
+                last_access: now,
                 hit_count: 0,
             },
         );
@@ -106,16 +173,16 @@ impl AdaptiveCache {
             .retain(|_, entry| entry.created_at.elapsed() < self.ttl);
     }
 
-    /// Evict the oldest entry by creation time
-    fn evict_oldest(&self) {
-        let oldest = self
+    /// Evict the entry with the smallest `key`
+    fn evict_min_by_key<K: Ord>(&self, key: impl Fn(&CacheEntry) -> K) {
+        let victim = self
             .entries
             .iter()
-            .min_by_key(|entry| entry.created_at)
+            .min_by_key(|entry| key(entry.value()))
             .map(|entry| entry.key().clone());
 
-        if let Some(key) = oldest {
-            self.entries.remove(&key);
+        if let Some(victim) = victim {
+            self.entries.remove(&victim);
         }
     }
 
@@ -232,4 +299,60 @@ mod tests {
         // Cache should never exceed max_capacity + 1 (insert happens after eviction)
         assert!(cache.stats().entries <= 4);
     }
+
+    fn product(i: usize) -> PriceQuery {
+        PriceQuery {
+            product_id: format!("product-{i}"),
+            quantity: 1,
+            options: vec![],
+        }
+    }
+
+    fn fill(policy: EvictionPolicy) -> AdaptiveCache {
+        let cache =
+            AdaptiveCache::with_ttl_and_capacity(Duration::from_secs(300), 2).with_policy(policy);
+        let result = PriceResult {
+            base_price: 10.0,
+            quantity_discount: 0.0,
+            options_cost: 0.0,
+            total_price: 10.0,
+        };
+        cache.insert(product(0), result.clone());
+        cache.insert(product(1), result.clone());
+        // product-0 is used twice, then product-1 once more recently
+        std::thread::sleep(Duration::from_millis(2));
+        cache.get(&product(0));
+        cache.get(&product(0));
+        std::thread::sleep(Duration::from_millis(2));
+        cache.get(&product(1));
+        cache.insert(product(2), result);
+        cache
+    }
+
+    #[test]
+    fn test_eviction_policies() {
+        let lru = fill(EvictionPolicy::Lru);
+        assert!(lru.get(&product(0)).is_none());
+        assert!(lru.get(&product(1)).is_some());
+        assert!(lru.get(&product(2)).is_some());
+
+        let lfu = fill(EvictionPolicy::Lfu);
+        assert!(lfu.get(&product(0)).is_some());
+        assert!(lfu.get(&product(1)).is_none());
+        assert!(lfu.get(&product(2)).is_some());
+
+        let ttl_only = fill(EvictionPolicy::TtlOnly);
+        assert!(ttl_only.get(&product(0)).is_some());
+        assert!(ttl_only.get(&product(1)).is_some());
+        assert!(ttl_only.get(&product(2)).is_none());
+        assert_eq!(ttl_only.stats().entries, 2);
+    }
+
+    #[test]
+    fn test_policy_names_match_serde() {
+        for policy in EvictionPolicy::ALL {
+            let json = serde_json::to_string(&policy).unwrap();
+            assert_eq!(json, format!("\"{}\"", policy.name()));
+        }
+    }
 }
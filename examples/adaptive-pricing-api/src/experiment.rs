@@ -0,0 +1,522 @@
+//! Self-experiment: a built-in load generator and its analysis report
+//!
+//! `POST /experiment/run` ramps synthetic traffic through a list of
+//! concurrency levels against a fresh cache, records throughput, latency, and
+//! hit rate at each level, and returns an `AnalysisReport` built by the
+//! library from those measurements. Each run starts from a cold cache and
+//! issues the same workload, so it does not depend on what live traffic the
+//! service has already seen.
+//!
+//! `POST /experiment/compare` runs the same ramp once per cache eviction
+//! policy, on a cache small enough that eviction matters, and ranks the
+//! policies by the classification and fitted power-law exponent of their
+//! throughput curves.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use antifragile::attribution::{AttributionConfig, curvature_attribution};
+use antifragile::empirical::EmpiricalSystem;
+use antifragile::fit::{Family, fit_family};
+use antifragile::report::{AnalysisReport, ReportEntry, ReportSection};
+use antifragile::{Triad, TriadAnalysis};
+use axum::Json;
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{AdaptiveCache, EvictionPolicy};
+use crate::pricing::PriceQuery;
+
+/// Request body for `/experiment/run`; every field is optional
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExperimentRequest {
+    /// Concurrent synthetic clients at each step of the ramp, increasing
+    pub levels: Vec<u32>,
+    /// Requests issued at each level, shared among its clients
+    pub requests_per_level: u32,
+    /// Distinct products the synthetic clients ask about
+    pub products: u32,
+}
+
+impl Default for ExperimentRequest {
+    fn default() -> Self {
+        Self {
+            levels: vec![1, 2, 4, 8, 16],
+            requests_per_level: 200,
+            products: 25,
+        }
+    }
+}
+
+impl ExperimentRequest {
+    /// Reject ramps that cannot be classified or would overload the service
+    fn validate(&self) -> Result<(), StatusCode> {
+        let levels_ok = (3..=20).contains(&self.levels.len())
+            && self.levels.iter().all(|&level| (1..=256).contains(&level))
+            && self.levels.windows(2).all(|w| w[0] < w[1]);
+        if !levels_ok || !(1..=10_000).contains(&self.requests_per_level) || self.products == 0 {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        Ok(())
+    }
+}
+
+/// Measurements at one step of the ramp
+#[derive(Debug, Clone, Serialize)]
+pub struct LevelResult {
+    pub level: u32,
+    pub requests: u32,
+    pub throughput: f64,
+    pub mean_latency_ms: f64,
+    pub hit_rate: f64,
+}
+
+/// Response body for `/experiment/run`
+#[derive(Debug, Serialize)]
+pub struct ExperimentResponse {
+    pub levels: Vec<LevelResult>,
+    pub report: AnalysisReport,
+}
+
+/// Run the ramp in the background and report on it
+pub async fn run_experiment(
+    Json(request): Json<ExperimentRequest>,
+) -> Result<Json<ExperimentResponse>, StatusCode> {
+    request.validate()?;
+    let levels = tokio::spawn(generate_load(request))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let report = analyze(&levels);
+    Ok(Json(ExperimentResponse { levels, report }))
+}
+
+/// Drive every level of the ramp through a fresh cache, in order
+///
+/// Request `k` of a level asks for product `k % products`; client `c` of `n`
+/// issues requests `c, c + n, c + 2n, …`, so the workload is identical from
+/// run to run.
+pub async fn generate_load(request: ExperimentRequest) -> Vec<LevelResult> {
+    generate_load_with(Arc::new(AdaptiveCache::new()), &request).await
+}
+
+/// Drive every level of the ramp through `cache`, in order
+async fn generate_load_with(
+    cache: Arc<AdaptiveCache>,
+    request: &ExperimentRequest,
+) -> Vec<LevelResult> {
+    let mut results = Vec::with_capacity(request.levels.len());
+
+    for &level in &request.levels {
+        let start = Instant::now();
+        let clients: Vec<_> = (0..level)
+            .map(|client| {
+                let cache = Arc::clone(&cache);
+                let (total, products) = (request.requests_per_level, request.products);
+                tokio::spawn(async move {
+                    let (mut hits, mut latency) = (0_u32, Duration::ZERO);
+                    for k in (client..total).step_by(level as usize) {
+                        let query = PriceQuery {
+                            product_id: format!("experiment-{}", k % products),
+                            quantity: 1,
+                            options: Vec::new(),
+                        };
+                        let issued = Instant::now();
+                        let (_, hit) = cache.get_or_compute(query).await;
+                        latency += issued.elapsed();
+                        hits += u32::from(hit);
+                    }
+                    (hits, latency)
+                })
+            })
+            .collect();
+
+        let (mut hits, mut latency) = (0_u32, Duration::ZERO);
+        for client in clients {
+            if let Ok((client_hits, client_latency)) = client.await {
+                hits += client_hits;
+                latency += client_latency;
+            }
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+        let requests = request.requests_per_level;
+
+        results.push(LevelResult {
+            level,
+            requests,
+            throughput: requests as f64 / elapsed,
+            mean_latency_ms: latency.as_secs_f64() * 1000.0 / requests as f64,
+            hit_rate: hits as f64 / requests as f64,
+        });
+    }
+    results
+}
+
+/// Build the library report from the ramp's measurements
+///
+/// Throughput and negated latency are each turned into an `EmpiricalSystem`
+/// over the levels and classified at every interior level, perturbed by the
+/// distance to its nearest neighbour. A curvature attribution shows which
+/// part of the ramp the throughput curvature comes from.
+pub fn analyze(levels: &[LevelResult]) -> AnalysisReport {
+    let mut report = AnalysisReport::new("self-experiment");
+
+    let throughput = EmpiricalSystem::new(levels.iter().map(|l| (l.level as f64, l.throughput)));
+    let latency = EmpiricalSystem::new(levels.iter().map(|l| (l.level as f64, -l.mean_latency_ms)));
+    let (Ok(throughput), Ok(latency)) = (throughput, latency) else {
+        return report;
+    };
+
+    let mut throughput_section = ReportSection::new("throughput");
+    let mut latency_section = ReportSection::new("latency");
+    for window in levels.windows(3) {
+        let (below, at, above) = (&window[0], &window[1], &window[2]);
+        let x = at.level as f64;
+        let delta = (x - below.level as f64).min(above.level as f64 - x);
+        let label = format!("{} clients", at.level);
+
+        throughput_section = throughput_section.with_entry(
+            ReportEntry::new(label.clone(), throughput.classify(x, delta))
+                .with_score(at.throughput),
+        );
+        latency_section = latency_section.with_entry(
+            ReportEntry::new(label, latency.classify(x, delta)).with_score(-at.mean_latency_ms),
+        );
+    }
+    report.push_section(throughput_section);
+    report.push_section(latency_section);
+
+    let (low, high) = throughput.range();
+    let config = AttributionConfig {
+        bands: levels.len() - 1,
+        ..AttributionConfig::default()
+    };
+    report.push_section(
+        curvature_attribution(&throughput, low..=high, &config).section("throughput curvature"),
+    );
+    report
+}
+
+/// Request body for `/experiment/compare`; every field is optional
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CompareRequest {
+    /// Concurrent synthetic clients at each step of the ramp, increasing
+    pub levels: Vec<u32>,
+    /// Requests issued at each level, shared among its clients
+    pub requests_per_level: u32,
+    /// Distinct products the synthetic clients ask about
+    pub products: u32,
+    /// Entries each policy's cache may hold
+    pub capacity: usize,
+    /// Policies to compare, each at most once
+    pub policies: Vec<EvictionPolicy>,
+}
+
+impl Default for CompareRequest {
+    fn default() -> Self {
+        let ramp = ExperimentRequest::default();
+        Self {
+            levels: ramp.levels,
+            requests_per_level: ramp.requests_per_level,
+            products: ramp.products,
+            capacity: 10,
+            policies: EvictionPolicy::ALL.to_vec(),
+        }
+    }
+}
+
+impl CompareRequest {
+    /// The ramp each policy is driven through
+    fn ramp(&self) -> ExperimentRequest {
+        ExperimentRequest {
+            levels: self.levels.clone(),
+            requests_per_level: self.requests_per_level,
+            products: self.products,
+        }
+    }
+
+    /// Reject invalid ramps, empty caches, and missing or repeated policies
+    fn validate(&self) -> Result<(), StatusCode> {
+        self.ramp().validate()?;
+        let distinct = self
+            .policies
+            .iter()
+            .enumerate()
+            .all(|(i, policy)| !self.policies[..i].contains(policy));
+        if self.capacity == 0 || self.policies.is_empty() || !distinct {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        Ok(())
+    }
+}
+
+/// How one eviction policy fared over the ramp
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyResult {
+    pub policy: EvictionPolicy,
+    /// Position in the ranking, starting at 1 for the best policy
+    pub rank: usize,
+    /// Classification of throughput at the middle level of the ramp
+    pub classification: Triad,
+    /// Exponent of the power law fitted to throughput against clients, if
+    /// one could be fitted
+    pub exponent: Option<f64>,
+    /// Hits over requests across the whole ramp
+    pub hit_rate: f64,
+    pub levels: Vec<LevelResult>,
+}
+
+/// Response body for `/experiment/compare`
+#[derive(Debug, Serialize)]
+pub struct CompareResponse {
+    /// Policies, best first
+    pub policies: Vec<PolicyResult>,
+    pub report: AnalysisReport,
+}
+
+/// Run the ramp once per policy and rank the policies
+pub async fn compare_policies(
+    Json(request): Json<CompareRequest>,
+) -> Result<Json<CompareResponse>, StatusCode> {
+    request.validate()?;
+    let mut runs = Vec::with_capacity(request.policies.len());
+    for &policy in &request.policies {
+        let cache = Arc::new(
+            AdaptiveCache::with_ttl_and_capacity(Duration::from_secs(300), request.capacity)
+                .with_policy(policy),
+        );
+        let ramp = request.ramp();
+        let levels = tokio::spawn(async move { generate_load_with(cache, &ramp).await })
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        runs.push((policy, levels));
+    }
+    Ok(Json(rank_policies(runs)))
+}
+
+/// Classify each policy's throughput curve and rank the policies
+///
+/// Each run is classified at its middle level, perturbed by the distance to
+/// the nearest neighbouring level, and its throughput is fitted with the
+/// library's power-law family. Policies are ranked by classification, then
+/// by exponent; the report carries one entry per policy, scored by its
+/// exponent and explained in words.
+pub fn rank_policies(runs: Vec<(EvictionPolicy, Vec<LevelResult>)>) -> CompareResponse {
+    let mut results: Vec<(PolicyResult, Option<String>)> = runs
+        .into_iter()
+        .map(|(policy, levels)| {
+            let (classification, rationale) = classify_middle(&levels);
+            let requests: u32 = levels.iter().map(|l| l.requests).sum();
+            let hits: f64 = levels.iter().map(|l| l.hit_rate * l.requests as f64).sum();
+            let result = PolicyResult {
+                policy,
+                rank: 0,
+                classification,
+                exponent: power_law_exponent(&levels),
+                hit_rate: hits / requests.max(1) as f64,
+                levels,
+            };
+            (result, rationale)
+        })
+        .collect();
+    let exponent = |r: &PolicyResult| r.exponent.unwrap_or(f64::NEG_INFINITY);
+    results.sort_by(|(a, _), (b, _)| {
+        b.classification
+            .cmp(&a.classification)
+            .then_with(|| exponent(b).total_cmp(&exponent(a)))
+    });
+
+    let mut section = ReportSection::new("eviction policies");
+    let mut policies = Vec::with_capacity(results.len());
+    for (rank, (mut result, rationale)) in results.into_iter().enumerate() {
+        result.rank = rank + 1;
+        let mut entry = ReportEntry::new(result.policy.name(), result.classification);
+        if let Some(exponent) = result.exponent {
+            entry = entry.with_score(exponent);
+        }
+        if let Some(rationale) = rationale {
+            entry = entry.with_rationale(rationale);
+        }
+        section = section.with_entry(entry);
+        policies.push(result);
+    }
+
+    let mut report = AnalysisReport::new("eviction policy comparison");
+    report.push_section(section);
+    CompareResponse { policies, report }
+}
+
+/// Classify throughput at the middle level, with the reasoning behind it
+fn classify_middle(levels: &[LevelResult]) -> (Triad, Option<String>) {
+    let middle = levels.len() / 2;
+    let throughput = EmpiricalSystem::new(levels.iter().map(|l| (l.level as f64, l.throughput)));
+    let (Ok(throughput), Some([below, at, above])) = (
+        throughput,
+        levels.get(middle.saturating_sub(1)..=middle + 1),
+    ) else {
+        return (Triad::Robust, None);
+    };
+    let x = at.level as f64;
+    let delta = (x - below.level as f64).min(above.level as f64 - x);
+    let explanation = throughput.explain(x, delta);
+    (explanation.detail.triad, Some(format!("{explanation:.1}")))
+}
+
+/// Exponent of the power law fitted to throughput against clients
+fn power_law_exponent(levels: &[LevelResult]) -> Option<f64> {
+    let observations: Vec<(f64, f64)> = levels
+        .iter()
+        .map(|l| (l.level as f64, l.throughput))
+        .collect();
+    fit_family(&observations)
+        .ok()?
+        .fits()
+        .iter()
+        .find_map(|fit| match fit.family {
+            Family::PowerLaw { exponent, .. } => Some(exponent),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(level: u32, throughput: f64, mean_latency_ms: f64) -> LevelResult {
+        LevelResult {
+            level,
+            requests: 100,
+            throughput,
+            mean_latency_ms,
+            hit_rate: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_accelerating_throughput_is_antifragile() {
+        let levels = [
+            level(1, 100.0, 10.0),
+            level(2, 210.0, 9.0),
+            level(4, 480.0, 7.0),
+            level(8, 1200.0, 4.0),
+        ];
+        let report = analyze(&levels);
+
+        let throughput = report.section("throughput").unwrap();
+        assert_eq!(throughput.entries().len(), 2);
+        assert!(
+            throughput
+                .entries()
+                .iter()
+                .all(|e| e.classification == Triad::Antifragile)
+        );
+        assert_eq!(throughput.entries()[0].label, "2 clients");
+        assert_eq!(
+            report
+                .section("throughput curvature")
+                .unwrap()
+                .entries()
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(ExperimentRequest::default().validate().is_ok());
+        let too_short = ExperimentRequest {
+            levels: vec![1, 2],
+            ..ExperimentRequest::default()
+        };
+        assert_eq!(too_short.validate(), Err(StatusCode::BAD_REQUEST));
+        let decreasing = ExperimentRequest {
+            levels: vec![4, 2, 8],
+            ..ExperimentRequest::default()
+        };
+        assert_eq!(decreasing.validate(), Err(StatusCode::BAD_REQUEST));
+        let no_products = ExperimentRequest {
+            products: 0,
+            ..ExperimentRequest::default()
+        };
+        assert_eq!(no_products.validate(), Err(StatusCode::BAD_REQUEST));
+    }
+
+    #[tokio::test]
+    async fn test_generated_load_warms_the_cache() {
+        let levels = generate_load(ExperimentRequest {
+            levels: vec![1, 2, 4],
+            requests_per_level: 20,
+            products: 2,
+        })
+        .await;
+
+        assert_eq!(levels.len(), 3);
+        // Only the first two requests of the whole ramp miss
+        assert!((levels[0].hit_rate - 0.9).abs() < 1e-12);
+        assert!((levels[2].hit_rate - 1.0).abs() < 1e-12);
+        assert!(levels.iter().all(|l| l.throughput > 0.0));
+    }
+
+    fn ramp(scale: impl Fn(f64) -> f64) -> Vec<LevelResult> {
+        [1, 2, 4, 8, 16]
+            .into_iter()
+            .map(|l| level(l, scale(f64::from(l)), 5.0))
+            .collect()
+    }
+
+    #[test]
+    fn test_policies_are_ranked_by_classification_then_exponent() {
+        let response = rank_policies(vec![
+            (EvictionPolicy::Lru, ramp(|x| 100.0 * x.sqrt())),
+            (EvictionPolicy::Lfu, ramp(|x| 100.0 * x.powf(1.2))),
+            (EvictionPolicy::TtlOnly, ramp(|x| 100.0 * x.powf(1.5))),
+        ]);
+
+        let order: Vec<_> = response.policies.iter().map(|p| p.policy).collect();
+        assert_eq!(
+            order,
+            [
+                EvictionPolicy::TtlOnly,
+                EvictionPolicy::Lfu,
+                EvictionPolicy::Lru
+            ]
+        );
+        assert_eq!(response.policies[0].rank, 1);
+        assert_eq!(response.policies[2].classification, Triad::Fragile);
+        assert!((response.policies[0].exponent.unwrap() - 1.5).abs() < 0.01);
+
+        let section = response.report.section("eviction policies").unwrap();
+        assert_eq!(section.entries()[0].label, "ttl-only");
+        assert!(
+            section.entries()[0]
+                .rationale
+                .as_deref()
+                .unwrap()
+                .ends_with("therefore Antifragile")
+        );
+    }
+
+    #[test]
+    fn test_compare_validation() {
+        assert!(CompareRequest::default().validate().is_ok());
+        let repeated = CompareRequest {
+            policies: vec![EvictionPolicy::Lru, EvictionPolicy::Lru],
+            ..CompareRequest::default()
+        };
+        assert_eq!(repeated.validate(), Err(StatusCode::BAD_REQUEST));
+        let empty = CompareRequest {
+            capacity: 0,
+            ..CompareRequest::default()
+        };
+        assert_eq!(empty.validate(), Err(StatusCode::BAD_REQUEST));
+
+        let parsed: CompareRequest =
+            serde_json::from_str(r#"{"policies": ["lfu", "ttl-only"], "capacity": 5}"#).unwrap();
+        assert_eq!(
+            parsed.policies,
+            [EvictionPolicy::Lfu, EvictionPolicy::TtlOnly]
+        );
+        assert_eq!(parsed.levels, ExperimentRequest::default().levels);
+    }
+}
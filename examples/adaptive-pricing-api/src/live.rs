@@ -0,0 +1,145 @@
+//! Live classification stream over WebSocket
+//!
+//! `/antifragile/ws` pushes a `LiveUpdate` as soon as a client connects and
+//! then once per interval while requests keep arriving, so a dashboard can
+//! follow the Fragile → Robust → Antifragile transition without polling the
+//! REST endpoints.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use antifragile::TriadAnalysis;
+use axum::extract::State;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::response::Response;
+use serde::Serialize;
+
+use crate::metrics::ServiceSnapshot;
+use crate::{AppState, CacheStatsResponse, CurrentMetrics};
+
+/// How often connected clients are checked for new data
+const UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Normalized load at which the convexity score is measured
+pub const OPERATING_LOAD: f64 = 0.5;
+
+/// Perturbation around the operating load
+pub const LOAD_DELTA: f64 = 0.25;
+
+/// One message on the live stream
+#[derive(Debug, Serialize)]
+pub struct LiveUpdate {
+    pub timestamp: String,
+    pub classification: String,
+    pub rank: u8,
+    /// Relative second difference of the payoff around the operating load
+    pub convexity: f64,
+    pub exponent: f64,
+    pub metrics: CurrentMetrics,
+    pub cache: CacheStatsResponse,
+}
+
+impl LiveUpdate {
+    /// Capture the current classification, convexity, and cache statistics
+    pub fn capture(state: &AppState) -> Self {
+        let stats = state.metrics.get_stats();
+        let snapshot = ServiceSnapshot {
+            total_requests: stats.total_requests,
+            cache_hits: stats.cache_hits,
+            cache_misses: stats.cache_misses,
+            avg_response_time_ms: stats.avg_response_time_ms,
+        };
+        let classification = snapshot.classify();
+
+        Self {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            classification: format!("{classification:?}"),
+            rank: classification.rank(),
+            convexity: snapshot.convexity(OPERATING_LOAD, LOAD_DELTA),
+            exponent: snapshot.exponent(),
+            metrics: CurrentMetrics {
+                total_requests: stats.total_requests,
+                cache_hit_rate: stats.cache_hit_rate,
+                avg_response_time_ms: stats.avg_response_time_ms,
+                requests_per_second: stats.requests_per_second,
+            },
+            cache: CacheStatsResponse {
+                entries: state.cache.stats().entries,
+                hits: stats.cache_hits,
+                misses: stats.cache_misses,
+                hit_rate: stats.cache_hit_rate,
+            },
+        }
+    }
+}
+
+/// Upgrade `/antifragile/ws` to a WebSocket carrying live updates
+pub async fn antifragile_ws(ws: WebSocketUpgrade, State(state): State<Arc<AppState>>) -> Response {
+    ws.on_upgrade(move |socket| stream_updates(socket, state))
+}
+
+/// Send updates until the client disconnects
+///
+/// Ticks with no new requests since the last update are skipped, so an idle
+/// service does not flood its watchers with identical messages.
+async fn stream_updates(mut socket: WebSocket, state: Arc<AppState>) {
+    let mut interval = tokio::time::interval(UPDATE_INTERVAL);
+    let mut last_sent = None;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let update = LiveUpdate::capture(&state);
+                if last_sent == Some(update.metrics.total_requests) {
+                    continue;
+                }
+                last_sent = Some(update.metrics.total_requests);
+
+                let Ok(text) = serde_json::to_string(&update) else {
+                    continue;
+                };
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+    tracing::debug!("live stream client disconnected");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_reflects_cache_state() {
+        let state = AppState::default();
+        for _ in 0..9 {
+            state.metrics.record_cache_hit();
+            state.metrics.record_request(Duration::from_millis(1));
+        }
+        state.metrics.record_cache_miss();
+        state.metrics.record_request(Duration::from_millis(1));
+
+        let update = LiveUpdate::capture(&state);
+        assert_eq!(update.classification, "Antifragile");
+        assert!(update.convexity > 0.0);
+        assert_eq!(update.cache.hits, 9);
+        assert!((update.cache.hit_rate - 0.9).abs() < 1e-12);
+
+        let json = serde_json::to_value(&update).unwrap();
+        assert_eq!(json["rank"], 2);
+        assert_eq!(json["metrics"]["total_requests"], 10);
+    }
+
+    #[test]
+    fn test_cold_service_streams_fragile() {
+        let update = LiveUpdate::capture(&AppState::default());
+        assert_eq!(update.classification, "Fragile");
+        assert!(update.convexity < 0.0);
+    }
+}
@@ -4,6 +4,8 @@
 //! under load due to adaptive caching, exhibiting convex payoff characteristics.
 
 mod cache;
+mod experiment;
+mod live;
 mod metrics;
 mod pricing;
 
@@ -23,7 +25,7 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use crate::cache::AdaptiveCache;
 use crate::metrics::ServiceMetrics;
-use crate::pricing::{PriceQuery, calculate_price};
+use crate::pricing::PriceQuery;
 
 /// Application state shared across handlers
 #[derive(Default)]
@@ -66,7 +68,10 @@ async fn main() {
         .route("/antifragile/status", get(antifragile_status))
         .route("/antifragile/curve", get(antifragile_curve))
         .route("/antifragile/history", get(antifragile_history))
+        .route("/antifragile/ws", get(live::antifragile_ws))
         .route("/cache/stats", get(cache_stats))
+        .route("/experiment/run", post(experiment::run_experiment))
+        .route("/experiment/compare", post(experiment::compare_policies))
         .layer(TraceLayer::new_for_http())
         .layer(CorsLayer::permissive())
         .with_state(state);
@@ -75,6 +80,7 @@ async fn main() {
     tracing::info!("Adaptive Pricing API listening on http://0.0.0.0:3000");
     tracing::info!("Metrics available at http://0.0.0.0:3000/metrics");
     tracing::info!("Antifragile status at http://0.0.0.0:3000/antifragile/status");
+    tracing::info!("Live classification stream at ws://0.0.0.0:3000/antifragile/ws");
 
     axum::serve(listener, app)
         .with_graceful_shutdown(shutdown_signal())
@@ -136,15 +142,12 @@ async fn calculate_price_handler(
     }
     .normalized();
 
-    let (result, cache_hit) = if let Some(cached) = state.cache.get(&query) {
+    let (result, cache_hit) = state.cache.get_or_compute(query).await;
+    if cache_hit {
         state.metrics.record_cache_hit();
-        (cached, true)
     } else {
         state.metrics.record_cache_miss();
-        let result = calculate_price(&query).await;
-        state.cache.insert(query, result.clone());
-        (result, false)
-    };
+    }
 
     let elapsed = start.elapsed();
     state.metrics.record_request(elapsed);
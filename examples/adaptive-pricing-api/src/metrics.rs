@@ -7,12 +7,14 @@ use std::collections::VecDeque;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use antifragile::Antifragile;
+use antifragile::{Antifragile, TriadAnalysis};
 use chrono::{DateTime, Utc};
 use metrics::{counter, gauge, histogram};
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
 use parking_lot::RwLock;
 
+use crate::live::{LOAD_DELTA, OPERATING_LOAD};
+
 /// Sliding window size for requests-per-second calculation
 const RPS_WINDOW: Duration = Duration::from_secs(10);
 
@@ -155,7 +157,11 @@ impl ServiceMetrics {
             avg_response_time_ms: stats.avg_response_time_ms,
         };
         gauge!("antifragile_exponent").set(snapshot.exponent());
-        gauge!("antifragile_classification_rank").set(snapshot.classify().rank() as f64);
+        antifragile::telemetry::publish_classification(
+            "pricing",
+            snapshot.classify(),
+            Some(snapshot.convexity(OPERATING_LOAD, LOAD_DELTA)),
+        );
     }
 
     fn record_history_entry(&self) {
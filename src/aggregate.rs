@@ -0,0 +1,254 @@
+//! # Aggregate classification
+//!
+//! A platform of dozens of services needs one verdict as well as one per
+//! service. [`classify_all`](crate::aggregate::classify_all) runs the
+//! convexity test on every system at the same operating point and combines
+//! the results under an [`Aggregation`](crate::aggregate::Aggregation)
+//! policy:
+//!
+//! | Policy | Verdict |
+//! |--------|---------|
+//! | `WeakestLink` | The least desirable classification: a chain is as fragile as its weakest link |
+//! | `Majority` | The most common classification |
+//! | `Weighted` | The classification with the most weight, such as traffic share |
+//!
+//! Ties in a vote go to the less desirable classification.
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad};
+//! use antifragile::aggregate::{Aggregation, classify_all};
+//!
+//! /// A service whose throughput bends with load
+//! struct Service {
+//!     curvature: f64,
+//! }
+//!
+//! impl Antifragile for Service {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, load: f64) -> f64 {
+//!         self.curvature * load * load
+//!     }
+//! }
+//!
+//! let services = [
+//!     Service { curvature: 1.0 },
+//!     Service { curvature: 0.5 },
+//!     Service { curvature: -1.0 },
+//! ];
+//!
+//! let fleet = classify_all(&services, 10.0, 1.0, Aggregation::WeakestLink);
+//! assert_eq!(fleet.triads, [Triad::Antifragile, Triad::Antifragile, Triad::Fragile]);
+//! assert_eq!(fleet.verdict, Some(Triad::Fragile));
+//!
+//! let fleet = classify_all(&services, 10.0, 1.0, Aggregation::Majority);
+//! assert_eq!(fleet.verdict, Some(Triad::Antifragile));
+//!
+//! // The fragile service carries most of the traffic
+//! let traffic = [0.2, 0.2, 0.6];
+//! let fleet = classify_all(&services, 10.0, 1.0, Aggregation::Weighted(&traffic));
+//! assert_eq!(fleet.verdict, Some(Triad::Fragile));
+//! ```
+//!
+//! ## Batches of operating points
+//!
+//! The other way round, [`BatchAnalysis`](crate::aggregate::BatchAnalysis)
+//! classifies one system at many `(at, delta)` points. With the `rayon`
+//! feature,
+//! [`par_classify_batch`](crate::aggregate::BatchAnalysis::par_classify_batch)
+//! spreads the points over a thread pool, which pays off when each payoff
+//! is expensive, such as a call into a simulator.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::aggregate::BatchAnalysis;
+//!
+//! let system = antifragile::antifragile!(|x: f64| x.powi(3));
+//! let points = [(-5.0, 1.0), (0.0, 1.0), (5.0, 1.0)];
+//! assert_eq!(
+//!     system.classify_batch(&points),
+//!     [Triad::Fragile, Triad::Robust, Triad::Antifragile]
+//! );
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Antifragile, Triad, TriadAnalysis};
+
+/// How per-system classifications combine into one verdict
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Aggregation<'a> {
+    /// The least desirable classification
+    #[default]
+    WeakestLink,
+    /// The most common classification
+    Majority,
+    /// The classification with the largest total weight
+    ///
+    /// Weights are matched to systems by position. Systems without a
+    /// weight, and negative or non-finite weights, count as zero.
+    Weighted(&'a [f64]),
+}
+
+impl Aggregation<'_> {
+    /// Combine `triads` into one verdict
+    ///
+    /// `None` if there is nothing to combine: no classifications, or no
+    /// positive weight under [`Weighted`](Self::Weighted).
+    #[must_use]
+    pub fn combine(&self, triads: &[Triad]) -> Option<Triad> {
+        match self {
+            Self::WeakestLink => Triad::worst_of(triads.iter().copied()),
+            Self::Majority => vote(triads.iter().map(|&triad| (triad, 1.0))),
+            Self::Weighted(weights) => vote(
+                triads
+                    .iter()
+                    .zip(weights.iter())
+                    .map(|(&triad, &weight)| (triad, weight)),
+            ),
+        }
+    }
+}
+
+/// Per-system classifications and their combined verdict
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AggregateClassification {
+    /// The classification of each system, in input order
+    pub triads: Vec<Triad>,
+    /// The combined verdict, `None` if there was nothing to combine
+    pub verdict: Option<Triad>,
+}
+
+impl AggregateClassification {
+    /// Number of systems with the given classification
+    #[must_use]
+    pub fn count(&self, triad: Triad) -> usize {
+        self.triads.iter().filter(|&&t| t == triad).count()
+    }
+}
+
+/// Classify every system at `at` with perturbation `delta` and combine the
+/// results
+///
+/// Borrowed systems work as well as owned ones, so a slice can be passed
+/// by reference.
+pub fn classify_all<I>(
+    systems: I,
+    at: <I::Item as Antifragile>::Stressor,
+    delta: <I::Item as Antifragile>::Stressor,
+    aggregation: Aggregation<'_>,
+) -> AggregateClassification
+where
+    I: IntoIterator,
+    I::Item: Antifragile,
+{
+    let triads: Vec<Triad> = systems
+        .into_iter()
+        .map(|system| system.classify(at, delta))
+        .collect();
+    let verdict = aggregation.combine(&triads);
+    AggregateClassification { triads, verdict }
+}
+
+/// Extension trait classifying one system at many operating points
+pub trait BatchAnalysis: TriadAnalysis {
+    /// Classify at each `(at, delta)` point, in order
+    fn classify_batch(&self, points: &[(Self::Stressor, Self::Stressor)]) -> Vec<Triad> {
+        points
+            .iter()
+            .map(|&(at, delta)| self.classify(at, delta))
+            .collect()
+    }
+
+    /// Classify at each `(at, delta)` point in parallel, returning the
+    /// results in order
+    ///
+    /// Runs on the global `rayon` thread pool. Worth it when payoffs are
+    /// expensive; for cheap ones the scheduling costs more than it saves.
+    #[cfg(feature = "rayon")]
+    fn par_classify_batch(&self, points: &[(Self::Stressor, Self::Stressor)]) -> Vec<Triad>
+    where
+        Self: Sync,
+        Self::Stressor: Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        points
+            .par_iter()
+            .map(|&(at, delta)| self.classify(at, delta))
+            .collect()
+    }
+}
+
+impl<T: TriadAnalysis + ?Sized> BatchAnalysis for T {}
+
+/// The classification with the most weight, ties going to the less
+/// desirable one
+fn vote(ballots: impl Iterator<Item = (Triad, f64)>) -> Option<Triad> {
+    let mut totals = [0.0_f64; 3];
+    for (triad, weight) in ballots {
+        if weight.is_finite() && weight > 0.0 {
+            totals[triad.rank() as usize] += weight;
+        }
+    }
+    let mut winner = None;
+    let mut most = 0.0;
+    for triad in Triad::ALL {
+        let total = totals[triad.rank() as usize];
+        if total > most {
+            winner = Some(triad);
+            most = total;
+        }
+    }
+    winner
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ties_and_empty_inputs() {
+        let split = [Triad::Antifragile, Triad::Robust];
+        assert_eq!(Aggregation::Majority.combine(&split), Some(Triad::Robust));
+        assert_eq!(Aggregation::Majority.combine(&[]), None);
+        assert_eq!(Aggregation::WeakestLink.combine(&[]), None);
+    }
+
+    #[test]
+    fn test_weights_are_sanitized() {
+        let triads = [Triad::Fragile, Triad::Antifragile, Triad::Robust];
+        let weights = [f64::NAN, -5.0];
+        assert_eq!(Aggregation::Weighted(&weights).combine(&triads), None);
+
+        let weights = [f64::INFINITY, 1.0];
+        assert_eq!(
+            Aggregation::Weighted(&weights).combine(&triads),
+            Some(Triad::Antifragile)
+        );
+    }
+
+    #[test]
+    fn test_classify_all_owned_systems() {
+        let linear = crate::antifragile!(|x: i64| 2 * x);
+        let fleet = classify_all([linear, linear], 5, 1, Aggregation::default());
+        assert_eq!(fleet.count(Triad::Robust), 2);
+        assert_eq!(fleet.verdict, Some(Triad::Robust));
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_parallel_batch_matches_serial() {
+        let system = crate::antifragile!(|x: f64| x.sin());
+        let points: Vec<(f64, f64)> = (0..1_000).map(|i| (f64::from(i) * 0.01, 0.1)).collect();
+        assert_eq!(
+            system.par_classify_batch(&points),
+            system.classify_batch(&points)
+        );
+        assert!(system.par_classify_batch(&[]).is_empty());
+    }
+}
@@ -28,11 +28,24 @@
 
 use core::cmp::Ordering;
 use core::fmt::Display;
+use core::marker::PhantomData;
 use core::ops::{Add, Sub};
 use core::str::FromStr;
+use core::time::Duration;
 
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
 #[cfg(feature = "std")]
 use std::error::Error;
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+#[cfg(feature = "std")]
+use crate::distribution::{Noise, StressorDistribution};
+#[cfg(any(feature = "std", feature = "libm"))]
+use crate::math::sqrt;
+#[cfg(any(feature = "std", feature = "libm"))]
+use crate::special::student_t_two_sided;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -66,12 +79,28 @@ pub trait Antifragile {
     }
 }
 
+impl<T: Antifragile> Antifragile for &T {
+    type Stressor = T::Stressor;
+    type Payoff = T::Payoff;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        (**self).payoff(stressor)
+    }
+
+    #[inline]
+    fn twin(r: Self::Payoff) -> Self::Payoff {
+        T::twin(r)
+    }
+}
+
 /// Triad: the three categories of response to volatility
 ///
 /// Variants are ordered by desirability: Fragile < Robust < Antifragile.
 /// This ordering is consistent with `Ord`, `rank()`, and numeric conversions.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[repr(u8)]
 #[must_use]
 pub enum Triad {
@@ -135,6 +164,58 @@ impl Triad {
             Self::Robust => Self::Robust,
         }
     }
+
+    /// Returns the less desirable of two classifications
+    ///
+    /// The verdict for two sub-systems in series: a chain is as fragile as
+    /// its weakest link.
+    #[inline]
+    pub const fn meet(self, other: Self) -> Self {
+        if other.rank() < self.rank() {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Returns the more desirable of two classifications
+    ///
+    /// The verdict for redundant sub-systems where the best one can be
+    /// relied on alone.
+    #[inline]
+    pub const fn join(self, other: Self) -> Self {
+        if other.rank() > self.rank() {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Returns the least desirable classification in `triads`, or `None`
+    /// if it is empty
+    ///
+    /// Aggregates sub-systems with [`meet`](Self::meet) semantics.
+    ///
+    /// ```
+    /// use antifragile::Triad;
+    ///
+    /// let links = [Triad::Antifragile, Triad::Robust, Triad::Antifragile];
+    /// assert_eq!(Triad::worst_of(links), Some(Triad::Robust));
+    /// assert_eq!(Triad::worst_of([]), None);
+    /// ```
+    #[must_use]
+    pub fn worst_of<I: IntoIterator<Item = Self>>(triads: I) -> Option<Self> {
+        triads.into_iter().reduce(Self::meet)
+    }
+
+    /// Returns the most desirable classification in `triads`, or `None` if
+    /// it is empty
+    ///
+    /// Aggregates sub-systems with [`join`](Self::join) semantics.
+    #[must_use]
+    pub fn best_of<I: IntoIterator<Item = Self>>(triads: I) -> Option<Self> {
+        triads.into_iter().reduce(Self::join)
+    }
 }
 
 impl PartialOrd for Triad {
@@ -247,6 +328,291 @@ impl FromStr for Triad {
     }
 }
 
+/// A classification from a statistical test, with how strongly the evidence
+/// supports it
+///
+/// Returned by [`classify_stochastic`](TriadAnalysis::classify_stochastic) and
+/// by the repeated-trial analyses, so a barely significant reading can be
+/// told apart from a decisive one.
+///
+/// Ordered by desirability: first by [`Triad`], then by how decisively the
+/// evidence supports the verdict, so a decisive fragile reading sorts below a
+/// marginal one and a decisive antifragile reading above a marginal one.
+/// Robust readings, and otherwise equal ones, are ordered by `margin`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TriadWithConfidence<P = f64> {
+    /// The classification
+    pub triad: Triad,
+    /// One minus the p-value of the test that the curvature is zero, in
+    /// `[0, 1]`; high for a robust reading means the test came close to
+    /// significance
+    pub confidence: f64,
+    /// Mean second difference `f(x+Δ) + f(x-Δ) - 2·f(x)` the verdict rests on
+    pub margin: P,
+}
+
+impl<P: PartialOrd> PartialOrd for TriadWithConfidence<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.triad.cmp(&other.triad) {
+            Ordering::Equal => {}
+            unequal => return Some(unequal),
+        }
+        let confidence = self.confidence.partial_cmp(&other.confidence)?;
+        let margin = self.margin.partial_cmp(&other.margin)?;
+        Some(match self.triad {
+            Triad::Fragile => confidence.reverse().then(margin),
+            Triad::Robust => margin.then(confidence),
+            Triad::Antifragile => confidence.then(margin),
+        })
+    }
+}
+
+/// Every intermediate value of the three-point convexity test
+///
+/// Returned by [`classify_detailed`](TriadAnalysis::classify_detailed) so a
+/// surprising classification can be checked without redoing the arithmetic
+/// by hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClassificationDetail<P> {
+    /// `f(x - Δ)`
+    pub below: P,
+    /// `f(x)`
+    pub at: P,
+    /// `f(x + Δ)`
+    pub above: P,
+    /// `f(x + Δ) + f(x - Δ)`
+    pub sum: P,
+    /// `2·f(x)` as computed by [`Antifragile::twin`]
+    pub twin: P,
+    /// `|sum - twin|`; the direction is given by `triad`
+    pub difference: P,
+    /// The classification [`classify`](TriadAnalysis::classify) returns
+    pub triad: Triad,
+}
+
+impl<P: Display> Display for ClassificationDetail<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let relation = match self.triad {
+            Triad::Antifragile => '>',
+            Triad::Fragile => '<',
+            Triad::Robust => '=',
+        };
+        write!(
+            f,
+            "f(x - delta) = {}, f(x) = {}, f(x + delta) = {}; sum {} {relation} twin {} \
+             (difference {}): {:?}",
+            self.below, self.at, self.above, self.sum, self.twin, self.difference, self.triad
+        )
+    }
+}
+
+/// Human-readable rationale for a classification
+///
+/// Returned by [`explain`](TriadAnalysis::explain). The fields keep the
+/// numbers for structured logging; [`Display`] renders them as a sentence
+/// such as `f(x+Δ) + f(x-Δ) = 104.2 exceeded 2·f(x) = 100.0 by 4.2, therefore
+/// Antifragile`. A precision in the format string, as in `{:.1}`, applies to
+/// every number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Explanation<P> {
+    /// The values the classification was derived from
+    pub detail: ClassificationDetail<P>,
+}
+
+impl<P: Display + PartialOrd> Display for Explanation<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let ClassificationDetail {
+            sum,
+            twin,
+            difference,
+            triad,
+            ..
+        } = &self.detail;
+        let precision = f.precision();
+        let (sum, twin, difference) = (
+            Precise(sum, precision),
+            Precise(twin, precision),
+            Precise(difference, precision),
+        );
+        match self.detail.sum.partial_cmp(&self.detail.twin) {
+            Some(Ordering::Greater) => write!(
+                f,
+                "f(x+Δ) + f(x-Δ) = {sum} exceeded 2·f(x) = {twin} by {difference}, therefore {triad:?}"
+            ),
+            Some(Ordering::Less) => write!(
+                f,
+                "f(x+Δ) + f(x-Δ) = {sum} fell short of 2·f(x) = {twin} by {difference}, therefore {triad:?}"
+            ),
+            Some(Ordering::Equal) => write!(
+                f,
+                "f(x+Δ) + f(x-Δ) = {sum} equals 2·f(x) = {twin}, therefore {triad:?}"
+            ),
+            None => write!(
+                f,
+                "f(x+Δ) + f(x-Δ) = {sum} cannot be compared with 2·f(x) = {twin}, so the test \
+                 falls back to {triad:?}"
+            ),
+        }
+    }
+}
+
+/// A value displayed with an optional precision carried from an outer format
+struct Precise<'a, P>(&'a P, Option<usize>);
+
+impl<P: Display> Display for Precise<'_, P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.1 {
+            Some(digits) => write!(f, "{:.*}", digits, self.0),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
+/// Which of the three payoff evaluations of a convexity test failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Probe {
+    /// `f(x - Δ)`
+    Below,
+    /// `f(x)`
+    At,
+    /// `f(x + Δ)`
+    Above,
+}
+
+impl Display for Probe {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Below => write!(f, "f(x - delta)"),
+            Self::At => write!(f, "f(x)"),
+            Self::Above => write!(f, "f(x + delta)"),
+        }
+    }
+}
+
+/// Error returned by [`TriadAnalysis::try_classify`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ClassifyError {
+    /// A payoff evaluated to NaN
+    NotANumber {
+        /// The evaluation that produced NaN
+        probe: Probe,
+    },
+    /// A payoff evaluated to positive or negative infinity
+    Infinite {
+        /// The evaluation that produced infinity
+        probe: Probe,
+    },
+    /// The payoffs were finite, but `f(x+Δ) + f(x-Δ)` or `2·f(x)` overflowed
+    Overflow,
+    /// The stressor `x ± Δ` for an evaluation does not fit its type
+    OutOfRange {
+        /// The evaluation whose stressor overflowed
+        probe: Probe,
+    },
+}
+
+impl Display for ClassifyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotANumber { probe } => write!(f, "payoff {probe} is NaN"),
+            Self::Infinite { probe } => write!(f, "payoff {probe} is infinite"),
+            Self::Overflow => write!(f, "payoff sum overflowed during the convexity test"),
+            Self::OutOfRange { probe } => write!(f, "stressor for {probe} is out of range"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ClassifyError {}
+
+/// Why [`TriadAnalysis::classify_checked`] could not decide
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Inconclusive {
+    /// A payoff was NaN or infinite, or the payoffs overflowed when summed
+    Invalid(ClassifyError),
+    /// The perturbation was zero, so all three probes evaluate the same point
+    ZeroDelta,
+    /// `f(x+Δ) + f(x-Δ) - 2·f(x)` was nonzero but within the tolerance
+    WithinTolerance,
+}
+
+impl Display for Inconclusive {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Invalid(error) => write!(f, "{error}"),
+            Self::ZeroDelta => write!(f, "perturbation is zero"),
+            Self::WithinTolerance => write!(f, "second difference is within the tolerance"),
+        }
+    }
+}
+
+/// A Triad classification, or the reason the test could not produce one
+///
+/// Returned by [`TriadAnalysis::classify_checked`], which keeps measurement
+/// failures out of the three Triad buckets instead of reporting them as
+/// [`Triad::Robust`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Classification {
+    /// Concave response: harmed by volatility
+    Fragile,
+    /// Exactly linear response
+    Robust,
+    /// Convex response: gains from volatility
+    Antifragile,
+    /// The test could not decide
+    Inconclusive(Inconclusive),
+}
+
+impl Classification {
+    /// The Triad, or `None` if the test was inconclusive
+    #[inline]
+    #[must_use]
+    pub const fn triad(self) -> Option<Triad> {
+        match self {
+            Self::Fragile => Some(Triad::Fragile),
+            Self::Robust => Some(Triad::Robust),
+            Self::Antifragile => Some(Triad::Antifragile),
+            Self::Inconclusive(_) => None,
+        }
+    }
+
+    /// Returns true if the test could not decide
+    #[inline]
+    #[must_use]
+    pub const fn is_inconclusive(self) -> bool {
+        matches!(self, Self::Inconclusive(_))
+    }
+}
+
+impl From<Triad> for Classification {
+    fn from(triad: Triad) -> Self {
+        match triad {
+            Triad::Fragile => Self::Fragile,
+            Triad::Robust => Self::Robust,
+            Triad::Antifragile => Self::Antifragile,
+        }
+    }
+}
+
+impl Display for Classification {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Fragile => Triad::Fragile.fmt(f),
+            Self::Robust => Triad::Robust.fmt(f),
+            Self::Antifragile => Triad::Antifragile.fmt(f),
+            Self::Inconclusive(reason) => write!(f, "Inconclusive ({reason})"),
+        }
+    }
+}
+
 /// Extension trait providing Triad classification methods
 pub trait TriadAnalysis: Antifragile {
     /// Classify the system on Taleb's Triad at a specific operating point
@@ -264,7 +630,65 @@ pub trait TriadAnalysis: Antifragile {
     /// This uses exact comparison. For floating-point payoffs where exact
     /// equality is unlikely, use [`classify_with_tolerance`](Self::classify_with_tolerance).
     #[inline]
-    fn classify(&self, at: Self::Stressor, delta: Self::Stressor) -> Triad
+    fn classify(&self, at: Self::Stressor, delta: Self::Stressor) -> Triad {
+        let f_x = self.payoff(at);
+        let f_x_plus = self.payoff(at + delta);
+        let f_x_minus = self.payoff(at - delta);
+
+        let sum = f_x_plus + f_x_minus;
+        let twin_f_x = Self::twin(f_x);
+
+        let triad = if sum > twin_f_x {
+            Triad::Antifragile
+        } else if sum < twin_f_x {
+            Triad::Fragile
+        } else {
+            Triad::Robust
+        };
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            system = core::any::type_name::<Self>(),
+            ?triad,
+            "classified"
+        );
+        triad
+    }
+
+    /// Classify, returning every intermediate value of the test
+    ///
+    /// Evaluates exactly as [`classify`](Self::classify) does and reports
+    /// `f(x-Δ)`, `f(x)`, `f(x+Δ)`, their sum and twin, the size of the gap
+    /// between them, and the resulting [`Triad`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use antifragile::{Antifragile, Triad, TriadAnalysis};
+    ///
+    /// struct Square;
+    /// impl Antifragile for Square {
+    ///     type Stressor = i32;
+    ///     type Payoff = i32;
+    ///     fn payoff(&self, x: Self::Stressor) -> Self::Payoff {
+    ///         x * x
+    ///     }
+    /// }
+    ///
+    /// let detail = Square.classify_detailed(3, 1);
+    /// assert_eq!((detail.below, detail.at, detail.above), (4, 9, 16));
+    /// assert_eq!((detail.sum, detail.twin, detail.difference), (20, 18, 2));
+    /// assert_eq!(detail.triad, Triad::Antifragile);
+    /// assert_eq!(
+    ///     detail.to_string(),
+    ///     "f(x - delta) = 4, f(x) = 9, f(x + delta) = 16; sum 20 > twin 18 (difference 2): Antifragile"
+    /// );
+    /// ```
+    #[inline]
+    fn classify_detailed(
+        &self,
+        at: Self::Stressor,
+        delta: Self::Stressor,
+    ) -> ClassificationDetail<Self::Payoff>
     where
         Self::Payoff: Sub<Output = Self::Payoff> + Default + PartialOrd,
     {
@@ -275,12 +699,185 @@ pub trait TriadAnalysis: Antifragile {
         let sum = f_x_plus + f_x_minus;
         let twin_f_x = Self::twin(f_x);
 
-        if sum > twin_f_x {
+        let (triad, difference) = if sum > twin_f_x {
+            (Triad::Antifragile, sum - twin_f_x)
+        } else if sum < twin_f_x {
+            (Triad::Fragile, twin_f_x - sum)
+        } else {
+            (Triad::Robust, Self::Payoff::default())
+        };
+        ClassificationDetail {
+            below: f_x_minus,
+            at: f_x,
+            above: f_x_plus,
+            sum,
+            twin: twin_f_x,
+            difference,
+            triad,
+        }
+    }
+
+    /// Explain the classification at `at` in words
+    ///
+    /// Runs [`classify_detailed`](Self::classify_detailed) and wraps the
+    /// result in an [`Explanation`] whose [`Display`] states how the sum
+    /// compared with the twin and by how much, for logs, reports, and audits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use antifragile::{Antifragile, Triad, TriadAnalysis};
+    ///
+    /// struct Revenue;
+    /// impl Antifragile for Revenue {
+    ///     type Stressor = f64;
+    ///     type Payoff = f64;
+    ///     fn payoff(&self, x: Self::Stressor) -> Self::Payoff {
+    ///         50.0 + 2.1 * x * x
+    ///     }
+    /// }
+    ///
+    /// let explanation = Revenue.explain(0.0, 1.0);
+    /// assert_eq!(explanation.detail.triad, Triad::Antifragile);
+    /// assert_eq!(
+    ///     format!("{explanation:.1}"),
+    ///     "f(x+Δ) + f(x-Δ) = 104.2 exceeded 2·f(x) = 100.0 by 4.2, therefore Antifragile"
+    /// );
+    /// ```
+    #[inline]
+    fn explain(&self, at: Self::Stressor, delta: Self::Stressor) -> Explanation<Self::Payoff>
+    where
+        Self::Payoff: Sub<Output = Self::Payoff> + Default + PartialOrd,
+    {
+        Explanation {
+            detail: self.classify_detailed(at, delta),
+        }
+    }
+
+    /// Classify, rejecting NaN and infinite payoffs instead of misclassifying
+    ///
+    /// [`classify`](Self::classify) compares payoffs with `>` and `<`; when a
+    /// payoff is NaN both comparisons are false and the result is silently
+    /// [`Triad::Robust`]. This checks every evaluation first and reports
+    /// which one went wrong.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClassifyError::NotANumber`] or [`ClassifyError::Infinite`]
+    /// naming the offending evaluation, or [`ClassifyError::Overflow`] if the
+    /// finite payoffs overflow when summed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use antifragile::{Antifragile, ClassifyError, Probe, TriadAnalysis};
+    ///
+    /// struct Buggy;
+    /// impl Antifragile for Buggy {
+    ///     type Stressor = f64;
+    ///     type Payoff = f64;
+    ///     fn payoff(&self, x: Self::Stressor) -> Self::Payoff {
+    ///         x.ln() // NaN for negative stress
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(
+    ///     Buggy.try_classify(0.5, 1.0),
+    ///     Err(ClassifyError::NotANumber { probe: Probe::Below })
+    /// );
+    /// ```
+    #[inline]
+    fn try_classify(
+        &self,
+        at: Self::Stressor,
+        delta: Self::Stressor,
+    ) -> Result<Triad, ClassifyError>
+    where
+        Self::Payoff: Into<f64>,
+    {
+        let f_x_minus = self.payoff(at - delta);
+        let f_x = self.payoff(at);
+        let f_x_plus = self.payoff(at + delta);
+
+        let (sum, twin_f_x) = checked_sum::<Self>(f_x_minus, f_x, f_x_plus)?;
+
+        Ok(if sum > twin_f_x {
             Triad::Antifragile
         } else if sum < twin_f_x {
             Triad::Fragile
         } else {
             Triad::Robust
+        })
+    }
+
+    /// Classify, reporting when the test cannot decide instead of forcing a
+    /// Triad
+    ///
+    /// Returns [`Classification::Inconclusive`] when a payoff is NaN or
+    /// infinite or the payoffs overflow (see
+    /// [`try_classify`](Self::try_classify)), when `delta` is zero, or when
+    /// the second difference `f(x+Δ) + f(x-Δ) - 2·f(x)` is nonzero but no
+    /// larger than `epsilon`. [`Classification::Robust`] is reserved for an
+    /// exactly zero second difference; use
+    /// [`classify_with_tolerance`](Self::classify_with_tolerance) to count
+    /// sub-tolerance differences as robust instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use antifragile::{Antifragile, Classification, Inconclusive, TriadAnalysis};
+    ///
+    /// struct Square;
+    /// impl Antifragile for Square {
+    ///     type Stressor = f64;
+    ///     type Payoff = f64;
+    ///     fn payoff(&self, x: Self::Stressor) -> Self::Payoff {
+    ///         x * x
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(Square.classify_checked(1.0, 0.5, 1e-9), Classification::Antifragile);
+    /// assert_eq!(
+    ///     Square.classify_checked(1.0, 0.0, 1e-9),
+    ///     Classification::Inconclusive(Inconclusive::ZeroDelta)
+    /// );
+    /// // 2·Δ² = 2e-10 is too small to tell from measurement error
+    /// assert_eq!(
+    ///     Square.classify_checked(1.0, 1e-5, 1e-9),
+    ///     Classification::Inconclusive(Inconclusive::WithinTolerance)
+    /// );
+    /// ```
+    #[inline]
+    fn classify_checked(
+        &self,
+        at: Self::Stressor,
+        delta: Self::Stressor,
+        epsilon: f64,
+    ) -> Classification
+    where
+        Self::Stressor: Default + PartialEq,
+        Self::Payoff: Into<f64>,
+    {
+        if delta == Self::Stressor::default() {
+            return Classification::Inconclusive(Inconclusive::ZeroDelta);
+        }
+        let f_x_minus = self.payoff(at - delta);
+        let f_x = self.payoff(at);
+        let f_x_plus = self.payoff(at + delta);
+
+        let (sum, twin_f_x) = match checked_sum::<Self>(f_x_minus, f_x, f_x_plus) {
+            Ok(pair) => pair,
+            Err(error) => return Classification::Inconclusive(Inconclusive::Invalid(error)),
+        };
+        let difference = sum.into() - twin_f_x.into();
+        if difference == 0.0 {
+            Classification::Robust
+        } else if abs(difference) <= epsilon {
+            Classification::Inconclusive(Inconclusive::WithinTolerance)
+        } else if difference > 0.0 {
+            Classification::Antifragile
+        } else {
+            Classification::Fragile
         }
     }
 
@@ -323,7 +920,7 @@ pub trait TriadAnalysis: Antifragile {
         epsilon: Self::Payoff,
     ) -> Triad
     where
-        Self::Payoff: Sub<Output = Self::Payoff> + Default + PartialOrd,
+        Self::Payoff: Sub<Output = Self::Payoff>,
     {
         let f_x = self.payoff(at);
         let f_x_plus = self.payoff(at + delta);
@@ -348,7 +945,174 @@ pub trait TriadAnalysis: Antifragile {
         }
     }
 
-    /// Check if system is antifragile at a given point (convexity test)
+    /// Continuous convexity score at a specific operating point
+    ///
+    /// Returns the normalized second difference
+    /// `(f(x+Δ) + f(x-Δ) - 2·f(x)) / |2·f(x)|`: positive for convex payoffs,
+    /// negative for concave ones, and zero for linear ones. Where
+    /// [`classify`](Self::classify) only reports the sign, this keeps the
+    /// magnitude, so systems can be ranked by *how* antifragile they are.
+    ///
+    /// The denominator is the magnitude of `2·f(x)`, so the sign of the score
+    /// always agrees with [`classify`](Self::classify), even for negative
+    /// payoffs. When `f(x)` is zero the ratio is undefined and the result is
+    /// infinite, or NaN if the second difference is zero too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use antifragile::{Antifragile, TriadAnalysis};
+    ///
+    /// struct Quadratic(f64);
+    /// impl Antifragile for Quadratic {
+    ///     type Stressor = f64;
+    ///     type Payoff = f64;
+    ///     fn payoff(&self, x: Self::Stressor) -> Self::Payoff {
+    ///         self.0 * x * x
+    ///     }
+    /// }
+    ///
+    /// // (121 + 81 - 200) / 200
+    /// assert!((Quadratic(1.0).convexity(10.0, 1.0) - 0.01).abs() < 1e-12);
+    /// // Both are antifragile, but scaling preserves the relative score
+    /// assert!((Quadratic(5.0).convexity(10.0, 1.0) - 0.01).abs() < 1e-12);
+    /// ```
+    #[inline]
+    #[must_use]
+    fn convexity(&self, at: Self::Stressor, delta: Self::Stressor) -> f64
+    where
+        Self::Payoff: Into<f64>,
+    {
+        let f_x = self.payoff(at);
+        let sum: f64 = (self.payoff(at + delta) + self.payoff(at - delta)).into();
+        let twin_f_x: f64 = Self::twin(f_x).into();
+
+        (sum - twin_f_x) / abs(twin_f_x)
+    }
+
+    /// Bounded antifragility index in `[-1, 1]` at a specific operating point
+    ///
+    /// With `Δ⁺ = f(x+Δ) - f(x)` the response to more stress and
+    /// `Δ⁻ = f(x) - f(x-Δ)` the response to less, the index is
+    /// `(Δ⁺ - Δ⁻) / (|Δ⁺| + |Δ⁻|)`: the share of the total movement that is
+    /// asymmetric.
+    ///
+    /// - `+1`: fully antifragile, the payoff rises whichever way the stressor
+    ///   moves (as at the bottom of `x²`)
+    /// - `0`: robust, both moves change the payoff by the same amount
+    /// - `-1`: fully fragile, the payoff falls whichever way the stressor moves
+    ///
+    /// Values in between say how much of the response is curvature rather
+    /// than trend. The index is invariant to scaling and shifting the payoff,
+    /// so it is comparable across unrelated systems, and its sign always
+    /// agrees with [`classify`](Self::classify). A flat payoff has index `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use antifragile::{Antifragile, TriadAnalysis};
+    ///
+    /// struct Quadratic;
+    /// impl Antifragile for Quadratic {
+    ///     type Stressor = f64;
+    ///     type Payoff = f64;
+    ///     fn payoff(&self, x: Self::Stressor) -> Self::Payoff {
+    ///         x * x
+    ///     }
+    /// }
+    ///
+    /// assert!((Quadratic.antifragility_index(0.0, 1.0) - 1.0).abs() < 1e-12);
+    /// // Far from the minimum the trend dominates: (21 - 19) / (21 + 19)
+    /// assert!((Quadratic.antifragility_index(10.0, 1.0) - 0.05).abs() < 1e-12);
+    /// ```
+    #[inline]
+    #[must_use]
+    fn antifragility_index(&self, at: Self::Stressor, delta: Self::Stressor) -> f64
+    where
+        Self::Payoff: Into<f64>,
+    {
+        let f_x: f64 = self.payoff(at).into();
+        let f_up: f64 = self.payoff(at + delta).into();
+        let f_down: f64 = self.payoff(at - delta).into();
+
+        let gain_up = f_up - f_x;
+        let gain_down = f_x - f_down;
+        let total = abs(gain_up) + abs(gain_down);
+        if total > 0.0 {
+            (gain_up - gain_down) / total
+        } else {
+            0.0
+        }
+    }
+
+    /// Classify the system at evenly spaced points across a stressor range
+    ///
+    /// Many payoffs are convex in one region and concave in another; a single
+    /// operating point hides that. This sweeps `steps` points from the start
+    /// to the end of `range` (both inclusive) and classifies each with
+    /// [`classify`](Self::classify) using the same `delta`.
+    ///
+    /// `steps = 0` returns an empty profile and `steps = 1` classifies the
+    /// start of the range only.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use antifragile::{Antifragile, Triad, TriadAnalysis};
+    ///
+    /// struct Cubic;
+    /// impl Antifragile for Cubic {
+    ///     type Stressor = f64;
+    ///     type Payoff = f64;
+    ///     fn payoff(&self, x: Self::Stressor) -> Self::Payoff {
+    ///         x * x * x
+    ///     }
+    /// }
+    ///
+    /// let profile = Cubic.classify_profile(-2.0..=2.0, 5, 0.5);
+    /// assert_eq!(
+    ///     profile,
+    ///     vec![
+    ///         (-2.0, Triad::Fragile),
+    ///         (-1.0, Triad::Fragile),
+    ///         (0.0, Triad::Robust),
+    ///         (1.0, Triad::Antifragile),
+    ///         (2.0, Triad::Antifragile),
+    ///     ]
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    fn classify_profile(
+        &self,
+        range: core::ops::RangeInclusive<Self::Stressor>,
+        steps: usize,
+        delta: Self::Stressor,
+    ) -> Vec<(Self::Stressor, Triad)>
+    where
+        Self::Stressor: From<f64> + Into<f64>,
+        Self::Payoff: Sub<Output = Self::Payoff> + Default + PartialOrd,
+    {
+        let (start, end): (f64, f64) = ((*range.start()).into(), (*range.end()).into());
+        let points: Vec<f64> = match steps {
+            0 => Vec::new(),
+            1 => vec![start],
+            _ => {
+                let step = (end - start) / (steps - 1) as f64;
+                (0..steps).map(|i| start + step * i as f64).collect()
+            }
+        };
+        points
+            .into_iter()
+            .map(|point| {
+                let at = Self::Stressor::from(point);
+                (at, self.classify(at, delta))
+            })
+            .collect()
+    }
+
+    /// Check if system is antifragile at a given point (convexity test)
     #[inline]
     #[must_use]
     fn is_antifragile(&self, at: Self::Stressor, delta: Self::Stressor) -> bool
@@ -411,20 +1175,276 @@ pub trait TriadAnalysis: Antifragile {
             payoff_low - payoff_high <= threshold
         }
     }
+
+    /// Jensen gap `E[f(X)] - f(E[X])` under a stressor distribution
+    ///
+    /// `E[f(X)]` is estimated from `samples` draws of a fixed-seed
+    /// [`Noise`] stream, so repeated calls agree; `E[X]` is the
+    /// distribution's own [`mean`](StressorDistribution::mean). Positive for
+    /// payoffs that gain from the distribution's spread, negative for those
+    /// it costs. NaN with no samples.
+    ///
+    /// For a classification with a standard error, see
+    /// [`monte_carlo_distribution`](crate::monte_carlo::monte_carlo_distribution).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use antifragile::{Antifragile, TriadAnalysis};
+    /// use antifragile::distribution::Empirical;
+    ///
+    /// struct Square;
+    /// impl Antifragile for Square {
+    ///     type Stressor = f64;
+    ///     type Payoff = f64;
+    ///     fn payoff(&self, x: Self::Stressor) -> Self::Payoff {
+    ///         x * x
+    ///     }
+    /// }
+    ///
+    /// // For x², the Jensen gap is the variance: 1 for {1, 3}
+    /// let stressors = Empirical::new(vec![1.0, 3.0]);
+    /// assert!((Square.jensen_gap(&stressors, 100_000) - 1.0).abs() < 0.02);
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    fn jensen_gap<D>(&self, distribution: &D, samples: usize) -> Self::Payoff
+    where
+        Self: Antifragile<Stressor = f64, Payoff = f64>,
+        D: StressorDistribution + ?Sized,
+    {
+        let mut noise = Noise::new(0x5EED);
+        let total: f64 = (0..samples)
+            .map(|_| self.payoff(distribution.sample(&mut noise)))
+            .sum();
+        total / samples as f64 - self.payoff(distribution.mean())
+    }
+
+    /// Classify a noisy payoff, returning Robust unless the curvature is
+    /// statistically significant
+    ///
+    /// Evaluates the three-point second difference
+    /// `f(x+Δ) + f(x-Δ) - 2·f(x)` `n_samples` times with fresh payoff
+    /// evaluations and runs a two-sided one-sample t-test of its mean
+    /// against zero. The classification follows the sign of the mean only if
+    /// the p-value is below `alpha`; an inconclusive test, or fewer than two
+    /// samples, gives [`Triad::Robust`]. The result carries one minus the
+    /// p-value as its confidence and the mean second difference as its
+    /// margin.
+    ///
+    /// Use this when single evaluations carry measurement noise, such as
+    /// benchmark timings, and [`classify`](Self::classify) flips from run to
+    /// run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::cell::RefCell;
+    ///
+    /// use antifragile::learning::Noise;
+    /// use antifragile::{Antifragile, Triad, TriadAnalysis};
+    ///
+    /// /// Throughput measured with jitter
+    /// struct Benchmark(RefCell<Noise>);
+    /// impl Antifragile for Benchmark {
+    ///     type Stressor = f64;
+    ///     type Payoff = f64;
+    ///     fn payoff(&self, load: Self::Stressor) -> Self::Payoff {
+    ///         100.0 - load * load + self.0.borrow_mut().normal()
+    ///     }
+    /// }
+    ///
+    /// let bench = Benchmark(RefCell::new(Noise::new(7)));
+    /// let reading = bench.classify_stochastic(5.0, 1.0, 200, 0.01);
+    /// assert_eq!(reading.triad, Triad::Fragile);
+    /// assert!(reading.confidence > 0.99);
+    /// // Too small a perturbation to see through the jitter
+    /// assert_eq!(bench.classify_stochastic(5.0, 0.01, 200, 0.01).triad, Triad::Robust);
+    /// ```
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[allow(clippy::cast_precision_loss)]
+    fn classify_stochastic(
+        &self,
+        at: f64,
+        delta: f64,
+        n_samples: usize,
+        alpha: f64,
+    ) -> TriadWithConfidence<f64>
+    where
+        Self: Antifragile<Stressor = f64, Payoff = f64>,
+    {
+        if n_samples < 2 {
+            return TriadWithConfidence {
+                triad: Triad::Robust,
+                confidence: 0.0,
+                margin: 0.0,
+            };
+        }
+        // Welford's running mean and variance, so no sample buffer is needed
+        let (mut mean, mut squares) = (0.0, 0.0);
+        for k in 1..=n_samples {
+            let difference =
+                self.payoff(at + delta) + self.payoff(at - delta) - 2.0 * self.payoff(at);
+            let step = difference - mean;
+            mean += step / k as f64;
+            squares += step * (difference - mean);
+        }
+        let count = n_samples as f64;
+        let std_error = sqrt(squares / (count - 1.0) / count);
+
+        let p_value = if std_error > 0.0 {
+            student_t_two_sided(mean / std_error, (n_samples - 1) as f64)
+        } else if mean == 0.0 {
+            1.0
+        } else {
+            0.0
+        };
+        let triad = if p_value >= alpha {
+            Triad::Robust
+        } else if mean > 0.0 {
+            Triad::Antifragile
+        } else {
+            Triad::Fragile
+        };
+        TriadWithConfidence {
+            triad,
+            confidence: 1.0 - p_value,
+            margin: mean,
+        }
+    }
 }
 
 // Blanket implementation for all Antifragile types
 impl<T: Antifragile> TriadAnalysis for T {}
 
-/// A wrapper that marks a system as verified on the Triad
+/// `f(x+Δ) + f(x-Δ)` and `2·f(x)`, rejecting non-finite payoffs and sums
+fn checked_sum<T>(
+    f_x_minus: T::Payoff,
+    f_x: T::Payoff,
+    f_x_plus: T::Payoff,
+) -> Result<(T::Payoff, T::Payoff), ClassifyError>
+where
+    T: Antifragile + ?Sized,
+    T::Payoff: Into<f64>,
+{
+    for (probe, value) in [
+        (Probe::Below, f_x_minus),
+        (Probe::At, f_x),
+        (Probe::Above, f_x_plus),
+    ] {
+        let value: f64 = value.into();
+        if value.is_nan() {
+            return Err(ClassifyError::NotANumber { probe });
+        }
+        if value.is_infinite() {
+            return Err(ClassifyError::Infinite { probe });
+        }
+    }
+
+    let sum = f_x_plus + f_x_minus;
+    let twin_f_x = T::twin(f_x);
+    if !sum.into().is_finite() || !twin_f_x.into().is_finite() {
+        return Err(ClassifyError::Overflow);
+    }
+    Ok((sum, twin_f_x))
+}
+
+/// Absolute value without `std` (`f64::abs` is not available in `core`)
+#[inline]
+pub(crate) fn abs(value: f64) -> f64 {
+    if value < 0.0 { -value } else { value }
+}
+
+/// How long a [`Verified`] classification stays trustworthy
+///
+/// Timestamps are caller-supplied durations since any fixed epoch, so the
+/// policy works without a clock on `no_std` targets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum VerifiedPolicy {
+    /// The classification never goes stale
+    #[default]
+    Forever,
+    /// The classification goes stale once it is this old
+    Ttl(Duration),
+}
+
+impl VerifiedPolicy {
+    /// Returns true if a classification made at `verified_at` is stale at `now`
+    ///
+    /// A `now` earlier than `verified_at` counts as no time passed.
+    #[must_use]
+    pub fn is_stale(&self, verified_at: Duration, now: Duration) -> bool {
+        match self {
+            Self::Forever => false,
+            Self::Ttl(ttl) => now.saturating_sub(verified_at) >= *ttl,
+        }
+    }
+}
+
+/// One re-verification recorded in the audit log of an [`AuditedVerified`]
+#[cfg(feature = "std")]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Verified<T> {
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Reverification<S> {
+    /// The operating point of the test
+    pub at: S,
+    /// The perturbation of the test
+    pub delta: S,
+    /// The classification it produced
+    pub classification: Triad,
+    /// The wall-clock time it ran
+    pub timestamp: SystemTime,
+}
+
+/// A wrapper that marks a system as verified on the Triad
+///
+/// Alongside the classification it records the conditions it was made
+/// under: the operating point and delta of the stressor type `S`, and under
+/// `std` the wall-clock time of the test. [`VerifiedOf`] names the type
+/// [`check`](Self::check) returns for a system.
+///
+/// By default the classification is trusted forever. Attach a
+/// [`VerifiedPolicy`] with [`with_policy`](Self::with_policy) to have it go
+/// stale, and refresh it with [`ensure_fresh`](Self::ensure_fresh). Under
+/// `std`, [`with_history`](Self::with_history) wraps it in an
+/// [`AuditedVerified`] that keeps an audit log of re-verifications, so that
+/// `Verified` itself stays `Copy` when the system and stressor are.
+///
+/// Two verifications are equal when they hold equal systems with the same
+/// classification, operating point, and delta; when each was made, and
+/// under which policy, does not take part.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "schemars",
+    schemars(bound = "T: schemars::JsonSchema, S: schemars::JsonSchema + Default + Serialize")
+)]
+pub struct Verified<T, S> {
     inner: T,
     classification: Triad,
+    #[cfg_attr(feature = "serde", serde(default))]
+    at: S,
+    #[cfg_attr(feature = "serde", serde(default))]
+    delta: S,
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(default = "unix_epoch"))]
+    timestamp: SystemTime,
+    #[cfg_attr(feature = "serde", serde(default))]
+    policy: VerifiedPolicy,
+    #[cfg_attr(feature = "serde", serde(default))]
+    checked_at: Duration,
 }
 
-impl<T: Antifragile> Verified<T>
+/// A [`Verified`] system with the operating point and delta of its own
+/// stressor type, as [`Verified::check`] returns
+pub type VerifiedOf<T> = Verified<T, <T as Antifragile>::Stressor>;
+
+impl<T: Antifragile> Verified<T, T::Stressor>
 where
     T::Payoff: Sub<Output = T::Payoff> + Default + PartialOrd,
 {
@@ -432,18 +1452,174 @@ where
     #[must_use]
     pub fn check(system: T, at: T::Stressor, delta: T::Stressor) -> Self {
         let classification = system.classify(at, delta);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            system = core::any::type_name::<T>(),
+            ?classification,
+            "verified"
+        );
+        Self::from_parts(system, classification, at, delta)
+    }
+
+    /// Re-verify classification at a new operating point
+    ///
+    /// Updates the stored classification and conditions by re-running the
+    /// convexity test at the specified operating point and delta.
+    ///
+    /// Under `std`, [`verified_at`](Self::verified_at) advances by the
+    /// wall-clock time since the previous test, so the policy clock sees the
+    /// new classification as made now. Without `std` there is no clock to
+    /// read; use [`re_verify_at`](Self::re_verify_at) instead.
+    #[inline]
+    pub fn re_verify(&mut self, at: T::Stressor, delta: T::Stressor) {
+        self.retest(at, delta);
+        #[cfg(feature = "std")]
+        {
+            let now = SystemTime::now();
+            let elapsed = now.duration_since(self.timestamp).unwrap_or_default();
+            self.checked_at = self.checked_at.saturating_add(elapsed);
+            self.timestamp = now;
+        }
+    }
+
+    /// Re-verify at a new operating point, recording `now` on the policy
+    /// clock as the time the classification was made
+    pub fn re_verify_at(&mut self, at: T::Stressor, delta: T::Stressor, now: Duration) {
+        self.retest(at, delta);
+        #[cfg(feature = "std")]
+        {
+            self.timestamp = SystemTime::now();
+        }
+        self.checked_at = now;
+    }
+
+    fn retest(&mut self, at: T::Stressor, delta: T::Stressor) {
+        self.classification = self.inner.classify(at, delta);
+        self.at = at;
+        self.delta = delta;
+    }
+
+    /// Check if the classification still holds at a different operating point
+    ///
+    /// Returns `true` if classifying at the new point yields the same result
+    /// as the stored classification.
+    #[inline]
+    #[must_use]
+    pub fn still_holds(&self, at: T::Stressor, delta: T::Stressor) -> bool {
+        self.inner.classify(at, delta) == self.classification
+    }
+
+    /// Re-verify if the classification is stale at `now`
+    ///
+    /// Re-runs the convexity test at `at` and `delta` and records `now` as
+    /// the verification time. Returns `true` if the test was re-run.
+    pub fn ensure_fresh(&mut self, at: T::Stressor, delta: T::Stressor, now: Duration) -> bool {
+        if !self.is_stale(now) {
+            return false;
+        }
+        self.re_verify_at(at, delta, now);
+        true
+    }
+}
+
+impl<T, S: Copy> Verified<T, S> {
+    /// Pair a system with a classification computed elsewhere at `at` and
+    /// `delta`
+    pub(crate) fn from_parts(inner: T, classification: Triad, at: S, delta: S) -> Self {
         Self {
-            inner: system,
+            inner,
             classification,
+            at,
+            delta,
+            #[cfg(feature = "std")]
+            timestamp: SystemTime::now(),
+            policy: VerifiedPolicy::Forever,
+            checked_at: Duration::ZERO,
         }
     }
 
+    /// Attach a staleness policy, counting the classification as made at
+    /// `now`
+    #[inline]
+    #[must_use]
+    pub const fn with_policy(mut self, policy: VerifiedPolicy, now: Duration) -> Self {
+        self.policy = policy;
+        self.checked_at = now;
+        self
+    }
+
+    /// Keep an audit log of the last `capacity` re-verifications
+    ///
+    /// See [`AuditedVerified`].
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn with_history(self, capacity: usize) -> AuditedVerified<T, S> {
+        AuditedVerified {
+            verified: self,
+            history: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Get the staleness policy
+    #[inline]
+    #[must_use]
+    pub const fn policy(&self) -> VerifiedPolicy {
+        self.policy
+    }
+
+    /// Get the time the classification was last made
+    #[inline]
+    #[must_use]
+    pub const fn verified_at(&self) -> Duration {
+        self.checked_at
+    }
+
+    /// Returns true if the classification is stale at `now` under the policy
+    #[inline]
+    #[must_use]
+    pub fn is_stale(&self, now: Duration) -> bool {
+        self.policy.is_stale(self.checked_at, now)
+    }
+
+    /// Get the classification if it is still fresh at `now`
+    #[inline]
+    #[must_use]
+    pub fn fresh_classification(&self, now: Duration) -> Option<Triad> {
+        (!self.is_stale(now)).then_some(self.classification)
+    }
+
     /// Get the verified Triad classification
     #[inline]
     pub const fn classification(&self) -> Triad {
         self.classification
     }
 
+    /// Get the operating point the classification was made at
+    #[inline]
+    #[must_use]
+    pub const fn at(&self) -> S {
+        self.at
+    }
+
+    /// Get the perturbation the classification was made with
+    #[inline]
+    #[must_use]
+    pub const fn delta(&self) -> S {
+        self.delta
+    }
+
+    /// Get the wall-clock time the convexity test last ran
+    ///
+    /// Set by [`check`](Self::check) and [`re_verify`](Self::re_verify), and
+    /// independent of the caller-supplied clock of the [`VerifiedPolicy`].
+    #[cfg(feature = "std")]
+    #[inline]
+    #[must_use]
+    pub const fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
     /// Get reference to inner system
     #[inline]
     #[must_use]
@@ -458,6 +1634,57 @@ where
         self.inner
     }
 
+    /// Transform the inner system and re-verify it under the same
+    /// conditions
+    ///
+    /// The result is classified at the recorded operating point and delta,
+    /// and keeps the staleness policy and the time it was last verified
+    /// against it, so a mapped system goes stale no later than the original.
+    pub fn map<U, F>(self, f: F) -> Verified<U, S>
+    where
+        U: Antifragile<Stressor = S>,
+        F: FnOnce(T) -> U,
+    {
+        let inner = f(self.inner);
+        Verified {
+            classification: inner.classify(self.at, self.delta),
+            inner,
+            at: self.at,
+            delta: self.delta,
+            #[cfg(feature = "std")]
+            timestamp: SystemTime::now(),
+            policy: self.policy,
+            checked_at: self.checked_at,
+        }
+    }
+
+    /// Compare this verification, as the earlier one, with `other`
+    ///
+    /// `other` may wrap a different system, such as the same service before
+    /// and after a deploy.
+    #[must_use]
+    pub fn diff<U>(&self, other: &Verified<U, S>) -> VerificationDiff<S> {
+        VerificationDiff {
+            before: self.classification,
+            after: other.classification,
+            before_at: self.at,
+            before_delta: self.delta,
+            after_at: other.at,
+            after_delta: other.delta,
+        }
+    }
+
+    /// Transform the recorded classification, keeping everything else
+    ///
+    /// For systems whose classification is known to change in a fixed way,
+    /// such as a negated payoff swapping Fragile and Antifragile, without
+    /// running the test again.
+    #[must_use]
+    pub fn map_classification<F: FnOnce(Triad) -> Triad>(mut self, f: F) -> Self {
+        self.classification = f(self.classification);
+        self
+    }
+
     /// Returns true if the system was classified as Antifragile
     #[inline]
     #[must_use]
@@ -478,53 +1705,292 @@ where
     pub const fn is_robust(&self) -> bool {
         self.classification.is_robust()
     }
+}
+
+impl<T, S> AsRef<T> for Verified<T, S> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T, S> core::ops::Deref for Verified<T, S> {
+    type Target = T;
 
-    /// Re-verify classification at a new operating point
-    ///
-    /// Updates the stored classification by re-running the convexity test
-    /// at the specified operating point and delta.
     #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T: PartialEq, S: PartialEq> PartialEq for Verified<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+            && self.classification == other.classification
+            && self.at == other.at
+            && self.delta == other.delta
+    }
+}
+
+impl<T: Eq, S: Eq> Eq for Verified<T, S> {}
+
+/// A [`Verified`] system that keeps an audit log of its re-verifications
+///
+/// Created by [`Verified::with_history`]. Each call to
+/// [`re_verify`](Self::re_verify), [`re_verify_at`](Self::re_verify_at),
+/// or a re-running [`ensure_fresh`](Self::ensure_fresh) appends an entry,
+/// and the oldest entries are dropped beyond the capacity. Dereferences to the
+/// verification, and equality ignores the log as it does the timestamp.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "T: Deserialize<'de>, S: Deserialize<'de> + Default"))
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "schemars",
+    schemars(bound = "T: schemars::JsonSchema, S: schemars::JsonSchema + Default + Serialize")
+)]
+pub struct AuditedVerified<T, S> {
+    verified: Verified<T, S>,
+    history: VecDeque<Reverification<S>>,
+    capacity: usize,
+}
+
+#[cfg(feature = "std")]
+impl<T: Antifragile> AuditedVerified<T, T::Stressor>
+where
+    T::Payoff: Sub<Output = T::Payoff> + Default + PartialOrd,
+{
+    /// Re-verify at a new operating point, as [`Verified::re_verify`], and
+    /// log the result
     pub fn re_verify(&mut self, at: T::Stressor, delta: T::Stressor) {
-        self.classification = self.inner.classify(at, delta);
+        self.verified.re_verify(at, delta);
+        self.record();
     }
 
-    /// Check if the classification still holds at a different operating point
-    ///
-    /// Returns `true` if classifying at the new point yields the same result
-    /// as the stored classification.
+    /// Re-verify as of `now`, as [`Verified::re_verify_at`], and log the
+    /// result
+    pub fn re_verify_at(&mut self, at: T::Stressor, delta: T::Stressor, now: Duration) {
+        self.verified.re_verify_at(at, delta, now);
+        self.record();
+    }
+
+    /// Re-verify if stale at `now`, as [`Verified::ensure_fresh`], logging
+    /// the result if the test was re-run
+    pub fn ensure_fresh(&mut self, at: T::Stressor, delta: T::Stressor, now: Duration) -> bool {
+        let refreshed = self.verified.ensure_fresh(at, delta, now);
+        if refreshed {
+            self.record();
+        }
+        refreshed
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S: Copy> AuditedVerified<T, S> {
+    /// Change the capacity of the log, dropping the oldest entries beyond
+    /// it; a capacity of zero turns the log off and clears it
+    #[must_use]
+    pub fn with_history(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        while self.history.len() > capacity {
+            self.history.pop_front();
+        }
+        self
+    }
+
+    /// Get the audit log of re-verifications, oldest first
     #[inline]
     #[must_use]
-    pub fn still_holds(&self, at: T::Stressor, delta: T::Stressor) -> bool {
-        self.inner.classify(at, delta) == self.classification
+    pub const fn history(&self) -> &VecDeque<Reverification<S>> {
+        &self.history
+    }
+
+    /// Get the verification
+    #[inline]
+    #[must_use]
+    pub const fn verified(&self) -> &Verified<T, S> {
+        &self.verified
+    }
+
+    /// Drop the log and return the verification
+    #[inline]
+    #[must_use]
+    pub fn into_verified(self) -> Verified<T, S> {
+        self.verified
+    }
+
+    fn record(&mut self) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.history.len() == self.capacity {
+            self.history.pop_front();
+        }
+        self.history.push_back(Reverification {
+            at: self.verified.at,
+            delta: self.verified.delta,
+            classification: self.verified.classification,
+            timestamp: self.verified.timestamp,
+        });
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> core::ops::Deref for AuditedVerified<T, S> {
+    type Target = Verified<T, S>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.verified
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: PartialEq, S: PartialEq> PartialEq for AuditedVerified<T, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.verified == other.verified
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Eq, S: Eq> Eq for AuditedVerified<T, S> {}
+
+/// The timestamp of a verification serialized before timestamps were kept
+#[cfg(all(feature = "std", feature = "serde"))]
+const fn unix_epoch() -> SystemTime {
+    SystemTime::UNIX_EPOCH
+}
+
+impl<T: Antifragile + Default> Default for Verified<T, T::Stressor>
+where
+    T::Stressor: Default,
+    T::Payoff: Sub<Output = T::Payoff> + Default + PartialOrd,
+{
+    /// Creates a verified system using `T::default()` classified at the default stressor
+    fn default() -> Self {
+        let system = T::default();
+        let at = T::Stressor::default();
+        Self::check(system, at, at)
+    }
+}
+
+/// How two verifications differ, from [`Verified::diff`]
+///
+/// Displays as, for example, `Antifragile at 10 ± 1 became Fragile at 12 ± 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VerificationDiff<S> {
+    /// The earlier classification
+    pub before: Triad,
+    /// The later classification
+    pub after: Triad,
+    /// The operating point of the earlier verification
+    pub before_at: S,
+    /// The perturbation of the earlier verification
+    pub before_delta: S,
+    /// The operating point of the later verification
+    pub after_at: S,
+    /// The perturbation of the later verification
+    pub after_delta: S,
+}
+
+impl<S: PartialEq> VerificationDiff<S> {
+    /// Returns true if the classification changed
+    #[inline]
+    #[must_use]
+    pub fn changed(&self) -> bool {
+        self.before != self.after
+    }
+
+    /// Returns true if the classification became more fragile
+    #[inline]
+    #[must_use]
+    pub fn regressed(&self) -> bool {
+        self.after < self.before
+    }
+
+    /// Returns true if the classification became less fragile
+    #[inline]
+    #[must_use]
+    pub fn improved(&self) -> bool {
+        self.after > self.before
+    }
+
+    /// Returns true if both verifications used the same operating point and
+    /// perturbation, so a change is down to the system alone
+    #[inline]
+    #[must_use]
+    pub fn same_conditions(&self) -> bool {
+        self.before_at == self.after_at && self.before_delta == self.after_delta
+    }
+}
+
+impl<S: Display> Display for VerificationDiff<S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let verb = if self.before == self.after {
+            "stayed"
+        } else {
+            "became"
+        };
+        write!(
+            f,
+            "{:?} at {} ± {} {verb} {:?} at {} ± {}",
+            self.before,
+            self.before_at,
+            self.before_delta,
+            self.after,
+            self.after_at,
+            self.after_delta
+        )
     }
 }
 
-impl<T> AsRef<T> for Verified<T> {
-    #[inline]
-    fn as_ref(&self) -> &T {
-        &self.inner
-    }
+/// An anonymous system defined by a payoff closure
+///
+/// Usually built with the [`antifragile!`](crate::antifragile!) macro, which
+/// spares tests, examples, and exploratory code a named type per system.
+#[derive(Clone, Copy)]
+pub struct FnSystem<F, S, P> {
+    payoff: F,
+    _types: PhantomData<fn(S) -> P>,
 }
 
-impl<T> core::ops::Deref for Verified<T> {
-    type Target = T;
+impl<F, S, P> FnSystem<F, S, P>
+where
+    F: Fn(S) -> P,
+{
+    /// Wrap `payoff` as a system
+    pub const fn new(payoff: F) -> Self {
+        Self {
+            payoff,
+            _types: PhantomData,
+        }
+    }
+}
 
-    #[inline]
-    fn deref(&self) -> &Self::Target {
-        &self.inner
+impl<F, S, P> core::fmt::Debug for FnSystem<F, S, P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FnSystem").finish_non_exhaustive()
     }
 }
 
-impl<T: Antifragile + Default> Default for Verified<T>
+impl<F, S, P> Antifragile for FnSystem<F, S, P>
 where
-    T::Stressor: Default,
-    T::Payoff: Sub<Output = T::Payoff> + Default + PartialOrd,
+    F: Fn(S) -> P,
+    S: Copy + Add<Output = S> + Sub<Output = S>,
+    P: Copy + Add<Output = P> + PartialOrd,
 {
-    /// Creates a verified system using `T::default()` classified at the default stressor
-    fn default() -> Self {
-        let system = T::default();
-        let at = T::Stressor::default();
-        Self::check(system, at, at)
+    type Stressor = S;
+    type Payoff = P;
+
+    #[inline]
+    fn payoff(&self, stressor: S) -> P {
+        (self.payoff)(stressor)
     }
 }
 
@@ -533,6 +1999,7 @@ mod tests {
     use super::*;
 
     // Test helpers - mathematical functions for verifying the convexity test
+    #[derive(Debug, Clone, Copy, PartialEq)]
     struct ConvexFn; // f(x) = x²
     struct ConcaveFn; // f(x) = √x
     struct LinearFn {
@@ -586,6 +2053,377 @@ mod tests {
         assert_eq!(system.classify(10.0, 1.0), Triad::Robust);
     }
 
+    #[test]
+    fn test_convexity_sign_matches_classification() {
+        assert!(ConvexFn.convexity(10.0, 1.0) > 0.0);
+        assert!(ConcaveFn.convexity(10.0, 1.0) < 0.0);
+        let linear = LinearFn {
+            slope: 2.0,
+            intercept: 5.0,
+        };
+        assert!(linear.convexity(10.0, 1.0).abs() < f64::EPSILON);
+
+        // Negative payoffs keep the sign of the second difference
+        let negative = LinearFn {
+            slope: 0.0,
+            intercept: -1.0,
+        };
+        assert!(negative.convexity(10.0, 1.0).abs() < f64::EPSILON);
+        assert!(ConvexFn.convexity(-10.0, 1.0) > 0.0);
+    }
+
+    #[test]
+    fn test_convexity_magnitude_ranks_systems() {
+        // (121 + 81 - 200) / 200 vs (4 + 0 - 2) / 2
+        assert!((ConvexFn.convexity(10.0, 1.0) - 0.01).abs() < 1e-12);
+        assert!((ConvexFn.convexity(1.0, 1.0) - 1.0).abs() < 1e-12);
+        assert!(ConvexFn.convexity(0.0, 1.0).is_infinite());
+    }
+
+    #[test]
+    fn test_convexity_integer_payoff() {
+        struct Cube;
+        impl Antifragile for Cube {
+            type Stressor = i32;
+            type Payoff = i32;
+            fn payoff(&self, x: i32) -> i32 {
+                x * x * x
+            }
+        }
+        // (27 + 1 - 16) / 16
+        assert!((Cube.convexity(2, 1) - 0.75).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_antifragility_index_bounds_and_sign() {
+        assert!((ConvexFn.antifragility_index(0.0, 1.0) - 1.0).abs() < 1e-12);
+        // (√8 - 2 - 2) / √8
+        let expected = 1.0 - 2.0_f64.sqrt();
+        assert!((ConcaveFn.antifragility_index(4.0, 4.0) - expected).abs() < 1e-12);
+        let linear = LinearFn {
+            slope: 2.0,
+            intercept: 5.0,
+        };
+        assert!(linear.antifragility_index(10.0, 1.0).abs() < f64::EPSILON);
+        let flat = LinearFn {
+            slope: 0.0,
+            intercept: 3.0,
+        };
+        assert!(flat.antifragility_index(10.0, 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_antifragility_index_is_scale_free() {
+        struct Scaled(f64, f64);
+        impl Antifragile for Scaled {
+            type Stressor = f64;
+            type Payoff = f64;
+            fn payoff(&self, x: f64) -> f64 {
+                self.0 * x * x + self.1
+            }
+        }
+        let base = Scaled(1.0, 0.0).antifragility_index(10.0, 1.0);
+        assert!((base - 0.05).abs() < 1e-12);
+        assert!((Scaled(250.0, -1e3).antifragility_index(10.0, 1.0) - base).abs() < 1e-12);
+        assert!((Scaled(-1.0, 0.0).antifragility_index(0.0, 1.0) + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_classify_profile_finds_regions() {
+        struct Cubic;
+        impl Antifragile for Cubic {
+            type Stressor = f64;
+            type Payoff = f64;
+            fn payoff(&self, x: f64) -> f64 {
+                x * x * x
+            }
+        }
+        let profile = Cubic.classify_profile(-3.0..=3.0, 7, 0.5);
+        assert_eq!(profile.len(), 7);
+        assert!(profile[..3].iter().all(|(_, t)| *t == Triad::Fragile));
+        assert_eq!(profile[3], (0.0, Triad::Robust));
+        assert!(profile[4..].iter().all(|(_, t)| *t == Triad::Antifragile));
+    }
+
+    #[test]
+    fn test_classify_profile_degenerate_steps() {
+        assert!(ConvexFn.classify_profile(0.0..=1.0, 0, 0.1).is_empty());
+        assert_eq!(
+            ConvexFn.classify_profile(2.0..=5.0, 1, 0.1),
+            vec![(2.0, Triad::Antifragile)]
+        );
+    }
+
+    #[test]
+    fn test_try_classify_matches_classify() {
+        assert_eq!(ConvexFn.try_classify(10.0, 1.0), Ok(Triad::Antifragile));
+        assert_eq!(ConcaveFn.try_classify(10.0, 1.0), Ok(Triad::Fragile));
+    }
+
+    #[test]
+    fn test_try_classify_rejects_non_finite_payoffs() {
+        struct Reciprocal;
+        impl Antifragile for Reciprocal {
+            type Stressor = f64;
+            type Payoff = f64;
+            fn payoff(&self, x: f64) -> f64 {
+                1.0 / x
+            }
+        }
+        struct Log;
+        impl Antifragile for Log {
+            type Stressor = f64;
+            type Payoff = f64;
+            fn payoff(&self, x: f64) -> f64 {
+                x.ln()
+            }
+        }
+        // classify() silently calls a NaN payoff robust
+        assert_eq!(Log.classify(0.5, 1.0), Triad::Robust);
+        assert_eq!(
+            Log.try_classify(0.5, 1.0),
+            Err(ClassifyError::NotANumber {
+                probe: Probe::Below
+            })
+        );
+        assert_eq!(
+            Reciprocal.try_classify(0.0, 1.0),
+            Err(ClassifyError::Infinite { probe: Probe::At })
+        );
+        assert_eq!(
+            Reciprocal.try_classify(-1.0, 1.0),
+            Err(ClassifyError::Infinite {
+                probe: Probe::Above
+            })
+        );
+    }
+
+    #[test]
+    fn test_classify_checked_separates_inconclusive_readings() {
+        assert_eq!(
+            ConvexFn.classify_checked(10.0, 1.0, 1e-9),
+            Classification::Antifragile
+        );
+        assert_eq!(
+            ConcaveFn.classify_checked(10.0, 1.0, 1e-9),
+            Classification::Fragile
+        );
+        let linear = LinearFn {
+            slope: 2.0,
+            intercept: 1.0,
+        };
+        assert_eq!(
+            linear.classify_checked(3.0, 1.0, 0.0),
+            Classification::Robust
+        );
+        assert_eq!(
+            ConvexFn.classify_checked(10.0, 0.0, 0.0),
+            Classification::Inconclusive(Inconclusive::ZeroDelta)
+        );
+        // Second difference of x² is 2·Δ² = 2
+        assert_eq!(
+            ConvexFn.classify_checked(10.0, 1.0, 2.0),
+            Classification::Inconclusive(Inconclusive::WithinTolerance)
+        );
+        let overflow = LinearFn {
+            slope: 0.0,
+            intercept: f64::MAX,
+        }
+        .classify_checked(1.0, 1.0, 0.0);
+        assert_eq!(
+            overflow,
+            Classification::Inconclusive(Inconclusive::Invalid(ClassifyError::Overflow))
+        );
+        assert!(overflow.is_inconclusive());
+        assert_eq!(overflow.triad(), None);
+        assert_eq!(
+            overflow.to_string(),
+            "Inconclusive (payoff sum overflowed during the convexity test)"
+        );
+        assert_eq!(
+            Classification::from(Triad::Fragile).triad(),
+            Some(Triad::Fragile)
+        );
+        assert_eq!(
+            Classification::Antifragile.to_string(),
+            Triad::Antifragile.to_string()
+        );
+    }
+
+    #[test]
+    fn test_classify_detailed_matches_classify() {
+        let detail = ConcaveFn.classify_detailed(10.0, 1.0);
+        assert_eq!(detail.triad, ConcaveFn.classify(10.0, 1.0));
+        assert_eq!(detail.triad, Triad::Fragile);
+        assert!((detail.sum - (detail.below + detail.above)).abs() < f64::EPSILON);
+        assert!((detail.twin - 2.0 * detail.at).abs() < f64::EPSILON);
+        assert!((detail.difference - (detail.twin - detail.sum)).abs() < f64::EPSILON);
+
+        // NaN compares false both ways, as in classify
+        let nan = LinearFn {
+            slope: 1.0,
+            intercept: f64::NAN,
+        }
+        .classify_detailed(1.0, 1.0);
+        assert_eq!(nan.triad, Triad::Robust);
+        assert!(nan.sum.is_nan());
+        assert!(nan.difference.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_explain_states_the_comparison() {
+        let fragile = ConcaveFn.explain(4.0, 1.0);
+        assert_eq!(fragile.detail, ConcaveFn.classify_detailed(4.0, 1.0));
+        assert_eq!(
+            format!("{fragile:.3}"),
+            "f(x+Δ) + f(x-Δ) = 3.968 fell short of 2·f(x) = 4.000 by 0.032, therefore Fragile"
+        );
+        let linear = LinearFn {
+            slope: 2.0,
+            intercept: 0.0,
+        };
+        assert_eq!(
+            linear.explain(2.0, 1.0).to_string(),
+            "f(x+Δ) + f(x-Δ) = 8 equals 2·f(x) = 8, therefore Robust"
+        );
+        let nan = LinearFn {
+            slope: 1.0,
+            intercept: f64::NAN,
+        };
+        assert!(
+            nan.explain(1.0, 1.0).to_string().ends_with(
+                "cannot be compared with 2·f(x) = NaN, so the test falls back to Robust"
+            )
+        );
+    }
+
+    #[test]
+    fn test_try_classify_detects_overflow() {
+        let huge = LinearFn {
+            slope: 0.0,
+            intercept: f64::MAX,
+        };
+        assert_eq!(huge.try_classify(1.0, 1.0), Err(ClassifyError::Overflow));
+        assert_eq!(
+            ClassifyError::Infinite {
+                probe: Probe::Above
+            }
+            .to_string(),
+            "payoff f(x + delta) is infinite"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_jensen_gap_sign_and_size() {
+        use crate::distribution::Empirical;
+
+        let stressors = Empirical::new(vec![8.0, 10.0, 12.0]);
+        // Var({8, 10, 12}) = 8/3
+        let convex = ConvexFn.jensen_gap(&stressors, 50_000);
+        assert!((convex - 8.0 / 3.0).abs() < 0.1);
+        assert!(ConcaveFn.jensen_gap(&stressors, 50_000) < 0.0);
+        let linear = LinearFn {
+            slope: 2.0,
+            intercept: 1.0,
+        };
+        assert!(linear.jensen_gap(&stressors, 0).is_nan());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_classify_stochastic_needs_significance() {
+        use core::cell::RefCell;
+
+        struct Jittered<F> {
+            curve: F,
+            noise: RefCell<Noise>,
+        }
+
+        impl<F: Fn(f64) -> f64> Antifragile for Jittered<F> {
+            type Stressor = f64;
+            type Payoff = f64;
+
+            fn payoff(&self, x: f64) -> f64 {
+                (self.curve)(x) + 0.5 * self.noise.borrow_mut().normal()
+            }
+        }
+
+        fn jittered<F>(curve: F) -> Jittered<F> {
+            Jittered {
+                curve,
+                noise: RefCell::new(Noise::new(3)),
+            }
+        }
+
+        let convex = jittered(|x: f64| x * x);
+        let linear = jittered(|x: f64| 2.0 * x);
+        // A single noisy evaluation is no better than a coin flip on a linear payoff
+        assert_ne!(linear.classify(1.0, 0.1), Triad::Robust);
+        assert_eq!(
+            linear.classify_stochastic(1.0, 0.1, 500, 0.01).triad,
+            Triad::Robust
+        );
+        let reading = convex.classify_stochastic(1.0, 1.0, 100, 0.01);
+        assert_eq!(reading.triad, Triad::Antifragile);
+        assert!(reading.confidence > 0.99);
+        // f(2) + f(0) - 2·f(1) = 2
+        assert!((reading.margin - 2.0).abs() < 0.5);
+        assert_eq!(
+            convex.classify_stochastic(1.0, 1.0, 1, 0.01).triad,
+            Triad::Robust
+        );
+
+        // Without noise any nonzero curvature is significant
+        assert_eq!(
+            ConcaveFn.classify_stochastic(4.0, 1.0, 2, 0.05).triad,
+            Triad::Fragile
+        );
+        let exact = LinearFn {
+            slope: 2.0,
+            intercept: 0.0,
+        };
+        assert_eq!(
+            exact.classify_stochastic(1.0, 0.5, 10, 0.05).triad,
+            Triad::Robust
+        );
+    }
+
+    #[test]
+    fn test_triad_with_confidence_ordering() {
+        let reading = |triad, confidence, margin| TriadWithConfidence {
+            triad,
+            confidence,
+            margin,
+        };
+        let mut readings = [
+            reading(Triad::Antifragile, 0.999, 2.0),
+            reading(Triad::Robust, 0.5, 0.1),
+            reading(Triad::Fragile, 0.96, -0.5),
+            reading(Triad::Antifragile, 0.96, 0.5),
+            reading(Triad::Fragile, 0.999, -2.0),
+            reading(Triad::Robust, 0.5, -0.1),
+        ];
+        readings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let order: Vec<(Triad, f64)> = readings.iter().map(|r| (r.triad, r.margin)).collect();
+        assert_eq!(
+            order,
+            [
+                (Triad::Fragile, -2.0),
+                (Triad::Fragile, -0.5),
+                (Triad::Robust, -0.1),
+                (Triad::Robust, 0.1),
+                (Triad::Antifragile, 0.5),
+                (Triad::Antifragile, 2.0),
+            ]
+        );
+        assert!(reading(Triad::Robust, f64::NAN, 0.0) > reading(Triad::Fragile, 0.5, -1.0));
+        assert_eq!(
+            reading(Triad::Robust, f64::NAN, 0.0).partial_cmp(&reading(Triad::Robust, 0.5, 0.0)),
+            None
+        );
+    }
+
     #[test]
     fn test_gains_from_stress() {
         let convex = ConvexFn;
@@ -761,6 +2599,20 @@ mod tests {
         assert_eq!(Triad::Antifragile.opposite().opposite(), Triad::Antifragile);
     }
 
+    #[test]
+    fn test_triad_lattice() {
+        for a in Triad::ALL {
+            for b in Triad::ALL {
+                assert_eq!(a.meet(b), a.min(b));
+                assert_eq!(a.join(b), a.max(b));
+            }
+        }
+        assert_eq!(Triad::worst_of(Triad::ALL), Some(Triad::Fragile));
+        assert_eq!(Triad::best_of(Triad::ALL), Some(Triad::Antifragile));
+        assert_eq!(Triad::best_of([Triad::Robust]), Some(Triad::Robust));
+        assert_eq!(Triad::best_of([]), None);
+    }
+
     #[test]
     fn test_triad_iter() {
         let all: Vec<_> = Triad::iter().collect();
@@ -987,6 +2839,172 @@ mod tests {
         assert!(verified.still_holds(20.0, 2.0));
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_verified_records_conditions() {
+        let mut verified = Verified::check(ConvexFn, 10.0, 1.0);
+        assert!((verified.at() - 10.0).abs() < f64::EPSILON);
+        assert!((verified.delta() - 1.0).abs() < f64::EPSILON);
+        let first = verified.timestamp();
+
+        verified.re_verify(5.0, 0.0);
+        assert!((verified.at() - 5.0).abs() < f64::EPSILON);
+        assert!(verified.delta().abs() < f64::EPSILON);
+        assert!(verified.timestamp() >= first);
+    }
+
+    #[test]
+    fn test_verified_of_uses_the_system_stressor() {
+        struct Squares;
+        impl Antifragile for Squares {
+            type Stressor = i64;
+            type Payoff = i64;
+            fn payoff(&self, x: i64) -> i64 {
+                x * x
+            }
+        }
+
+        let verified: VerifiedOf<Squares> = Verified::check(Squares, 10, 2);
+        assert_eq!((verified.at(), verified.delta()), (10, 2));
+    }
+
+    #[test]
+    fn test_verified_equality_ignores_when() {
+        let first = Verified::check(ConvexFn, 10.0, 1.0);
+        let second = Verified::check(ConvexFn, 10.0, 1.0).with_policy(
+            VerifiedPolicy::Ttl(Duration::from_secs(60)),
+            Duration::from_secs(5),
+        );
+        assert_eq!(first, second);
+        assert_ne!(first, Verified::check(ConvexFn, 10.0, 2.0));
+    }
+
+    #[test]
+    fn test_verified_map_reverifies_at_recorded_conditions() {
+        struct Scaled(f64);
+        impl Antifragile for Scaled {
+            type Stressor = f64;
+            type Payoff = f64;
+            fn payoff(&self, x: Self::Stressor) -> Self::Payoff {
+                self.0 * x * x
+            }
+        }
+
+        let ttl = VerifiedPolicy::Ttl(Duration::from_secs(60));
+        let verified =
+            Verified::check(Scaled(1.0), 10.0, 1.0).with_policy(ttl, Duration::from_secs(5));
+        let negated = verified.map(|system| Scaled(-system.0));
+        assert!(negated.is_fragile());
+        assert!((negated.at() - 10.0).abs() < f64::EPSILON);
+        assert_eq!(negated.policy(), ttl);
+        assert_eq!(negated.verified_at(), Duration::from_secs(5));
+
+        let flipped = negated.map_classification(Triad::opposite);
+        assert!(flipped.is_antifragile());
+        assert!((flipped.inner().0 + 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_verified_history_is_bounded() {
+        // Without a log, a verification of a Copy system is Copy
+        let verified = Verified::check(ConvexFn, 10.0, 1.0);
+        let copy = verified;
+        assert_eq!(copy, verified);
+
+        let mut verified = verified.with_history(2);
+        for delta in [1.0, 2.0, 0.0] {
+            verified.re_verify(10.0, delta);
+        }
+        let deltas: Vec<f64> = verified.history().iter().map(|entry| entry.delta).collect();
+        assert_eq!(deltas, [2.0, 0.0]);
+        let last = verified.history().back().unwrap();
+        assert_eq!(last.classification, Triad::Robust);
+        assert_eq!(last.timestamp, verified.timestamp());
+
+        assert_eq!(*verified.verified(), Verified::check(ConvexFn, 10.0, 0.0));
+        assert_eq!(
+            verified,
+            Verified::check(ConvexFn, 10.0, 0.0).with_history(2)
+        );
+        let verified = verified.with_history(0);
+        assert!(verified.history().is_empty());
+        assert!(verified.into_verified().is_robust());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_verified_diff() {
+        let before = Verified::check(ConvexFn, 10.0, 1.0);
+        let mut after = Verified::check(ConvexFn, 10.0, 1.0);
+        after.re_verify(12.0, 0.0);
+
+        let diff = before.diff(&after);
+        assert!(diff.changed() && diff.regressed() && !diff.improved());
+        assert!(!diff.same_conditions());
+        assert_eq!(
+            diff.to_string(),
+            "Antifragile at 10 ± 1 became Robust at 12 ± 0"
+        );
+
+        let same = before.diff(&before);
+        assert!(!same.changed() && same.same_conditions());
+        assert_eq!(
+            same.to_string(),
+            "Antifragile at 10 ± 1 stayed Antifragile at 10 ± 1"
+        );
+    }
+
+    #[test]
+    fn test_verified_policy_forever_never_stale() {
+        let verified = Verified::check(ConvexFn, 10.0, 1.0);
+        assert_eq!(verified.policy(), VerifiedPolicy::Forever);
+        assert!(!verified.is_stale(Duration::MAX));
+        assert_eq!(
+            verified.fresh_classification(Duration::MAX),
+            Some(Triad::Antifragile)
+        );
+    }
+
+    #[test]
+    fn test_verified_ensure_fresh_after_ttl() {
+        let ttl = VerifiedPolicy::Ttl(Duration::from_secs(60));
+        let mut verified =
+            Verified::check(ConvexFn, 10.0, 1.0).with_policy(ttl, Duration::from_secs(100));
+        assert_eq!(verified.verified_at(), Duration::from_secs(100));
+
+        // Fresh until the TTL elapses; an earlier clock counts as no time
+        assert!(!verified.ensure_fresh(10.0, 0.0, Duration::from_secs(159)));
+        assert!(!verified.is_stale(Duration::from_secs(50)));
+        assert_eq!(
+            verified.fresh_classification(Duration::from_secs(160)),
+            None
+        );
+
+        assert!(verified.ensure_fresh(10.0, 0.0, Duration::from_secs(160)));
+        assert_eq!(verified.classification(), Triad::Robust);
+        assert_eq!(verified.verified_at(), Duration::from_secs(160));
+        assert!(!verified.is_stale(Duration::from_secs(160)));
+
+        verified.re_verify_at(10.0, 1.0, Duration::from_secs(200));
+        assert_eq!(verified.verified_at(), Duration::from_secs(200));
+        assert!(verified.is_antifragile());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_verified_re_verify_advances_policy_clock() {
+        let ttl = VerifiedPolicy::Ttl(Duration::from_secs(60));
+        let mut verified =
+            Verified::check(ConvexFn, 10.0, 1.0).with_policy(ttl, Duration::from_secs(100));
+        // The last test ran half a minute ago
+        verified.timestamp -= Duration::from_secs(30);
+
+        verified.re_verify(10.0, 1.0);
+        assert!(verified.verified_at() >= Duration::from_secs(130));
+        assert!(!verified.is_stale(Duration::from_secs(175)));
+    }
+
     #[test]
     fn test_classify_with_tolerance_exact_boundary() {
         // When sum == twin_f_x exactly (linear function) and epsilon < 0,
@@ -1005,4 +3023,20 @@ mod tests {
         let result = linear.classify_with_tolerance(10.0, 1.0, -1.0);
         assert_eq!(result, Triad::Fragile);
     }
+
+    #[test]
+    fn test_antifragile_macro_builds_fn_systems() {
+        let explicit = crate::antifragile!(|x: f64| -> f64 { -x.abs() });
+        assert_eq!(explicit.classify(0.0, 1.0), Triad::Fragile);
+        assert!((explicit.payoff(-2.0) + 2.0).abs() < f64::EPSILON);
+
+        let inferred = crate::antifragile!(|n: i128| n * n);
+        assert_eq!(inferred.classify(10_i128.pow(18), 1), Triad::Antifragile);
+
+        let offset = 5.0;
+        let captured = crate::antifragile!(|x: f64| x + offset);
+        let copy = captured;
+        assert_eq!(copy.classify(1.0, 0.5), Triad::Robust);
+        assert_eq!(format!("{captured:?}"), "FnSystem { .. }");
+    }
 }
@@ -0,0 +1,299 @@
+//! # Arrow and Parquet export
+//!
+//! Analysis results as Arrow
+//! [`RecordBatch`](arrow_array::RecordBatch)es, ready for a data platform
+//! without a detour through JSON.
+//! [`ToRecordBatch`](crate::arrow::ToRecordBatch) is implemented for
+//! profile sweeps, delta ladders, Monte Carlo draws, and regime verdicts:
+//! either a [`VerifiedMap`](crate::regime::VerifiedMap)'s current snapshot
+//! or a history of verdicts collected over time. Classifications are written
+//! as their short names
+//! (`Fragile`, `Robust`, `Antifragile`).
+//!
+//! [`to_parquet`](crate::arrow::ToRecordBatch::to_parquet) writes the same
+//! batch as a Parquet file:
+//!
+//! ```rust
+//! use antifragile::Antifragile;
+//! use antifragile::arrow::ToRecordBatch;
+//! use antifragile::sweep::ProfileSweep;
+//!
+//! struct Convex;
+//!
+//! impl Antifragile for Convex {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, x: f64) -> f64 {
+//!         x * x
+//!     }
+//! }
+//!
+//! let sweep = ProfileSweep::new("convex", &Convex, [1.0, 2.0, 3.0], 0.5);
+//! let batch = sweep.to_record_batch()?;
+//! assert_eq!(batch.num_rows(), 3);
+//! assert_eq!(batch.schema().field(0).name(), "stressor");
+//!
+//! let parquet = sweep.to_parquet(Vec::new())?;
+//! assert!(parquet.starts_with(b"PAR1"));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::io::Write;
+use std::sync::Arc;
+
+use arrow_array::{
+    Array, ArrayRef, BooleanArray, Float64Array, RecordBatch, StringArray, UInt64Array,
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use parquet::arrow::ArrowWriter;
+use parquet::errors::ParquetError;
+
+use crate::ladder::DeltaLadder;
+use crate::monte_carlo::MonteCarloSamples;
+use crate::regime::{RegimeVerdict, VerifiedMap};
+use crate::sweep::ProfileSweep;
+use crate::{Antifragile, Triad};
+
+/// Analysis results that can be exported as one Arrow record batch
+pub trait ToRecordBatch {
+    /// One row per point, rung, draw, or verdict
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ArrowError`] if the columns do not form a valid batch.
+    fn to_record_batch(&self) -> Result<RecordBatch, ArrowError>;
+
+    /// Write [`to_record_batch`](Self::to_record_batch) to `writer` as a
+    /// Parquet file with default properties, returning the writer
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParquetError`] if the batch cannot be built or written.
+    fn to_parquet<W: Write + Send>(&self, writer: W) -> Result<W, ParquetError> {
+        let batch = self.to_record_batch()?;
+        let mut parquet = ArrowWriter::try_new(writer, batch.schema(), None)?;
+        parquet.write(&batch)?;
+        parquet.into_inner()
+    }
+}
+
+impl ToRecordBatch for ProfileSweep<f64, f64> {
+    /// Columns `stressor`, `below`, `payoff`, `above`, `classification`
+    fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        let points = self.points();
+        batch([
+            float("stressor", points.iter().map(|(at, _)| *at)),
+            float("below", points.iter().map(|(_, detail)| detail.below)),
+            float("payoff", points.iter().map(|(_, detail)| detail.at)),
+            float("above", points.iter().map(|(_, detail)| detail.above)),
+            triads(points.iter().map(|(_, detail)| detail.triad)),
+        ])
+    }
+}
+
+impl ToRecordBatch for DeltaLadder {
+    /// Columns `delta`, `down`, `up`, `gap`, `classification`
+    fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        batch([
+            float("delta", self.rungs.iter().map(|rung| rung.delta)),
+            float("down", self.rungs.iter().map(|rung| rung.down)),
+            float("up", self.rungs.iter().map(|rung| rung.up)),
+            float("gap", self.rungs.iter().map(|rung| rung.gap)),
+            triads(self.rungs.iter().map(|rung| rung.classification)),
+        ])
+    }
+}
+
+impl ToRecordBatch for MonteCarloSamples {
+    /// Columns `stressor`, `payoff`, one row per draw
+    fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        batch([
+            float("stressor", self.stressors.iter().copied()),
+            float("payoff", self.payoffs.iter().copied()),
+        ])
+    }
+}
+
+impl ToRecordBatch for [RegimeVerdict<f64>] {
+    /// Columns `at`, `delta`, `classification`, `generation`, in the order
+    /// the verdicts were collected
+    fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        batch(verdicts(&self.iter().collect::<Vec<_>>()))
+    }
+}
+
+impl<T> ToRecordBatch for VerifiedMap<T>
+where
+    T: Antifragile<Stressor = f64>,
+    T::Payoff: core::ops::Sub<Output = T::Payoff> + Default + PartialOrd,
+{
+    /// Columns `regime`, `at`, `delta`, `classification`, `generation`,
+    /// `stale`, in regime name order
+    fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        let names: StringArray = self.iter().map(|(name, _)| Some(name)).collect();
+        let stale: BooleanArray = self.iter().map(|(name, _)| self.is_stale(name)).collect();
+        let [at, delta, classification, generation] =
+            verdicts(&self.iter().map(|(_, verdict)| verdict).collect::<Vec<_>>());
+        batch([
+            column("regime", DataType::Utf8, names),
+            at,
+            delta,
+            classification,
+            generation,
+            column("stale", DataType::Boolean, stale),
+        ])
+    }
+}
+
+/// A named, non-nullable column
+type Column = (Field, ArrayRef);
+
+/// Assemble columns into a batch
+fn batch(columns: impl IntoIterator<Item = Column>) -> Result<RecordBatch, ArrowError> {
+    let (fields, arrays): (Vec<Field>, Vec<ArrayRef>) = columns.into_iter().unzip();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+}
+
+/// A column of `data_type`
+fn column(name: &str, data_type: DataType, array: impl Array + 'static) -> Column {
+    (Field::new(name, data_type, false), Arc::new(array))
+}
+
+/// A `Float64` column
+fn float(name: &str, values: impl Iterator<Item = f64>) -> Column {
+    let array: Float64Array = values.map(Some).collect();
+    column(name, DataType::Float64, array)
+}
+
+/// A `classification` column of short Triad names
+fn triads(values: impl Iterator<Item = Triad>) -> Column {
+    let array: StringArray = values.map(|triad| Some(format!("{triad:?}"))).collect();
+    column("classification", DataType::Utf8, array)
+}
+
+/// The `at`, `delta`, `classification`, and `generation` columns of verdicts
+fn verdicts(verdicts: &[&RegimeVerdict<f64>]) -> [Column; 4] {
+    let generation: UInt64Array = verdicts.iter().map(|v| Some(v.generation)).collect();
+    [
+        float("at", verdicts.iter().map(|v| v.at)),
+        float("delta", verdicts.iter().map(|v| v.delta)),
+        triads(verdicts.iter().map(|v| v.classification)),
+        column("generation", DataType::UInt64, generation),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ladder::{LadderConfig, delta_ladder};
+    use crate::monte_carlo::{MonteCarloConfig, Noise, monte_carlo_samples};
+
+    struct Square;
+
+    impl Antifragile for Square {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&self, x: f64) -> f64 {
+            x * x
+        }
+    }
+
+    fn names(batch: &RecordBatch) -> Vec<String> {
+        batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect()
+    }
+
+    fn strings(batch: &RecordBatch, column: &str) -> Vec<String> {
+        let array = batch.column_by_name(column).unwrap();
+        let array = array.as_any().downcast_ref::<StringArray>().unwrap();
+        (0..array.len())
+            .map(|i| array.value(i).to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_sweep_and_ladder_columns() {
+        let sweep = ProfileSweep::new("square", &Square, [1.0, 2.0], 0.5);
+        let batch = sweep.to_record_batch().unwrap();
+        assert_eq!(
+            names(&batch),
+            ["stressor", "below", "payoff", "above", "classification"]
+        );
+        assert_eq!(strings(&batch, "classification"), ["Antifragile"; 2]);
+
+        let config = LadderConfig {
+            relative: false,
+            ..LadderConfig::default()
+        };
+        let ladder = delta_ladder(&Square, 1.0, 7, &config);
+        let batch = ladder.to_record_batch().unwrap();
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(names(&batch)[3], "gap");
+    }
+
+    #[test]
+    fn test_monte_carlo_draws() {
+        let config = MonteCarloConfig {
+            samples: 20,
+            ..MonteCarloConfig::default()
+        };
+        let run = monte_carlo_samples(&Square, |noise: &mut Noise| noise.normal(), &config);
+        let batch = run.to_record_batch().unwrap();
+        assert_eq!(names(&batch), ["stressor", "payoff"]);
+        let payoffs = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert!((payoffs.value(7) - run.payoffs[7]).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_regime_snapshot_and_history() {
+        let mut map = VerifiedMap::new(Square);
+        let mut history = Vec::new();
+        history.extend(map.verify("low", 1.0, 0.5));
+        map.mark_changed();
+        history.extend(map.verify("low", 2.0, 0.5));
+        map.verify("high", 10.0, 1.0);
+
+        let snapshot = map.to_record_batch().unwrap();
+        assert_eq!(strings(&snapshot, "regime"), ["high", "low"]);
+        let stale = |batch: &RecordBatch| {
+            let array = batch.column_by_name("stale").unwrap();
+            let array = array.as_any().downcast_ref::<BooleanArray>().unwrap();
+            array.iter().collect::<Vec<_>>()
+        };
+        assert_eq!(stale(&snapshot), [Some(false); 2]);
+        map.mark_changed();
+        assert_eq!(stale(&map.to_record_batch().unwrap()), [Some(true); 2]);
+
+        history.extend(map.get("low").copied());
+        let batch = history.to_record_batch().unwrap();
+        assert_eq!(
+            names(&batch),
+            ["at", "delta", "classification", "generation"]
+        );
+        let generations = batch
+            .column_by_name("generation")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(generations.values().to_vec(), [0, 1]);
+    }
+
+    #[test]
+    fn test_parquet_file_is_framed() {
+        let sweep = ProfileSweep::new("square", &Square, [1.0, 2.0], 0.5);
+        let bytes = sweep.to_parquet(Vec::new()).unwrap();
+        assert!(bytes.starts_with(b"PAR1"));
+        assert!(bytes.ends_with(b"PAR1"));
+    }
+}
@@ -0,0 +1,241 @@
+//! # Asynchronous payoffs
+//!
+//! When a payoff is an HTTP call to a load-testing backend or a query
+//! against a live service, blocking on it inside an async runtime stalls
+//! the executor. [`AntifragileAsync`](crate::asynchronous::AntifragileAsync)
+//! mirrors [`Antifragile`](crate::Antifragile) with a payoff that returns a
+//! future, and
+//! [`AsyncTriadAnalysis`](crate::asynchronous::AsyncTriadAnalysis) runs the
+//! convexity test on it.
+//!
+//! The three evaluations `f(x-Δ)`, `f(x)`, and `f(x+Δ)` are independent, so
+//! [`classify`](crate::asynchronous::AsyncTriadAnalysis::classify) polls
+//! them concurrently on the calling task rather than one after another. No
+//! runtime is assumed: the futures work under any executor, and on
+//! `no_std` targets.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::asynchronous::{AntifragileAsync, AsyncTriadAnalysis};
+//!
+//! /// Throughput reported by a load-testing backend
+//! struct Backend;
+//!
+//! impl AntifragileAsync for Backend {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     async fn payoff(&self, load: f64) -> f64 {
+//!         // a real system would await an HTTP response here
+//!         load * load
+//!     }
+//! }
+//!
+//! let triad = pollster::block_on(Backend.classify(10.0, 1.0));
+//! assert_eq!(triad, Triad::Antifragile);
+//! ```
+
+use core::future::{Future, poll_fn};
+use core::ops::{Add, Sub};
+use core::pin::pin;
+use core::task::Poll;
+
+use crate::{Triad, Verified};
+
+/// A system whose payoff is computed asynchronously
+///
+/// The asynchronous counterpart of [`Antifragile`](crate::Antifragile), with
+/// the same bounds on stressor and payoff. Implementations may write
+/// `async fn payoff`.
+pub trait AntifragileAsync {
+    /// The type of stressor (e.g., volatility, load, perturbation)
+    type Stressor: Copy + Add<Output = Self::Stressor> + Sub<Output = Self::Stressor>;
+
+    /// The type of payoff/outcome (must be comparable and additive)
+    type Payoff: Copy + Add<Output = Self::Payoff> + PartialOrd;
+
+    /// The payoff function, resolving to the outcome under the given stress
+    fn payoff(&self, stressor: Self::Stressor) -> impl Future<Output = Self::Payoff>;
+
+    /// Returns the payoff added to itself (r + r)
+    ///
+    /// See [`Antifragile::twin`](crate::Antifragile::twin).
+    fn twin(r: Self::Payoff) -> Self::Payoff {
+        r + r
+    }
+}
+
+/// Extension trait running the convexity test on asynchronous systems
+pub trait AsyncTriadAnalysis: AntifragileAsync {
+    /// Classify the system at operating point `at` with perturbation `delta`
+    ///
+    /// Compares `f(x+Δ) + f(x-Δ)` against `2·f(x)` exactly as
+    /// [`TriadAnalysis::classify`](crate::TriadAnalysis::classify) does,
+    /// awaiting the three payoffs concurrently.
+    fn classify(&self, at: Self::Stressor, delta: Self::Stressor) -> impl Future<Output = Triad> {
+        async move {
+            let (f_x_minus, f_x, f_x_plus) = join3(
+                self.payoff(at - delta),
+                self.payoff(at),
+                self.payoff(at + delta),
+            )
+            .await;
+
+            let sum = f_x_plus + f_x_minus;
+            let twin_f_x = Self::twin(f_x);
+            if sum > twin_f_x {
+                Triad::Antifragile
+            } else if sum < twin_f_x {
+                Triad::Fragile
+            } else {
+                Triad::Robust
+            }
+        }
+    }
+}
+
+impl<T: AntifragileAsync + ?Sized> AsyncTriadAnalysis for T {}
+
+impl<T: AntifragileAsync> Verified<T, T::Stressor> {
+    /// Verify an asynchronous system's classification at a given operating
+    /// point, the counterpart of [`Verified::check`]
+    pub async fn check_async(system: T, at: T::Stressor, delta: T::Stressor) -> Self {
+        let classification = system.classify(at, delta).await;
+        Self::from_parts(system, classification, at, delta)
+    }
+
+    /// Check if the classification still holds at a different operating
+    /// point, the counterpart of [`Verified::still_holds`]
+    pub async fn still_holds_async(&self, at: T::Stressor, delta: T::Stressor) -> bool {
+        self.inner().classify(at, delta).await == self.classification()
+    }
+}
+
+/// Await three futures concurrently on the current task
+async fn join3<A: Future, B: Future, C: Future>(
+    a: A,
+    b: B,
+    c: C,
+) -> (A::Output, B::Output, C::Output) {
+    let (mut a, mut b, mut c) = (pin!(a), pin!(b), pin!(c));
+    let (mut a_out, mut b_out, mut c_out) = (None, None, None);
+    poll_fn(move |cx| {
+        if a_out.is_none() {
+            if let Poll::Ready(value) = a.as_mut().poll(cx) {
+                a_out = Some(value);
+            }
+        }
+        if b_out.is_none() {
+            if let Poll::Ready(value) = b.as_mut().poll(cx) {
+                b_out = Some(value);
+            }
+        }
+        if c_out.is_none() {
+            if let Poll::Ready(value) = c.as_mut().poll(cx) {
+                c_out = Some(value);
+            }
+        }
+        match (a_out.take(), b_out.take(), c_out.take()) {
+            (Some(a), Some(b), Some(c)) => Poll::Ready((a, b, c)),
+            (a, b, c) => {
+                (a_out, b_out, c_out) = (a, b, c);
+                Poll::Pending
+            }
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    /// A future that is pending for `polls` polls before resolving
+    struct Delay<T> {
+        polls: usize,
+        value: Option<T>,
+    }
+
+    impl<T: Unpin> Future for Delay<T> {
+        type Output = T;
+
+        fn poll(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<T> {
+            if self.polls == 0 {
+                return Poll::Ready(self.value.take().expect("polled after completion"));
+            }
+            self.polls -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    /// A concave payoff whose responses arrive after a delay, counting the
+    /// requests issued
+    struct Remote {
+        issued: Cell<usize>,
+    }
+
+    impl AntifragileAsync for Remote {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&self, load: f64) -> impl Future<Output = f64> {
+            self.issued.set(self.issued.get() + 1);
+            Delay {
+                polls: 2,
+                value: Some(load.sqrt()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_classify_awaits_all_probes() {
+        let remote = Remote {
+            issued: Cell::new(0),
+        };
+        assert_eq!(
+            pollster::block_on(remote.classify(4.0, 1.0)),
+            Triad::Fragile
+        );
+        assert_eq!(remote.issued.get(), 3);
+    }
+
+    #[test]
+    fn test_join3_polls_concurrently() {
+        let (a, b, c) = pollster::block_on(join3(
+            Delay {
+                polls: 3,
+                value: Some(1),
+            },
+            Delay {
+                polls: 0,
+                value: Some("two"),
+            },
+            Delay {
+                polls: 1,
+                value: Some(3.0),
+            },
+        ));
+        assert_eq!((a, b), (1, "two"));
+        assert!((c - 3.0_f64).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_verified_check_async() {
+        struct Linear;
+
+        impl AntifragileAsync for Linear {
+            type Stressor = i32;
+            type Payoff = i32;
+
+            async fn payoff(&self, x: i32) -> i32 {
+                3 * x + 1
+            }
+        }
+
+        let verified = pollster::block_on(Verified::check_async(Linear, 5, 2));
+        assert!(verified.is_robust());
+        assert!(pollster::block_on(verified.still_holds_async(-5, 1)));
+    }
+}
@@ -0,0 +1,392 @@
+//! # Curvature attribution
+//!
+//! A single classification over a range hides *where* the curvature is. A
+//! service can test antifragile across `0..=1000` requests per second while
+//! every bit of that convexity sits in a narrow band it rarely operates in.
+//!
+//! The total curvature over `[a, b]` is the integral of the second
+//! derivative, `∫ f''(x) dx = f'(b) - f'(a)`: the change in slope across the
+//! range. [`curvature_attribution`](crate::attribution::curvature_attribution)
+//! decomposes that integral over equal-width bands. Each
+//! [`CurvatureBand`](crate::attribution::CurvatureBand) records the change in
+//! slope across it and its share of all the curvature of the same sign, so
+//! the bands sum to the total and the largest shares show where the convexity
+//! or concavity lives.
+//!
+//! [`CurvatureAttribution::section`](crate::attribution::CurvatureAttribution::section)
+//! renders the bands as a [`ReportSection`](crate::report::ReportSection).
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad};
+//! use antifragile::attribution::{AttributionConfig, curvature_attribution};
+//!
+//! /// Flat revenue until surge pricing kicks in above 85 rps
+//! struct Service;
+//!
+//! impl Antifragile for Service {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, rps: f64) -> f64 {
+//!         4.0 * (rps - 85.0).max(0.0)
+//!     }
+//! }
+//!
+//! let attribution = curvature_attribution(&Service, 0.0..=100.0, &AttributionConfig::default());
+//! let dominant = attribution.dominant(Triad::Antifragile).unwrap();
+//! assert_eq!((dominant.start, dominant.end), (80.0, 90.0));
+//! assert_eq!(dominant.share, 1.0);
+//! // A tenth of the range holds all of the convexity
+//! assert_eq!(attribution.concentration(Triad::Antifragile, 1.0), Some(0.1));
+//! ```
+
+use core::ops::RangeInclusive;
+
+use crate::report::{ReportEntry, ReportSection};
+use crate::{Antifragile, Triad};
+
+/// Configuration for [`curvature_attribution`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttributionConfig {
+    /// Number of equal-width bands the range is divided into
+    pub bands: usize,
+    /// Evaluation cells per band; finer cells separate convex and concave
+    /// regions that share a band
+    pub resolution: usize,
+    /// Curvature at or below this fraction of the steepest slope counts as
+    /// none, absorbing rounding in linear regions
+    pub tolerance: f64,
+}
+
+impl Default for AttributionConfig {
+    /// Ten bands of eight cells each, tolerance `1e-9`
+    fn default() -> Self {
+        Self {
+            bands: 10,
+            resolution: 8,
+            tolerance: 1e-9,
+        }
+    }
+}
+
+/// The curvature contributed by one band of the stressor range
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurvatureBand {
+    /// Lower edge of the band
+    pub start: f64,
+    /// Upper edge of the band
+    pub end: f64,
+    /// Change in slope across the band: its part of `∫ f''`
+    pub curvature: f64,
+    /// Fraction of all the curvature of the same sign held by this band; zero
+    /// for robust bands
+    pub share: f64,
+    /// Sign of the band's curvature beyond the tolerance
+    pub classification: Triad,
+}
+
+/// Result of [`curvature_attribution`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurvatureAttribution {
+    /// The bands in stressor order
+    pub bands: Vec<CurvatureBand>,
+    /// Sum of the curvature of antifragile bands
+    pub convex: f64,
+    /// Sum of the curvature of fragile bands, a negative number
+    pub concave: f64,
+}
+
+impl CurvatureAttribution {
+    /// Net curvature over the range: `f'(b) - f'(a)` up to the tolerance
+    #[must_use]
+    pub fn net(&self) -> f64 {
+        self.convex + self.concave
+    }
+
+    /// The band holding the largest share of curvature classified `triad`
+    ///
+    /// `None` for [`Triad::Robust`] or when no band has that classification.
+    #[must_use]
+    pub fn dominant(&self, triad: Triad) -> Option<&CurvatureBand> {
+        if triad == Triad::Robust {
+            return None;
+        }
+        self.bands
+            .iter()
+            .filter(|band| band.classification == triad)
+            .max_by(|a, b| a.share.total_cmp(&b.share))
+    }
+
+    /// Fraction of the range width needed to hold `fraction` of the curvature
+    /// classified `triad`, taking the bands with the largest shares first
+    ///
+    /// Values near zero mean the curvature is concentrated in a narrow band.
+    /// `None` for [`Triad::Robust`] or when no band has that classification.
+    #[must_use]
+    pub fn concentration(&self, triad: Triad, fraction: f64) -> Option<f64> {
+        if triad == Triad::Robust {
+            return None;
+        }
+        let mut shares: Vec<&CurvatureBand> = self
+            .bands
+            .iter()
+            .filter(|band| band.classification == triad)
+            .collect();
+        if shares.is_empty() {
+            return None;
+        }
+        shares.sort_by(|a, b| b.share.total_cmp(&a.share));
+
+        let (first, last) = (self.bands.first()?, self.bands.last()?);
+        let range = last.end - first.start;
+        let (mut held, mut width) = (0.0, 0.0);
+        for band in shares {
+            held += band.share;
+            width += band.end - band.start;
+            if held >= fraction {
+                break;
+            }
+        }
+        Some(width / range)
+    }
+
+    /// A report section with one entry per band, classified by the sign of
+    /// its curvature and scored by its share
+    ///
+    /// Bands holding more than half of the curvature of their sign carry a
+    /// warning naming the share and the fraction of the range they span.
+    #[must_use]
+    pub fn section(&self, title: &str) -> ReportSection {
+        let range = match (self.bands.first(), self.bands.last()) {
+            (Some(first), Some(last)) => last.end - first.start,
+            _ => return ReportSection::new(title),
+        };
+        self.bands
+            .iter()
+            .fold(ReportSection::new(title), |section, band| {
+                let mut entry =
+                    ReportEntry::new(format!("{}..{}", band.start, band.end), band.classification)
+                        .with_score(band.share);
+                if band.share > 0.5 {
+                    let kind = if band.classification == Triad::Antifragile {
+                        "convex"
+                    } else {
+                        "concave"
+                    };
+                    entry = entry.with_warning(format!(
+                        "{:.0}% of the {kind} curvature lies in {:.0}% of the range",
+                        100.0 * band.share,
+                        100.0 * (band.end - band.start) / range
+                    ));
+                }
+                section.with_entry(entry)
+            })
+    }
+}
+
+/// Decompose the curvature of `system` over `range` into
+/// [`AttributionConfig::bands`] equal-width bands
+///
+/// The range is evaluated at `bands · resolution + 1` evenly spaced points,
+/// never outside it. The slope change at each interior point is credited to
+/// the band containing it, split evenly between two bands at a shared edge,
+/// so the band curvatures sum to the difference between the last and first
+/// secant slopes. An empty or reversed range, or zero bands, yields no bands.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn curvature_attribution<T>(
+    system: &T,
+    range: RangeInclusive<f64>,
+    config: &AttributionConfig,
+) -> CurvatureAttribution
+where
+    T: Antifragile<Stressor = f64, Payoff = f64> + ?Sized,
+{
+    let (start, end) = (*range.start(), *range.end());
+    let resolution = config.resolution.max(1);
+    let cells = config.bands * resolution;
+    if cells == 0 || end <= start {
+        return CurvatureAttribution {
+            bands: Vec::new(),
+            convex: 0.0,
+            concave: 0.0,
+        };
+    }
+
+    let width = (end - start) / cells as f64;
+    let values: Vec<f64> = (0..=cells)
+        .map(|j| system.payoff(start + width * j as f64))
+        .collect();
+    let slopes: Vec<f64> = values.windows(2).map(|w| (w[1] - w[0]) / width).collect();
+    let steepest = slopes.iter().fold(1.0_f64, |m, s| m.max(s.abs()));
+
+    let mut curvature = vec![0.0; config.bands];
+    for (j, pair) in slopes.windows(2).enumerate() {
+        let (node, kink) = (j + 1, pair[1] - pair[0]);
+        let band = node / resolution;
+        if node % resolution == 0 {
+            curvature[band - 1] += 0.5 * kink;
+            curvature[band] += 0.5 * kink;
+        } else {
+            curvature[band] += kink;
+        }
+    }
+
+    let classify = |c: f64| {
+        if c > config.tolerance * steepest {
+            Triad::Antifragile
+        } else if c < -config.tolerance * steepest {
+            Triad::Fragile
+        } else {
+            Triad::Robust
+        }
+    };
+    let (mut convex, mut concave) = (0.0, 0.0);
+    for &c in &curvature {
+        match classify(c) {
+            Triad::Antifragile => convex += c,
+            Triad::Fragile => concave += c,
+            Triad::Robust => {}
+        }
+    }
+
+    let edge = |i: usize| start + (end - start) * i as f64 / config.bands as f64;
+    let bands = curvature
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| {
+            let classification = classify(c);
+            let share = match classification {
+                Triad::Antifragile => c / convex,
+                Triad::Fragile => c / concave,
+                Triad::Robust => 0.0,
+            };
+            CurvatureBand {
+                start: edge(i),
+                end: edge(i + 1),
+                curvature: c,
+                share,
+                classification,
+            }
+        })
+        .collect();
+
+    CurvatureAttribution {
+        bands,
+        convex,
+        concave,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Payoff<F>(F);
+
+    impl<F: Fn(f64) -> f64> Antifragile for Payoff<F> {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&self, x: f64) -> f64 {
+            (self.0)(x)
+        }
+    }
+
+    #[test]
+    fn test_quadratic_spreads_curvature_evenly() {
+        let attribution = curvature_attribution(
+            &Payoff(|x: f64| x * x),
+            0.0..=10.0,
+            &AttributionConfig::default(),
+        );
+        assert_eq!(attribution.bands.len(), 10);
+        assert!(
+            attribution
+                .bands
+                .iter()
+                .all(|b| b.classification == Triad::Antifragile)
+        );
+        // Secant slopes run from 0.125 to 19.875
+        assert!((attribution.net() - 19.75).abs() < 1e-9);
+        assert!(attribution.dominant(Triad::Fragile).is_none());
+        let interior = &attribution.bands[1..9];
+        assert!(interior.iter().all(|b| (b.curvature - 2.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_mixed_signs_are_attributed_to_their_bands() {
+        // Concave below zero, convex above
+        let attribution = curvature_attribution(
+            &Payoff(|x: f64| x * x * x),
+            -1.0..=1.0,
+            &AttributionConfig {
+                bands: 4,
+                ..AttributionConfig::default()
+            },
+        );
+        let classes: Vec<Triad> = attribution.bands.iter().map(|b| b.classification).collect();
+        assert_eq!(
+            classes,
+            [
+                Triad::Fragile,
+                Triad::Fragile,
+                Triad::Antifragile,
+                Triad::Antifragile
+            ]
+        );
+        assert!((attribution.convex + attribution.concave).abs() < 1e-9);
+        assert!((attribution.dominant(Triad::Fragile).unwrap().start + 1.0).abs() < f64::EPSILON);
+        let shares: f64 = attribution.bands[2..].iter().map(|b| b.share).sum();
+        assert!((shares - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_section_warns_about_concentrated_curvature() {
+        let kinked = Payoff(|x: f64| (x - 55.0).max(0.0));
+        let attribution =
+            curvature_attribution(&kinked, 0.0..=100.0, &AttributionConfig::default());
+        assert!((attribution.convex - 1.0).abs() < 1e-9);
+        assert_eq!(
+            attribution.concentration(Triad::Antifragile, 1.0),
+            Some(0.1)
+        );
+        assert_eq!(attribution.concentration(Triad::Robust, 1.0), None);
+
+        let section = attribution.section("load");
+        assert_eq!(section.entries().len(), 10);
+        let band = &section.entries()[5];
+        assert_eq!(band.label, "50..60");
+        assert_eq!(band.classification, Triad::Antifragile);
+        assert_eq!(
+            band.warnings,
+            vec!["100% of the convex curvature lies in 10% of the range".to_string()]
+        );
+        assert!(section.entries()[0].warnings.is_empty());
+    }
+
+    #[test]
+    fn test_linear_and_degenerate_ranges() {
+        let linear = curvature_attribution(
+            &Payoff(|x: f64| 3.0 * x + 1.0),
+            0.1..=0.7,
+            &AttributionConfig::default(),
+        );
+        assert!(
+            linear
+                .bands
+                .iter()
+                .all(|b| b.classification == Triad::Robust)
+        );
+        assert!(linear.net().abs() < f64::EPSILON);
+        assert_eq!(linear.concentration(Triad::Antifragile, 0.5), None);
+
+        let empty = curvature_attribution(
+            &Payoff(|x: f64| x * x),
+            1.0..=1.0,
+            &AttributionConfig::default(),
+        );
+        assert!(empty.bands.is_empty());
+        assert!(empty.section("none").entries().is_empty());
+    }
+}
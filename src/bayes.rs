@@ -0,0 +1,323 @@
+//! # Bayesian posterior over the Triad
+//!
+//! A point classification says nothing about how sure it is. Streaming
+//! telemetry arrives one `(stressor, payoff)` pair at a time, and early on
+//! a handful of noisy points can look convex or concave by chance.
+//! [`TriadPosterior`](crate::bayes::TriadPosterior) instead keeps a
+//! probability for each class and sharpens it as observations accumulate.
+//!
+//! The payoff is modelled locally as a quadratic with Gaussian noise,
+//! `y = a + b·x + (κ/2)·x² + ε`, so `κ` is its second derivative. The three
+//! classes are hypotheses about `κ`:
+//!
+//! - **Robust**: `κ = 0`.
+//! - **Antifragile**: `κ > 0`, half-normal with scale `τ`.
+//! - **Fragile**: `κ < 0`, half-normal with scale `τ`.
+//!
+//! With flat priors on `a` and `b`, the likelihood of `κ` is Gaussian around
+//! its least-squares estimate, so the evidence for each hypothesis has a
+//! closed form and the posterior is exact. The point hypothesis gives the
+//! usual Bayesian Occam's razor: curvature has to be supported by the data
+//! before Robust loses.
+//!
+//! Observations update running sums, so each one costs constant time and
+//! memory.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::bayes::{BayesConfig, TriadPosterior};
+//!
+//! let mut posterior = TriadPosterior::new(BayesConfig::default());
+//! assert!((posterior.posterior().robust - 1.0 / 3.0).abs() < 1e-12);
+//!
+//! // A convex payoff observed with small measurement wobble
+//! for i in 0..40 {
+//!     let x = f64::from(i) / 4.0;
+//!     let wobble = if i % 2 == 0 { 0.1 } else { -0.1 };
+//!     posterior.observe(x, 0.5 * x * x + wobble);
+//! }
+//! let probabilities = posterior.posterior();
+//! assert_eq!(probabilities.most_likely(), Triad::Antifragile);
+//! assert!(probabilities.antifragile > 0.99);
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Triad;
+use crate::quadratic::QuadraticSums;
+use crate::special::normal_cdf;
+
+/// A probability for each class of the Triad
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TriadProbabilities {
+    /// Probability of [`Triad::Fragile`]
+    pub fragile: f64,
+    /// Probability of [`Triad::Robust`]
+    pub robust: f64,
+    /// Probability of [`Triad::Antifragile`]
+    pub antifragile: f64,
+}
+
+impl TriadProbabilities {
+    /// Probabilities proportional to the given weights
+    ///
+    /// Weights must be non-negative with a positive sum.
+    #[must_use]
+    pub fn new(fragile: f64, robust: f64, antifragile: f64) -> Self {
+        let total = fragile + robust + antifragile;
+        Self {
+            fragile: fragile / total,
+            robust: robust / total,
+            antifragile: antifragile / total,
+        }
+    }
+
+    /// Probability of `triad`
+    #[must_use]
+    pub const fn probability(&self, triad: Triad) -> f64 {
+        match triad {
+            Triad::Fragile => self.fragile,
+            Triad::Robust => self.robust,
+            Triad::Antifragile => self.antifragile,
+        }
+    }
+
+    /// The most probable class; ties go to the less desirable class
+    pub fn most_likely(&self) -> Triad {
+        [Triad::Antifragile, Triad::Robust, Triad::Fragile]
+            .into_iter()
+            .max_by(|a, b| self.probability(*a).total_cmp(&self.probability(*b)))
+            .unwrap_or(Triad::Robust)
+    }
+}
+
+impl Default for TriadProbabilities {
+    /// Each class equally likely
+    fn default() -> Self {
+        Self::new(1.0, 1.0, 1.0)
+    }
+}
+
+/// Configuration for [`TriadPosterior`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BayesConfig {
+    /// Prior probability of each class
+    pub prior: TriadProbabilities,
+    /// Scale `τ` of the half-normal prior on the second derivative under
+    /// the curved hypotheses
+    pub curvature_scale: f64,
+    /// Standard deviation of the payoff noise, or `None` to estimate it
+    /// from the residuals
+    pub noise: Option<f64>,
+}
+
+impl Default for BayesConfig {
+    /// Uniform prior, curvature scale `1.0`, noise estimated from the data
+    fn default() -> Self {
+        Self {
+            prior: TriadProbabilities::default(),
+            curvature_scale: 1.0,
+            noise: None,
+        }
+    }
+}
+
+/// Streaming posterior over the Triad from `(stressor, payoff)` observations
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriadPosterior {
+    config: BayesConfig,
+    sums: QuadraticSums,
+}
+
+impl TriadPosterior {
+    /// A posterior equal to the configured prior
+    #[must_use]
+    pub const fn new(config: BayesConfig) -> Self {
+        Self {
+            config,
+            sums: QuadraticSums::new(),
+        }
+    }
+
+    /// Add one observation
+    ///
+    /// Observations with a non-finite stressor or payoff are ignored.
+    pub fn observe(&mut self, stressor: f64, payoff: f64) {
+        if stressor.is_finite() && payoff.is_finite() {
+            self.sums.add(stressor, payoff);
+        }
+    }
+
+    /// Add every observation of `observations`
+    pub fn observe_all(&mut self, observations: impl IntoIterator<Item = (f64, f64)>) {
+        for (stressor, payoff) in observations {
+            self.observe(stressor, payoff);
+        }
+    }
+
+    /// Number of observations so far
+    #[must_use]
+    pub fn observations(&self) -> usize {
+        self.sums.count()
+    }
+
+    /// Least-squares second derivative and its standard error
+    ///
+    /// `None` until the curvature is identified: three distinct stressors,
+    /// and a fourth observation when the noise is estimated.
+    #[must_use]
+    pub fn curvature(&self) -> Option<(f64, f64)> {
+        self.sums.curvature(self.config.noise)
+    }
+
+    /// Current probability of each class
+    ///
+    /// Equal to the prior until [`curvature`](Self::curvature) is
+    /// identified. A noiseless fit is decisive; one whose curvature is
+    /// within rounding of zero, such as exactly linear data, is Robust.
+    #[must_use]
+    pub fn posterior(&self) -> TriadProbabilities {
+        let prior = self.config.prior;
+        let Some((curvature, std_error)) = self.curvature() else {
+            return prior;
+        };
+        if std_error <= 0.0 {
+            let certain = if curvature > 0.0 {
+                Triad::Antifragile
+            } else if curvature < 0.0 {
+                Triad::Fragile
+            } else {
+                Triad::Robust
+            };
+            return TriadProbabilities::new(
+                f64::from(u8::from(certain == Triad::Fragile)),
+                f64::from(u8::from(certain == Triad::Robust)),
+                f64::from(u8::from(certain == Triad::Antifragile)),
+            );
+        }
+
+        let likelihood_variance = std_error * std_error;
+        let scale = self.config.curvature_scale;
+        let spread_variance = likelihood_variance + scale * scale;
+        // Posterior of κ under the unrestricted N(0, τ²) prior
+        let posterior_variance = 1.0 / (1.0 / likelihood_variance + 1.0 / (scale * scale));
+        let posterior_mean = posterior_variance * curvature / likelihood_variance;
+        let z = posterior_mean / posterior_variance.sqrt();
+
+        let curved = core::f64::consts::LN_2 + ln_normal_density(curvature, spread_variance);
+        let log_weights = [
+            prior.fragile.ln() + curved + normal_cdf(-z).ln(),
+            prior.robust.ln() + ln_normal_density(curvature, likelihood_variance),
+            prior.antifragile.ln() + curved + normal_cdf(z).ln(),
+        ];
+        let largest = log_weights
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max);
+        let [fragile, robust, antifragile] = log_weights.map(|w| (w - largest).exp());
+        TriadProbabilities::new(fragile, robust, antifragile)
+    }
+}
+
+/// `ln N(value; 0, variance)`
+fn ln_normal_density(value: f64, variance: f64) -> f64 {
+    -0.5 * ((core::f64::consts::TAU * variance).ln() + value * value / variance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    fn noisy(curve: impl Fn(f64) -> f64, n: u32, seed: u64) -> Vec<(f64, f64)> {
+        let mut rng = SplitMix64::new(seed);
+        (0..n)
+            .map(|i| {
+                let x = f64::from(i % 20) / 2.0;
+                (x, curve(x) + rng.next_normal())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_prior_until_identified() {
+        let prior = TriadProbabilities::new(2.0, 1.0, 1.0);
+        let mut posterior = TriadPosterior::new(BayesConfig {
+            prior,
+            ..BayesConfig::default()
+        });
+        assert!((posterior.posterior().fragile - 0.5).abs() < f64::EPSILON);
+        // Repeating one stressor never identifies curvature
+        posterior.observe_all([(1.0, 1.0), (1.0, 2.0), (1.0, 3.0), (1.0, 4.0)]);
+        assert_eq!(posterior.observations(), 4);
+        assert_eq!(posterior.curvature(), None);
+        assert_eq!(posterior.posterior(), prior);
+    }
+
+    #[test]
+    fn test_posterior_tightens_with_data() {
+        let data = noisy(|x| 0.05 * x * x, 400, 1);
+        let mut posterior = TriadPosterior::new(BayesConfig::default());
+        posterior.observe_all(data[..20].iter().copied());
+        let early = posterior.posterior().antifragile;
+        posterior.observe_all(data[20..].iter().copied());
+        let late = posterior.posterior();
+        assert!(late.antifragile > early);
+        assert!(late.antifragile > 0.999);
+        let (curvature, std_error) = posterior.curvature().unwrap();
+        assert!((curvature - 0.1).abs() < 3.0 * std_error);
+    }
+
+    #[test]
+    fn test_non_finite_observations_are_ignored() {
+        let data = noisy(|x| 0.05 * x * x, 400, 1);
+        let mut clean = TriadPosterior::new(BayesConfig::default());
+        clean.observe_all(data.iter().copied());
+        let mut noisy = clean.clone();
+        noisy.observe_all([
+            (f64::NAN, 1.0),
+            (1.0, f64::INFINITY),
+            (f64::NEG_INFINITY, 0.0),
+        ]);
+        assert_eq!(noisy.observations(), 400);
+        assert_eq!(noisy.posterior(), clean.posterior());
+    }
+
+    #[test]
+    fn test_linear_data_favours_robust() {
+        let mut posterior = TriadPosterior::new(BayesConfig::default());
+        posterior.observe_all(noisy(|x| 2.0 * x + 1.0, 400, 2));
+        let probabilities = posterior.posterior();
+        assert_eq!(probabilities.most_likely(), Triad::Robust);
+        assert!(probabilities.robust > 0.5);
+    }
+
+    #[test]
+    fn test_exact_and_known_noise() {
+        let mut exact = TriadPosterior::new(BayesConfig::default());
+        exact.observe_all((0..5).map(|i| (f64::from(i) + 100.0, -f64::from(i * i))));
+        assert!((exact.posterior().fragile - 1.0).abs() < f64::EPSILON);
+
+        for slope in [3.7, 0.0, -1.3] {
+            let mut line = TriadPosterior::new(BayesConfig::default());
+            line.observe_all((0..200).map(|i| {
+                let x = f64::from(i % 10);
+                (x, slope * x + 100.0)
+            }));
+            let robust = line.posterior().robust;
+            assert!(robust > 1.0 - 1e-6, "slope {slope}: {robust}");
+        }
+
+        let mut known = TriadPosterior::new(BayesConfig {
+            noise: Some(1.0),
+            ..BayesConfig::default()
+        });
+        known.observe_all([(0.0, 0.0), (1.0, -1.0), (2.0, -4.0)]);
+        let (curvature, std_error) = known.curvature().unwrap();
+        assert!((curvature + 2.0).abs() < 1e-9);
+        assert!(std_error > 0.0);
+        assert_eq!(known.posterior().most_likely(), Triad::Fragile);
+    }
+}
@@ -0,0 +1,648 @@
+//! # Result caching keyed by verification context
+//!
+//! Classification is cheap for closed-form payoffs, but once it is wired into
+//! request paths or CI jobs the same system is often classified over and over
+//! with identical inputs. [`AnalysisCache`](crate::cache::AnalysisCache)
+//! memoizes results keyed by the system's identity (any [`Hash`] value you
+//! choose, typically the system's configuration) together with the
+//! verification context (operating point, delta, tolerance). Keys are
+//! compared by the exact bytes their `Hash` implementation writes, not by a
+//! digest, so distinct keys never share a result.
+//!
+//! Entries never expire on their own: call
+//! [`invalidate`](crate::cache::AnalysisCache::invalidate) when a system's
+//! configuration changes, or [`clear`](crate::cache::AnalysisCache::clear) to
+//! drop everything.
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad};
+//! use antifragile::cache::ClassificationCache;
+//!
+//! struct Service {
+//!     replicas: u32,
+//! }
+//!
+//! impl Antifragile for Service {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, load: f64) -> f64 {
+//!         f64::from(self.replicas) * load * load
+//!     }
+//! }
+//!
+//! let service = Service { replicas: 3 };
+//! let mut cache = ClassificationCache::new();
+//!
+//! assert_eq!(cache.classify(&service.replicas, &service, 10.0, 1.0), Triad::Antifragile);
+//! assert_eq!(cache.classify(&service.replicas, &service, 10.0, 1.0), Triad::Antifragile);
+//! assert_eq!(cache.stats().hits, 1);
+//!
+//! // The configuration changed: drop everything cached for it
+//! cache.invalidate(&service.replicas);
+//! assert!(cache.is_empty());
+//! ```
+//!
+//! ## Memoized payoffs
+//!
+//! When the payoff itself is the expensive part, such as a simulator run,
+//! [`Memoized`](crate::cache::Memoized) caches each evaluation keyed on the
+//! stressor. Sweeps and repeated classifications probe the same points
+//! again and again, and each is evaluated once. The wrapper implements
+//! [`Antifragile`] itself, so it drops in wherever the system did.
+//!
+//! ```rust
+//! use antifragile::{Triad, TriadAnalysis};
+//! use antifragile::cache::Memoized;
+//!
+//! let system = Memoized::new(antifragile::antifragile!(|x: f64| x * x), 1_000);
+//!
+//! // Neighbouring points share two of their three probes
+//! assert_eq!(system.classify(10.0, 1.0), Triad::Antifragile);
+//! assert_eq!(system.classify(11.0, 1.0), Triad::Antifragile);
+//! assert_eq!(system.stats().hits, 2);
+//! assert_eq!(system.len(), 4);
+//! ```
+
+use core::hash::{Hash, Hasher};
+use core::ops::Sub;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::{Antifragile, Triad, TriadAnalysis};
+
+/// Values that can take part in a cache key
+///
+/// Implemented for the primitive numeric types. In a classification
+/// context, floating-point values are keyed by their bit pattern with `-0.0`
+/// treated as `0.0`; as a [`Memoized`] stressor, by their exact bits, since
+/// a payoff may tell the two zeros apart.
+pub trait ContextKey {
+    /// The exact value, compared bit for bit
+    type Bits: Copy + Eq + Hash;
+
+    /// Feed this value into `state`
+    fn hash_key<H: Hasher>(&self, state: &mut H);
+
+    /// The exact value, without normalization
+    fn bits(&self) -> Self::Bits;
+}
+
+macro_rules! impl_context_key_int {
+    ($($t:ty),*) => {
+        $(
+            impl ContextKey for $t {
+                type Bits = Self;
+
+                #[inline]
+                fn hash_key<H: Hasher>(&self, state: &mut H) {
+                    self.hash(state);
+                }
+
+                #[inline]
+                fn bits(&self) -> Self {
+                    *self
+                }
+            }
+        )*
+    };
+}
+
+impl_context_key_int!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+impl ContextKey for f32 {
+    type Bits = u32;
+
+    #[inline]
+    fn hash_key<H: Hasher>(&self, state: &mut H) {
+        let normalized = if *self == 0.0 { 0.0_f32 } else { *self };
+        normalized.to_bits().hash(state);
+    }
+
+    #[inline]
+    fn bits(&self) -> u32 {
+        self.to_bits()
+    }
+}
+
+impl ContextKey for f64 {
+    type Bits = u64;
+
+    #[inline]
+    fn hash_key<H: Hasher>(&self, state: &mut H) {
+        let normalized = if *self == 0.0 { 0.0_f64 } else { *self };
+        normalized.to_bits().hash(state);
+    }
+
+    #[inline]
+    fn bits(&self) -> u64 {
+        self.to_bits()
+    }
+}
+
+/// Hit and miss counters for an [`AnalysisCache`] or [`Memoized`] system
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups answered from the cache
+    pub hits: u64,
+    /// Lookups that had to run the analysis
+    pub misses: u64,
+}
+
+/// A cache of analysis results keyed by system identity and context
+///
+/// `V` is the cached result type. [`ClassificationCache`] is the common
+/// `V = Triad` case and provides `classify` helpers.
+#[derive(Debug, Clone)]
+pub struct AnalysisCache<V> {
+    /// Results by identity key, then by context key
+    entries: HashMap<Vec<u8>, HashMap<Vec<u8>, V>>,
+    stats: CacheStats,
+}
+
+/// An [`AnalysisCache`] holding Triad classifications
+pub type ClassificationCache = AnalysisCache<Triad>;
+
+impl<V> Default for AnalysisCache<V> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            stats: CacheStats::default(),
+        }
+    }
+}
+
+impl<V: Clone> AnalysisCache<V> {
+    /// Create an empty cache
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the cached result for `identity` and `context`, computing and
+    /// storing it with `compute` on a miss
+    pub fn get_or_insert_with<I, C, F>(&mut self, identity: &I, context: &C, compute: F) -> V
+    where
+        I: Hash + ?Sized,
+        C: Hash + ?Sized,
+        F: FnOnce() -> V,
+    {
+        let system = bytes_of(identity);
+        let context = bytes_of(context);
+
+        let contexts = self.entries.entry(system).or_default();
+        if let Some(value) = contexts.get(&context) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let value = compute();
+        contexts.insert(context, value.clone());
+        value
+    }
+
+    /// Drop every cached result for `identity`
+    ///
+    /// Returns the number of entries removed.
+    pub fn invalidate<I: Hash + ?Sized>(&mut self, identity: &I) -> usize {
+        self.entries
+            .remove(&bytes_of(identity))
+            .map_or(0, |contexts| contexts.len())
+    }
+
+    /// Drop every cached result
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Number of cached results
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.values().map(HashMap::len).sum()
+    }
+
+    /// Returns true if nothing is cached
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hit and miss counters since the cache was created
+    #[must_use]
+    pub const fn stats(&self) -> CacheStats {
+        self.stats
+    }
+}
+
+impl AnalysisCache<Triad> {
+    /// Cached [`classify`](TriadAnalysis::classify)
+    pub fn classify<I, T>(
+        &mut self,
+        identity: &I,
+        system: &T,
+        at: T::Stressor,
+        delta: T::Stressor,
+    ) -> Triad
+    where
+        I: Hash + ?Sized,
+        T: Antifragile,
+        T::Stressor: ContextKey,
+        T::Payoff: Sub<Output = T::Payoff> + Default + PartialOrd,
+    {
+        let context = Context {
+            kind: "classify",
+            at,
+            delta,
+            tolerance: None::<f64>,
+        };
+        self.get_or_insert_with(identity, &context, || system.classify(at, delta))
+    }
+
+    /// Cached [`classify_with_tolerance`](TriadAnalysis::classify_with_tolerance)
+    pub fn classify_with_tolerance<I, T>(
+        &mut self,
+        identity: &I,
+        system: &T,
+        at: T::Stressor,
+        delta: T::Stressor,
+        epsilon: T::Payoff,
+    ) -> Triad
+    where
+        I: Hash + ?Sized,
+        T: Antifragile,
+        T::Stressor: ContextKey,
+        T::Payoff: Sub<Output = T::Payoff> + Default + PartialOrd + ContextKey,
+    {
+        let context = Context {
+            kind: "classify_with_tolerance",
+            at,
+            delta,
+            tolerance: Some(epsilon),
+        };
+        self.get_or_insert_with(identity, &context, || {
+            system.classify_with_tolerance(at, delta, epsilon)
+        })
+    }
+}
+
+/// A system whose payoff evaluations are cached, keyed on the exact bits of
+/// the stressor
+///
+/// Holds at most `capacity` payoffs; once full, the oldest entry is evicted
+/// to make room. A capacity of zero caches nothing. The cache sits behind a
+/// mutex, so a memoized system can be shared across threads, for example by
+/// [`par_classify_batch`](crate::aggregate::BatchAnalysis::par_classify_batch).
+/// The lock is not held while the payoff runs.
+///
+/// Wrapping only pays off for payoffs that cost more than a hash lookup,
+/// and the payoff must be deterministic: a cached value is returned as is.
+pub struct Memoized<T: Antifragile>
+where
+    T::Stressor: ContextKey,
+{
+    system: T,
+    capacity: usize,
+    memo: Mutex<Memo<<T::Stressor as ContextKey>::Bits, T::Payoff>>,
+}
+
+/// Cached payoffs and their insertion order
+struct Memo<B, P> {
+    payoffs: HashMap<B, P>,
+    order: VecDeque<B>,
+    stats: CacheStats,
+}
+
+impl<T: Antifragile> Memoized<T>
+where
+    T::Stressor: ContextKey,
+{
+    /// Wrap `system`, caching up to `capacity` payoffs
+    #[must_use]
+    pub fn new(system: T, capacity: usize) -> Self {
+        Self {
+            system,
+            capacity,
+            memo: Mutex::new(Memo {
+                payoffs: HashMap::new(),
+                order: VecDeque::new(),
+                stats: CacheStats::default(),
+            }),
+        }
+    }
+
+    /// Maximum number of cached payoffs
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of cached payoffs
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.memo().payoffs.len()
+    }
+
+    /// Returns true if nothing is cached
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hit and miss counters since the wrapper was created
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        self.memo().stats
+    }
+
+    /// Drop every cached payoff, keeping the counters
+    pub fn clear(&self) {
+        let mut memo = self.memo();
+        memo.payoffs.clear();
+        memo.order.clear();
+    }
+
+    /// Access the wrapped system
+    #[must_use]
+    pub const fn inner(&self) -> &T {
+        &self.system
+    }
+
+    /// Consume the wrapper and return the system
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.system
+    }
+
+    /// The cache, recovered if a panicking payoff poisoned the lock
+    ///
+    /// The lock is never held while a payoff runs, so the cache is
+    /// consistent even then.
+    fn memo(&self) -> MutexGuard<'_, Memo<<T::Stressor as ContextKey>::Bits, T::Payoff>> {
+        self.memo.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Antifragile> Antifragile for Memoized<T>
+where
+    T::Stressor: ContextKey,
+{
+    type Stressor = T::Stressor;
+    type Payoff = T::Payoff;
+
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        let key = stressor.bits();
+        {
+            let mut memo = self.memo();
+            if let Some(&payoff) = memo.payoffs.get(&key) {
+                memo.stats.hits += 1;
+                return payoff;
+            }
+            memo.stats.misses += 1;
+        }
+
+        let payoff = self.system.payoff(stressor);
+        if self.capacity > 0 {
+            let mut memo = self.memo();
+            if memo.payoffs.insert(key, payoff).is_none() {
+                memo.order.push_back(key);
+                while memo.order.len() > self.capacity {
+                    if let Some(oldest) = memo.order.pop_front() {
+                        memo.payoffs.remove(&oldest);
+                    }
+                }
+            }
+        }
+        payoff
+    }
+
+    fn twin(r: Self::Payoff) -> Self::Payoff {
+        T::twin(r)
+    }
+}
+
+/// The verification context of a cached classification
+struct Context<S, P> {
+    kind: &'static str,
+    at: S,
+    delta: S,
+    tolerance: Option<P>,
+}
+
+impl<S: ContextKey, P: ContextKey> Hash for Context<S, P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind.hash(state);
+        self.at.hash_key(state);
+        self.delta.hash_key(state);
+        if let Some(tolerance) = &self.tolerance {
+            tolerance.hash_key(state);
+        }
+    }
+}
+
+/// The exact bytes `value` feeds to a hasher
+///
+/// Keys compare by these bytes rather than by a digest of them, so two
+/// identities or contexts never share an entry by hash collision.
+fn bytes_of<T: Hash + ?Sized>(value: &T) -> Vec<u8> {
+    let mut bytes = KeyBytes(Vec::new());
+    value.hash(&mut bytes);
+    bytes.0
+}
+
+/// A hasher that records everything written to it
+struct KeyBytes(Vec<u8>);
+
+impl Hasher for KeyBytes {
+    fn finish(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        hasher.write(&self.0);
+        hasher.finish()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    struct CountingConvex {
+        calls: Cell<usize>,
+    }
+
+    impl Antifragile for CountingConvex {
+        type Stressor = f64;
+        type Payoff = f64;
+        fn payoff(&self, x: f64) -> f64 {
+            self.calls.set(self.calls.get() + 1);
+            x * x
+        }
+    }
+
+    #[test]
+    fn test_repeated_classify_hits_cache() {
+        let system = CountingConvex {
+            calls: Cell::new(0),
+        };
+        let mut cache = ClassificationCache::new();
+
+        for _ in 0..5 {
+            assert_eq!(
+                cache.classify("convex", &system, 10.0, 1.0),
+                Triad::Antifragile
+            );
+        }
+
+        assert_eq!(system.calls.get(), 3);
+        assert_eq!(cache.stats(), CacheStats { hits: 4, misses: 1 });
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_context_changes_miss() {
+        let system = CountingConvex {
+            calls: Cell::new(0),
+        };
+        let mut cache = ClassificationCache::new();
+
+        let _ = cache.classify("convex", &system, 10.0, 1.0);
+        let _ = cache.classify("convex", &system, 10.0, 0.0);
+        let _ = cache.classify("convex", &system, 5.0, 1.0);
+        let _ = cache.classify_with_tolerance("convex", &system, 10.0, 1.0, 5.0);
+
+        assert_eq!(cache.stats().misses, 4);
+        assert_eq!(cache.len(), 4);
+        // Tolerance is part of the key, so the tolerant result is not confused
+        // with the exact one
+        assert_eq!(
+            cache.classify_with_tolerance("convex", &system, 10.0, 1.0, 5.0),
+            Triad::Robust
+        );
+        assert_eq!(
+            cache.classify("convex", &system, 10.0, 1.0),
+            Triad::Antifragile
+        );
+    }
+
+    #[test]
+    fn test_invalidate_only_affects_identity() {
+        let system = CountingConvex {
+            calls: Cell::new(0),
+        };
+        let mut cache = ClassificationCache::new();
+
+        let _ = cache.classify(&1_u32, &system, 10.0, 1.0);
+        let _ = cache.classify(&1_u32, &system, 20.0, 1.0);
+        let _ = cache.classify(&2_u32, &system, 10.0, 1.0);
+
+        assert_eq!(cache.invalidate(&1_u32), 2);
+        assert_eq!(cache.invalidate(&1_u32), 0);
+        assert_eq!(cache.len(), 1);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_negative_zero_shares_key() {
+        let system = CountingConvex {
+            calls: Cell::new(0),
+        };
+        let mut cache = ClassificationCache::new();
+
+        let _ = cache.classify("convex", &system, 0.0, 1.0);
+        let _ = cache.classify("convex", &system, -0.0, 1.0);
+        assert_eq!(cache.stats().hits, 1);
+    }
+
+    #[test]
+    fn test_keys_compare_exactly() {
+        assert_ne!(bytes_of(&("ab", "c")), bytes_of(&("a", "bc")));
+
+        let mut cache: AnalysisCache<u8> = AnalysisCache::new();
+        let _ = cache.get_or_insert_with(&("ab", "c"), &1_u8, || 1);
+        assert_eq!(cache.get_or_insert_with(&("a", "bc"), &1_u8, || 2), 2);
+        assert_eq!(cache.get_or_insert_with(&("ab", "c"), &1_u8, || 3), 1);
+        assert_eq!(cache.invalidate(&("a", "bc")), 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_generic_values() {
+        let mut cache: AnalysisCache<f64> = AnalysisCache::new();
+        let first = cache.get_or_insert_with("system", &("score", 3_u8), || 0.5);
+        let second = cache.get_or_insert_with("system", &("score", 3_u8), || 1.0);
+        assert!((first - second).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_memoized_evaluates_each_stressor_once() {
+        let system = Memoized::new(
+            CountingConvex {
+                calls: Cell::new(0),
+            },
+            16,
+        );
+
+        for at in [10.0, 11.0, 10.0, 12.0] {
+            assert_eq!(system.classify(at, 1.0), Triad::Antifragile);
+        }
+
+        // 9, 10, 11, 12, 13
+        assert_eq!(system.inner().calls.get(), 5);
+        assert_eq!(system.stats(), CacheStats { hits: 7, misses: 5 });
+        assert_eq!(system.len(), 5);
+    }
+
+    #[test]
+    fn test_memoized_evicts_oldest_beyond_capacity() {
+        let system = Memoized::new(
+            CountingConvex {
+                calls: Cell::new(0),
+            },
+            2,
+        );
+
+        let _ = system.payoff(1.0);
+        let _ = system.payoff(2.0);
+        let _ = system.payoff(3.0);
+        assert_eq!(system.len(), 2);
+
+        // 1.0 was evicted, 3.0 is still cached
+        let _ = system.payoff(3.0);
+        let _ = system.payoff(1.0);
+        assert_eq!(system.inner().calls.get(), 4);
+
+        system.clear();
+        assert!(system.is_empty());
+
+        let uncached = Memoized::new(
+            CountingConvex {
+                calls: Cell::new(0),
+            },
+            0,
+        );
+        let _ = uncached.payoff(1.0);
+        let _ = uncached.payoff(1.0);
+        assert_eq!(uncached.into_inner().calls.get(), 2);
+    }
+
+    #[test]
+    fn test_memoized_keys_stressors_exactly() {
+        let system = Memoized::new(crate::antifragile!(|x: f64| 1.0 / x), 16);
+
+        let positive = system.payoff(0.0);
+        let negative = system.payoff(-0.0);
+        assert!(positive.is_infinite() && positive.is_sign_positive());
+        assert!(negative.is_infinite() && negative.is_sign_negative());
+        assert_eq!(system.len(), 2);
+        assert_eq!(system.stats().hits, 0);
+    }
+}
@@ -0,0 +1,420 @@
+//! # Fault-injection experiments
+//!
+//! Chaos engineering asks how a service behaves as faults become more
+//! frequent. [`ChaosExperiment`](crate::chaos::ChaosExperiment) wraps
+//! whatever injects the faults (a closure around a fault-injection library,
+//! a proxy, a mock) as a [`FaultTarget`](crate::chaos::FaultTarget): given
+//! a fault rate it performs one request and reports an
+//! [`Observation`](crate::chaos::Observation) of success and latency. The
+//! experiment sends a batch of requests per rate and turns them into a
+//! payoff with a [`ChaosMetric`](crate::chaos::ChaosMetric), so the fault
+//! rate becomes a stressor like any other.
+//!
+//! [`run_ramp`](crate::chaos::ChaosExperiment::run_ramp) steps through a
+//! [`Ramp`](crate::chaos::Ramp) of rates and stops early when an
+//! [`Abort`](crate::chaos::Abort) condition trips, as a real experiment
+//! against a shared environment should.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::chaos::{Abort, ChaosExperiment, Observation, Ramp};
+//! use antifragile::learning::Noise;
+//!
+//! // Three retries hide faults until they become common
+//! let service = || {
+//!     |rate: f64, noise: &mut Noise| {
+//!         let attempts = (1..=4).find(|_| noise.uniform() >= rate);
+//!         Observation::new(attempts.is_some(), 10.0 * f64::from(attempts.unwrap_or(4)))
+//!     }
+//! };
+//!
+//! let report = ChaosExperiment::new(service)
+//!     .with_abort(Abort::SuccessBelow(0.5))
+//!     .run_ramp(&Ramp::linear(0.0, 0.9, 10));
+//!
+//! // Retries absorb the first faults, then success collapses: fragile
+//! assert_eq!(report.classification(), Triad::Fragile);
+//! assert!(report.aborted.is_some());
+//! ```
+
+use crate::learning::Noise;
+use crate::{Antifragile, Triad};
+
+/// Outcome of one request made under fault injection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Observation {
+    /// Whether the request succeeded
+    pub success: bool,
+    /// Latency of the request, in any unit
+    pub latency: f64,
+}
+
+impl Observation {
+    /// An observation with the given outcome and latency
+    #[must_use]
+    pub const fn new(success: bool, latency: f64) -> Self {
+        Self { success, latency }
+    }
+}
+
+/// Something that serves one request while faults are injected at a rate
+///
+/// Any `FnMut(f64, &mut Noise) -> Observation` closure is a fault target.
+/// The [`Noise`] stream is deterministic per request, so the same request
+/// sees the same draws at every rate.
+pub trait FaultTarget {
+    /// Serve one request with faults injected at `rate`
+    fn call(&mut self, rate: f64, noise: &mut Noise) -> Observation;
+}
+
+impl<F: FnMut(f64, &mut Noise) -> Observation> FaultTarget for F {
+    fn call(&mut self, rate: f64, noise: &mut Noise) -> Observation {
+        self(rate, noise)
+    }
+}
+
+/// How a batch of observations becomes a payoff
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ChaosMetric {
+    /// Share of requests that succeeded
+    #[default]
+    SuccessRate,
+    /// Negated mean latency of all requests
+    MeanLatency,
+    /// Negated 99th-percentile latency of all requests
+    TailLatency,
+    /// Successful requests per unit of total latency
+    Goodput,
+}
+
+/// A condition that stops a ramp early
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Abort {
+    /// Stop once the success rate falls below this share
+    SuccessBelow(f64),
+    /// Stop once the mean latency exceeds this value
+    MeanLatencyAbove(f64),
+    /// Stop once the 99th-percentile latency exceeds this value
+    TailLatencyAbove(f64),
+}
+
+impl Abort {
+    fn trips(self, step: &ChaosStep) -> bool {
+        match self {
+            Self::SuccessBelow(limit) => step.success_rate < limit,
+            Self::MeanLatencyAbove(limit) => step.mean_latency > limit,
+            Self::TailLatencyAbove(limit) => step.tail_latency > limit,
+        }
+    }
+}
+
+/// A schedule of fault rates
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ramp {
+    rates: Vec<f64>,
+}
+
+impl Ramp {
+    /// `steps` rates evenly spaced from `start` to `end` inclusive
+    #[must_use]
+    pub fn linear(start: f64, end: f64, steps: u32) -> Self {
+        let rates = match steps {
+            0 => Vec::new(),
+            1 => vec![start],
+            _ => (0..steps)
+                .map(|i| start + (end - start) * f64::from(i) / f64::from(steps - 1))
+                .collect(),
+        };
+        Self { rates }
+    }
+
+    /// `steps` rates starting at `start`, each `factor` times the last
+    #[must_use]
+    pub fn geometric(start: f64, factor: f64, steps: u32) -> Self {
+        let rates = (0..steps)
+            .scan(start, |rate, _| {
+                let current = *rate;
+                *rate *= factor;
+                Some(current)
+            })
+            .collect();
+        Self { rates }
+    }
+
+    /// The given rates, in order
+    #[must_use]
+    pub const fn custom(rates: Vec<f64>) -> Self {
+        Self { rates }
+    }
+
+    /// The rates of the schedule
+    #[must_use]
+    pub fn rates(&self) -> &[f64] {
+        &self.rates
+    }
+}
+
+/// Measurements at one fault rate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChaosStep {
+    /// Injected fault rate
+    pub rate: f64,
+    /// Share of requests that succeeded
+    pub success_rate: f64,
+    /// Mean latency of all requests
+    pub mean_latency: f64,
+    /// 99th-percentile latency of all requests
+    pub tail_latency: f64,
+    /// Payoff under the experiment's metric
+    pub payoff: f64,
+}
+
+/// Result of [`ChaosExperiment::run_ramp`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChaosReport {
+    /// Steps that were run, in ramp order, including the one that aborted
+    pub steps: Vec<ChaosStep>,
+    /// Rate and condition that stopped the ramp, if one did
+    pub aborted: Option<(f64, Abort)>,
+}
+
+impl ChaosReport {
+    /// Sign of the mean second divided difference of the payoff over the
+    /// steps run
+    ///
+    /// Works for unevenly spaced ramps. Robust with fewer than three steps.
+    pub fn classification(&self) -> Triad {
+        let curvatures: Vec<f64> = self
+            .steps
+            .windows(3)
+            .filter_map(|w| {
+                let (a, b, c) = (w[0], w[1], w[2]);
+                let left = (b.payoff - a.payoff) / (b.rate - a.rate);
+                let right = (c.payoff - b.payoff) / (c.rate - b.rate);
+                let curvature = (right - left) / (c.rate - a.rate);
+                curvature.is_finite().then_some(curvature)
+            })
+            .collect();
+        let total: f64 = curvatures.iter().sum();
+        if total > 0.0 {
+            Triad::Antifragile
+        } else if total < 0.0 {
+            Triad::Fragile
+        } else {
+            Triad::Robust
+        }
+    }
+}
+
+/// A fault-injection experiment whose stressor is the fault rate
+#[derive(Debug, Clone)]
+pub struct ChaosExperiment<F> {
+    factory: F,
+    requests: u32,
+    seed: u64,
+    metric: ChaosMetric,
+    aborts: Vec<Abort>,
+}
+
+impl<F, T> ChaosExperiment<F>
+where
+    F: Fn() -> T,
+    T: FaultTarget,
+{
+    /// An experiment that builds a fresh target for each rate with `factory`
+    ///
+    /// Defaults to 200 requests per rate, the success-rate metric, and no
+    /// abort conditions.
+    pub const fn new(factory: F) -> Self {
+        Self {
+            factory,
+            requests: 200,
+            seed: 0x5EED,
+            metric: ChaosMetric::SuccessRate,
+            aborts: Vec::new(),
+        }
+    }
+
+    /// Set the number of requests sent at each rate
+    #[must_use]
+    pub const fn with_requests(mut self, requests: u32) -> Self {
+        self.requests = requests;
+        self
+    }
+
+    /// Set the base seed; equal seeds give identical results
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set how observations become a payoff
+    #[must_use]
+    pub const fn with_metric(mut self, metric: ChaosMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Add a condition that stops [`run_ramp`](Self::run_ramp) early
+    #[must_use]
+    pub fn with_abort(mut self, abort: Abort) -> Self {
+        self.aborts.push(abort);
+        self
+    }
+
+    /// Send one batch of requests at `rate` and summarize it
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn measure(&self, rate: f64) -> ChaosStep {
+        let mut target = (self.factory)();
+        let observations: Vec<Observation> = (0..self.requests)
+            .map(|request| {
+                let mut noise = Noise::new(
+                    self.seed
+                        .wrapping_add(u64::from(request).wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+                );
+                target.call(rate, &mut noise)
+            })
+            .collect();
+
+        let count = observations.len() as f64;
+        let successes = observations.iter().filter(|o| o.success).count() as f64;
+        let total_latency: f64 = observations.iter().map(|o| o.latency).sum();
+        let mut latencies: Vec<f64> = observations.iter().map(|o| o.latency).collect();
+        latencies.sort_by(f64::total_cmp);
+        let tail_latency = latencies
+            .get(((0.99 * count).ceil() as usize).saturating_sub(1))
+            .copied()
+            .unwrap_or(f64::NAN);
+
+        let success_rate = successes / count;
+        let mean_latency = total_latency / count;
+        let payoff = match self.metric {
+            ChaosMetric::SuccessRate => success_rate,
+            ChaosMetric::MeanLatency => -mean_latency,
+            ChaosMetric::TailLatency => -tail_latency,
+            ChaosMetric::Goodput => successes / total_latency,
+        };
+        ChaosStep {
+            rate,
+            success_rate,
+            mean_latency,
+            tail_latency,
+            payoff,
+        }
+    }
+
+    /// Measure each rate of `ramp` in turn, stopping after the first step
+    /// that trips an abort condition
+    #[must_use]
+    pub fn run_ramp(&self, ramp: &Ramp) -> ChaosReport {
+        let mut steps = Vec::with_capacity(ramp.rates.len());
+        for &rate in &ramp.rates {
+            let step = self.measure(rate);
+            steps.push(step);
+            if let Some(&abort) = self.aborts.iter().find(|a| a.trips(&step)) {
+                return ChaosReport {
+                    steps,
+                    aborted: Some((rate, abort)),
+                };
+            }
+        }
+        ChaosReport {
+            steps,
+            aborted: None,
+        }
+    }
+}
+
+impl<F, T> Antifragile for ChaosExperiment<F>
+where
+    F: Fn() -> T,
+    T: FaultTarget,
+{
+    /// Fault rate
+    type Stressor = f64;
+    /// The experiment's metric over one batch of requests
+    type Payoff = f64;
+
+    fn payoff(&self, rate: f64) -> f64 {
+        self.measure(rate).payoff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TriadAnalysis;
+
+    /// Fails outright at the fault rate; fixed latency
+    fn fragile_target() -> impl FnMut(f64, &mut Noise) -> Observation {
+        |rate: f64, noise: &mut Noise| Observation::new(noise.uniform() >= rate, 5.0)
+    }
+
+    /// Retries until success, paying latency per attempt
+    fn retrying_target() -> impl FnMut(f64, &mut Noise) -> Observation {
+        |rate: f64, noise: &mut Noise| {
+            let mut attempts = 1.0;
+            while noise.uniform() < rate && attempts < 100.0 {
+                attempts += 1.0;
+            }
+            Observation::new(true, attempts)
+        }
+    }
+
+    #[test]
+    fn test_ramps() {
+        assert_eq!(Ramp::linear(0.0, 0.5, 3).rates(), &[0.0, 0.25, 0.5]);
+        assert_eq!(Ramp::linear(0.1, 0.5, 1).rates(), &[0.1]);
+        assert_eq!(
+            Ramp::geometric(0.01, 2.0, 4).rates(),
+            &[0.01, 0.02, 0.04, 0.08]
+        );
+        assert!(Ramp::custom(Vec::new()).rates().is_empty());
+    }
+
+    #[test]
+    fn test_success_rate_tracks_fault_rate() {
+        let experiment = ChaosExperiment::new(fragile_target).with_requests(2000);
+        let step = experiment.measure(0.3);
+        assert!((step.success_rate - 0.7).abs() < 0.03);
+        assert!((step.tail_latency - 5.0).abs() < f64::EPSILON);
+        // Success falls linearly, and common random numbers keep it exact
+        let report = experiment.run_ramp(&Ramp::linear(0.0, 0.8, 5));
+        assert_eq!(report.steps.len(), 5);
+        assert!(report.aborted.is_none());
+    }
+
+    #[test]
+    fn test_retry_latency_is_convex_in_fault_rate() {
+        // Expected attempts 1/(1 - r): latency accelerates as faults rise
+        let experiment = ChaosExperiment::new(retrying_target)
+            .with_requests(2000)
+            .with_metric(ChaosMetric::MeanLatency);
+        assert_eq!(experiment.classify(0.5, 0.2), Triad::Fragile);
+        let report = experiment.run_ramp(&Ramp::geometric(0.05, 2.0, 4));
+        assert_eq!(report.classification(), Triad::Fragile);
+
+        let goodput = experiment.with_metric(ChaosMetric::Goodput).measure(0.5);
+        assert!((goodput.payoff - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_abort_stops_the_ramp() {
+        let report = ChaosExperiment::new(retrying_target)
+            .with_abort(Abort::SuccessBelow(0.9))
+            .with_abort(Abort::TailLatencyAbove(10.0))
+            .run_ramp(&Ramp::linear(0.0, 0.9, 10));
+        let Some((rate, abort)) = report.aborted else {
+            panic!("ramp ran to completion");
+        };
+        assert_eq!(abort, Abort::TailLatencyAbove(10.0));
+        assert!((report.steps.last().map_or(0.0, |s| s.rate) - rate).abs() < f64::EPSILON);
+        assert!(report.steps.len() < 10);
+    }
+}
@@ -0,0 +1,942 @@
+//! # Combinators for composing systems
+//!
+//! A portfolio is a sum of positions, a service's cost is a sum of its
+//! components' costs. [`Sum`](crate::combinators::Sum) adds the payoffs of
+//! two systems that face the same stressor, so compositions can be
+//! classified without writing a new struct for each one.
+//! [`Scaled`](crate::combinators::Scaled) and
+//! [`Shifted`](crate::combinators::Shifted) express leverage and fixed costs
+//! without touching the underlying model.
+//!
+//! | Combinator | Payoff | Effect on convexity |
+//! |------------|--------|---------------------|
+//! | [`Sum`](crate::combinators::Sum) | `f(x) + g(x)` | Curvatures add |
+//! | [`Scaled`](crate::combinators::Scaled) | `k·f(x)` | Curvature scales by `k`; a negative `k` flips it |
+//! | [`Shifted`](crate::combinators::Shifted) | `f(x) + c` | None |
+//! | [`Negated`](crate::combinators::Negated) | `-f(x)` | Flips it: fragile and antifragile swap |
+//! | [`Portfolio`](crate::combinators::Portfolio) | `Σ wᵢ·fᵢ(x)` | Weighted sum of the component curvatures |
+//! | [`MaxOf`](crate::combinators::MaxOf) | `max(f(x), g(x))` | Adds convexity where the two cross |
+//! | [`MinOf`](crate::combinators::MinOf) | `min(f(x), g(x))` | Adds concavity where the two cross |
+//! | [`Compose`](crate::combinators::Compose) | `g(f(x))` | See [below](#convexity-under-composition) |
+//!
+//! ## Convexity under addition
+//!
+//! The convexity test is linear in the payoff: for `h(x) = f(x) + g(x)`,
+//!
+//! ```text
+//! h(x+Δ) + h(x-Δ) - 2·h(x) = [f(x+Δ) + f(x-Δ) - 2·f(x)] + [g(x+Δ) + g(x-Δ) - 2·g(x)]
+//! ```
+//!
+//! so the curvature of a sum is the sum of the curvatures. At any operating
+//! point and delta:
+//!
+//! | `f` | `g` | `f + g` |
+//! |-----|-----|---------|
+//! | Antifragile | Antifragile or Robust | Antifragile |
+//! | Fragile | Fragile or Robust | Fragile |
+//! | Robust | Robust | Robust |
+//! | Antifragile | Fragile | Whichever curvature is larger in magnitude |
+//!
+//! Adding a robust (linear) position never changes a classification, and a
+//! hedge works only if its convexity outweighs the fragility it offsets.
+//! With floating-point payoffs these rules hold up to rounding, which can
+//! make an exactly robust sum register as slightly curved.
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad, TriadAnalysis};
+//! use antifragile::combinators::Combine;
+//!
+//! /// Short volatility: collects premium, loses on large moves
+//! struct ShortStraddle;
+//!
+//! impl Antifragile for ShortStraddle {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, x: f64) -> f64 {
+//!         1.0 - x.abs()
+//!     }
+//! }
+//!
+//! /// Long convexity: pays on large moves in either direction
+//! struct LongWings;
+//!
+//! impl Antifragile for LongWings {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, x: f64) -> f64 {
+//!         0.5 * x * x
+//!     }
+//! }
+//!
+//! let book = ShortStraddle.plus(LongWings);
+//! assert_eq!(ShortStraddle.classify(0.0, 1.0), Triad::Fragile);
+//! // H = -2 + 1: the wings only offset half the fragility at Δ = 1...
+//! assert_eq!(book.classify(0.0, 1.0), Triad::Fragile);
+//! // ...but their curvature grows with Δ², H = -6 + 9 at Δ = 3
+//! assert_eq!(book.classify(0.0, 3.0), Triad::Antifragile);
+//!
+//! // Leverage and fixed costs never change the verdict's sign
+//! let levered = book.scaled(3.0).shifted(-0.25);
+//! assert_eq!(levered.classify(0.0, 3.0), Triad::Antifragile);
+//! ```
+//!
+//! ## Convexity under composition
+//!
+//! [`Compose`](crate::combinators::Compose) feeds one system's payoff into
+//! another as its stressor, as in a supply chain or a multi-stage pipeline.
+//! For smooth stages the chain rule gives
+//!
+//! ```text
+//! (g ∘ f)'' = g''(f)·f'² + g'(f)·f''
+//! ```
+//!
+//! The first term carries the outer stage's curvature, the second the inner
+//! stage's curvature weighted by how the outer stage responds to it:
+//!
+//! | `f` (inner) | `g` (outer) | `g ∘ f` |
+//! |-------------|-------------|---------|
+//! | Antifragile | Antifragile and increasing | Antifragile |
+//! | Fragile | Fragile and increasing | Fragile |
+//! | Fragile | Antifragile and decreasing | Antifragile |
+//! | Antifragile | Fragile and decreasing | Fragile |
+//! | Robust | any | The outer stage's classification |
+//! | any | Robust and increasing | The inner stage's classification |
+//!
+//! Mixed cases depend on magnitudes, which is why a chain of individually
+//! harmless stages can still be fragile.
+//!
+//! ## Envelopes
+//!
+//! [`MaxOf`](crate::combinators::MaxOf) and
+//! [`MinOf`](crate::combinators::MinOf) take the better or worse of two
+//! systems: a guarantee that floors a payoff, or a capacity limit that caps
+//! it. They generalise the constant floors and caps of
+//! [`transforms`](crate::transforms) to floors and caps that move with the
+//! stressor. Away from the points where the two payoffs cross, the envelope
+//! inherits the classification of whichever system is active. Where they
+//! cross, the kink dominates at small deltas: the maximum of two convex
+//! systems stays convex and the minimum of two concave systems stays
+//! concave, but the maximum of two *concave* systems can be antifragile at
+//! the crossing, and the minimum of two convex ones fragile.
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad, TriadAnalysis};
+//! use antifragile::combinators::Combine;
+//!
+//! /// Orders placed grow linearly with demand
+//! struct Orders;
+//!
+//! impl Antifragile for Orders {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, demand: f64) -> f64 {
+//!         2.0 * demand
+//!     }
+//! }
+//!
+//! /// A supplier's margin shrinks ever faster as orders approach capacity
+//! struct Supplier;
+//!
+//! impl Antifragile for Supplier {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, orders: f64) -> f64 {
+//!         orders - 0.01 * orders * orders
+//!     }
+//! }
+//!
+//! let chain = Orders.then(Supplier);
+//! assert!((chain.payoff(10.0) - 16.0).abs() < 1e-12);
+//! assert_eq!(chain.classify(10.0, 1.0), Triad::Fragile);
+//! ```
+
+use core::ops::{Mul, Neg};
+
+use crate::Antifragile;
+#[cfg(feature = "std")]
+use crate::{Triad, TriadAnalysis};
+
+/// Two systems under a shared stressor: `f(x) + g(x)`
+///
+/// Curvatures add; see the [module documentation](self) for how
+/// classifications combine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Sum<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Sum<A, B> {
+    /// Add the payoffs of `first` and `second`
+    pub const fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+
+    /// Get reference to the first system
+    pub const fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Get reference to the second system
+    pub const fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Unwrap both systems
+    pub fn into_parts(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A, B> Antifragile for Sum<A, B>
+where
+    A: Antifragile,
+    B: Antifragile<Stressor = A::Stressor, Payoff = A::Payoff>,
+{
+    type Stressor = A::Stressor;
+    type Payoff = A::Payoff;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        self.first.payoff(stressor) + self.second.payoff(stressor)
+    }
+}
+
+/// A system's payoff multiplied by a constant: `k·f(x)`
+///
+/// Models leverage. Curvature scales by `k`, so a positive factor keeps the
+/// classification, zero makes the system [`Robust`](crate::Triad::Robust),
+/// and a negative factor (a short position) swaps fragile and antifragile.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scaled<T: Antifragile> {
+    inner: T,
+    factor: T::Payoff,
+}
+
+/// A system's payoff plus a constant: `f(x) + c`
+///
+/// Models fixed costs (negative `c`) or income. The constant cancels in the
+/// convexity test, so the classification is unchanged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shifted<T: Antifragile> {
+    inner: T,
+    offset: T::Payoff,
+}
+
+impl<T: Antifragile> Scaled<T> {
+    /// Multiply the payoff of `inner` by `factor`
+    pub const fn new(inner: T, factor: T::Payoff) -> Self {
+        Self { inner, factor }
+    }
+
+    /// The multiplier
+    pub const fn factor(&self) -> T::Payoff {
+        self.factor
+    }
+
+    /// Get reference to the wrapped system
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Unwrap the scaled system
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Antifragile> Shifted<T> {
+    /// Add `offset` to the payoff of `inner`
+    pub const fn new(inner: T, offset: T::Payoff) -> Self {
+        Self { inner, offset }
+    }
+
+    /// The constant added
+    pub const fn offset(&self) -> T::Payoff {
+        self.offset
+    }
+
+    /// Get reference to the wrapped system
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Unwrap the shifted system
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T> Antifragile for Scaled<T>
+where
+    T: Antifragile,
+    T::Payoff: Mul<Output = T::Payoff>,
+{
+    type Stressor = T::Stressor;
+    type Payoff = T::Payoff;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        self.factor * self.inner.payoff(stressor)
+    }
+}
+
+impl<T: Antifragile> Antifragile for Shifted<T> {
+    type Stressor = T::Stressor;
+    type Payoff = T::Payoff;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        self.inner.payoff(stressor) + self.offset
+    }
+}
+
+/// The other side of the trade: `-f(x)`
+///
+/// Models short positions. Negation is exact in both integer and IEEE
+/// floating-point arithmetic, so the classification is always the
+/// [`opposite`](crate::Triad::opposite) of the inner system's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Negated<T> {
+    inner: T,
+}
+
+impl<T> Negated<T> {
+    /// Negate the payoff of `inner`
+    pub const fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    /// Get reference to the wrapped system
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Unwrap the negated system
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T> Antifragile for Negated<T>
+where
+    T: Antifragile,
+    T::Payoff: Neg<Output = T::Payoff>,
+{
+    type Stressor = T::Stressor;
+    type Payoff = T::Payoff;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        -self.inner.payoff(stressor)
+    }
+}
+
+/// One system's payoff fed into another as its stressor: `g(f(x))`
+///
+/// See the [module documentation](self#convexity-under-composition) for how
+/// classifications combine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Compose<A, B> {
+    inner: A,
+    outer: B,
+}
+
+impl<A, B> Compose<A, B> {
+    /// Feed the payoff of `inner` into `outer`
+    pub const fn new(inner: A, outer: B) -> Self {
+        Self { inner, outer }
+    }
+
+    /// Get reference to the first stage
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Get reference to the second stage
+    pub const fn outer(&self) -> &B {
+        &self.outer
+    }
+
+    /// Unwrap both stages
+    pub fn into_parts(self) -> (A, B) {
+        (self.inner, self.outer)
+    }
+}
+
+impl<A, B> Antifragile for Compose<A, B>
+where
+    A: Antifragile,
+    B: Antifragile<Stressor = A::Payoff>,
+{
+    type Stressor = A::Stressor;
+    type Payoff = B::Payoff;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        self.outer.payoff(self.inner.payoff(stressor))
+    }
+
+    #[inline]
+    fn twin(r: Self::Payoff) -> Self::Payoff {
+        B::twin(r)
+    }
+}
+
+/// The better of two systems under a shared stressor: `max(f(x), g(x))`
+///
+/// Ties and unordered payoffs (such as NaN) take the first system's payoff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MaxOf<A, B> {
+    first: A,
+    second: B,
+}
+
+/// The worse of two systems under a shared stressor: `min(f(x), g(x))`
+///
+/// Ties and unordered payoffs (such as NaN) take the first system's payoff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MinOf<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> MaxOf<A, B> {
+    /// Take the larger payoff of `first` and `second`
+    pub const fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+
+    /// Get reference to the first system
+    pub const fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Get reference to the second system
+    pub const fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Unwrap both systems
+    pub fn into_parts(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A, B> MinOf<A, B> {
+    /// Take the smaller payoff of `first` and `second`
+    pub const fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+
+    /// Get reference to the first system
+    pub const fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Get reference to the second system
+    pub const fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Unwrap both systems
+    pub fn into_parts(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A, B> Antifragile for MaxOf<A, B>
+where
+    A: Antifragile,
+    B: Antifragile<Stressor = A::Stressor, Payoff = A::Payoff>,
+{
+    type Stressor = A::Stressor;
+    type Payoff = A::Payoff;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        let first = self.first.payoff(stressor);
+        let second = self.second.payoff(stressor);
+        if second > first { second } else { first }
+    }
+}
+
+impl<A, B> Antifragile for MinOf<A, B>
+where
+    A: Antifragile,
+    B: Antifragile<Stressor = A::Stressor, Payoff = A::Payoff>,
+{
+    type Stressor = A::Stressor;
+    type Payoff = A::Payoff;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        let first = self.first.payoff(stressor);
+        let second = self.second.payoff(stressor);
+        if second < first { second } else { first }
+    }
+}
+
+/// Weighted positions in systems under a shared stressor: `Σ wᵢ·fᵢ(x)`
+///
+/// The weighted counterpart of nesting [`Sum`] and [`Scaled`] for any number
+/// of same-typed components; a negative weight is a short position.
+/// [`analyze`](Self::analyze) classifies each position alongside the
+/// aggregate.
+///
+/// ```rust
+/// use antifragile::{Antifragile, Triad, TriadAnalysis};
+/// use antifragile::combinators::Portfolio;
+///
+/// /// A straddle with the given strike
+/// struct Straddle(f64);
+///
+/// impl Antifragile for Straddle {
+///     type Stressor = f64;
+///     type Payoff = f64;
+///
+///     fn payoff(&self, price: f64) -> f64 {
+///         (price - self.0).abs()
+///     }
+/// }
+///
+/// let book = Portfolio::new()
+///     .with(2.0, Straddle(100.0))
+///     .with(-1.0, Straddle(100.0));
+///
+/// let analysis = book.analyze(100.0, 5.0);
+/// assert_eq!(analysis.positions[0].classification, Triad::Antifragile);
+/// assert_eq!(analysis.positions[1].classification, Triad::Fragile);
+/// assert_eq!(analysis.aggregate, Triad::Antifragile);
+/// assert_eq!(analysis.aggregate, book.classify(100.0, 5.0));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Portfolio<T> {
+    positions: Vec<(f64, T)>,
+}
+
+/// One position of a [`PortfolioAnalysis`]
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionVerdict {
+    /// Weight of the position
+    pub weight: f64,
+    /// Classification of the position as held, so a short position in an
+    /// antifragile system is fragile
+    pub classification: Triad,
+    /// `wᵢ·(fᵢ(x+Δ) + fᵢ(x-Δ) - 2·fᵢ(x))`; contributions sum to the
+    /// aggregate's
+    pub contribution: f64,
+}
+
+/// Result of [`Portfolio::analyze`]
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortfolioAnalysis {
+    /// One verdict per position, in insertion order
+    pub positions: Vec<PositionVerdict>,
+    /// Classification of the whole portfolio
+    pub aggregate: Triad,
+    /// `h(x+Δ) + h(x-Δ) - 2·h(x)` of the whole portfolio
+    pub curvature: f64,
+}
+
+#[cfg(feature = "std")]
+impl<T> Portfolio<T> {
+    /// An empty portfolio, whose payoff is zero
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            positions: Vec::new(),
+        }
+    }
+
+    /// Add a position of `weight` in `system`
+    #[must_use]
+    pub fn with(mut self, weight: f64, system: T) -> Self {
+        self.push(weight, system);
+        self
+    }
+
+    /// Add a position of `weight` in `system`
+    pub fn push(&mut self, weight: f64, system: T) {
+        self.positions.push((weight, system));
+    }
+
+    /// The positions as `(weight, system)`, in insertion order
+    #[must_use]
+    pub fn positions(&self) -> &[(f64, T)] {
+        &self.positions
+    }
+
+    /// Number of positions
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Whether the portfolio has no positions
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Antifragile<Payoff = f64>> Portfolio<T> {
+    /// Classify every position and the aggregate at `at`
+    ///
+    /// Costs three payoff evaluations per position; the aggregate is derived
+    /// from the positions' payoffs rather than re-evaluated.
+    #[must_use]
+    pub fn analyze(&self, at: T::Stressor, delta: T::Stressor) -> PortfolioAnalysis {
+        let mut totals = (0.0, 0.0, 0.0);
+        let positions = self
+            .positions
+            .iter()
+            .map(|(weight, system)| {
+                let detail = system.classify_detailed(at, delta);
+                totals.0 += weight * detail.below;
+                totals.1 += weight * detail.at;
+                totals.2 += weight * detail.above;
+                let contribution = weight * (detail.sum - detail.twin);
+                PositionVerdict {
+                    weight: *weight,
+                    classification: sign(contribution),
+                    contribution,
+                }
+            })
+            .collect();
+        let (below, at, above) = totals;
+        let curvature = (above + below) - Self::twin(at);
+        PortfolioAnalysis {
+            positions,
+            aggregate: sign(curvature),
+            curvature,
+        }
+    }
+}
+
+/// Classification of a curvature by its sign, robust when zero or NaN
+#[cfg(feature = "std")]
+fn sign(curvature: f64) -> Triad {
+    if curvature > 0.0 {
+        Triad::Antifragile
+    } else if curvature < 0.0 {
+        Triad::Fragile
+    } else {
+        Triad::Robust
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Default for Portfolio<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> FromIterator<(f64, T)> for Portfolio<T> {
+    fn from_iter<I: IntoIterator<Item = (f64, T)>>(iter: I) -> Self {
+        Self {
+            positions: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Antifragile<Payoff = f64>> Antifragile for Portfolio<T> {
+    type Stressor = T::Stressor;
+    type Payoff = f64;
+
+    fn payoff(&self, stressor: Self::Stressor) -> f64 {
+        self.positions
+            .iter()
+            .map(|(weight, system)| weight * system.payoff(stressor))
+            .sum()
+    }
+}
+
+/// Extension trait for composing systems fluently
+pub trait Combine: Antifragile + Sized {
+    /// Add another system's payoff under the same stressor (see [`Sum`])
+    fn plus<B>(self, other: B) -> Sum<Self, B>
+    where
+        B: Antifragile<Stressor = Self::Stressor, Payoff = Self::Payoff>,
+    {
+        Sum::new(self, other)
+    }
+
+    /// Take the larger of this and another system's payoff (see [`MaxOf`])
+    fn max_of<B>(self, other: B) -> MaxOf<Self, B>
+    where
+        B: Antifragile<Stressor = Self::Stressor, Payoff = Self::Payoff>,
+    {
+        MaxOf::new(self, other)
+    }
+
+    /// Take the smaller of this and another system's payoff (see [`MinOf`])
+    fn min_of<B>(self, other: B) -> MinOf<Self, B>
+    where
+        B: Antifragile<Stressor = Self::Stressor, Payoff = Self::Payoff>,
+    {
+        MinOf::new(self, other)
+    }
+
+    /// Multiply the payoff by a constant (see [`Scaled`])
+    fn scaled(self, factor: Self::Payoff) -> Scaled<Self>
+    where
+        Self::Payoff: Mul<Output = Self::Payoff>,
+    {
+        Scaled::new(self, factor)
+    }
+
+    /// Add a constant to the payoff (see [`Shifted`])
+    fn shifted(self, offset: Self::Payoff) -> Shifted<Self> {
+        Shifted::new(self, offset)
+    }
+
+    /// Take the other side of the trade (see [`Negated`])
+    fn negated(self) -> Negated<Self>
+    where
+        Self::Payoff: Neg<Output = Self::Payoff>,
+    {
+        Negated::new(self)
+    }
+
+    /// Feed the payoff into `outer` as its stressor (see [`Compose`])
+    fn then<B>(self, outer: B) -> Compose<Self, B>
+    where
+        B: Antifragile<Stressor = Self::Payoff>,
+    {
+        Compose::new(self, outer)
+    }
+}
+
+impl<T: Antifragile> Combine for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Triad, TriadAnalysis};
+
+    struct Linear(i64);
+
+    impl Antifragile for Linear {
+        type Stressor = i64;
+        type Payoff = i64;
+        fn payoff(&self, x: i64) -> i64 {
+            self.0 * x
+        }
+    }
+
+    struct Quadratic(i64);
+
+    impl Antifragile for Quadratic {
+        type Stressor = i64;
+        type Payoff = i64;
+        fn payoff(&self, x: i64) -> i64 {
+            self.0 * x * x
+        }
+    }
+
+    #[test]
+    fn test_robust_addend_preserves_classification() {
+        assert_eq!(
+            Quadratic(1).plus(Linear(5)).classify(3, 1),
+            Triad::Antifragile
+        );
+        assert_eq!(
+            Quadratic(-1).plus(Linear(-5)).classify(3, 1),
+            Triad::Fragile
+        );
+        assert_eq!(Linear(2).plus(Linear(-7)).classify(3, 1), Triad::Robust);
+    }
+
+    #[test]
+    fn test_opposing_curvatures_net_out() {
+        assert_eq!(
+            Quadratic(3).plus(Quadratic(-2)).classify(0, 1),
+            Triad::Antifragile
+        );
+        assert_eq!(
+            Quadratic(2).plus(Quadratic(-3)).classify(0, 1),
+            Triad::Fragile
+        );
+        assert_eq!(
+            Quadratic(2).plus(Quadratic(-2)).classify(0, 1),
+            Triad::Robust
+        );
+    }
+
+    #[test]
+    fn test_sums_nest() {
+        let book = Quadratic(1).plus(Linear(2)).plus(Quadratic(-1));
+        assert_eq!(book.payoff(4), 8);
+        assert_eq!(book.classify(4, 2), Triad::Robust);
+        let (inner, _) = book.into_parts();
+        assert_eq!(inner.second().payoff(1), 2);
+    }
+
+    #[test]
+    fn test_scaling_multiplies_curvature() {
+        assert_eq!(Quadratic(1).scaled(4).classify(0, 1), Triad::Antifragile);
+        assert_eq!(Quadratic(1).scaled(0).classify(0, 1), Triad::Robust);
+        assert_eq!(Quadratic(1).scaled(-1).classify(0, 1), Triad::Fragile);
+        let levered = Quadratic(1).scaled(3);
+        assert_eq!(levered.payoff(2), 12);
+        assert_eq!(levered.factor(), 3);
+        assert_eq!(levered.classify_detailed(0, 1).difference, 6);
+    }
+
+    #[test]
+    fn test_shifting_leaves_classification() {
+        let with_costs = Quadratic(-1).shifted(-100);
+        assert_eq!(with_costs.payoff(2), -104);
+        assert_eq!(with_costs.offset(), -100);
+        assert_eq!(with_costs.classify(5, 1), Triad::Fragile);
+        assert_eq!(Linear(1).shifted(7).classify(5, 1), Triad::Robust);
+        assert_eq!(with_costs.into_inner().payoff(2), -4);
+    }
+
+    #[test]
+    fn test_composition_follows_the_chain_rule() {
+        // x² then 2y: the inner convexity passes through an increasing stage
+        assert_eq!(
+            Quadratic(1).then(Linear(2)).classify(3, 1),
+            Triad::Antifragile
+        );
+        // ...and is flipped by a decreasing one
+        assert_eq!(Quadratic(1).then(Linear(-2)).classify(3, 1), Triad::Fragile);
+        // 3x then y²: a linear inner stage keeps the outer curvature
+        assert_eq!(
+            Linear(3).then(Quadratic(1)).classify(3, 1),
+            Triad::Antifragile
+        );
+        // -x² then y²: (x²)² = x⁴ is convex although the inner stage is concave
+        let chain = Quadratic(-1).then(Quadratic(1));
+        assert_eq!(chain.payoff(2), 16);
+        assert_eq!(chain.classify(2, 1), Triad::Antifragile);
+        assert_eq!(chain.outer().payoff(3), 9);
+    }
+
+    #[test]
+    fn test_negation_is_the_opposite() {
+        struct Cubic;
+
+        impl Antifragile for Cubic {
+            type Stressor = f64;
+            type Payoff = f64;
+            fn payoff(&self, x: f64) -> f64 {
+                x * x * x - 0.3 * x
+            }
+        }
+
+        for i in -20..=20 {
+            let at = f64::from(i) * 0.37;
+            for delta in [1e-3, 0.1, 2.5] {
+                assert_eq!(
+                    Cubic.negated().classify(at, delta),
+                    Cubic.classify(at, delta).opposite()
+                );
+            }
+        }
+        for at in -5..=5 {
+            let short = Quadratic(at).plus(Linear(3)).negated();
+            assert_eq!(
+                short.classify(at, 2),
+                Quadratic(at).plus(Linear(3)).classify(at, 2).opposite()
+            );
+        }
+        assert_eq!(Quadratic(2).negated().negated().payoff(3), 18);
+    }
+
+    #[test]
+    fn test_envelopes_add_curvature_at_the_crossing() {
+        // Demand 3x capped by a capacity of 2x + 4: crosses at x = 4
+        let served = Linear(3).min_of(Linear(2).shifted(4));
+        assert_eq!(served.payoff(2), 6);
+        assert_eq!(served.payoff(10), 24);
+        assert_eq!(served.classify(2, 1), Triad::Robust);
+        assert_eq!(served.classify(4, 1), Triad::Fragile);
+        assert_eq!(served.classify(8, 1), Triad::Robust);
+
+        // A guarantee of 5 under a linear loss: a long put
+        let guaranteed = Linear(-1).max_of(Linear(0).shifted(5));
+        assert_eq!(guaranteed.payoff(-10), 10);
+        assert_eq!(guaranteed.payoff(10), 5);
+        assert_eq!(guaranteed.classify(-5, 1), Triad::Antifragile);
+
+        // The maximum of two concave systems is antifragile where they cross
+        let upper = Quadratic(-1).max_of(Quadratic(-1).plus(Linear(4)));
+        assert_eq!(upper.classify(0, 1), Triad::Antifragile);
+        assert_eq!(upper.classify(2, 1), Triad::Fragile);
+        assert_eq!(upper.first().payoff(3), -9);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_portfolio_is_the_weighted_sum() {
+        struct Power(i32);
+
+        impl Antifragile for Power {
+            type Stressor = f64;
+            type Payoff = f64;
+            fn payoff(&self, x: f64) -> f64 {
+                x.powi(self.0)
+            }
+        }
+
+        let book: Portfolio<Power> = [(0.5, Power(2)), (-2.0, Power(1)), (-0.25, Power(2))]
+            .into_iter()
+            .collect();
+        assert_eq!(book.len(), 3);
+        assert!((book.payoff(2.0) - (2.0 - 4.0 - 1.0)).abs() < 1e-12);
+
+        let analysis = book.analyze(2.0, 1.0);
+        let verdicts: Vec<Triad> = analysis
+            .positions
+            .iter()
+            .map(|p| p.classification)
+            .collect();
+        assert_eq!(
+            verdicts,
+            [Triad::Antifragile, Triad::Robust, Triad::Fragile]
+        );
+        // 0.5·2 + 0 - 0.25·2
+        assert!((analysis.curvature - 0.5).abs() < 1e-12);
+        let total: f64 = analysis.positions.iter().map(|p| p.contribution).sum();
+        assert!((total - analysis.curvature).abs() < 1e-12);
+        assert_eq!(analysis.aggregate, book.classify(2.0, 1.0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_empty_portfolio_is_robust() {
+        struct Square;
+
+        impl Antifragile for Square {
+            type Stressor = f64;
+            type Payoff = f64;
+            fn payoff(&self, x: f64) -> f64 {
+                x * x
+            }
+        }
+
+        let book: Portfolio<Square> = Portfolio::default();
+        assert!(book.is_empty());
+        let analysis = book.analyze(1.0, 0.5);
+        assert!(analysis.positions.is_empty());
+        assert_eq!(analysis.aggregate, Triad::Robust);
+        assert_eq!(
+            book.with(1.0, Square).classify(1.0, 0.5),
+            Triad::Antifragile
+        );
+    }
+}
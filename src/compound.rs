@@ -0,0 +1,214 @@
+//! # Multi-period compounding
+//!
+//! A single-period classification treats gains and losses additively. Wealth
+//! does not work that way: each period's return is applied to whatever the
+//! previous periods left behind, so a +10% period followed by a -10% period
+//! ends at 99%, not 100%. Over a horizon this *volatility drag* compounds and
+//! can turn a system that looks robust, or even antifragile, in a point
+//! analysis into one that is harmed by volatility.
+//!
+//! [`Compounded`](crate::compound::Compounded) turns a per-period return
+//! `f(x)` into a system whose stressor is the *per-period volatility* `σ`
+//! and whose payoff is terminal wealth after `horizon` periods. Each period
+//! the stressor alternates between `center + σ` and `center - σ`, starting
+//! with the up move, and one unit of starting capital is reinvested at
+//! `1 + f(x)`. Wealth never drops below zero: a period with `f(x) ≤ -1` is
+//! ruin, and ruin is absorbing.
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad, TriadAnalysis};
+//! use antifragile::compound::Compounded;
+//!
+//! // Returns 10% per unit of market move: perfectly linear
+//! struct Tracker;
+//!
+//! impl Antifragile for Tracker {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, market: f64) -> f64 {
+//!         0.1 * market
+//!     }
+//! }
+//!
+//! // A single period is indifferent to volatility...
+//! assert_eq!(Tracker.classify(0.0, 1.0), Triad::Robust);
+//!
+//! // ...but compounded over 12 periods volatility costs wealth
+//! let compounded = Compounded::new(Tracker, 0.0, 12);
+//! assert_eq!(compounded.classify(0.0, 1.0), Triad::Fragile);
+//! ```
+
+use crate::{Antifragile, Triad, TriadAnalysis};
+
+/// Terminal wealth of a per-period return compounded over a horizon, as a
+/// function of per-period volatility
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Compounded<T> {
+    inner: T,
+    center: f64,
+    horizon: u32,
+}
+
+/// Point and compounded classifications side by side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompoundingAnalysis {
+    /// Single-period classification of the return at `center` with delta `σ`
+    pub point: Triad,
+    /// Classification of terminal wealth in volatility at `σ`
+    pub compounded: Triad,
+    /// Terminal wealth with zero volatility
+    pub calm_wealth: f64,
+    /// Terminal wealth with volatility `σ`
+    pub volatile_wealth: f64,
+}
+
+impl CompoundingAnalysis {
+    /// Returns true if compounding changed the classification
+    #[must_use]
+    pub fn point_analysis_misleads(&self) -> bool {
+        self.point != self.compounded
+    }
+
+    /// Terminal wealth gained (positive) or lost (negative) to volatility
+    #[must_use]
+    pub fn volatility_effect(&self) -> f64 {
+        self.volatile_wealth - self.calm_wealth
+    }
+}
+
+impl<T> Compounded<T> {
+    /// Compound `inner` over `horizon` periods around the per-period stressor
+    /// level `center`
+    pub const fn new(inner: T, center: f64, horizon: u32) -> Self {
+        Self {
+            inner,
+            center,
+            horizon,
+        }
+    }
+
+    /// Per-period stressor level around which volatility is applied
+    #[must_use]
+    pub const fn center(&self) -> f64 {
+        self.center
+    }
+
+    /// Number of compounding periods
+    #[must_use]
+    pub const fn horizon(&self) -> u32 {
+        self.horizon
+    }
+
+    /// Get reference to the per-period system
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Unwrap the per-period system
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Antifragile<Stressor = f64, Payoff = f64>> Compounded<T> {
+    /// Compare the single-period classification with the compounded one at
+    /// per-period volatility `sigma`
+    ///
+    /// The compounded classification probes volatilities `0`, `σ`, and `2σ`,
+    /// so it describes the effect of volatility growing from calm to `2σ`.
+    #[must_use]
+    pub fn analyze(&self, sigma: f64) -> CompoundingAnalysis {
+        CompoundingAnalysis {
+            point: self.inner.classify(self.center, sigma),
+            compounded: self.classify(sigma, sigma),
+            calm_wealth: self.payoff(0.0),
+            volatile_wealth: self.payoff(sigma),
+        }
+    }
+}
+
+impl<T: Antifragile<Stressor = f64, Payoff = f64>> Antifragile for Compounded<T> {
+    type Stressor = f64;
+    type Payoff = f64;
+
+    fn payoff(&self, sigma: f64) -> f64 {
+        let up = growth(self.inner.payoff(self.center + sigma));
+        let down = growth(self.inner.payoff(self.center - sigma));
+
+        let mut wealth = 1.0;
+        for period in 0..self.horizon {
+            wealth *= if period % 2 == 0 { up } else { down };
+        }
+        wealth
+    }
+}
+
+/// One period's growth factor, floored at zero (ruin)
+fn growth(ret: f64) -> f64 {
+    let factor = 1.0 + ret;
+    if factor > 0.0 { factor } else { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Linear(f64);
+
+    impl Antifragile for Linear {
+        type Stressor = f64;
+        type Payoff = f64;
+        fn payoff(&self, x: f64) -> f64 {
+            self.0 * x
+        }
+    }
+
+    #[test]
+    fn test_volatility_drag_on_linear_returns() {
+        let compounded = Compounded::new(Linear(0.1), 0.0, 2);
+        // (1.1)(0.9) = 0.99
+        assert!((compounded.payoff(1.0) - 0.99).abs() < 1e-12);
+        assert!((compounded.payoff(0.0) - 1.0).abs() < f64::EPSILON);
+
+        let analysis = compounded.analyze(1.0);
+        assert_eq!(analysis.point, Triad::Robust);
+        assert_eq!(analysis.compounded, Triad::Fragile);
+        assert!(analysis.point_analysis_misleads());
+        assert!(analysis.volatility_effect() < 0.0);
+    }
+
+    #[test]
+    fn test_convexity_can_outrun_the_drag() {
+        struct Straddle;
+        impl Antifragile for Straddle {
+            type Stressor = f64;
+            type Payoff = f64;
+            fn payoff(&self, x: f64) -> f64 {
+                0.1 * x.abs()
+            }
+        }
+
+        let analysis = Compounded::new(Straddle, 0.0, 10).analyze(1.0);
+        assert_eq!(analysis.point, Triad::Antifragile);
+        assert_eq!(analysis.compounded, Triad::Antifragile);
+        assert!(!analysis.point_analysis_misleads());
+        assert!(analysis.volatility_effect() > 0.0);
+    }
+
+    #[test]
+    fn test_ruin_is_absorbing() {
+        let compounded = Compounded::new(Linear(1.0), 0.0, 3);
+        // Down period returns -100%: nothing left afterwards
+        assert!(compounded.payoff(1.0).abs() < f64::EPSILON);
+        assert!(compounded.payoff(2.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_odd_horizon_ends_on_up_move() {
+        let compounded = Compounded::new(Linear(0.1), 0.0, 3);
+        assert!((compounded.payoff(1.0) - 1.1 * 0.9 * 1.1).abs() < 1e-12);
+        assert_eq!(compounded.horizon(), 3);
+        assert!(compounded.center().abs() < f64::EPSILON);
+    }
+}
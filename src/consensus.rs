@@ -0,0 +1,249 @@
+//! # Fleet consensus across replicas
+//!
+//! In a distributed deployment each replica runs its own online classifier,
+//! such as a [`TriadPosterior`](crate::bayes::TriadPosterior), over the
+//! traffic it happens to see. A fleet-level verdict needs to pool them
+//! without letting a replica that has just started, and has seen a handful
+//! of requests, pull the answer around.
+//!
+//! [`consensus`](crate::consensus::consensus) mixes the per-replica
+//! [`TriadProbabilities`](crate::bayes::TriadProbabilities) weighted by how
+//! many samples each replica has seen, leaves out replicas below
+//! [`ConsensusConfig::min_samples`](crate::consensus::ConsensusConfig::min_samples),
+//! and reports how far the replicas that were counted disagree with the
+//! result.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::bayes::TriadProbabilities;
+//! use antifragile::consensus::{ConsensusConfig, ReplicaState, consensus};
+//!
+//! let replicas = [
+//!     ReplicaState::new(TriadProbabilities::new(0.05, 0.15, 0.8), 900),
+//!     ReplicaState::new(TriadProbabilities::new(0.1, 0.2, 0.7), 1100),
+//!     // Just restarted: three noisy samples that happen to look fragile
+//!     ReplicaState::new(TriadProbabilities::new(0.9, 0.05, 0.05), 3),
+//! ];
+//!
+//! let fleet = consensus(&replicas, &ConsensusConfig::default()).unwrap();
+//! assert_eq!(fleet.classification, Triad::Antifragile);
+//! assert_eq!((fleet.warm, fleet.cold), (2, 1));
+//! assert!(fleet.dissenters.is_empty());
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Triad;
+use crate::bayes::{TriadPosterior, TriadProbabilities};
+
+/// One replica's current classifier state
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReplicaState {
+    /// The replica's probability for each class
+    pub probabilities: TriadProbabilities,
+    /// Number of observations behind `probabilities`
+    pub samples: u64,
+}
+
+impl ReplicaState {
+    /// A replica state from its probabilities and sample count
+    #[must_use]
+    pub const fn new(probabilities: TriadProbabilities, samples: u64) -> Self {
+        Self {
+            probabilities,
+            samples,
+        }
+    }
+}
+
+impl From<&TriadPosterior> for ReplicaState {
+    fn from(posterior: &TriadPosterior) -> Self {
+        Self::new(posterior.posterior(), posterior.observations() as u64)
+    }
+}
+
+/// Configuration for [`consensus`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsensusConfig {
+    /// Replicas with fewer samples are cold and left out of the mixture
+    pub min_samples: u64,
+}
+
+impl Default for ConsensusConfig {
+    /// Replicas need at least 30 samples to count
+    fn default() -> Self {
+        Self { min_samples: 30 }
+    }
+}
+
+/// Fleet-level classification with disagreement diagnostics
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Consensus {
+    /// Sample-weighted mixture of the warm replicas' probabilities
+    pub probabilities: TriadProbabilities,
+    /// Most likely class under `probabilities`
+    pub classification: Triad,
+    /// Replicas counted in the mixture
+    pub warm: usize,
+    /// Replicas left out for having too few samples
+    pub cold: usize,
+    /// Samples behind the mixture
+    pub samples: u64,
+    /// Sample-weighted fraction of warm replicas whose own most likely class
+    /// is `classification`
+    pub agreement: f64,
+    /// Sample-weighted mean total-variation distance between each warm
+    /// replica's probabilities and the mixture, in `[0, 1]`
+    pub divergence: f64,
+    /// Indices of warm replicas whose own most likely class differs
+    pub dissenters: Vec<usize>,
+}
+
+impl Consensus {
+    /// Returns true if every warm replica's most likely class is the
+    /// consensus
+    #[must_use]
+    pub fn is_unanimous(&self) -> bool {
+        self.dissenters.is_empty()
+    }
+}
+
+/// Merge per-replica classifier states into a fleet-level classification
+///
+/// Each replica with at least [`ConsensusConfig::min_samples`] samples
+/// contributes its probabilities in proportion to its sample count, so
+/// every sample carries the same weight whichever replica saw it. Returns
+/// `None` when no replica is warm.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn consensus(replicas: &[ReplicaState], config: &ConsensusConfig) -> Option<Consensus> {
+    let is_warm =
+        |replica: &ReplicaState| replica.samples > 0 && replica.samples >= config.min_samples;
+    let samples: u64 = replicas
+        .iter()
+        .filter(|r| is_warm(r))
+        .map(|r| r.samples)
+        .sum();
+    if samples == 0 {
+        return None;
+    }
+    let total = samples as f64;
+
+    let mut mixture = [0.0; 3];
+    for replica in replicas.iter().filter(|r| is_warm(r)) {
+        let weight = replica.samples as f64 / total;
+        mixture[0] += weight * replica.probabilities.fragile;
+        mixture[1] += weight * replica.probabilities.robust;
+        mixture[2] += weight * replica.probabilities.antifragile;
+    }
+    let probabilities = TriadProbabilities::new(mixture[0], mixture[1], mixture[2]);
+    let classification = probabilities.most_likely();
+
+    let (mut agreement, mut divergence) = (0.0, 0.0);
+    let mut dissenters = Vec::new();
+    for (index, replica) in replicas.iter().enumerate().filter(|(_, r)| is_warm(r)) {
+        let weight = replica.samples as f64 / total;
+        if replica.probabilities.most_likely() == classification {
+            agreement += weight;
+        } else {
+            dissenters.push(index);
+        }
+        divergence += weight
+            * 0.5
+            * Triad::iter()
+                .map(|t| {
+                    (replica.probabilities.probability(t) - probabilities.probability(t)).abs()
+                })
+                .sum::<f64>();
+    }
+
+    let warm = replicas.iter().filter(|r| is_warm(r)).count();
+    Some(Consensus {
+        probabilities,
+        classification,
+        warm,
+        cold: replicas.len() - warm,
+        samples,
+        agreement,
+        divergence,
+        dissenters,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bayes::BayesConfig;
+
+    fn replica(fragile: f64, robust: f64, antifragile: f64, samples: u64) -> ReplicaState {
+        ReplicaState::new(
+            TriadProbabilities::new(fragile, robust, antifragile),
+            samples,
+        )
+    }
+
+    #[test]
+    fn test_mixture_is_weighted_by_samples() {
+        let fleet = consensus(
+            &[replica(0.0, 0.0, 1.0, 300), replica(1.0, 0.0, 0.0, 100)],
+            &ConsensusConfig::default(),
+        )
+        .unwrap();
+        assert!((fleet.probabilities.antifragile - 0.75).abs() < 1e-12);
+        assert!((fleet.probabilities.fragile - 0.25).abs() < 1e-12);
+        assert_eq!(fleet.classification, Triad::Antifragile);
+        assert_eq!(fleet.samples, 400);
+        assert!((fleet.agreement - 0.75).abs() < 1e-12);
+        assert_eq!(fleet.dissenters, [1]);
+        assert!(!fleet.is_unanimous());
+        // Each replica is 0.25 or 0.75 away from the mixture
+        assert!((fleet.divergence - 0.375).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cold_replica_cannot_flip_the_verdict() {
+        let warm = replica(0.1, 0.6, 0.3, 50);
+        let cold = replica(0.0, 0.0, 1.0, 10);
+        let fleet = consensus(&[warm, cold], &ConsensusConfig::default()).unwrap();
+        assert_eq!(fleet.classification, Triad::Robust);
+        assert_eq!((fleet.warm, fleet.cold), (1, 1));
+        assert!(fleet.is_unanimous());
+
+        // Counted anyway, it still only carries its share of the samples
+        let fleet = consensus(&[warm, cold], &ConsensusConfig { min_samples: 0 }).unwrap();
+        assert_eq!(fleet.classification, Triad::Robust);
+        assert_eq!(fleet.dissenters, [1]);
+    }
+
+    #[test]
+    fn test_no_warm_replicas() {
+        let config = ConsensusConfig::default();
+        assert!(consensus(&[], &config).is_none());
+        assert!(consensus(&[replica(1.0, 1.0, 1.0, 5)], &config).is_none());
+        let unseen = ConsensusConfig { min_samples: 0 };
+        assert!(consensus(&[replica(1.0, 1.0, 1.0, 0)], &unseen).is_none());
+    }
+
+    #[test]
+    fn test_from_posteriors() {
+        let replicas: Vec<ReplicaState> = [0.0, 0.1]
+            .into_iter()
+            .map(|wobble| {
+                let mut posterior = TriadPosterior::new(BayesConfig::default());
+                for i in 0..40 {
+                    let x = f64::from(i) / 4.0;
+                    let jitter = if i % 2 == 0 { wobble } else { -wobble };
+                    posterior.observe(x, 3.0 - 0.5 * x * x + jitter + 0.05 * f64::from(i % 3));
+                }
+                ReplicaState::from(&posterior)
+            })
+            .collect();
+        let fleet = consensus(&replicas, &ConsensusConfig::default()).unwrap();
+        assert_eq!(fleet.classification, Triad::Fragile);
+        assert_eq!(fleet.samples, 80);
+        assert!(fleet.divergence < 0.01);
+    }
+}
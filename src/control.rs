@@ -0,0 +1,360 @@
+//! # Closed-loop controllers
+//!
+//! A control loop is designed for a nominal disturbance. The question this
+//! module answers is what happens as the disturbance grows: does tracking
+//! degrade gracefully, fall apart faster and faster (saturation, integrator
+//! windup), or even improve because the disturbance acts as dither that
+//! breaks stiction and quantization?
+//!
+//! [`ClosedLoop`](crate::control::ClosedLoop) simulates a discrete-time loop
+//! built from a [`Plant`](crate::control::Plant) and a
+//! [`Controller`](crate::control::Controller). Each step the controller
+//! sees the measurement, the plant receives the control input plus a
+//! disturbance `amplitude · n` with `n` standard normal, and the tracking
+//! error is recorded. The stressor is the disturbance amplitude; the payoff
+//! is the negated mean tracking error, so better tracking is a higher
+//! payoff. Every trial uses the same noise at every amplitude (common random
+//! numbers), so the second difference compares like with like.
+//!
+//! [`Pid`](crate::control::Pid) is a ready-made controller; plants and
+//! custom controllers can be plain closures.
+//!
+//! ```rust
+//! use antifragile::{Triad, TriadAnalysis};
+//! use antifragile::control::{ClosedLoop, Pid};
+//!
+//! // A first-order plant whose actuator saturates at ±1
+//! let plant = || {
+//!     let mut y = 0.0;
+//!     move |u: f64, d: f64| {
+//!         y = 0.9 * y + 0.1 * (u.clamp(-1.0, 1.0) + d);
+//!         y
+//!     }
+//! };
+//! let pid = || Pid::new(2.0, 0.5, 0.0);
+//!
+//! let looped = ClosedLoop::new(plant, pid).with_setpoint(0.5);
+//!
+//! // Errors grow faster than the disturbance once the actuator saturates
+//! assert_eq!(looped.classify(1.0, 0.5), Triad::Fragile);
+//! ```
+
+use crate::Antifragile;
+use crate::learning::Noise;
+
+/// A discrete-time plant
+///
+/// Any `FnMut(f64, f64) -> f64` closure taking the control input and the
+/// disturbance is a plant.
+pub trait Plant {
+    /// Advance one step under control input `input` and `disturbance`, and
+    /// return the new measurement
+    fn step(&mut self, input: f64, disturbance: f64) -> f64;
+}
+
+impl<F: FnMut(f64, f64) -> f64> Plant for F {
+    fn step(&mut self, input: f64, disturbance: f64) -> f64 {
+        self(input, disturbance)
+    }
+}
+
+/// A discrete-time controller
+///
+/// Any `FnMut(f64, f64) -> f64` closure taking the setpoint and the
+/// measurement is a controller.
+pub trait Controller {
+    /// Control input for the next step given `setpoint` and `measurement`
+    fn control(&mut self, setpoint: f64, measurement: f64) -> f64;
+}
+
+impl<F: FnMut(f64, f64) -> f64> Controller for F {
+    fn control(&mut self, setpoint: f64, measurement: f64) -> f64 {
+        self(setpoint, measurement)
+    }
+}
+
+/// Proportional-integral-derivative controller
+///
+/// With output limits, the integral is frozen while the output saturates
+/// (conditional integration), which prevents windup.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pid {
+    /// Proportional gain
+    pub kp: f64,
+    /// Integral gain
+    pub ki: f64,
+    /// Derivative gain
+    pub kd: f64,
+    dt: f64,
+    limits: Option<(f64, f64)>,
+    integral: f64,
+    previous_error: Option<f64>,
+}
+
+impl Pid {
+    /// A controller with the given gains, a unit time step, and no limits
+    #[must_use]
+    pub const fn new(kp: f64, ki: f64, kd: f64) -> Self {
+        Self {
+            kp,
+            ki,
+            kd,
+            dt: 1.0,
+            limits: None,
+            integral: 0.0,
+            previous_error: None,
+        }
+    }
+
+    /// Set the time step used to integrate and differentiate the error
+    #[must_use]
+    pub const fn with_dt(mut self, dt: f64) -> Self {
+        self.dt = dt;
+        self
+    }
+
+    /// Clamp the output to `[min, max]`
+    #[must_use]
+    pub const fn with_limits(mut self, min: f64, max: f64) -> Self {
+        self.limits = Some((min, max));
+        self
+    }
+
+    /// Clear the integral and derivative memory
+    pub const fn reset(&mut self) {
+        self.integral = 0.0;
+        self.previous_error = None;
+    }
+}
+
+impl Controller for Pid {
+    fn control(&mut self, setpoint: f64, measurement: f64) -> f64 {
+        let error = setpoint - measurement;
+        let derivative = self
+            .previous_error
+            .map_or(0.0, |previous| (error - previous) / self.dt);
+        self.previous_error = Some(error);
+
+        let integral = self.integral + error * self.dt;
+        let output = self.kp * error + self.ki * integral + self.kd * derivative;
+        match self.limits {
+            Some((min, max)) if output < min || output > max => output.clamp(min, max),
+            _ => {
+                self.integral = integral;
+                output
+            }
+        }
+    }
+}
+
+/// How tracking errors are scored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TrackingMetric {
+    /// Mean squared error
+    #[default]
+    Squared,
+    /// Mean absolute error
+    Absolute,
+}
+
+impl TrackingMetric {
+    fn score(self, error: f64) -> f64 {
+        match self {
+            Self::Squared => error * error,
+            Self::Absolute => error.abs(),
+        }
+    }
+}
+
+/// A closed loop whose disturbance amplitude is the stressor
+#[derive(Debug, Clone, Copy)]
+pub struct ClosedLoop<P, C> {
+    plant: P,
+    controller: C,
+    setpoint: f64,
+    steps: u32,
+    trials: u32,
+    seed: u64,
+    metric: TrackingMetric,
+}
+
+impl<P, C, PS, CS> ClosedLoop<P, C>
+where
+    P: Fn() -> PS,
+    C: Fn() -> CS,
+    PS: Plant,
+    CS: Controller,
+{
+    /// A loop that builds a fresh plant and controller for every trial
+    ///
+    /// Defaults to a setpoint of zero, 200 steps per trial, 32 trials, and
+    /// squared tracking error.
+    pub const fn new(plant: P, controller: C) -> Self {
+        Self {
+            plant,
+            controller,
+            setpoint: 0.0,
+            steps: 200,
+            trials: 32,
+            seed: 0x5EED,
+            metric: TrackingMetric::Squared,
+        }
+    }
+
+    /// Set the setpoint the controller tracks
+    #[must_use]
+    pub const fn with_setpoint(mut self, setpoint: f64) -> Self {
+        self.setpoint = setpoint;
+        self
+    }
+
+    /// Set the number of steps simulated per trial
+    #[must_use]
+    pub const fn with_steps(mut self, steps: u32) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    /// Set the number of independent trials
+    #[must_use]
+    pub const fn with_trials(mut self, trials: u32) -> Self {
+        self.trials = trials;
+        self
+    }
+
+    /// Set the base seed; equal seeds give identical results
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set how tracking errors are scored
+    #[must_use]
+    pub const fn with_metric(mut self, metric: TrackingMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Mean tracking error per step at disturbance `amplitude`, averaged
+    /// over trials
+    ///
+    /// The first measurement is the plant's response to one step with no
+    /// input and no disturbance. NaN with zero steps or zero trials.
+    #[must_use]
+    pub fn tracking_error(&self, amplitude: f64) -> f64 {
+        let mut total = 0.0;
+        for trial in 0..self.trials {
+            let mut plant = (self.plant)();
+            let mut controller = (self.controller)();
+            let mut noise = Noise::new(
+                self.seed
+                    .wrapping_add(u64::from(trial).wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+            );
+            let mut measurement = plant.step(0.0, 0.0);
+            for _ in 0..self.steps {
+                let input = controller.control(self.setpoint, measurement);
+                measurement = plant.step(input, amplitude * noise.normal());
+                total += self.metric.score(self.setpoint - measurement);
+            }
+        }
+        total / (f64::from(self.trials) * f64::from(self.steps))
+    }
+}
+
+impl<P, C, PS, CS> Antifragile for ClosedLoop<P, C>
+where
+    P: Fn() -> PS,
+    C: Fn() -> CS,
+    PS: Plant,
+    CS: Controller,
+{
+    /// Disturbance amplitude
+    type Stressor = f64;
+    /// Negated mean tracking error
+    type Payoff = f64;
+
+    fn payoff(&self, amplitude: f64) -> f64 {
+        -self.tracking_error(amplitude)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Triad, TriadAnalysis};
+
+    fn first_order() -> impl FnMut(f64, f64) -> f64 {
+        let mut y = 0.0;
+        move |u: f64, d: f64| {
+            y = 0.8 * y + 0.2 * (u + d);
+            y
+        }
+    }
+
+    /// Moves only when the applied force breaks static friction
+    fn sticky() -> impl FnMut(f64, f64) -> f64 {
+        let mut y = 0.0;
+        move |u: f64, d: f64| {
+            let force = u + d;
+            if force.abs() > 1.0 {
+                y += 0.05 * force;
+            }
+            y
+        }
+    }
+
+    #[test]
+    fn test_pid_integrates_and_saturates() {
+        let mut pid = Pid::new(1.0, 0.5, 0.0);
+        assert!((pid.control(1.0, 0.0) - 1.5).abs() < f64::EPSILON);
+        assert!((pid.control(1.0, 0.0) - 2.0).abs() < f64::EPSILON);
+
+        let mut limited = Pid::new(1.0, 0.5, 0.0).with_limits(-1.0, 1.0);
+        for _ in 0..100 {
+            assert!((limited.control(10.0, 0.0) - 1.0).abs() < f64::EPSILON);
+        }
+        // No windup: the output leaves saturation as soon as the error flips
+        assert!(limited.control(0.0, 1.0) < 0.0);
+
+        let mut derivative = Pid::new(0.0, 0.0, 2.0).with_dt(0.5);
+        assert!(derivative.control(1.0, 0.0).abs() < f64::EPSILON);
+        assert!((derivative.control(1.0, 0.5) + 2.0).abs() < f64::EPSILON);
+        derivative.reset();
+        assert!(derivative.control(1.0, 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_linear_loop_is_fragile_to_disturbance() {
+        let looped = ClosedLoop::new(first_order, || Pid::new(1.0, 0.2, 0.0));
+        // Squared error of a linear loop grows with the square of the amplitude
+        assert_eq!(looped.classify(1.0, 0.5), Triad::Fragile);
+        let ratio = looped.tracking_error(2.0) / looped.tracking_error(1.0);
+        assert!((ratio - 4.0).abs() < 1e-9);
+
+        let absolute = looped.with_metric(TrackingMetric::Absolute);
+        assert!(absolute.convexity(1.0, 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dither_breaks_stiction() {
+        let looped = ClosedLoop::new(sticky, || Pid::new(0.8, 0.0, 0.0))
+            .with_setpoint(1.0)
+            .with_metric(TrackingMetric::Absolute);
+        // Without disturbance the loop sticks short of the setpoint
+        assert!(looped.tracking_error(0.0) > 0.5);
+        assert!(looped.tracking_error(0.5) < 0.5 * looped.tracking_error(0.0));
+        // Small dither pays off at an accelerating rate; too much just adds noise
+        assert_eq!(looped.classify(0.1, 0.1), Triad::Antifragile);
+        assert!(looped.tracking_error(2.0) > looped.tracking_error(0.8));
+    }
+
+    #[test]
+    fn test_seed_and_degenerate_runs() {
+        let looped = ClosedLoop::new(first_order, || |_: f64, _: f64| 0.0).with_trials(4);
+        assert!((looped.tracking_error(1.0) - looped.tracking_error(1.0)).abs() < f64::EPSILON);
+        let reseeded = looped.with_seed(1);
+        assert!((looped.tracking_error(1.0) - reseeded.tracking_error(1.0)).abs() > 0.0);
+        assert!(looped.with_steps(0).tracking_error(1.0).is_nan());
+    }
+}
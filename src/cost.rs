@@ -0,0 +1,194 @@
+//! # Cost of fragility in money
+//!
+//! Convexity is measured in payoff units per unit of stress squared, which
+//! means little outside a risk team. [`fragility_cost`](crate::cost::fragility_cost)
+//! restates it in money per period. Given stressor samples for one period
+//! and a conversion from payoff to money, it compares:
+//!
+//! - **Forecast**: the money made if the stressor sat at its mean, which is
+//!   what a plan built on point estimates reports.
+//! - **Expected**: the money actually expected across the samples.
+//!
+//! The difference is the Jensen gap: the expected cost per period of the
+//! payoff's curvature, positive for fragile systems and negative (a gain)
+//! for antifragile ones. The tail loss is how far the worst
+//! `tail_fraction` of periods fall short of the forecast on average.
+//!
+//! ```rust
+//! use antifragile::Antifragile;
+//! use antifragile::cost::{CostConfig, fragility_cost};
+//!
+//! // Throughput in requests, falling off quadratically with load
+//! struct Service;
+//!
+//! impl Antifragile for Service {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, load: f64) -> f64 {
+//!         1000.0 - 10.0 * load * load
+//!     }
+//! }
+//!
+//! // Load is 4 or 6 with equal probability; each request earns 0.5
+//! let cost = fragility_cost(&Service, &[4.0, 6.0], |r| 0.5 * r, &CostConfig::default());
+//!
+//! // Planning for a load of 5 overstates revenue by 0.5 · 10 · Var(load)
+//! assert!((cost.forecast - 375.0).abs() < 1e-9);
+//! assert!((cost.jensen_gap - 5.0).abs() < 1e-9);
+//! assert!((cost.tail_loss - 55.0).abs() < 1e-9);
+//! ```
+
+use crate::Antifragile;
+
+/// Configuration for [`fragility_cost`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostConfig {
+    /// Share of worst periods averaged into the tail loss
+    pub tail_fraction: f64,
+}
+
+impl Default for CostConfig {
+    /// The worst 5% of periods
+    fn default() -> Self {
+        Self {
+            tail_fraction: 0.05,
+        }
+    }
+}
+
+/// Result of [`fragility_cost`], in money per period
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FragilityCost {
+    /// Money made at the mean stressor
+    pub forecast: f64,
+    /// Expected money across the stressor samples
+    pub expected: f64,
+    /// `forecast - expected`: positive when curvature costs money
+    pub jensen_gap: f64,
+    /// `forecast` minus the mean of the worst `tail_fraction` of periods
+    pub tail_loss: f64,
+}
+
+impl FragilityCost {
+    /// Returns true if volatility costs money on average
+    #[must_use]
+    pub fn is_cost(&self) -> bool {
+        self.jensen_gap > 0.0
+    }
+
+    /// Jensen gap accumulated over `periods` independent periods
+    #[must_use]
+    pub fn over_periods(&self, periods: u32) -> f64 {
+        self.jensen_gap * f64::from(periods)
+    }
+}
+
+/// Expected monetary cost per period of the curvature of `system` when the
+/// stressor follows the empirical distribution of `samples`
+///
+/// `payoff_to_money` converts a payoff into money; a non-linear conversion
+/// (penalties, tiered pricing) contributes its own curvature. The tail
+/// always includes at least the single worst period. Costs
+/// `samples.len() + 1` payoff evaluations; an empty sample set yields zero
+/// for every field.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn fragility_cost<T, M>(
+    system: &T,
+    samples: &[f64],
+    payoff_to_money: M,
+    config: &CostConfig,
+) -> FragilityCost
+where
+    T: Antifragile<Stressor = f64, Payoff = f64> + ?Sized,
+    M: Fn(f64) -> f64,
+{
+    if samples.is_empty() {
+        return FragilityCost {
+            forecast: 0.0,
+            expected: 0.0,
+            jensen_gap: 0.0,
+            tail_loss: 0.0,
+        };
+    }
+
+    let count = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / count;
+    let forecast = payoff_to_money(system.payoff(mean));
+
+    let mut outcomes: Vec<f64> = samples
+        .iter()
+        .map(|&x| payoff_to_money(system.payoff(x)))
+        .collect();
+    let expected = outcomes.iter().sum::<f64>() / count;
+
+    outcomes.sort_by(f64::total_cmp);
+    let tail_count = ((config.tail_fraction * count).ceil() as usize).clamp(1, outcomes.len());
+    let tail_mean = outcomes[..tail_count].iter().sum::<f64>() / tail_count as f64;
+
+    FragilityCost {
+        forecast,
+        expected,
+        jensen_gap: forecast - expected,
+        tail_loss: forecast - tail_mean,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Power(f64);
+
+    impl Antifragile for Power {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&self, x: f64) -> f64 {
+            x.powf(self.0)
+        }
+    }
+
+    const SAMPLES: [f64; 4] = [1.0, 2.0, 3.0, 4.0];
+
+    #[test]
+    fn test_linear_payoff_has_no_jensen_gap() {
+        let cost = fragility_cost(&Power(1.0), &SAMPLES, |p| 3.0 * p, &CostConfig::default());
+        assert!(cost.jensen_gap.abs() < 1e-12);
+        assert!(!cost.is_cost());
+        // Worst period is x = 1, forecast at x = 2.5
+        assert!((cost.tail_loss - 4.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_concave_payoff_costs_money() {
+        let cost = fragility_cost(&Power(0.5), &SAMPLES, |p| p, &CostConfig::default());
+        assert!(cost.is_cost());
+        assert!((cost.over_periods(12) - 12.0 * cost.jensen_gap).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_convex_payoff_gains_and_conversion_matters() {
+        let convex = fragility_cost(&Power(2.0), &SAMPLES, |p| p, &CostConfig::default());
+        // E[X²] - (E[X])² = Var(X) = 1.25
+        assert!((convex.jensen_gap + 1.25).abs() < 1e-12);
+
+        // A steep penalty on low payoffs turns the same system into a cost
+        let penalized = |p: f64| if p < 5.0 { p - 100.0 } else { p };
+        let cost = fragility_cost(&Power(2.0), &SAMPLES, penalized, &CostConfig::default());
+        assert!(cost.is_cost());
+    }
+
+    #[test]
+    fn test_tail_fraction_and_empty_samples() {
+        let config = CostConfig { tail_fraction: 0.5 };
+        let cost = fragility_cost(&Power(1.0), &SAMPLES, |p| p, &config);
+        // Worst half is {1, 2}, mean 1.5
+        assert!((cost.tail_loss - 1.0).abs() < 1e-12);
+
+        let empty = fragility_cost(&Power(1.0), &[], |p| p, &config);
+        assert!(empty.expected.abs() < f64::EPSILON);
+    }
+}
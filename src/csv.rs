@@ -0,0 +1,59 @@
+//! Minimal CSV writing for the `to_csv` methods of sweep and profile results
+//!
+//! Fields are quoted only when they contain a comma, quote, or line break,
+//! following RFC 4180. Lines end in `\n`.
+
+use core::fmt::Display;
+
+/// A CSV document built one row at a time
+pub(crate) struct CsvWriter {
+    out: String,
+}
+
+impl CsvWriter {
+    /// Start a document with a header row
+    pub(crate) fn new(header: &[&str]) -> Self {
+        let mut writer = Self { out: String::new() };
+        writer.row(header);
+        writer
+    }
+
+    /// Append a row
+    pub(crate) fn row<D: Display>(&mut self, fields: &[D]) {
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                self.out.push(',');
+            }
+            let field = field.to_string();
+            if field.contains([',', '"', '\n', '\r']) {
+                self.out.push('"');
+                self.out.push_str(&field.replace('"', "\"\""));
+                self.out.push('"');
+            } else {
+                self.out.push_str(&field);
+            }
+        }
+        self.out.push('\n');
+    }
+
+    /// The finished document
+    pub(crate) fn finish(self) -> String {
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_are_quoted_only_when_needed() {
+        let mut csv = CsvWriter::new(&["label", "value"]);
+        csv.row(&["plain", "1.5"]);
+        csv.row(&["a, b", "say \"hi\""]);
+        assert_eq!(
+            csv.finish(),
+            "label,value\nplain,1.5\n\"a, b\",\"say \"\"hi\"\"\"\n"
+        );
+    }
+}
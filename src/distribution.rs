@@ -0,0 +1,253 @@
+//! # Stressor distributions
+//!
+//! Distributional analyses need to know what stressors a system will face.
+//! [`StressorDistribution`](crate::distribution::StressorDistribution) is
+//! the common interface: draw a sample from a deterministic
+//! [`Noise`](crate::learning::Noise) stream, and report the mean and
+//! variance the draws should have. Any type implementing it can be handed to
+//! [`monte_carlo_distribution`](crate::monte_carlo::monte_carlo_distribution).
+//!
+//! [`Empirical`](crate::distribution::Empirical) resamples observed
+//! stressors and needs no dependencies. With the `rand` feature,
+//! [`Noise`](crate::learning::Noise) implements `rand::RngCore`,
+//! `rand_distr::Normal` is a stressor distribution as it is, and
+//! [`Sampled`](crate::distribution::Sampled) adapts any other `rand`
+//! distribution whose moments you know.
+//!
+//! ```rust
+//! use antifragile::distribution::{Empirical, StressorDistribution};
+//! use antifragile::learning::Noise;
+//!
+//! // Daily request peaks observed last week
+//! let peaks = Empirical::new(vec![120.0, 95.0, 130.0, 410.0, 110.0, 100.0, 125.0]);
+//! assert!((peaks.mean() - 155.714).abs() < 1e-3);
+//!
+//! let mut noise = Noise::new(7);
+//! let draw = peaks.sample(&mut noise);
+//! assert!([120.0, 95.0, 130.0, 410.0, 110.0, 100.0, 125.0].contains(&draw));
+//! ```
+
+pub use crate::learning::Noise;
+
+/// A distribution of scalar stressors
+pub trait StressorDistribution {
+    /// Draw one stressor from `noise`
+    fn sample(&self, noise: &mut Noise) -> f64;
+
+    /// Expected stressor
+    fn mean(&self) -> f64;
+
+    /// Variance of the stressor
+    fn variance(&self) -> f64;
+
+    /// Standard deviation of the stressor
+    fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+}
+
+impl<D: StressorDistribution + ?Sized> StressorDistribution for &D {
+    fn sample(&self, noise: &mut Noise) -> f64 {
+        (**self).sample(noise)
+    }
+
+    fn mean(&self) -> f64 {
+        (**self).mean()
+    }
+
+    fn variance(&self) -> f64 {
+        (**self).variance()
+    }
+}
+
+/// The empirical distribution of observed stressors
+///
+/// Each draw picks one observation uniformly at random (the bootstrap), so
+/// the mean and variance are those of the observations themselves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Empirical {
+    samples: Vec<f64>,
+}
+
+impl Empirical {
+    /// Distribution over `samples`
+    ///
+    /// With no samples every draw and moment is NaN.
+    #[must_use]
+    pub const fn new(samples: Vec<f64>) -> Self {
+        Self { samples }
+    }
+
+    /// The observations, in the order given
+    #[must_use]
+    pub fn samples(&self) -> &[f64] {
+        &self.samples
+    }
+}
+
+impl StressorDistribution for Empirical {
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn sample(&self, noise: &mut Noise) -> f64 {
+        let index = (noise.uniform() * self.samples.len() as f64) as usize;
+        self.samples.get(index).copied().unwrap_or(f64::NAN)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn mean(&self) -> f64 {
+        if self.samples.is_empty() {
+            return f64::NAN;
+        }
+        self.samples.iter().sum::<f64>() / self.samples.len() as f64
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn variance(&self) -> f64 {
+        let mean = self.mean();
+        self.samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / self.samples.len() as f64
+    }
+}
+
+#[cfg(feature = "rand")]
+impl StressorDistribution for rand_distr::Normal<f64> {
+    fn sample(&self, noise: &mut Noise) -> f64 {
+        rand_distr::Distribution::sample(self, noise)
+    }
+
+    fn mean(&self) -> f64 {
+        rand_distr::Normal::mean(self)
+    }
+
+    fn variance(&self) -> f64 {
+        self.std_dev().powi(2)
+    }
+}
+
+/// Any `rand` distribution paired with its known mean and variance
+///
+/// Most `rand_distr` types do not expose their moments, so they are given
+/// here once. The constructors for common families compute them for you.
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sampled<D> {
+    distribution: D,
+    mean: f64,
+    variance: f64,
+}
+
+#[cfg(feature = "rand")]
+impl<D: rand_distr::Distribution<f64>> Sampled<D> {
+    /// Wrap `distribution`, declaring its mean and variance
+    ///
+    /// The moments are taken on trust; analyses that compare draws against
+    /// them will be misled if they are wrong.
+    #[must_use]
+    pub const fn new(distribution: D, mean: f64, variance: f64) -> Self {
+        Self {
+            distribution,
+            mean,
+            variance,
+        }
+    }
+
+    /// The wrapped distribution
+    #[must_use]
+    pub const fn distribution(&self) -> &D {
+        &self.distribution
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Sampled<rand_distr::LogNormal<f64>> {
+    /// `exp(N(mu, sigma²))`, or `None` if `sigma` is negative or not finite
+    #[must_use]
+    pub fn log_normal(mu: f64, sigma: f64) -> Option<Self> {
+        let distribution = rand_distr::LogNormal::new(mu, sigma).ok()?;
+        let s2 = sigma * sigma;
+        Some(Self::new(
+            distribution,
+            (mu + 0.5 * s2).exp(),
+            s2.exp_m1() * (2.0 * mu + s2).exp(),
+        ))
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Sampled<rand_distr::Uniform<f64>> {
+    /// Uniform on `[low, high)`, or `None` unless `low < high` and both are finite
+    #[must_use]
+    pub fn uniform(low: f64, high: f64) -> Option<Self> {
+        let distribution = rand_distr::Uniform::new(low, high).ok()?;
+        Some(Self::new(
+            distribution,
+            0.5 * (low + high),
+            (high - low).powi(2) / 12.0,
+        ))
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<D: rand_distr::Distribution<f64>> StressorDistribution for Sampled<D> {
+    fn sample(&self, noise: &mut Noise) -> f64 {
+        self.distribution.sample(noise)
+    }
+
+    fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    fn variance(&self) -> f64 {
+        self.variance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_moments<D: StressorDistribution>(distribution: &D) -> (f64, f64) {
+        let mut noise = Noise::new(11);
+        let n = 100_000_u32;
+        let draws: Vec<f64> = (0..n).map(|_| distribution.sample(&mut noise)).collect();
+        let mean = draws.iter().sum::<f64>() / f64::from(n);
+        let variance = draws.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / f64::from(n);
+        (mean, variance)
+    }
+
+    #[test]
+    fn test_empirical_moments() {
+        let distribution = Empirical::new(vec![1.0, 2.0, 3.0, 6.0]);
+        assert!((distribution.mean() - 3.0).abs() < f64::EPSILON);
+        assert!((distribution.variance() - 3.5).abs() < f64::EPSILON);
+
+        let (mean, variance) = sample_moments(&distribution);
+        assert!((mean - 3.0).abs() < 0.05);
+        assert!((variance - 3.5).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_empty_empirical_is_nan() {
+        let distribution = Empirical::new(Vec::new());
+        assert!(distribution.mean().is_nan());
+        assert!(distribution.sample(&mut Noise::new(0)).is_nan());
+    }
+
+    #[cfg(feature = "rand")]
+    fn assert_declared_moments<D: StressorDistribution>(distribution: Option<D>) {
+        let Some(distribution) = distribution else {
+            panic!("valid parameters were rejected");
+        };
+        let (mean, variance) = sample_moments(&distribution);
+        assert!((mean - distribution.mean()).abs() < 0.05 * distribution.std_dev());
+        assert!((variance / distribution.variance() - 1.0).abs() < 0.05);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_rand_distributions_match_declared_moments() {
+        assert_declared_moments(rand_distr::Normal::new(2.0, 3.0).ok());
+        assert_declared_moments(Sampled::log_normal(0.0, 0.5));
+        assert_declared_moments(Sampled::uniform(-1.0, 3.0));
+        assert!(Sampled::uniform(1.0, 1.0).is_none());
+    }
+}
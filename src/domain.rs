@@ -0,0 +1,291 @@
+//! # Domain-bounded stressors
+//!
+//! Many payoffs are only defined on part of the stressor axis: claim rates
+//! and volatilities are non-negative, utilisation lives in `[0, 1]`. The
+//! convexity test probes `at - Δ` and `at + Δ` without knowing that, so near
+//! a boundary it can evaluate the payoff where it is meaningless.
+//!
+//! [`BoundedDomain`](crate::domain::BoundedDomain) attaches a valid range to
+//! a system and offers both behaviours:
+//!
+//! - **Error**: [`classify_in_domain`](crate::domain::BoundedDomain::classify_in_domain)
+//!   refuses to classify when any probe falls outside the domain, and says
+//!   which one.
+//! - **Clamp**: the wrapper itself implements [`Antifragile`](crate::Antifragile)
+//!   with the stressor clamped into the domain, so
+//!   [`classify`](crate::TriadAnalysis::classify) and every analysis module
+//!   never evaluate the inner payoff outside it.
+//!
+//! Clamping makes the payoff flat beyond the boundary, which adds a kink of
+//! its own; a classification computed across the boundary describes the
+//! clamped system, not the original one.
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad, TriadAnalysis};
+//! use antifragile::domain::{BoundedDomain, DomainError};
+//! use antifragile::Probe;
+//!
+//! struct ClaimCost;
+//!
+//! impl Antifragile for ClaimCost {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, claim_rate: f64) -> f64 {
+//!         -claim_rate.sqrt() // NaN for negative rates
+//!     }
+//! }
+//!
+//! let bounded = BoundedDomain::new(ClaimCost).with_min(0.0);
+//! assert_eq!(bounded.classify_in_domain(4.0, 1.0), Ok(Triad::Antifragile));
+//! assert_eq!(
+//!     bounded.classify_in_domain(0.5, 1.0),
+//!     Err(DomainError::BelowMinimum { probe: Probe::Below })
+//! );
+//!
+//! // Clamped, the payoff is never evaluated below zero
+//! assert!(bounded.payoff(-1.0).is_finite());
+//! ```
+
+use core::fmt::Display;
+use core::ops::Sub;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::{Antifragile, Probe, Triad, TriadAnalysis};
+
+/// Error returned by [`BoundedDomain::classify_in_domain`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DomainError {
+    /// A probe fell below the domain's minimum
+    BelowMinimum {
+        /// The evaluation that left the domain
+        probe: Probe,
+    },
+    /// A probe fell above the domain's maximum
+    AboveMaximum {
+        /// The evaluation that left the domain
+        probe: Probe,
+    },
+}
+
+impl Display for DomainError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::BelowMinimum { probe } => {
+                write!(f, "stressor for {probe} is below the domain minimum")
+            }
+            Self::AboveMaximum { probe } => {
+                write!(f, "stressor for {probe} is above the domain maximum")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DomainError {}
+
+/// A system whose stressor is only valid within `[min, max]`
+///
+/// Either bound may be left open. As an [`Antifragile`] system the wrapper
+/// clamps the stressor into the domain before evaluating the inner payoff.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundedDomain<T: Antifragile> {
+    inner: T,
+    min: Option<T::Stressor>,
+    max: Option<T::Stressor>,
+}
+
+impl<T: Antifragile> BoundedDomain<T>
+where
+    T::Stressor: PartialOrd,
+{
+    /// Wrap a system with an unbounded domain
+    pub const fn new(inner: T) -> Self {
+        Self {
+            inner,
+            min: None,
+            max: None,
+        }
+    }
+
+    /// Set the smallest valid stressor
+    #[must_use]
+    pub fn with_min(mut self, min: T::Stressor) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Set the largest valid stressor
+    #[must_use]
+    pub fn with_max(mut self, max: T::Stressor) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// The smallest valid stressor, if bounded below
+    pub const fn min(&self) -> Option<T::Stressor> {
+        self.min
+    }
+
+    /// The largest valid stressor, if bounded above
+    pub const fn max(&self) -> Option<T::Stressor> {
+        self.max
+    }
+
+    /// Get a reference to the wrapped system
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Unwrap the bounded system
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns true if `stressor` lies within the domain
+    pub fn contains(&self, stressor: T::Stressor) -> bool {
+        self.min.is_none_or(|min| stressor >= min) && self.max.is_none_or(|max| stressor <= max)
+    }
+
+    /// The nearest stressor within the domain
+    ///
+    /// If `min > max` the minimum wins.
+    pub fn clamp(&self, stressor: T::Stressor) -> T::Stressor {
+        let stressor = match self.max {
+            Some(max) if stressor > max => max,
+            _ => stressor,
+        };
+        match self.min {
+            Some(min) if stressor < min => min,
+            _ => stressor,
+        }
+    }
+
+    /// Check that all three probes of the convexity test lie in the domain
+    ///
+    /// # Errors
+    ///
+    /// Returns the first probe, from below to above, that leaves the domain.
+    pub fn check(&self, at: T::Stressor, delta: T::Stressor) -> Result<(), DomainError> {
+        for (probe, stressor) in [
+            (Probe::Below, at - delta),
+            (Probe::At, at),
+            (Probe::Above, at + delta),
+        ] {
+            if self.min.is_some_and(|min| stressor < min) {
+                return Err(DomainError::BelowMinimum { probe });
+            }
+            if self.max.is_some_and(|max| stressor > max) {
+                return Err(DomainError::AboveMaximum { probe });
+            }
+        }
+        Ok(())
+    }
+
+    /// Classify the inner system, refusing to probe outside the domain
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DomainError`] naming the probe that left the domain.
+    pub fn classify_in_domain(
+        &self,
+        at: T::Stressor,
+        delta: T::Stressor,
+    ) -> Result<Triad, DomainError>
+    where
+        T::Payoff: Sub<Output = T::Payoff> + Default + PartialOrd,
+    {
+        self.check(at, delta)?;
+        Ok(self.inner.classify(at, delta))
+    }
+}
+
+impl<T: Antifragile> Antifragile for BoundedDomain<T>
+where
+    T::Stressor: PartialOrd,
+{
+    type Stressor = T::Stressor;
+    type Payoff = T::Payoff;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        self.inner.payoff(self.clamp(stressor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sqrt;
+
+    impl Antifragile for Sqrt {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&self, x: f64) -> f64 {
+            x.sqrt()
+        }
+    }
+
+    struct Counter;
+
+    impl Antifragile for Counter {
+        type Stressor = i32;
+        type Payoff = i32;
+
+        fn payoff(&self, x: i32) -> i32 {
+            x * x
+        }
+    }
+
+    #[test]
+    fn test_probes_outside_domain_are_rejected() {
+        let bounded = BoundedDomain::new(Sqrt).with_min(0.0).with_max(10.0);
+        assert_eq!(bounded.classify_in_domain(5.0, 1.0), Ok(Triad::Fragile));
+        assert_eq!(
+            bounded.classify_in_domain(0.5, 1.0),
+            Err(DomainError::BelowMinimum {
+                probe: Probe::Below
+            })
+        );
+        assert_eq!(
+            bounded.classify_in_domain(9.5, 1.0),
+            Err(DomainError::AboveMaximum {
+                probe: Probe::Above
+            })
+        );
+        // The boundary itself is valid
+        assert_eq!(bounded.classify_in_domain(1.0, 1.0), Ok(Triad::Fragile));
+    }
+
+    #[test]
+    fn test_clamped_payoff_stays_in_domain() {
+        let bounded = BoundedDomain::new(Sqrt).with_min(0.0);
+        assert!(bounded.payoff(-4.0).abs() < f64::EPSILON);
+        assert!((bounded.payoff(4.0) - 2.0).abs() < f64::EPSILON);
+        // Every probe is now finite, so try_classify succeeds
+        assert!(bounded.try_classify(0.5, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_contains_and_clamp_with_integers() {
+        let bounded = BoundedDomain::new(Counter).with_min(-2).with_max(3);
+        assert!(bounded.contains(-2) && bounded.contains(3));
+        assert!(!bounded.contains(4));
+        assert_eq!(bounded.clamp(-7), -2);
+        assert_eq!(bounded.clamp(7), 3);
+        // Flat beyond the maximum: the clamp adds concavity at the boundary
+        assert_eq!(bounded.classify(3, 1), Triad::Fragile);
+        assert_eq!(bounded.inner().classify(3, 1), Triad::Antifragile);
+    }
+
+    #[test]
+    fn test_unbounded_domain_is_transparent() {
+        let unbounded = BoundedDomain::new(Counter);
+        assert_eq!(unbounded.min(), None);
+        assert_eq!(unbounded.check(-100, 50), Ok(()));
+        assert_eq!(unbounded.payoff(-5), Counter.payoff(-5));
+    }
+}
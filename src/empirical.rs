@@ -0,0 +1,812 @@
+//! # Empirical systems from observed data
+//!
+//! Production systems rarely come with a closed-form payoff. What you usually
+//! have is telemetry: pairs of (load, latency), (volatility, `PnL`),
+//! (error rate, conversion). [`EmpiricalSystem`](crate::empirical::EmpiricalSystem)
+//! turns such observations into an [`Antifragile`](crate::Antifragile)
+//! implementation so they can be fed straight into
+//! [`classify`](crate::TriadAnalysis::classify) and every other analysis.
+//!
+//! Between observations the payoff is interpolated with a configurable
+//! [`Interpolation`](crate::empirical::Interpolation). Outside the observed
+//! range it is extended linearly along the slope at the nearest edge
+//! ([`Interpolation::Nearest`](crate::empirical::Interpolation::Nearest)
+//! holds the edge value instead), so extrapolation never invents curvature.
+//! Repeated observations at the same stressor are averaged.
+//!
+//! ```rust
+//! use antifragile::{Triad, TriadAnalysis};
+//! use antifragile::empirical::{EmpiricalSystem, Interpolation};
+//!
+//! // Observed p99 latency (ms) against requests per second
+//! let observations = [
+//!     (100.0, 20.0),
+//!     (200.0, 22.0),
+//!     (300.0, 27.0),
+//!     (400.0, 40.0),
+//!     (500.0, 70.0),
+//! ];
+//!
+//! let system = EmpiricalSystem::new(observations)?
+//!     .with_interpolation(Interpolation::CubicSpline);
+//!
+//! // Latency accelerates with load: more load hurts ever more
+//! assert_eq!(system.classify(300.0, 100.0), Triad::Antifragile);
+//! # Ok::<(), antifragile::empirical::EmpiricalError>(())
+//! ```
+//!
+//! Note that the example's payoff is a *cost*: convex latency means the
+//! service is fragile to load. Negate costs before classifying if you want
+//! the Triad to read from the system's point of view.
+
+use core::fmt::Display;
+use std::error::Error;
+use std::io::Read;
+
+use crate::Antifragile;
+
+/// How payoffs are estimated between observations
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Interpolation {
+    /// Payoff of the nearest observation (ties go to the lower stressor)
+    Nearest,
+    /// Straight lines between neighbouring observations
+    ///
+    /// Convexity appears only at the observations themselves, where the
+    /// slope changes.
+    #[default]
+    Linear,
+    /// Natural cubic spline through every observation
+    ///
+    /// Smooth everywhere, so the convexity test sees curvature between
+    /// observations too. Can overshoot on noisy data.
+    CubicSpline,
+}
+
+/// Error returned when building an [`EmpiricalSystem`] fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmpiricalError {
+    /// Fewer than two distinct stressor values were observed
+    TooFewSamples {
+        /// Number of distinct stressor values found
+        found: usize,
+    },
+    /// An observation contained a NaN or infinite value
+    NonFinite {
+        /// Position of the offending observation in the input
+        index: usize,
+    },
+}
+
+impl Display for EmpiricalError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooFewSamples { found } => write!(
+                f,
+                "too few samples: found {found} distinct stressor values, need at least 2"
+            ),
+            Self::NonFinite { index } => {
+                write!(f, "non-finite observation at index {index}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for EmpiricalError {}
+
+/// A system defined by observed `(stressor, payoff)` samples
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmpiricalSystem {
+    stressors: Vec<f64>,
+    payoffs: Vec<f64>,
+    interpolation: Interpolation,
+    /// Spline second derivatives at each observation (cubic spline only)
+    curvatures: Vec<f64>,
+}
+
+impl EmpiricalSystem {
+    /// Build a system from observations using linear interpolation
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EmpiricalError::NonFinite`] if any value is NaN or infinite,
+    /// and [`EmpiricalError::TooFewSamples`] if fewer than two distinct
+    /// stressor values were observed.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(samples: impl IntoIterator<Item = (f64, f64)>) -> Result<Self, EmpiricalError> {
+        let mut observed = Vec::new();
+        for (index, (stressor, payoff)) in samples.into_iter().enumerate() {
+            if !stressor.is_finite() || !payoff.is_finite() {
+                return Err(EmpiricalError::NonFinite { index });
+            }
+            observed.push((stressor, payoff));
+        }
+        observed.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut stressors: Vec<f64> = Vec::with_capacity(observed.len());
+        let mut payoffs: Vec<f64> = Vec::with_capacity(observed.len());
+        let mut run = 0_usize;
+        for (stressor, payoff) in observed {
+            match (stressors.last(), payoffs.last_mut()) {
+                (Some(last), Some(mean)) if last.total_cmp(&stressor).is_eq() => {
+                    // Running mean over repeated observations
+                    run += 1;
+                    *mean += (payoff - *mean) / run as f64;
+                }
+                _ => {
+                    stressors.push(stressor);
+                    payoffs.push(payoff);
+                    run = 1;
+                }
+            }
+        }
+
+        if stressors.len() < 2 {
+            return Err(EmpiricalError::TooFewSamples {
+                found: stressors.len(),
+            });
+        }
+
+        Ok(Self {
+            stressors,
+            payoffs,
+            interpolation: Interpolation::Linear,
+            curvatures: Vec::new(),
+        })
+    }
+
+    /// Change the interpolation method
+    #[must_use]
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self.curvatures = if interpolation == Interpolation::CubicSpline {
+            natural_spline(&self.stressors, &self.payoffs)
+        } else {
+            Vec::new()
+        };
+        self
+    }
+
+    /// The interpolation method in use
+    #[must_use]
+    pub const fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// The distinct observations in ascending stressor order, with repeated
+    /// stressors averaged
+    pub fn observations(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.stressors
+            .iter()
+            .copied()
+            .zip(self.payoffs.iter().copied())
+    }
+
+    /// Number of distinct observed stressor values
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.stressors.len()
+    }
+
+    /// Always false: an empirical system holds at least two observations
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.stressors.is_empty()
+    }
+
+    /// Observed stressor range
+    #[must_use]
+    pub fn range(&self) -> (f64, f64) {
+        (self.stressors[0], self.stressors[self.stressors.len() - 1])
+    }
+
+    /// Slope of the interpolant at the left (`at_end = false`) or right edge
+    fn edge_slope(&self, at_end: bool) -> f64 {
+        let n = self.stressors.len();
+        let (i, j) = if at_end { (n - 2, n - 1) } else { (0, 1) };
+        let h = self.stressors[j] - self.stressors[i];
+        let secant = (self.payoffs[j] - self.payoffs[i]) / h;
+        if self.curvatures.is_empty() {
+            secant
+        } else if at_end {
+            secant + h * (self.curvatures[i] + 2.0 * self.curvatures[j]) / 6.0
+        } else {
+            secant - h * (2.0 * self.curvatures[i] + self.curvatures[j]) / 6.0
+        }
+    }
+
+    fn interpolate(&self, at: f64) -> f64 {
+        // NaN fails both edge checks below and has no segment
+        if at.is_nan() {
+            return f64::NAN;
+        }
+        let last = self.stressors.len() - 1;
+        let (lo, hi) = self.range();
+
+        if at <= lo || at >= hi {
+            let (edge, value, at_end) = if at <= lo {
+                (lo, self.payoffs[0], false)
+            } else {
+                (hi, self.payoffs[last], true)
+            };
+            return match self.interpolation {
+                Interpolation::Nearest => value,
+                Interpolation::Linear | Interpolation::CubicSpline => {
+                    value + self.edge_slope(at_end) * (at - edge)
+                }
+            };
+        }
+
+        // Index of the segment [x_i, x_{i+1}] containing the stressor
+        let i = self.stressors.partition_point(|&s| s <= at) - 1;
+        let (x0, x1) = (self.stressors[i], self.stressors[i + 1]);
+        let (y0, y1) = (self.payoffs[i], self.payoffs[i + 1]);
+        let width = x1 - x0;
+
+        match self.interpolation {
+            Interpolation::Nearest => {
+                if at - x0 <= x1 - at {
+                    y0
+                } else {
+                    y1
+                }
+            }
+            Interpolation::Linear => y0 + (y1 - y0) * (at - x0) / width,
+            Interpolation::CubicSpline => {
+                let (m0, m1) = (self.curvatures[i], self.curvatures[i + 1]);
+                let (left, right) = (x1 - at, at - x0);
+                m0 * left.powi(3) / (6.0 * width)
+                    + m1 * right.powi(3) / (6.0 * width)
+                    + (y0 / width - m0 * width / 6.0) * left
+                    + (y1 / width - m1 * width / 6.0) * right
+            }
+        }
+    }
+}
+
+impl Antifragile for EmpiricalSystem {
+    type Stressor = f64;
+    type Payoff = f64;
+
+    fn payoff(&self, stressor: f64) -> f64 {
+        self.interpolate(stressor)
+    }
+}
+
+/// A field of an observation file
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Column {
+    /// The field with this header (CSV) or key (JSON objects)
+    Named(String),
+    /// The field at this zero-based position (CSV or JSON arrays)
+    Index(usize),
+}
+
+impl From<&str> for Column {
+    fn from(name: &str) -> Self {
+        Self::Named(name.into())
+    }
+}
+
+impl From<String> for Column {
+    fn from(name: String) -> Self {
+        Self::Named(name)
+    }
+}
+
+impl From<usize> for Column {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+
+impl Display for Column {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Named(name) => write!(f, "\"{name}\""),
+            Self::Index(index) => write!(f, "#{index}"),
+        }
+    }
+}
+
+/// Where [`EmpiricalSystem::from_csv`] and `from_json` find the stressor
+/// and payoff of each observation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnMapping {
+    /// Field holding the stressor
+    pub stressor: Column,
+    /// Field holding the payoff
+    pub payoff: Column,
+    /// CSV field separator
+    pub delimiter: char,
+    /// Whether the first CSV line names the columns
+    pub header: bool,
+    /// Whether to negate payoffs, turning a cost such as latency into a
+    /// payoff read from the system's point of view
+    pub negate: bool,
+}
+
+impl Default for ColumnMapping {
+    /// Columns named `stressor` and `payoff`, comma-separated, with a header
+    fn default() -> Self {
+        Self::new("stressor", "payoff")
+    }
+}
+
+impl ColumnMapping {
+    /// Read the stressor and payoff from the given fields
+    #[must_use]
+    pub fn new(stressor: impl Into<Column>, payoff: impl Into<Column>) -> Self {
+        Self {
+            stressor: stressor.into(),
+            payoff: payoff.into(),
+            delimiter: ',',
+            header: true,
+            negate: false,
+        }
+    }
+
+    /// Separate CSV fields with `delimiter` instead of a comma
+    #[must_use]
+    pub const fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Treat the first CSV line as data; columns must then be [`Column::Index`]
+    #[must_use]
+    pub const fn without_header(mut self) -> Self {
+        self.header = false;
+        self
+    }
+
+    /// Negate every payoff as it is read
+    #[must_use]
+    pub const fn negated(mut self) -> Self {
+        self.negate = true;
+        self
+    }
+}
+
+/// Error returned when loading an [`EmpiricalSystem`] from a file fails
+#[derive(Debug)]
+pub enum LoadError {
+    /// The input could not be read
+    Io(std::io::Error),
+    /// The input is not valid JSON, or not an array of observations
+    Json(String),
+    /// A mapped column is not in the header, or is named but there is no header
+    MissingColumn(Column),
+    /// A record has no value for a mapped column
+    MissingField {
+        /// One-based line (CSV) or record (JSON) number
+        record: usize,
+        /// The column that was missing
+        column: Column,
+    },
+    /// A value could not be read as a number
+    InvalidNumber {
+        /// One-based line (CSV) or record (JSON) number
+        record: usize,
+        /// The column holding the value
+        column: Column,
+        /// The offending value
+        value: String,
+    },
+    /// The observations do not form a valid system
+    Empirical(EmpiricalError),
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read observations: {error}"),
+            Self::Json(message) => write!(f, "invalid JSON observations: {message}"),
+            Self::MissingColumn(column) => write!(f, "column {column} not found"),
+            Self::MissingField { record, column } => {
+                write!(f, "record {record} has no value for column {column}")
+            }
+            Self::InvalidNumber {
+                record,
+                column,
+                value,
+            } => write!(
+                f,
+                "record {record} column {column}: {value:?} is not a number"
+            ),
+            Self::Empirical(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Empirical(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for LoadError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<EmpiricalError> for LoadError {
+    fn from(error: EmpiricalError) -> Self {
+        Self::Empirical(error)
+    }
+}
+
+impl EmpiricalSystem {
+    /// Build a system from CSV observations using linear interpolation
+    ///
+    /// Blank lines are skipped. Fields may be quoted with `"`, doubling any
+    /// quote inside; quoted fields cannot span lines. Values are trimmed
+    /// before being parsed as numbers.
+    ///
+    /// ```rust
+    /// use antifragile::{Triad, TriadAnalysis};
+    /// use antifragile::empirical::{ColumnMapping, EmpiricalSystem};
+    ///
+    /// let csv = "timestamp,rps,p99_ms\n\
+    ///            10:00,100,20\n\
+    ///            10:05,200,22\n\
+    ///            10:10,300,27\n\
+    ///            10:15,400,40\n";
+    ///
+    /// let mapping = ColumnMapping::new("rps", "p99_ms").negated();
+    /// let system = EmpiricalSystem::from_csv(csv.as_bytes(), &mapping)?;
+    ///
+    /// // Latency accelerates with load, so the service is fragile to it
+    /// assert_eq!(system.classify(300.0, 100.0), Triad::Fragile);
+    /// # Ok::<(), antifragile::empirical::LoadError>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LoadError`] if the input cannot be read, a mapped column
+    /// or value is missing or not a number, or the observations are rejected
+    /// by [`new`](Self::new).
+    pub fn from_csv(reader: impl Read, mapping: &ColumnMapping) -> Result<Self, LoadError> {
+        let mut text = String::new();
+        std::io::BufReader::new(reader).read_to_string(&mut text)?;
+
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let (stressor, payoff) = if mapping.header {
+            let header = lines
+                .next()
+                .map(|(_, line)| split_csv_line(line, mapping.delimiter))
+                .unwrap_or_default();
+            (
+                column_position(&mapping.stressor, &header)?,
+                column_position(&mapping.payoff, &header)?,
+            )
+        } else {
+            (
+                column_position(&mapping.stressor, &[])?,
+                column_position(&mapping.payoff, &[])?,
+            )
+        };
+
+        let mut observations = Vec::new();
+        for (index, line) in lines {
+            let fields = split_csv_line(line, mapping.delimiter);
+            let field = |position: usize, column: &Column| {
+                let value = fields
+                    .get(position)
+                    .ok_or_else(|| LoadError::MissingField {
+                        record: index + 1,
+                        column: column.clone(),
+                    })?;
+                value
+                    .trim()
+                    .parse::<f64>()
+                    .map_err(|_| LoadError::InvalidNumber {
+                        record: index + 1,
+                        column: column.clone(),
+                        value: value.clone(),
+                    })
+            };
+            let x = field(stressor, &mapping.stressor)?;
+            let y = field(payoff, &mapping.payoff)?;
+            observations.push((x, if mapping.negate { -y } else { y }));
+        }
+        Ok(Self::new(observations)?)
+    }
+
+    /// Build a system from a JSON array of observations using linear
+    /// interpolation
+    ///
+    /// Each element is either an object, read with [`Column::Named`] keys,
+    /// or an array, read with [`Column::Index`] positions. Values must be
+    /// JSON numbers. The CSV-only settings of `mapping` are ignored.
+    ///
+    /// ```rust
+    /// use antifragile::{Triad, TriadAnalysis};
+    /// use antifragile::empirical::{ColumnMapping, EmpiricalSystem};
+    ///
+    /// let json = r#"[
+    ///     {"volatility": 0.1, "pnl": 1.0},
+    ///     {"volatility": 0.2, "pnl": 4.0},
+    ///     {"volatility": 0.3, "pnl": 9.0}
+    /// ]"#;
+    ///
+    /// let mapping = ColumnMapping::new("volatility", "pnl");
+    /// let system = EmpiricalSystem::from_json(json.as_bytes(), &mapping)?;
+    /// assert_eq!(system.classify(0.2, 0.1), Triad::Antifragile);
+    /// # Ok::<(), antifragile::empirical::LoadError>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`LoadError`] if the input cannot be read or parsed, a
+    /// mapped value is missing or not a number, or the observations are
+    /// rejected by [`new`](Self::new).
+    #[cfg(feature = "json")]
+    pub fn from_json(reader: impl Read, mapping: &ColumnMapping) -> Result<Self, LoadError> {
+        use serde_json::Value;
+
+        let value: Value = serde_json::from_reader(std::io::BufReader::new(reader))
+            .map_err(|error| LoadError::Json(error.to_string()))?;
+        let Value::Array(records) = value else {
+            return Err(LoadError::Json("expected an array of observations".into()));
+        };
+
+        let field = |record: &Value, index: usize, column: &Column| {
+            let value = match (record, column) {
+                (Value::Object(fields), Column::Named(name)) => fields.get(name),
+                (Value::Array(fields), Column::Index(position)) => fields.get(*position),
+                _ => None,
+            };
+            let value = value.ok_or_else(|| LoadError::MissingField {
+                record: index + 1,
+                column: column.clone(),
+            })?;
+            value.as_f64().ok_or_else(|| LoadError::InvalidNumber {
+                record: index + 1,
+                column: column.clone(),
+                value: value.to_string(),
+            })
+        };
+
+        let mut observations = Vec::with_capacity(records.len());
+        for (index, record) in records.iter().enumerate() {
+            let x = field(record, index, &mapping.stressor)?;
+            let y = field(record, index, &mapping.payoff)?;
+            observations.push((x, if mapping.negate { -y } else { y }));
+        }
+        Ok(Self::new(observations)?)
+    }
+}
+
+/// Position of `column` in a CSV header (empty when there is none)
+fn column_position(column: &Column, header: &[String]) -> Result<usize, LoadError> {
+    match column {
+        Column::Index(index) => Ok(*index),
+        Column::Named(name) => header
+            .iter()
+            .position(|field| field.trim() == name)
+            .ok_or_else(|| LoadError::MissingColumn(column.clone())),
+    }
+}
+
+/// Split one CSV line into fields, unquoting quoted fields
+fn split_csv_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(core::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Second derivatives of the natural cubic spline through `(xs, ys)`
+fn natural_spline(xs: &[f64], ys: &[f64]) -> Vec<f64> {
+    let n = xs.len();
+    let mut curvatures = vec![0.0; n];
+    if n < 3 {
+        return curvatures;
+    }
+
+    // Tridiagonal system for the interior second derivatives (Thomas algorithm)
+    let mut diag = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+    for i in 1..n - 1 {
+        let (h0, h1) = (xs[i] - xs[i - 1], xs[i + 1] - xs[i]);
+        diag[i] = 2.0 * (h0 + h1);
+        rhs[i] = 6.0 * ((ys[i + 1] - ys[i]) / h1 - (ys[i] - ys[i - 1]) / h0);
+    }
+    for i in 2..n - 1 {
+        let lower = xs[i] - xs[i - 1];
+        let factor = lower / diag[i - 1];
+        diag[i] -= factor * lower;
+        rhs[i] -= factor * rhs[i - 1];
+    }
+    for i in (1..n - 1).rev() {
+        let upper = xs[i + 1] - xs[i];
+        curvatures[i] = (rhs[i] - upper * curvatures[i + 1]) / diag[i];
+    }
+    curvatures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Triad, TriadAnalysis};
+
+    fn squares() -> EmpiricalSystem {
+        EmpiricalSystem::new((-5..=5).map(|i| {
+            let x = f64::from(i);
+            (x, x * x)
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_linear_interpolation_matches_observations() {
+        let system = squares();
+        assert!((system.payoff(2.0) - 4.0).abs() < f64::EPSILON);
+        assert!((system.payoff(2.5) - 6.5).abs() < 1e-12);
+        assert_eq!(system.classify(0.0, 1.0), Triad::Antifragile);
+        // Within a single segment a linear interpolant has no curvature
+        assert_eq!(system.classify(2.5, 0.25), Triad::Robust);
+    }
+
+    #[test]
+    fn test_cubic_spline_sees_curvature_between_observations() {
+        let system = squares().with_interpolation(Interpolation::CubicSpline);
+        assert_eq!(system.interpolation(), Interpolation::CubicSpline);
+        assert!((system.payoff(3.0) - 9.0).abs() < 1e-12);
+        assert_eq!(system.classify(2.5, 0.25), Triad::Antifragile);
+    }
+
+    #[test]
+    fn test_nearest_and_extrapolation() {
+        let nearest = squares().with_interpolation(Interpolation::Nearest);
+        assert!((nearest.payoff(2.4) - 4.0).abs() < f64::EPSILON);
+        assert!((nearest.payoff(2.6) - 9.0).abs() < f64::EPSILON);
+        assert!((nearest.payoff(100.0) - 25.0).abs() < f64::EPSILON);
+
+        // Linear extrapolation continues the last segment's slope of 9
+        let linear = squares();
+        assert!((linear.payoff(6.0) - 34.0).abs() < 1e-12);
+        assert_eq!(linear.classify(10.0, 1.0), Triad::Robust);
+    }
+
+    #[test]
+    fn test_repeated_stressors_are_averaged() {
+        let system =
+            EmpiricalSystem::new([(1.0, 10.0), (0.0, 0.0), (1.0, 20.0), (1.0, 30.0)]).unwrap();
+        assert_eq!(system.len(), 2);
+        assert_eq!(
+            system.observations().collect::<Vec<_>>(),
+            vec![(0.0, 0.0), (1.0, 20.0)]
+        );
+    }
+
+    #[test]
+    fn test_nan_stressor_yields_nan() {
+        let system = EmpiricalSystem::new([(0.0, 0.0), (1.0, 1.0), (2.0, 4.0)]).unwrap();
+        for interpolation in [
+            Interpolation::Nearest,
+            Interpolation::Linear,
+            Interpolation::CubicSpline,
+        ] {
+            let system = system.clone().with_interpolation(interpolation);
+            assert!(system.payoff(f64::NAN).is_nan());
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert_eq!(
+            EmpiricalSystem::new([(1.0, 1.0), (1.0, 2.0)]),
+            Err(EmpiricalError::TooFewSamples { found: 1 })
+        );
+        assert_eq!(
+            EmpiricalSystem::new([(0.0, 1.0), (1.0, f64::NAN)]),
+            Err(EmpiricalError::NonFinite { index: 1 })
+        );
+        assert_eq!(
+            EmpiricalError::TooFewSamples { found: 0 }.to_string(),
+            "too few samples: found 0 distinct stressor values, need at least 2"
+        );
+    }
+
+    #[test]
+    fn test_from_csv_maps_named_and_quoted_columns() {
+        let csv = "label,x,\"payoff, net\"\n\n\"a, b\",-1,1\nc,0,0\nd, 1 ,1\n";
+        let mapping = ColumnMapping::new("x", "payoff, net");
+        let system = EmpiricalSystem::from_csv(csv.as_bytes(), &mapping).unwrap();
+        assert_eq!(
+            system.observations().collect::<Vec<_>>(),
+            vec![(-1.0, 1.0), (0.0, 0.0), (1.0, 1.0)]
+        );
+        assert_eq!(system.classify(0.0, 1.0), Triad::Antifragile);
+    }
+
+    #[test]
+    fn test_from_csv_by_position_without_header() {
+        let csv = "0;5\n1;4\n2;1\n";
+        let mapping = ColumnMapping::new(0, 1)
+            .with_delimiter(';')
+            .without_header()
+            .negated();
+        let system = EmpiricalSystem::from_csv(csv.as_bytes(), &mapping).unwrap();
+        assert_eq!(
+            system.observations().collect::<Vec<_>>(),
+            vec![(0.0, -5.0), (1.0, -4.0), (2.0, -1.0)]
+        );
+    }
+
+    #[test]
+    fn test_from_csv_errors() {
+        let load = |csv: &str, mapping: &ColumnMapping| {
+            EmpiricalSystem::from_csv(csv.as_bytes(), mapping).unwrap_err()
+        };
+        let mapping = ColumnMapping::default();
+        assert!(matches!(
+            load("x,payoff\n1,2\n", &mapping),
+            LoadError::MissingColumn(Column::Named(name)) if name == "stressor"
+        ));
+        let error = load("stressor,payoff\n1,2\n2,n/a\n", &mapping);
+        assert!(matches!(error, LoadError::InvalidNumber { record: 3, .. }));
+        assert_eq!(
+            error.to_string(),
+            "record 3 column \"payoff\": \"n/a\" is not a number"
+        );
+        assert!(matches!(
+            load("stressor,payoff\n1\n", &mapping),
+            LoadError::MissingField { record: 2, .. }
+        ));
+        assert!(matches!(
+            load("stressor,payoff\n1,2\n", &mapping),
+            LoadError::Empirical(EmpiricalError::TooFewSamples { found: 1 })
+        ));
+        assert!(matches!(
+            load("1,2\n", &ColumnMapping::default().without_header()),
+            LoadError::MissingColumn(_)
+        ));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_from_json_objects_and_arrays() {
+        let objects = r#"[{"x": 0, "y": 1}, {"x": 1, "y": 0}, {"x": 2, "y": 1}]"#;
+        let system =
+            EmpiricalSystem::from_json(objects.as_bytes(), &ColumnMapping::new("x", "y")).unwrap();
+        assert_eq!(system.classify(1.0, 1.0), Triad::Antifragile);
+
+        let arrays = "[[0, 1], [1, 0], [2, 1]]";
+        let by_position =
+            EmpiricalSystem::from_json(arrays.as_bytes(), &ColumnMapping::new(0, 1)).unwrap();
+        assert_eq!(by_position, system);
+
+        let error = EmpiricalSystem::from_json(arrays.as_bytes(), &ColumnMapping::new("x", "y"))
+            .unwrap_err();
+        assert!(matches!(error, LoadError::MissingField { record: 1, .. }));
+        assert!(matches!(
+            EmpiricalSystem::from_json(&b"{}"[..], &ColumnMapping::default()),
+            Err(LoadError::Json(_))
+        ));
+    }
+}
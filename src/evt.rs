@@ -0,0 +1,396 @@
+//! # Extreme value theory: peaks over threshold
+//!
+//! Convexity says which way a system bends; it does not say how bad the
+//! worst period in a thousand will be. Extreme value theory does. Above a
+//! high enough threshold `u`, the excesses of almost any loss distribution
+//! follow a Generalized Pareto Distribution
+//!
+//! ```text
+//! P(L - u > y | L > u) = (1 + ξ·y/σ)^(-1/ξ)
+//! ```
+//!
+//! with shape `ξ` and scale `σ`. The shape classifies the tail: `ξ > 0` is
+//! a power law with tail index `1/ξ` (no mean beyond `ξ ≥ 1`), `ξ = 0` is
+//! exponential, and `ξ < 0` has a finite worst case at `u - σ/ξ`.
+//!
+//! [`fit_losses`](crate::evt::fit_losses) treats negated payoffs as losses,
+//! takes the threshold from a quantile, and fits `ξ` and `σ` by maximum
+//! likelihood. [`GpdFit::return_level`](crate::evt::GpdFit::return_level)
+//! then answers "how bad is a 1-in-`m` period", extrapolating past the
+//! largest loss observed.
+//!
+//! ```rust
+//! use antifragile::evt::{EvtConfig, fit_losses};
+//!
+//! // Pareto losses with tail index 2: P(L > x) = x⁻²
+//! let payoffs: Vec<f64> = (1..=5000)
+//!     .map(|i| -(f64::from(i) / 5001.0).powf(-0.5))
+//!     .collect();
+//!
+//! let fit = fit_losses(&payoffs, &EvtConfig::default())?;
+//! assert!((fit.shape - 0.5).abs() < 0.1);
+//!
+//! // The 1-in-10 000 loss is √10 000 = 100, beyond anything observed
+//! let level = fit.return_level(10_000.0);
+//! assert!((level - 100.0).abs() < 25.0);
+//! # Ok::<(), antifragile::evt::EvtError>(())
+//! ```
+
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::Antifragile;
+
+/// Configuration for [`fit_losses`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvtConfig {
+    /// Quantile of the losses used as the threshold
+    pub threshold_quantile: f64,
+    /// Fewest exceedances a fit accepts
+    pub min_exceedances: usize,
+}
+
+impl Default for EvtConfig {
+    /// Threshold at the 90th percentile of losses, with at least 10 exceedances
+    fn default() -> Self {
+        Self {
+            threshold_quantile: 0.9,
+            min_exceedances: 10,
+        }
+    }
+}
+
+/// A Generalized Pareto fit to the losses above a threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpdFit {
+    /// Loss threshold `u`
+    pub threshold: f64,
+    /// Shape `ξ`; positive for power-law tails
+    pub shape: f64,
+    /// Scale `σ`
+    pub scale: f64,
+    /// Number of losses above the threshold
+    pub exceedances: usize,
+    /// Total number of losses the threshold was applied to
+    pub samples: usize,
+    /// Maximized log-likelihood of the excesses
+    pub log_likelihood: f64,
+}
+
+impl GpdFit {
+    /// Share of losses above the threshold, `ζ = exceedances / samples`
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn exceedance_rate(&self) -> f64 {
+        self.exceedances as f64 / self.samples as f64
+    }
+
+    /// Tail index `1/ξ` of a power-law tail, or `None` if `ξ <= 0`
+    #[must_use]
+    pub fn tail_index(&self) -> Option<f64> {
+        (self.shape > 0.0).then(|| 1.0 / self.shape)
+    }
+
+    /// Largest possible loss when `ξ < 0`, else `None`
+    #[must_use]
+    pub fn upper_bound(&self) -> Option<f64> {
+        (self.shape < 0.0).then(|| self.threshold - self.scale / self.shape)
+    }
+
+    /// Probability that a single loss exceeds `loss`
+    ///
+    /// Only meaningful at or above the threshold; below it the empirical
+    /// rate [`exceedance_rate`](Self::exceedance_rate) is returned.
+    #[must_use]
+    pub fn exceedance_probability(&self, loss: f64) -> f64 {
+        let excess = (loss - self.threshold).max(0.0);
+        self.exceedance_rate() * survival(excess, self.shape, self.scale)
+    }
+
+    /// Loss exceeded on average once every `period` observations
+    ///
+    /// Periods short enough that the level falls below the threshold
+    /// return the threshold.
+    #[must_use]
+    pub fn return_level(&self, period: f64) -> f64 {
+        let exposure = (period * self.exceedance_rate()).max(1.0);
+        let excess = if self.shape.abs() < SHAPE_EPSILON {
+            self.scale * exposure.ln()
+        } else {
+            self.scale / self.shape * (exposure.powf(self.shape) - 1.0)
+        };
+        self.threshold + excess
+    }
+}
+
+/// Error returned by the fitting functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvtError {
+    /// Too few losses above the threshold to fit a tail
+    TooFewExceedances {
+        /// Number of exceedances found
+        found: usize,
+        /// Number required by the configuration
+        needed: usize,
+    },
+    /// A loss was NaN or infinite
+    NonFinite {
+        /// Position of the offending sample
+        index: usize,
+    },
+}
+
+impl Display for EvtError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooFewExceedances { found, needed } => write!(
+                f,
+                "too few exceedances: found {found}, need at least {needed}"
+            ),
+            Self::NonFinite { index } => write!(f, "non-finite sample at index {index}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for EvtError {}
+
+/// Shapes closer to zero than this are treated as exponential
+const SHAPE_EPSILON: f64 = 1e-9;
+
+/// Fit a Generalized Pareto Distribution to the losses above `threshold`
+///
+/// The fit maximizes the profile likelihood over `θ = ξ/σ` and is
+/// restricted to `ξ > -1`, where the likelihood is bounded.
+///
+/// # Errors
+///
+/// Returns [`EvtError::NonFinite`] if a loss is NaN or infinite and
+/// [`EvtError::TooFewExceedances`] if fewer than `min_exceedances` losses
+/// lie above `threshold`.
+pub fn peaks_over_threshold(
+    losses: &[f64],
+    threshold: f64,
+    min_exceedances: usize,
+) -> Result<GpdFit, EvtError> {
+    if let Some(index) = losses.iter().position(|x| !x.is_finite()) {
+        return Err(EvtError::NonFinite { index });
+    }
+    let excesses: Vec<f64> = losses
+        .iter()
+        .filter(|&&loss| loss > threshold)
+        .map(|loss| loss - threshold)
+        .collect();
+    let needed = min_exceedances.max(2);
+    if excesses.len() < needed {
+        return Err(EvtError::TooFewExceedances {
+            found: excesses.len(),
+            needed,
+        });
+    }
+
+    let (shape, scale, log_likelihood) = fit_excesses(&excesses);
+    Ok(GpdFit {
+        threshold,
+        shape,
+        scale,
+        exceedances: excesses.len(),
+        samples: losses.len(),
+        log_likelihood,
+    })
+}
+
+/// Fit the tail of the losses `-payoff`, with the threshold at a quantile
+///
+/// # Errors
+///
+/// As [`peaks_over_threshold`].
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub fn fit_losses(payoffs: &[f64], config: &EvtConfig) -> Result<GpdFit, EvtError> {
+    if let Some(index) = payoffs.iter().position(|x| !x.is_finite()) {
+        return Err(EvtError::NonFinite { index });
+    }
+    let losses: Vec<f64> = payoffs.iter().map(|p| -p).collect();
+    let mut sorted = losses.clone();
+    sorted.sort_by(f64::total_cmp);
+    let position = (config.threshold_quantile.clamp(0.0, 1.0) * sorted.len() as f64) as usize;
+    let threshold = sorted
+        .get(position.min(sorted.len().saturating_sub(1)))
+        .copied()
+        .unwrap_or(0.0);
+    peaks_over_threshold(&losses, threshold, config.min_exceedances)
+}
+
+/// Fit the tail of the losses of `system` at observed `stressors`
+///
+/// # Errors
+///
+/// As [`peaks_over_threshold`], with indices referring to `stressors`.
+pub fn payoff_losses<T>(
+    system: &T,
+    stressors: &[f64],
+    config: &EvtConfig,
+) -> Result<GpdFit, EvtError>
+where
+    T: Antifragile<Stressor = f64, Payoff = f64> + ?Sized,
+{
+    let payoffs: Vec<f64> = stressors.iter().map(|&x| system.payoff(x)).collect();
+    fit_losses(&payoffs, config)
+}
+
+/// `P(Y > y)` for a GPD excess
+fn survival(excess: f64, shape: f64, scale: f64) -> f64 {
+    if shape.abs() < SHAPE_EPSILON {
+        (-excess / scale).exp()
+    } else {
+        (1.0 + shape * excess / scale).max(0.0).powf(-1.0 / shape)
+    }
+}
+
+/// Maximum-likelihood `(ξ, σ, log-likelihood)` for positive excesses
+///
+/// For fixed `θ = ξ/σ` the likelihood is maximized by
+/// `ξ = mean(ln(1 + θ·y))`, leaving a one-dimensional search over `θ`. A
+/// coarse grid brackets the maximum and golden-section search refines it.
+#[allow(clippy::cast_precision_loss)]
+fn fit_excesses(excesses: &[f64]) -> (f64, f64, f64) {
+    let n = excesses.len() as f64;
+    let largest = excesses.iter().copied().fold(0.0, f64::max);
+    let mean = excesses.iter().sum::<f64>() / n;
+
+    let profile = |theta: f64| -> Option<(f64, f64, f64)> {
+        if theta.abs() * largest < SHAPE_EPSILON {
+            return Some((0.0, mean, -n * (mean.ln() + 1.0)));
+        }
+        if theta * largest <= -1.0 {
+            return None;
+        }
+        let shape = excesses.iter().map(|y| (theta * y).ln_1p()).sum::<f64>() / n;
+        let scale = shape / theta;
+        if shape <= -1.0 || scale <= 0.0 || !scale.is_finite() {
+            return None;
+        }
+        Some((shape, scale, -n * (scale.ln() + shape + 1.0)))
+    };
+    let score = |t: f64| profile(t / largest).map_or(f64::NEG_INFINITY, |(.., ll)| ll);
+
+    // t = θ·max(y) ranges over (-1, ∞); the grid is logarithmic towards
+    // -1, 0, and ∞
+    let steps = (-60..=60).map(|i| f64::from(i) / 5.0);
+    let grid: Vec<f64> = steps
+        .clone()
+        .map(|s| -1.0 / (1.0 + s.exp()))
+        .chain([0.0])
+        .chain(steps.map(f64::exp))
+        .collect();
+    let best = grid
+        .iter()
+        .enumerate()
+        .max_by(|a, b| score(*a.1).total_cmp(&score(*b.1)))
+        .map_or(0, |(i, _)| i);
+
+    let (mut low, mut high) = (
+        grid[best.saturating_sub(1)],
+        grid[(best + 1).min(grid.len() - 1)],
+    );
+    let ratio = 0.5 * (5.0_f64.sqrt() - 1.0);
+    for _ in 0..100 {
+        let a = high - ratio * (high - low);
+        let b = low + ratio * (high - low);
+        if score(a) >= score(b) {
+            high = b;
+        } else {
+            low = a;
+        }
+    }
+    let theta = 0.5 * (low + high) / largest;
+    profile(theta).unwrap_or((0.0, mean, -n * (mean.ln() + 1.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    fn losses(n: usize, seed: u64, inverse_cdf: impl Fn(f64) -> f64) -> Vec<f64> {
+        let mut rng = SplitMix64::new(seed);
+        (0..n).map(|_| inverse_cdf(1.0 - rng.next_f64())).collect()
+    }
+
+    #[test]
+    fn test_shape_classifies_tails() {
+        let pareto = losses(20_000, 1, |u| u.powf(-1.0 / 3.0));
+        let exponential = losses(20_000, 2, |u| -u.ln());
+        let bounded = losses(20_000, 3, |u| 1.0 - u.sqrt());
+
+        let fit = |l: &[f64]| {
+            let payoffs: Vec<f64> = l.iter().map(|x| -x).collect();
+            fit_losses(&payoffs, &EvtConfig::default()).unwrap()
+        };
+        let pareto = fit(&pareto);
+        assert!((pareto.shape - 1.0 / 3.0).abs() < 0.1);
+        assert!(pareto.tail_index().is_some_and(|a| (a - 3.0).abs() < 1.0));
+        assert!(fit(&exponential).shape.abs() < 0.1);
+
+        // 1 - √U has a finite worst case at 1 and ξ = -1/2 near it
+        let bounded = fit(&bounded);
+        assert!((bounded.shape + 0.5).abs() < 0.15);
+        assert!(
+            bounded
+                .upper_bound()
+                .is_some_and(|b| (b - 1.0).abs() < 0.05)
+        );
+    }
+
+    #[test]
+    fn test_return_levels_extrapolate() {
+        let exponential = losses(10_000, 4, |u| -u.ln());
+        let fit = peaks_over_threshold(&exponential, 2.0, 10).unwrap();
+        // Exact 1-in-m level of a unit exponential is ln(m)
+        assert!((fit.return_level(1e5) - 1e5_f64.ln()).abs() < 1.0);
+        assert!((fit.exceedance_probability(fit.return_level(1e5)) - 1e-5).abs() < 1e-9);
+        // Short periods stay at the threshold
+        assert!((fit.return_level(1.0) - 2.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_system_losses() {
+        struct ShortStraddle;
+
+        impl Antifragile for ShortStraddle {
+            type Stressor = f64;
+            type Payoff = f64;
+
+            fn payoff(&self, x: f64) -> f64 {
+                1.0 - x * x
+            }
+        }
+
+        // Normal moves: squared losses have an exponential-like tail
+        let mut rng = SplitMix64::new(6);
+        let moves: Vec<f64> = (0..20_000).map(|_| rng.next_normal()).collect();
+        let fit = payoff_losses(&ShortStraddle, &moves, &EvtConfig::default()).unwrap();
+        assert!(fit.shape.abs() < 0.15);
+        assert!(fit.return_level(1000.0) > fit.threshold);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            peaks_over_threshold(&[1.0, 2.0, 3.0], 2.5, 10),
+            Err(EvtError::TooFewExceedances {
+                found: 1,
+                needed: 10
+            })
+        );
+        assert_eq!(
+            fit_losses(&[1.0, f64::NAN], &EvtConfig::default()),
+            Err(EvtError::NonFinite { index: 1 })
+        );
+    }
+}
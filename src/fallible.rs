@@ -0,0 +1,171 @@
+//! # Fallible payoffs
+//!
+//! A payoff measured by a network request, a simulation that can diverge,
+//! or a parser fed live data does not always produce a value.
+//! [`TryAntifragile`](crate::fallible::TryAntifragile) returns a `Result`
+//! from its payoff, and
+//! [`FallibleAnalysis`](crate::fallible::FallibleAnalysis) runs the
+//! convexity test on it, stopping at the first failed probe.
+//! [`Verified::try_check`](crate::Verified::try_check) wraps the result.
+//!
+//! ```rust
+//! use antifragile::{Triad, Verified};
+//! use antifragile::fallible::TryAntifragile;
+//!
+//! /// Throughput of a service that refuses loads above its capacity
+//! struct Service {
+//!     capacity: f64,
+//! }
+//!
+//! impl TryAntifragile for Service {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!     type Error = f64;
+//!
+//!     fn try_payoff(&self, load: f64) -> Result<f64, f64> {
+//!         if load > self.capacity {
+//!             return Err(load);
+//!         }
+//!         Ok(load.sqrt())
+//!     }
+//! }
+//!
+//! let service = Service { capacity: 100.0 };
+//! let verified = Verified::try_check(service, 50.0, 10.0).unwrap();
+//! assert_eq!(verified.classification(), Triad::Fragile);
+//!
+//! let service = Service { capacity: 100.0 };
+//! assert_eq!(Verified::try_check(service, 95.0, 10.0).err(), Some(105.0));
+//! ```
+
+use core::ops::{Add, Sub};
+
+use crate::{Triad, Verified};
+
+/// A system whose payoff can fail
+///
+/// The fallible counterpart of [`Antifragile`](crate::Antifragile), with the
+/// same bounds on stressor and payoff.
+pub trait TryAntifragile {
+    /// The type of stressor (e.g., volatility, load, perturbation)
+    type Stressor: Copy + Add<Output = Self::Stressor> + Sub<Output = Self::Stressor>;
+
+    /// The type of payoff/outcome (must be comparable and additive)
+    type Payoff: Copy + Add<Output = Self::Payoff> + PartialOrd;
+
+    /// The reason a payoff could not be computed
+    type Error;
+
+    /// The payoff function, or the error that prevented computing it
+    ///
+    /// # Errors
+    ///
+    /// Whatever the system reports when it cannot produce a payoff.
+    fn try_payoff(&self, stressor: Self::Stressor) -> Result<Self::Payoff, Self::Error>;
+
+    /// Returns the payoff added to itself (r + r)
+    ///
+    /// See [`Antifragile::twin`](crate::Antifragile::twin).
+    fn twin(r: Self::Payoff) -> Self::Payoff {
+        r + r
+    }
+}
+
+/// Extension trait running the convexity test on fallible systems
+pub trait FallibleAnalysis: TryAntifragile {
+    /// Classify the system at operating point `at` with perturbation `delta`
+    ///
+    /// Compares `f(x+Δ) + f(x-Δ)` against `2·f(x)` exactly as
+    /// [`TriadAnalysis::classify`](crate::TriadAnalysis::classify) does,
+    /// evaluating `f(x-Δ)`, `f(x)`, then `f(x+Δ)`.
+    ///
+    /// # Errors
+    ///
+    /// The error of the first probe that fails; later probes are not run.
+    fn classify(&self, at: Self::Stressor, delta: Self::Stressor) -> Result<Triad, Self::Error> {
+        let f_x_minus = self.try_payoff(at - delta)?;
+        let f_x = self.try_payoff(at)?;
+        let f_x_plus = self.try_payoff(at + delta)?;
+
+        let sum = f_x_plus + f_x_minus;
+        let twin_f_x = Self::twin(f_x);
+        Ok(if sum > twin_f_x {
+            Triad::Antifragile
+        } else if sum < twin_f_x {
+            Triad::Fragile
+        } else {
+            Triad::Robust
+        })
+    }
+}
+
+impl<T: TryAntifragile + ?Sized> FallibleAnalysis for T {}
+
+impl<T: TryAntifragile> Verified<T, T::Stressor> {
+    /// Verify a fallible system's classification at a given operating
+    /// point, the counterpart of [`Verified::check`]
+    ///
+    /// # Errors
+    ///
+    /// The error of the first probe that fails, with the system dropped.
+    pub fn try_check(system: T, at: T::Stressor, delta: T::Stressor) -> Result<Self, T::Error> {
+        let classification = system.classify(at, delta)?;
+        Ok(Self::from_parts(system, classification, at, delta))
+    }
+
+    /// Check if the classification still holds at a different operating
+    /// point, the counterpart of [`Verified::still_holds`]
+    ///
+    /// # Errors
+    ///
+    /// The error of the first probe that fails.
+    pub fn try_still_holds(&self, at: T::Stressor, delta: T::Stressor) -> Result<bool, T::Error> {
+        Ok(self.inner().classify(at, delta)? == self.classification())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    /// A convex payoff that fails on negative stress, counting its calls
+    struct Sensor {
+        calls: Cell<usize>,
+    }
+
+    impl TryAntifragile for Sensor {
+        type Stressor = i32;
+        type Payoff = i32;
+        type Error = i32;
+
+        fn try_payoff(&self, x: i32) -> Result<i32, i32> {
+            self.calls.set(self.calls.get() + 1);
+            if x < 0 { Err(x) } else { Ok(x * x) }
+        }
+    }
+
+    #[test]
+    fn test_classify_stops_at_first_error() {
+        let sensor = Sensor {
+            calls: Cell::new(0),
+        };
+        assert_eq!(sensor.classify(5, 2), Ok(Triad::Antifragile));
+        assert_eq!(sensor.calls.get(), 3);
+
+        assert_eq!(sensor.classify(1, 2), Err(-1));
+        assert_eq!(sensor.calls.get(), 4);
+    }
+
+    #[test]
+    fn test_try_check_records_conditions() {
+        let sensor = Sensor {
+            calls: Cell::new(0),
+        };
+        let verified = Verified::try_check(sensor, 5, 2).unwrap();
+        assert!(verified.is_antifragile());
+        assert_eq!((verified.at(), verified.delta()), (5, 2));
+        assert_eq!(verified.try_still_holds(10, 3), Ok(true));
+        assert_eq!(verified.try_still_holds(0, 1), Err(-1));
+    }
+}
@@ -0,0 +1,628 @@
+//! # Stress-response curve fitting
+//!
+//! Finite-difference classification of noisy measurements is itself noisy.
+//! A more interpretable route is to ask which familiar *shape* the data
+//! follows, then read the classification off that shape.
+//!
+//! [`fit_family`](crate::fit::fit_family) fits four named families to
+//! observed `(stressor, payoff)` pairs by least squares:
+//!
+//! | Family | Model | Parameters |
+//! |--------|-------|------------|
+//! | Quadratic | `a + b·x + c·x²` | 3 |
+//! | Exponential | `a + b·e^(k·x)` | 3 |
+//! | Power law | `a + b·x^p` (positive stressors only) | 3 |
+//! | Logistic | `a + b / (1 + e^(-k·(x - m)))` | 4 |
+//!
+//! The families are ranked by an information criterion (AIC by default), and
+//! the winner classifies each operating point from the sign of its *analytic*
+//! second derivative. The fitted model and its parameters are reported so the
+//! result can be explained, not just trusted.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::fit::{Family, fit_family};
+//!
+//! // Error rate against load, roughly exponential
+//! let observations: Vec<(f64, f64)> = (0..20)
+//!     .map(|i| {
+//!         let load = f64::from(i) * 0.5;
+//!         (load, 0.01 * (0.4 * load).exp() + 0.002 * (-1.0_f64).powi(i))
+//!     })
+//!     .collect();
+//!
+//! let fit = fit_family(&observations)?;
+//! assert!(matches!(fit.best().family, Family::Exponential { .. }));
+//! assert_eq!(fit.classify(5.0), Triad::Antifragile);
+//! # Ok::<(), antifragile::fit::FitError>(())
+//! ```
+
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::{Antifragile, Triad};
+
+/// Minimum number of observations [`fit_family`] accepts
+pub const MIN_OBSERVATIONS: usize = 5;
+
+/// Points in the coarse grid over each nonlinear parameter
+const GRID_POINTS: usize = 41;
+
+/// Golden-section refinement steps per nonlinear parameter
+const REFINE_STEPS: usize = 60;
+
+/// Information criterion used to rank fitted families
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Criterion {
+    /// Akaike information criterion: `n·ln(RSS/n) + 2k`
+    #[default]
+    Aic,
+    /// Bayesian information criterion: `n·ln(RSS/n) + k·ln(n)`
+    ///
+    /// Penalizes extra parameters more heavily than AIC once `n ≥ 8`.
+    Bic,
+}
+
+/// A fitted stress-response family with its parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Family {
+    /// `a + b·x + c·x²`
+    Quadratic {
+        /// Intercept
+        a: f64,
+        /// Linear coefficient
+        b: f64,
+        /// Quadratic coefficient; its sign is the classification everywhere
+        c: f64,
+    },
+    /// `a + b·e^(rate·x)`
+    Exponential {
+        /// Offset
+        a: f64,
+        /// Scale
+        b: f64,
+        /// Growth rate
+        rate: f64,
+    },
+    /// `a + b·x^exponent`
+    PowerLaw {
+        /// Offset
+        a: f64,
+        /// Scale
+        b: f64,
+        /// Exponent
+        exponent: f64,
+    },
+    /// `a + b / (1 + e^(-rate·(x - midpoint)))`
+    Logistic {
+        /// Lower asymptote
+        a: f64,
+        /// Height of the step
+        b: f64,
+        /// Steepness (positive)
+        rate: f64,
+        /// Inflection point
+        midpoint: f64,
+    },
+}
+
+impl Family {
+    /// Short lowercase name of the family
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Quadratic { .. } => "quadratic",
+            Self::Exponential { .. } => "exponential",
+            Self::PowerLaw { .. } => "power-law",
+            Self::Logistic { .. } => "logistic",
+        }
+    }
+
+    /// Number of fitted parameters
+    #[must_use]
+    pub const fn parameter_count(&self) -> usize {
+        match self {
+            Self::Quadratic { .. } | Self::Exponential { .. } | Self::PowerLaw { .. } => 3,
+            Self::Logistic { .. } => 4,
+        }
+    }
+
+    /// Evaluate the fitted curve at `x`
+    #[must_use]
+    pub fn evaluate(&self, x: f64) -> f64 {
+        match *self {
+            Self::Quadratic { a, b, c } => a + b * x + c * x * x,
+            Self::Exponential { a, b, rate } => a + b * (rate * x).exp(),
+            Self::PowerLaw { a, b, exponent } => a + b * x.powf(exponent),
+            Self::Logistic {
+                a,
+                b,
+                rate,
+                midpoint,
+            } => a + b * sigmoid(rate * (x - midpoint)),
+        }
+    }
+
+    /// Analytic second derivative of the fitted curve at `x`
+    #[must_use]
+    pub fn second_derivative(&self, x: f64) -> f64 {
+        match *self {
+            Self::Quadratic { c, .. } => 2.0 * c,
+            Self::Exponential { b, rate, .. } => b * rate * rate * (rate * x).exp(),
+            Self::PowerLaw { b, exponent, .. } => {
+                b * exponent * (exponent - 1.0) * x.powf(exponent - 2.0)
+            }
+            Self::Logistic {
+                b, rate, midpoint, ..
+            } => {
+                let s = sigmoid(rate * (x - midpoint));
+                b * rate * rate * s * (1.0 - s) * (1.0 - 2.0 * s)
+            }
+        }
+    }
+
+    /// Classify from the sign of the analytic second derivative at `x`
+    pub fn classify(&self, x: f64) -> Triad {
+        let curvature = self.second_derivative(x);
+        if curvature > 0.0 {
+            Triad::Antifragile
+        } else if curvature < 0.0 {
+            Triad::Fragile
+        } else {
+            Triad::Robust
+        }
+    }
+}
+
+impl Antifragile for Family {
+    type Stressor = f64;
+    type Payoff = f64;
+
+    fn payoff(&self, stressor: f64) -> f64 {
+        self.evaluate(stressor)
+    }
+}
+
+/// One family fitted to the observations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FamilyFit {
+    /// The family and its fitted parameters
+    pub family: Family,
+    /// Residual sum of squares
+    pub rss: f64,
+    /// Akaike information criterion (lower is better)
+    pub aic: f64,
+    /// Bayesian information criterion (lower is better)
+    pub bic: f64,
+}
+
+impl FamilyFit {
+    /// Score under `criterion` (lower is better)
+    #[must_use]
+    pub const fn score(&self, criterion: Criterion) -> f64 {
+        match criterion {
+            Criterion::Aic => self.aic,
+            Criterion::Bic => self.bic,
+        }
+    }
+}
+
+/// Every family that could be fitted, ranked by an information criterion
+#[derive(Debug, Clone, PartialEq)]
+pub struct FitResult {
+    fits: Vec<FamilyFit>,
+    criterion: Criterion,
+}
+
+impl FitResult {
+    /// The best-ranked fit
+    #[must_use]
+    pub fn best(&self) -> &FamilyFit {
+        &self.fits[0]
+    }
+
+    /// All fits, best first
+    #[must_use]
+    pub fn fits(&self) -> &[FamilyFit] {
+        &self.fits
+    }
+
+    /// The criterion used for ranking
+    #[must_use]
+    pub const fn criterion(&self) -> Criterion {
+        self.criterion
+    }
+
+    /// Classify `at` from the best fit's analytic second derivative
+    pub fn classify(&self, at: f64) -> Triad {
+        self.best().family.classify(at)
+    }
+}
+
+/// Error returned when fitting fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitError {
+    /// Fewer than [`MIN_OBSERVATIONS`] observations were supplied
+    TooFewObservations {
+        /// Number of observations supplied
+        found: usize,
+    },
+    /// An observation contained a NaN or infinite value
+    NonFinite {
+        /// Position of the offending observation
+        index: usize,
+    },
+    /// No family could be fitted (for example, all stressors are equal)
+    Degenerate,
+}
+
+impl Display for FitError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooFewObservations { found } => write!(
+                f,
+                "too few observations: found {found}, need at least {MIN_OBSERVATIONS}"
+            ),
+            Self::NonFinite { index } => write!(f, "non-finite observation at index {index}"),
+            Self::Degenerate => write!(f, "no family could be fitted to the observations"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for FitError {}
+
+/// Fit every family to `observations` and rank them by AIC
+///
+/// # Errors
+///
+/// See [`fit_family_with`].
+pub fn fit_family(observations: &[(f64, f64)]) -> Result<FitResult, FitError> {
+    fit_family_with(observations, Criterion::Aic)
+}
+
+/// Fit every family to `observations` and rank them by `criterion`
+///
+/// The power-law family is only attempted when every stressor is positive.
+///
+/// # Errors
+///
+/// Returns [`FitError::TooFewObservations`] for fewer than
+/// [`MIN_OBSERVATIONS`] observations, [`FitError::NonFinite`] if any value is
+/// NaN or infinite, and [`FitError::Degenerate`] if no family could be fitted.
+pub fn fit_family_with(
+    observations: &[(f64, f64)],
+    criterion: Criterion,
+) -> Result<FitResult, FitError> {
+    if observations.len() < MIN_OBSERVATIONS {
+        return Err(FitError::TooFewObservations {
+            found: observations.len(),
+        });
+    }
+    if let Some(index) = observations
+        .iter()
+        .position(|(x, y)| !x.is_finite() || !y.is_finite())
+    {
+        return Err(FitError::NonFinite { index });
+    }
+
+    let xs: Vec<f64> = observations.iter().map(|&(x, _)| x).collect();
+    let ys: Vec<f64> = observations.iter().map(|&(_, y)| y).collect();
+
+    let mut fits: Vec<FamilyFit> = [
+        fit_quadratic(&xs, &ys),
+        fit_exponential(&xs, &ys),
+        fit_power_law(&xs, &ys),
+        fit_logistic(&xs, &ys),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|(family, rss)| scored(family, rss, xs.len()))
+    .collect();
+
+    if fits.is_empty() {
+        return Err(FitError::Degenerate);
+    }
+    fits.sort_by(|a, b| a.score(criterion).total_cmp(&b.score(criterion)));
+    Ok(FitResult { fits, criterion })
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn scored(family: Family, rss: f64, n: usize) -> FamilyFit {
+    let count = n as f64;
+    let params = family.parameter_count() as f64;
+    // Floor the residual so perfect fits rank by parameter count
+    let fit_term = count * (rss / count).max(1e-300).ln();
+    FamilyFit {
+        family,
+        rss,
+        aic: fit_term + 2.0 * params,
+        bic: fit_term + params * count.ln(),
+    }
+}
+
+fn fit_quadratic(xs: &[f64], ys: &[f64]) -> Option<(Family, f64)> {
+    let (coef, rss) = least_squares(xs, ys, |x| [1.0, x, x * x])?;
+    Some((
+        Family::Quadratic {
+            a: coef[0],
+            b: coef[1],
+            c: coef[2],
+        },
+        rss,
+    ))
+}
+
+fn fit_exponential(xs: &[f64], ys: &[f64]) -> Option<(Family, f64)> {
+    let span = stressor_span(xs)?;
+    let limit = 20.0 / span;
+    let rate = minimize_1d(-limit, limit, |rate| {
+        least_squares(xs, ys, |x| [1.0, (rate * x).exp(), 0.0]).map(|(_, rss)| rss)
+    })?;
+    let (coef, rss) = least_squares(xs, ys, |x| [1.0, (rate * x).exp(), 0.0])?;
+    Some((
+        Family::Exponential {
+            a: coef[0],
+            b: coef[1],
+            rate,
+        },
+        rss,
+    ))
+}
+
+fn fit_power_law(xs: &[f64], ys: &[f64]) -> Option<(Family, f64)> {
+    if xs.iter().any(|&x| x <= 0.0) {
+        return None;
+    }
+    stressor_span(xs)?;
+    let exponent = minimize_1d(-5.0, 5.0, |p| {
+        least_squares(xs, ys, |x| [1.0, x.powf(p), 0.0]).map(|(_, rss)| rss)
+    })?;
+    let (coef, rss) = least_squares(xs, ys, |x| [1.0, x.powf(exponent), 0.0])?;
+    Some((
+        Family::PowerLaw {
+            a: coef[0],
+            b: coef[1],
+            exponent,
+        },
+        rss,
+    ))
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn fit_logistic(xs: &[f64], ys: &[f64]) -> Option<(Family, f64)> {
+    let span = stressor_span(xs)?;
+    let lo = xs.iter().copied().fold(f64::INFINITY, f64::min);
+    let hi = xs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let rss = |rate: f64, midpoint: f64| {
+        least_squares(xs, ys, |x| [1.0, sigmoid(rate * (x - midpoint)), 0.0]).map(|(_, r)| r)
+    };
+
+    // Coarse grid: log-spaced steepness, midpoints across the observed range
+    let (log_lo, log_hi) = ((0.1 / span).ln(), (100.0 / span).ln());
+    let mut best: Option<(f64, f64, f64)> = None;
+    for i in 0..GRID_POINTS {
+        let rate = (log_lo + (log_hi - log_lo) * i as f64 / (GRID_POINTS - 1) as f64).exp();
+        for j in 0..GRID_POINTS {
+            let midpoint = lo + (hi - lo) * j as f64 / (GRID_POINTS - 1) as f64;
+            let Some(residual) = rss(rate, midpoint) else {
+                continue;
+            };
+            if best.is_none_or(|(_, _, lowest)| residual < lowest) {
+                best = Some((rate, midpoint, residual));
+            }
+        }
+    }
+    let (mut rate, mut midpoint, _) = best?;
+
+    // Alternate golden-section refinement of each parameter
+    for _ in 0..4 {
+        let log_rate = golden(rate.ln() - 0.5, rate.ln() + 0.5, |lr| {
+            rss(lr.exp(), midpoint).unwrap_or(f64::INFINITY)
+        });
+        rate = log_rate.exp();
+        let step = (hi - lo) / (GRID_POINTS - 1) as f64;
+        midpoint = golden(midpoint - step, midpoint + step, |m| {
+            rss(rate, m).unwrap_or(f64::INFINITY)
+        });
+    }
+
+    let (coef, rss) = least_squares(xs, ys, |x| [1.0, sigmoid(rate * (x - midpoint)), 0.0])?;
+    Some((
+        Family::Logistic {
+            a: coef[0],
+            b: coef[1],
+            rate,
+            midpoint,
+        },
+        rss,
+    ))
+}
+
+fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+fn stressor_span(xs: &[f64]) -> Option<f64> {
+    let lo = xs.iter().copied().fold(f64::INFINITY, f64::min);
+    let hi = xs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    (hi > lo).then_some(hi - lo)
+}
+
+/// Grid search over `[lo, hi]` followed by golden-section refinement
+#[allow(clippy::cast_precision_loss)]
+fn minimize_1d(lo: f64, hi: f64, objective: impl Fn(f64) -> Option<f64>) -> Option<f64> {
+    let step = (hi - lo) / (GRID_POINTS - 1) as f64;
+    let (best, _) = (0..GRID_POINTS)
+        .map(|i| lo + step * i as f64)
+        .filter_map(|p| objective(p).map(|value| (p, value)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))?;
+    Some(golden(best - step, best + step, |p| {
+        objective(p).unwrap_or(f64::INFINITY)
+    }))
+}
+
+/// Golden-section search for the minimum of a unimodal function on `[lo, hi]`
+fn golden(mut lo: f64, mut hi: f64, objective: impl Fn(f64) -> f64) -> f64 {
+    let ratio = 0.5 * (5.0_f64.sqrt() - 1.0);
+    let mut left = hi - ratio * (hi - lo);
+    let mut right = lo + ratio * (hi - lo);
+    let (mut f_left, mut f_right) = (objective(left), objective(right));
+    for _ in 0..REFINE_STEPS {
+        if f_left < f_right {
+            hi = right;
+            right = left;
+            f_right = f_left;
+            left = hi - ratio * (hi - lo);
+            f_left = objective(left);
+        } else {
+            lo = left;
+            left = right;
+            f_left = f_right;
+            right = lo + ratio * (hi - lo);
+            f_right = objective(right);
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+/// Ordinary least squares on up to three basis functions
+///
+/// Basis columns that are identically zero are dropped. Returns the
+/// coefficients and residual sum of squares, or `None` if the system is
+/// singular or the result is not finite.
+fn least_squares(
+    xs: &[f64],
+    ys: &[f64],
+    basis: impl Fn(f64) -> [f64; 3],
+) -> Option<([f64; 3], f64)> {
+    let rows: Vec<[f64; 3]> = xs.iter().map(|&x| basis(x)).collect();
+    let used: Vec<usize> = (0..3)
+        .filter(|&k| rows.iter().any(|row| row[k] != 0.0))
+        .collect();
+    let dim = used.len();
+
+    // Normal equations, augmented with the right-hand side
+    let mut system = [[0.0_f64; 4]; 3];
+    for (row, &y) in rows.iter().zip(ys) {
+        for (i, &ki) in used.iter().enumerate() {
+            for (j, &kj) in used.iter().enumerate() {
+                system[i][j] += row[ki] * row[kj];
+            }
+            system[i][3] += row[ki] * y;
+        }
+    }
+
+    // Gaussian elimination with partial pivoting
+    for col in 0..dim {
+        let pivot =
+            (col..dim).max_by(|&a, &b| system[a][col].abs().total_cmp(&system[b][col].abs()))?;
+        if system[pivot][col].abs() < 1e-12 * (1.0 + system[col][col].abs()) {
+            return None;
+        }
+        system.swap(col, pivot);
+        let pivot_row = system[col];
+        for row in &mut system[col + 1..dim] {
+            let factor = row[col] / pivot_row[col];
+            for (value, pivot_value) in row.iter_mut().zip(pivot_row).skip(col) {
+                *value -= factor * pivot_value;
+            }
+        }
+    }
+    let mut solved = [0.0_f64; 3];
+    for i in (0..dim).rev() {
+        let tail: f64 = (i + 1..dim).map(|k| system[i][k] * solved[k]).sum();
+        solved[i] = (system[i][3] - tail) / system[i][i];
+    }
+
+    let mut coef = [0.0_f64; 3];
+    for (i, &k) in used.iter().enumerate() {
+        coef[k] = solved[i];
+    }
+    let rss: f64 = rows
+        .iter()
+        .zip(ys)
+        .map(|(row, &y)| {
+            let fitted: f64 = row.iter().zip(&coef).map(|(b, c)| b * c).sum();
+            (y - fitted).powi(2)
+        })
+        .sum();
+
+    (rss.is_finite() && coef.iter().all(|c| c.is_finite())).then_some((coef, rss))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(f: impl Fn(f64) -> f64, range: core::ops::Range<i32>, scale: f64) -> Vec<(f64, f64)> {
+        range
+            .map(|i| {
+                let x = f64::from(i) * scale;
+                (x, f(x))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_recovers_quadratic_exactly() {
+        let observations = sample(|x| 1.0 - 2.0 * x + 0.5 * x * x, -10..10, 0.5);
+        let fit = fit_family(&observations).unwrap();
+        let Family::Quadratic { a, b, c } = fit.best().family else {
+            panic!("expected quadratic, got {:?}", fit.best().family);
+        };
+        assert!((a - 1.0).abs() < 1e-9);
+        assert!((b + 2.0).abs() < 1e-9);
+        assert!((c - 0.5).abs() < 1e-9);
+        assert_eq!(fit.classify(0.0), Triad::Antifragile);
+    }
+
+    #[test]
+    fn test_recovers_power_law() {
+        let observations = sample(|x| 3.0 + 2.0 * x.sqrt(), 1..30, 1.0);
+        let fit = fit_family(&observations).unwrap();
+        let Family::PowerLaw { exponent, .. } = fit.best().family else {
+            panic!("expected power law, got {:?}", fit.best().family);
+        };
+        assert!((exponent - 0.5).abs() < 1e-3);
+        assert_eq!(fit.classify(10.0), Triad::Fragile);
+    }
+
+    #[test]
+    fn test_logistic_changes_classification_at_midpoint() {
+        let observations = sample(|x| 2.0 + 10.0 * sigmoid(1.5 * (x - 4.0)), 0..40, 0.25);
+        let fit = fit_family(&observations).unwrap();
+        let Family::Logistic { midpoint, .. } = fit.best().family else {
+            panic!("expected logistic, got {:?}", fit.best().family);
+        };
+        assert!((midpoint - 4.0).abs() < 1e-2);
+        assert_eq!(fit.classify(2.0), Triad::Antifragile);
+        assert_eq!(fit.classify(6.0), Triad::Fragile);
+        assert_eq!(fit.fits().len(), 3);
+    }
+
+    #[test]
+    fn test_bic_ranking_and_names() {
+        let observations = sample(|x| (0.3 * x).exp(), 0..20, 1.0);
+        let fit = fit_family_with(&observations, Criterion::Bic).unwrap();
+        assert_eq!(fit.criterion(), Criterion::Bic);
+        assert_eq!(fit.best().family.name(), "exponential");
+        assert!(fit.fits().windows(2).all(|pair| pair[0].bic <= pair[1].bic));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert_eq!(
+            fit_family(&[(0.0, 0.0); 4]),
+            Err(FitError::TooFewObservations { found: 4 })
+        );
+        let mut observations = vec![(1.0, 1.0); 6];
+        observations[3].1 = f64::NAN;
+        assert_eq!(
+            fit_family(&observations),
+            Err(FitError::NonFinite { index: 3 })
+        );
+        assert_eq!(fit_family(&[(1.0, 1.0); 6]), Err(FitError::Degenerate));
+    }
+}
@@ -0,0 +1,285 @@
+//! # Frequency-domain transfer analysis
+//!
+//! The three-point test treats the stressor as a static level. Systems with
+//! memory (controllers, queues, thermal masses, filters) respond differently
+//! to a disturbance that oscillates slowly than to one that oscillates fast:
+//! a slow swing passes straight through, a fast one is averaged away before
+//! it does damage, or before it can be exploited.
+//!
+//! [`FrequencyResponse`](crate::frequency::FrequencyResponse) drives a
+//! fresh [`DynamicSystem`](crate::frequency::DynamicSystem) with
+//! `x(t) = at + amplitude · sin(2π·f·t)`, lets it settle, and measures the
+//! output over whole cycles, rounded to whole steps. At each frequency it
+//! reports:
+//!
+//! - **Gain** and **phase**: amplitude and angle of the output's component
+//!   at the driving frequency, per unit of input amplitude. This is the
+//!   Bode plot a control engineer expects.
+//! - **Asymmetry**: mean output under oscillation minus the output under the
+//!   constant stressor `at`. This is the Jensen gap at that frequency:
+//!   positive if oscillating at `f` helps (antifragile), negative if it
+//!   hurts (fragile).
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::frequency::FrequencyResponse;
+//!
+//! // A first-order lag feeding a quadratic cost: the cost is concave in the
+//! // filtered stressor, so only oscillations that get through the lag hurt
+//! let lagged_cost = || {
+//!     let mut state = 0.0;
+//!     move |x: f64| {
+//!         state += 0.05 * (x - state);
+//!         -state * state
+//!     }
+//! };
+//!
+//! let response = FrequencyResponse::new(lagged_cost);
+//! let slow = response.at_frequency(0.0, 1.0, 0.001);
+//! let fast = response.at_frequency(0.0, 1.0, 0.25);
+//!
+//! assert_eq!(slow.classification, Triad::Fragile);
+//! assert!(fast.asymmetry.abs() < 0.01 * slow.asymmetry.abs());
+//! ```
+
+use core::f64::consts::TAU;
+
+use crate::Triad;
+
+/// A system with internal state, advanced one time step at a time
+///
+/// Any `FnMut(f64) -> f64` closure is a dynamic system, which makes
+/// factories returning closures the simplest way to describe one.
+pub trait DynamicSystem {
+    /// Apply `stressor` for one time step and return the output
+    fn step(&mut self, stressor: f64) -> f64;
+}
+
+impl<F: FnMut(f64) -> f64> DynamicSystem for F {
+    fn step(&mut self, stressor: f64) -> f64 {
+        self(stressor)
+    }
+}
+
+/// Response of a [`DynamicSystem`] to one driving frequency
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrequencyPoint {
+    /// Driving frequency in cycles per time step
+    pub frequency: f64,
+    /// Output amplitude at the driving frequency per unit input amplitude
+    pub gain: f64,
+    /// Phase of the output relative to the input in radians; negative is a lag
+    pub phase: f64,
+    /// Mean output under oscillation minus the output at the constant stressor
+    pub asymmetry: f64,
+    /// Sign of `asymmetry`, robust within the tolerance
+    pub classification: Triad,
+}
+
+/// Sinusoidal sweep protocol for a [`DynamicSystem`]
+#[derive(Debug, Clone, Copy)]
+pub struct FrequencyResponse<F> {
+    factory: F,
+    cycles: u32,
+    settle_cycles: u32,
+    min_steps: u32,
+    tolerance: f64,
+}
+
+impl<F, S> FrequencyResponse<F>
+where
+    F: Fn() -> S,
+    S: DynamicSystem,
+{
+    /// A protocol that builds a fresh system for each run with `factory`
+    ///
+    /// Defaults to 16 measured cycles after 4 settling cycles, at least 256
+    /// measured steps, and a tolerance of `1e-9` on the asymmetry.
+    pub const fn new(factory: F) -> Self {
+        Self {
+            factory,
+            cycles: 16,
+            settle_cycles: 4,
+            min_steps: 256,
+            tolerance: 1e-9,
+        }
+    }
+
+    /// Set the number of cycles measured
+    #[must_use]
+    pub const fn with_cycles(mut self, cycles: u32) -> Self {
+        self.cycles = cycles;
+        self
+    }
+
+    /// Set the number of cycles run before measuring, to let transients decay
+    #[must_use]
+    pub const fn with_settle_cycles(mut self, cycles: u32) -> Self {
+        self.settle_cycles = cycles;
+        self
+    }
+
+    /// Set the fewest steps measured and settled at high frequencies
+    ///
+    /// More cycles are run as needed so that fast oscillations are still
+    /// measured, and transients still settle, over this many steps.
+    #[must_use]
+    pub const fn with_min_steps(mut self, steps: u32) -> Self {
+        self.min_steps = steps;
+        self
+    }
+
+    /// Set the asymmetry at or below which a frequency classifies as robust
+    #[must_use]
+    pub const fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Gain, phase, and asymmetry when oscillating around `at` with
+    /// `amplitude` at `frequency` cycles per step
+    ///
+    /// Frequencies above the Nyquist limit of `0.5` alias onto lower ones.
+    /// Runs two fresh systems: one oscillating and one held at `at`. A zero
+    /// amplitude gives a non-finite gain.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn at_frequency(&self, at: f64, amplitude: f64, frequency: f64) -> FrequencyPoint {
+        let period = 1.0 / frequency.abs();
+        let whole_cycles = |cycles: u32| {
+            let by_cycles = f64::from(cycles) * period;
+            let cycles_needed = (f64::from(self.min_steps) / period).ceil();
+            let by_steps = cycles_needed * period;
+            let steps = by_cycles.max(by_steps).round();
+            if steps.is_finite() { steps as usize } else { 0 }
+        };
+        let settle = if self.settle_cycles == 0 {
+            0
+        } else {
+            whole_cycles(self.settle_cycles)
+        };
+        let measured = whole_cycles(self.cycles).max(1);
+
+        let mut oscillating = (self.factory)();
+        let mut constant = (self.factory)();
+        for t in 0..settle {
+            oscillating.step(at + amplitude * (TAU * frequency * t as f64).sin());
+            constant.step(at);
+        }
+
+        let (mut mean, mut in_phase, mut quadrature, mut baseline) = (0.0, 0.0, 0.0, 0.0);
+        for t in settle..settle + measured {
+            let angle = TAU * frequency * t as f64;
+            let output = oscillating.step(at + amplitude * angle.sin());
+            mean += output;
+            in_phase += output * angle.sin();
+            quadrature += output * angle.cos();
+            baseline = constant.step(at);
+        }
+        let n = measured as f64;
+        let (in_phase, quadrature) = (2.0 * in_phase / n, 2.0 * quadrature / n);
+        let asymmetry = mean / n - baseline;
+
+        let classification = if asymmetry > self.tolerance {
+            Triad::Antifragile
+        } else if asymmetry < -self.tolerance {
+            Triad::Fragile
+        } else {
+            Triad::Robust
+        };
+        FrequencyPoint {
+            frequency,
+            gain: in_phase.hypot(quadrature) / amplitude.abs(),
+            phase: quadrature.atan2(in_phase),
+            asymmetry,
+            classification,
+        }
+    }
+
+    /// [`at_frequency`](Self::at_frequency) at each of `frequencies`
+    #[must_use]
+    pub fn sweep(&self, at: f64, amplitude: f64, frequencies: &[f64]) -> Vec<FrequencyPoint> {
+        frequencies
+            .iter()
+            .map(|&frequency| self.at_frequency(at, amplitude, frequency))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> impl FnMut(f64) -> f64 {
+        |x: f64| x * x
+    }
+
+    fn lagged(rate: f64) -> impl Fn() -> Box<dyn FnMut(f64) -> f64> {
+        move || {
+            let mut state = 0.0;
+            Box::new(move |x: f64| {
+                state += rate * (x - state);
+                state
+            })
+        }
+    }
+
+    #[test]
+    fn test_memoryless_payoff_is_frequency_independent() {
+        // (2 + A·sin)² = 4 + 4A·sin + A²/2 - A²/2·cos(2ωt)
+        let points = FrequencyResponse::new(square).sweep(2.0, 0.5, &[0.01, 0.05, 0.125]);
+        for point in points {
+            assert!((point.gain - 4.0).abs() < 1e-9);
+            assert!(point.phase.abs() < 1e-9);
+            assert!((point.asymmetry - 0.125).abs() < 1e-9);
+            assert_eq!(point.classification, Triad::Antifragile);
+        }
+    }
+
+    #[test]
+    fn test_first_order_lag_bode_plot() {
+        let response = FrequencyResponse::new(lagged(0.1));
+        let points = response.sweep(1.0, 1.0, &[0.001, 0.01, 0.1, 0.4]);
+        assert!((points[0].gain - 1.0).abs() < 0.01);
+        assert!(points.windows(2).all(|w| w[1].gain < w[0].gain));
+        // The lag grows with frequency until the sampling rate is approached
+        assert!(points[..3].windows(2).all(|w| w[1].phase < w[0].phase));
+        assert!(points.iter().all(|p| p.phase < 0.0));
+        // A linear system does not rectify: no asymmetry at any frequency
+        assert!(points.iter().all(|p| p.asymmetry.abs() < 1e-3));
+    }
+
+    #[test]
+    fn test_fast_oscillations_are_filtered_out() {
+        let lag = lagged(0.05);
+        let cost = move || {
+            let mut filter = lag();
+            move |x: f64| -filter(x).powi(2)
+        };
+        let response = FrequencyResponse::new(cost).with_tolerance(1e-3);
+        let slow = response.at_frequency(0.0, 1.0, 0.001);
+        let fast = response.at_frequency(0.0, 1.0, 0.45);
+        assert_eq!(slow.classification, Triad::Fragile);
+        assert!((slow.asymmetry + 0.5).abs() < 0.01);
+        assert_eq!(fast.classification, Triad::Robust);
+    }
+
+    #[test]
+    fn test_builders_and_degenerate_inputs() {
+        let response = FrequencyResponse::new(square)
+            .with_cycles(2)
+            .with_settle_cycles(0)
+            .with_min_steps(1);
+        let point = response.at_frequency(1.0, 0.1, 0.25);
+        assert!((point.gain - 2.0).abs() < 1e-9);
+        assert!(!response.at_frequency(1.0, 0.0, 0.25).gain.is_finite());
+        assert_eq!(
+            response.at_frequency(1.0, 0.1, 0.0).classification,
+            Triad::Robust
+        );
+    }
+}
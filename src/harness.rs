@@ -0,0 +1,347 @@
+//! # Stress-aware property testing
+//!
+//! Property tests check that an invariant holds on generated inputs. They
+//! rarely ask how the invariant holds up as the inputs get nastier: bigger
+//! batches, more injected faults, deeper nesting. A component whose pass
+//! rate falls off a cliff at twice the usual load is fragile, however green
+//! its test suite is at the usual load.
+//!
+//! [`StressHarness`](crate::harness::StressHarness) turns a property into a
+//! system this crate can classify. A generator builds one input per case
+//! from the stress level and a deterministic [`Noise`](crate::learning::Noise) stream; the property
+//! scores it: `true`/`false` for an invariant, or any `f64` such as a
+//! throughput or a negated latency. The payoff at a stress level is the
+//! mean score over all cases. Case `i` receives the same random stream at
+//! every stress level, so [`analyze`](crate::harness::StressHarness::analyze)
+//! measures the curvature case by case and only calls it significant when
+//! it clears the standard error.
+//!
+//! ```rust
+//! use antifragile::harness::StressHarness;
+//! use antifragile::learning::Noise;
+//!
+//! // A bounded queue that must never drop work
+//! fn accepts_all(arrivals: &[u32]) -> bool {
+//!     let mut depth = 0_u32;
+//!     for &arriving in arrivals {
+//!         depth = (depth + arriving).saturating_sub(3);
+//!         if depth > 20 {
+//!             return false;
+//!         }
+//!     }
+//!     true
+//! }
+//!
+//! // Stress is the mean number of arrivals per tick
+//! let generate = |stress: f64, noise: &mut Noise| -> Vec<u32> {
+//!     (0..50).map(|_| (2.0 * stress * noise.uniform()) as u32).collect()
+//! };
+//! let harness = StressHarness::new(generate, |arrivals: &Vec<u32>| accepts_all(arrivals));
+//!
+//! let levels = harness.escalate(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+//! assert!((levels[0].mean - 1.0).abs() < f64::EPSILON);
+//! assert_eq!(harness.breaking_point(&[1.0, 2.0, 3.0, 4.0, 5.0], 0.99), Some(4.0));
+//! ```
+//!
+//! In a CI test, [`assert_not_fragile`](crate::harness::StressHarness::assert_not_fragile)
+//! fails the build when a change makes the component significantly fragile
+//! around its operating point.
+
+use crate::learning::{Noise, mean_and_error, normal_confidence};
+use crate::{Antifragile, Triad, TriadWithConfidence};
+
+/// A property outcome that can be averaged into a payoff
+pub trait Score {
+    /// The outcome as a number; higher is better
+    fn score(&self) -> f64;
+}
+
+impl Score for bool {
+    /// `1.0` if the property held, else `0.0`
+    fn score(&self) -> f64 {
+        if *self { 1.0 } else { 0.0 }
+    }
+}
+
+impl Score for f64 {
+    fn score(&self) -> f64 {
+        *self
+    }
+}
+
+impl<T, E> Score for Result<T, E> {
+    /// `1.0` for `Ok`, `0.0` for `Err`
+    fn score(&self) -> f64 {
+        if self.is_ok() { 1.0 } else { 0.0 }
+    }
+}
+
+/// Scores of one stress level across all cases
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelResult {
+    /// Stress level
+    pub stress: f64,
+    /// Mean score; the pass rate for boolean properties
+    pub mean: f64,
+    /// Standard error of `mean`
+    pub std_error: f64,
+    /// Lowest score of any case
+    pub worst: f64,
+}
+
+/// Classification of a property from paired cases
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PropertyAnalysis {
+    /// Antifragile or fragile if the curvature is significant, else robust
+    pub classification: Triad,
+    /// Mean over cases of `s(x+Δ) + s(x-Δ) - 2·s(x)`
+    pub curvature: f64,
+    /// Standard error of `curvature`
+    pub std_error: f64,
+}
+
+impl PropertyAnalysis {
+    /// The classification with the normal-approximation confidence that the
+    /// curvature is nonzero and the mean curvature as its margin
+    #[must_use]
+    pub fn triad_with_confidence(&self) -> TriadWithConfidence {
+        TriadWithConfidence {
+            triad: self.classification,
+            confidence: normal_confidence(self.curvature, self.std_error),
+            margin: self.curvature,
+        }
+    }
+}
+
+/// Runs a property under escalating generated stress
+#[derive(Debug, Clone, Copy)]
+pub struct StressHarness<G, P> {
+    generate: G,
+    property: P,
+    cases: u32,
+    seed: u64,
+    significance: f64,
+}
+
+impl<G, P, I, S> StressHarness<G, P>
+where
+    G: Fn(f64, &mut Noise) -> I,
+    P: Fn(&I) -> S,
+    S: Score,
+{
+    /// A harness generating inputs with `generate` and scoring them with
+    /// `property`
+    ///
+    /// Defaults to 100 cases per stress level and a significance threshold
+    /// of two standard errors.
+    pub const fn new(generate: G, property: P) -> Self {
+        Self {
+            generate,
+            property,
+            cases: 100,
+            seed: 0x5EED,
+            significance: 2.0,
+        }
+    }
+
+    /// Set the number of generated cases per stress level
+    #[must_use]
+    pub const fn with_cases(mut self, cases: u32) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Set the base seed; equal seeds give identical results
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set how many standard errors the curvature must clear to count
+    #[must_use]
+    pub const fn with_significance(mut self, standard_errors: f64) -> Self {
+        self.significance = standard_errors;
+        self
+    }
+
+    /// Score of every case at `stress`
+    #[must_use]
+    pub fn scores(&self, stress: f64) -> Vec<f64> {
+        (0..self.cases)
+            .map(|case| {
+                let mut noise = Noise::new(
+                    self.seed
+                        .wrapping_add(u64::from(case).wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+                );
+                let input = (self.generate)(stress, &mut noise);
+                (self.property)(&input).score()
+            })
+            .collect()
+    }
+
+    /// Mean, standard error, and worst score at `stress`
+    #[must_use]
+    pub fn run(&self, stress: f64) -> LevelResult {
+        let scores = self.scores(stress);
+        let (mean, std_error) = mean_and_error(&scores);
+        LevelResult {
+            stress,
+            mean,
+            std_error,
+            worst: scores.iter().copied().fold(f64::NAN, f64::min),
+        }
+    }
+
+    /// [`run`](Self::run) at each of `levels`, in order
+    #[must_use]
+    pub fn escalate(&self, levels: &[f64]) -> Vec<LevelResult> {
+        levels.iter().map(|&stress| self.run(stress)).collect()
+    }
+
+    /// The first of `levels` whose mean score falls below `threshold`
+    ///
+    /// Stops at the first failing level, so escalating levels are only run
+    /// as far as needed.
+    #[must_use]
+    pub fn breaking_point(&self, levels: &[f64], threshold: f64) -> Option<f64> {
+        levels
+            .iter()
+            .copied()
+            .find(|&stress| self.run(stress).mean < threshold)
+    }
+
+    /// Classify the property at `at` from paired cases at `at` and `at ± delta`
+    #[must_use]
+    pub fn analyze(&self, at: f64, delta: f64) -> PropertyAnalysis {
+        let center = self.scores(at);
+        let up = self.scores(at + delta);
+        let down = self.scores(at - delta);
+        let differences: Vec<f64> = center
+            .iter()
+            .zip(&up)
+            .zip(&down)
+            .map(|((c, u), d)| u + d - 2.0 * c)
+            .collect();
+        let (curvature, std_error) = mean_and_error(&differences);
+
+        let threshold = self.significance * std_error;
+        let classification = if curvature > threshold {
+            Triad::Antifragile
+        } else if curvature < -threshold {
+            Triad::Fragile
+        } else {
+            Triad::Robust
+        };
+        PropertyAnalysis {
+            classification,
+            curvature,
+            std_error,
+        }
+    }
+
+    /// Fail a test if the property is significantly fragile at `at`
+    ///
+    /// # Panics
+    ///
+    /// Panics with the curvature and its standard error if
+    /// [`analyze`](Self::analyze) classifies the property as fragile.
+    pub fn assert_not_fragile(&self, at: f64, delta: f64) {
+        let analysis = self.analyze(at, delta);
+        assert!(
+            analysis.classification != Triad::Fragile,
+            "property is fragile at stress {at} ± {delta}: curvature {} (standard error {})",
+            analysis.curvature,
+            analysis.std_error
+        );
+    }
+}
+
+impl<G, P, I, S> Antifragile for StressHarness<G, P>
+where
+    G: Fn(f64, &mut Noise) -> I,
+    P: Fn(&I) -> S,
+    S: Score,
+{
+    /// Stress level handed to the generator
+    type Stressor = f64;
+    /// Mean score across cases
+    type Payoff = f64;
+
+    fn payoff(&self, stress: f64) -> f64 {
+        self.run(stress).mean
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sorts by insertion; "passes" while the work stays within budget
+    fn within_budget(values: &Vec<f64>) -> bool {
+        let mut sorted: Vec<f64> = Vec::with_capacity(values.len());
+        let mut work = 0_usize;
+        for &v in values {
+            let position = sorted.partition_point(|&x| x <= v);
+            work += sorted.len() - position;
+            sorted.insert(position, v);
+        }
+        work <= 2000
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn inversions(values: &[f64]) -> f64 {
+        let count: usize = (0..values.len())
+            .map(|i| values[..i].iter().filter(|&&b| b > values[i]).count())
+            .sum();
+        count as f64
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn shuffled(stress: f64, noise: &mut Noise) -> Vec<f64> {
+        (0..stress.max(0.0) as usize)
+            .map(|_| noise.uniform())
+            .collect()
+    }
+
+    #[test]
+    fn test_pass_rate_falls_off_a_cliff() {
+        let harness = StressHarness::new(shuffled, within_budget);
+        let levels = harness.escalate(&[40.0, 60.0, 80.0, 100.0]);
+        assert!((levels[0].mean - 1.0).abs() < f64::EPSILON);
+        assert!(levels.windows(2).all(|w| w[1].mean <= w[0].mean));
+        assert!(levels[3].worst.abs() < f64::EPSILON);
+        assert_eq!(
+            harness.breaking_point(&[40.0, 60.0, 80.0, 100.0], 0.5),
+            Some(100.0)
+        );
+    }
+
+    #[test]
+    fn test_analysis_is_significant_only_when_clear() {
+        let harness = StressHarness::new(shuffled, within_budget);
+        let cliff = harness.analyze(80.0, 20.0);
+        assert_eq!(cliff.classification, Triad::Fragile);
+        assert!(cliff.triad_with_confidence() < harness.analyze(20.0, 5.0).triad_with_confidence());
+        // Far below the budget nothing ever fails
+        let easy = harness.analyze(20.0, 5.0);
+        assert_eq!(easy.classification, Triad::Robust);
+        assert!(easy.curvature.abs() < f64::EPSILON);
+        harness.assert_not_fragile(20.0, 5.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "property is fragile")]
+    fn test_assert_not_fragile_panics() {
+        StressHarness::new(shuffled, within_budget).assert_not_fragile(80.0, 20.0);
+    }
+
+    #[test]
+    fn test_performance_scores() {
+        // Negated work of the sort: quadratic in the input size
+        let harness =
+            StressHarness::new(shuffled, |values: &Vec<f64>| -inversions(values)).with_cases(20);
+        assert_eq!(harness.analyze(100.0, 50.0).classification, Triad::Fragile);
+        assert!(Ok::<(), ()>(()).score() > Err::<(), ()>(()).score());
+    }
+}
@@ -0,0 +1,251 @@
+//! # The Taleb–Douady fragility heuristic
+//!
+//! The detection heuristic published by Taleb, Canetti, Kinda, Loukoianova,
+//! and Schmieder for the IMF stress-tests a model at a base point `α` with
+//! shocks of increasing size and measures
+//!
+//! ```text
+//! H(Δ) = [f(α - Δ) + f(α + Δ)] / 2 - f(α)
+//! ```
+//!
+//! A negative `H` means the average shocked outcome is worse than the
+//! unshocked one: the payoff is concave and the system is fragile. Repeating
+//! the test at `Δ, 2Δ, 3Δ, …` shows whether harm *accelerates*: a fragile
+//! system loses more for each additional unit of shock than it did for the
+//! first. Unlike the three-point [`classify`](crate::TriadAnalysis::classify),
+//! the heuristic needs nothing but a model that can be re-run under shocks,
+//! which is why it is the method risk practitioners expect.
+//!
+//! [`fragility_heuristic`](crate::heuristic::fragility_heuristic) runs the
+//! ladder of shocks and returns a
+//! [`HeuristicReport`](crate::heuristic::HeuristicReport).
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad};
+//! use antifragile::heuristic::{HeuristicConfig, fragility_heuristic};
+//!
+//! /// Debt service: defaults cascade once rates rise past a threshold
+//! struct Sovereign;
+//!
+//! impl Antifragile for Sovereign {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, rate: f64) -> f64 {
+//!         100.0 - 2.0 * rate - (rate - 5.0).max(0.0).powi(3)
+//!     }
+//! }
+//!
+//! let report = fragility_heuristic(&Sovereign, 5.0, &HeuristicConfig::default());
+//! assert_eq!(report.classification, Triad::Fragile);
+//! assert!(report.accelerating);
+//! ```
+
+use crate::{Antifragile, Triad};
+
+/// Configuration for [`fragility_heuristic`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeuristicConfig {
+    /// Size of the first shock; the `k`-th shock is `k` times larger
+    pub base_shock: f64,
+    /// Number of shocks in the ladder
+    pub steps: u32,
+    /// Whether `base_shock` is a fraction of the base point rather than an
+    /// absolute size; falls back to absolute at a base point of zero
+    pub relative: bool,
+    /// `|H|` at or below which a shock counts as harmless
+    pub tolerance: f64,
+}
+
+impl Default for HeuristicConfig {
+    /// Four shocks of 5%, 10%, 15%, and 20% of the base point, tolerance `1e-12`
+    fn default() -> Self {
+        Self {
+            base_shock: 0.05,
+            steps: 4,
+            relative: true,
+            tolerance: 1e-12,
+        }
+    }
+}
+
+/// The heuristic at one shock size
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HarmPoint {
+    /// Shock size `Δ`
+    pub shock: f64,
+    /// Payoff at `α - Δ`
+    pub down: f64,
+    /// Payoff at `α + Δ`
+    pub up: f64,
+    /// `H(Δ)`: mean shocked payoff minus the base payoff
+    pub h: f64,
+    /// Base payoff minus the worse of the two shocked payoffs
+    pub harm: f64,
+    /// Extra harm per unit of shock since the previous, smaller shock
+    pub marginal_harm: f64,
+}
+
+/// Result of [`fragility_heuristic`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeuristicReport {
+    /// Base point `α`
+    pub at: f64,
+    /// Payoff at the base point
+    pub base: f64,
+    /// One entry per shock, smallest first
+    pub points: Vec<HarmPoint>,
+    /// Antifragile or fragile when `H` has that sign beyond the tolerance at
+    /// every shock, else robust
+    pub classification: Triad,
+    /// Whether the marginal harm is positive and grows with every shock
+    pub accelerating: bool,
+}
+
+impl HeuristicReport {
+    /// `H` divided by `Δ²` at each shock: half the second derivative for a
+    /// smooth payoff, so values that grow with the shock reveal tail
+    /// nonlinearity beyond local curvature
+    #[must_use]
+    pub fn normalized(&self) -> Vec<f64> {
+        self.points
+            .iter()
+            .map(|p| p.h / (p.shock * p.shock))
+            .collect()
+    }
+}
+
+/// Run the fragility heuristic on `system` at `at`
+///
+/// Shocks are applied in both directions; the harm of a shock is measured
+/// against the worse direction, so the heuristic works whichever way the
+/// stressor is adverse.
+#[must_use]
+pub fn fragility_heuristic<T>(system: &T, at: f64, config: &HeuristicConfig) -> HeuristicReport
+where
+    T: Antifragile<Stressor = f64, Payoff = f64> + ?Sized,
+{
+    let unit = if config.relative && at != 0.0 {
+        config.base_shock * at.abs()
+    } else {
+        config.base_shock
+    };
+    let base = system.payoff(at);
+
+    let mut points: Vec<HarmPoint> = Vec::with_capacity(config.steps as usize);
+    let (mut previous_shock, mut previous_harm) = (0.0, 0.0);
+    for k in 1..=config.steps {
+        let shock = unit * f64::from(k);
+        let down = system.payoff(at - shock);
+        let up = system.payoff(at + shock);
+        let harm = base - down.min(up);
+        points.push(HarmPoint {
+            shock,
+            down,
+            up,
+            h: 0.5 * (down + up) - base,
+            harm,
+            marginal_harm: (harm - previous_harm) / (shock - previous_shock),
+        });
+        (previous_shock, previous_harm) = (shock, harm);
+    }
+
+    let classification = if !points.is_empty() && points.iter().all(|p| p.h > config.tolerance) {
+        Triad::Antifragile
+    } else if !points.is_empty() && points.iter().all(|p| p.h < -config.tolerance) {
+        Triad::Fragile
+    } else {
+        Triad::Robust
+    };
+    let accelerating = points.first().is_some_and(|p| p.marginal_harm > 0.0)
+        && points
+            .windows(2)
+            .all(|w| w[1].marginal_harm > w[0].marginal_harm);
+
+    HeuristicReport {
+        at,
+        base,
+        points,
+        classification,
+        accelerating,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Payoff<F>(F);
+
+    impl<F: Fn(f64) -> f64> Antifragile for Payoff<F> {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&self, x: f64) -> f64 {
+            (self.0)(x)
+        }
+    }
+
+    #[test]
+    fn test_concave_payoff_is_fragile_and_accelerating() {
+        let report =
+            fragility_heuristic(&Payoff(|x: f64| -x * x), 10.0, &HeuristicConfig::default());
+        assert_eq!(report.classification, Triad::Fragile);
+        assert!(report.accelerating);
+        let shocks: Vec<f64> = report.points.iter().map(|p| p.shock).collect();
+        assert_eq!(shocks, [0.5, 1.0, 1.5, 2.0]);
+        // H = -Δ² exactly for a quadratic
+        assert!(report.normalized().iter().all(|n| (n + 1.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_convex_and_linear_payoffs() {
+        let config = HeuristicConfig {
+            relative: false,
+            ..HeuristicConfig::default()
+        };
+        let convex = fragility_heuristic(&Payoff(f64::exp), 0.0, &config);
+        assert_eq!(convex.classification, Triad::Antifragile);
+        assert!(convex.normalized().windows(2).all(|w| w[1] > w[0]));
+
+        let linear = fragility_heuristic(&Payoff(|x: f64| 3.0 * x), 1.0, &config);
+        assert_eq!(linear.classification, Triad::Robust);
+        // Harm in the adverse direction grows only linearly
+        assert!(!linear.accelerating);
+        assert!(
+            linear
+                .points
+                .iter()
+                .all(|p| (p.marginal_harm - 3.0).abs() < 1e-9)
+        );
+    }
+
+    #[test]
+    fn test_harm_that_starts_beyond_the_first_shock() {
+        // Flat until a cliff 1.2 units away: only larger shocks see it
+        let cliff = Payoff(|x: f64| -(x - 1.2).max(0.0).powi(2));
+        let config = HeuristicConfig {
+            base_shock: 0.5,
+            relative: false,
+            ..HeuristicConfig::default()
+        };
+        let report = fragility_heuristic(&cliff, 0.0, &config);
+        assert!(report.points[0].h.abs() < f64::EPSILON);
+        assert!(report.points[3].h < 0.0);
+        // Not fragile at every shock, so the heuristic does not call it
+        assert_eq!(report.classification, Triad::Robust);
+        assert!(!report.accelerating);
+    }
+
+    #[test]
+    fn test_empty_ladder() {
+        let config = HeuristicConfig {
+            steps: 0,
+            ..HeuristicConfig::default()
+        };
+        let report = fragility_heuristic(&Payoff(|x: f64| -x * x), 1.0, &config);
+        assert!(report.points.is_empty());
+        assert_eq!(report.classification, Triad::Robust);
+        assert!(!report.accelerating);
+    }
+}
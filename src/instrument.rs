@@ -0,0 +1,187 @@
+//! # Instrumented payoffs
+//!
+//! When the payoff calls an external service, every evaluation has a cost.
+//! [`Instrumented`](crate::instrument::Instrumented) wraps a system and
+//! records how many times its payoff ran and the smallest and largest
+//! stressors it was probed with, so an analysis can be budgeted, and the
+//! probes it makes checked against the domain the service accepts. The
+//! wrapper implements [`Antifragile`] itself, so it drops in wherever the
+//! system did.
+//!
+//! ```rust
+//! use antifragile::{Triad, TriadAnalysis};
+//! use antifragile::instrument::Instrumented;
+//!
+//! let system = Instrumented::new(antifragile::antifragile!(|x: f64| x * x));
+//!
+//! assert_eq!(system.classify(10.0, 1.0), Triad::Antifragile);
+//!
+//! let stats = system.stats();
+//! assert_eq!(stats.evaluations, 3);
+//! assert_eq!(stats.min_stressor, Some(9.0));
+//! assert_eq!(stats.max_stressor, Some(11.0));
+//! ```
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Antifragile;
+
+/// Payoff evaluations recorded by an [`Instrumented`] system
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct EvaluationStats<S> {
+    /// Number of payoff evaluations
+    pub evaluations: usize,
+    /// Smallest stressor probed, `None` before the first ordered probe
+    pub min_stressor: Option<S>,
+    /// Largest stressor probed, `None` before the first ordered probe
+    pub max_stressor: Option<S>,
+}
+
+impl<S> Default for EvaluationStats<S> {
+    /// No evaluations and no stressors
+    fn default() -> Self {
+        Self {
+            evaluations: 0,
+            min_stressor: None,
+            max_stressor: None,
+        }
+    }
+}
+
+impl<S: Copy + PartialOrd> EvaluationStats<S> {
+    /// Record one evaluation at `stressor`
+    ///
+    /// Stressors that do not compare with themselves, such as `NaN`, are
+    /// counted but left out of the range.
+    pub fn record(&mut self, stressor: S) {
+        self.evaluations += 1;
+        if stressor.partial_cmp(&stressor).is_none() {
+            return;
+        }
+        if self.min_stressor.is_none_or(|min| stressor < min) {
+            self.min_stressor = Some(stressor);
+        }
+        if self.max_stressor.is_none_or(|max| stressor > max) {
+            self.max_stressor = Some(stressor);
+        }
+    }
+}
+
+/// A system that records its payoff evaluations
+///
+/// The counters sit behind a mutex, so an instrumented system can be shared
+/// across threads, for example by
+/// [`par_classify_batch`](crate::aggregate::BatchAnalysis::par_classify_batch).
+/// The lock is not held while the payoff runs.
+pub struct Instrumented<T: Antifragile> {
+    system: T,
+    stats: Mutex<EvaluationStats<T::Stressor>>,
+}
+
+impl<T: Antifragile> Instrumented<T>
+where
+    T::Stressor: PartialOrd,
+{
+    /// Wrap `system` with empty counters
+    #[must_use]
+    pub fn new(system: T) -> Self {
+        Self {
+            system,
+            stats: Mutex::new(EvaluationStats::default()),
+        }
+    }
+
+    /// Evaluations recorded since creation or the last [`reset`](Self::reset)
+    #[must_use]
+    pub fn stats(&self) -> EvaluationStats<T::Stressor> {
+        *self.lock()
+    }
+
+    /// Number of payoff evaluations recorded
+    #[must_use]
+    pub fn evaluations(&self) -> usize {
+        self.lock().evaluations
+    }
+
+    /// Clear the counters, returning what they held
+    pub fn reset(&self) -> EvaluationStats<T::Stressor> {
+        core::mem::take(&mut *self.lock())
+    }
+
+    /// Access the wrapped system
+    #[must_use]
+    pub const fn inner(&self) -> &T {
+        &self.system
+    }
+
+    /// Consume the wrapper and return the system
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.system
+    }
+
+    /// The counters, recovered if a panicking payoff poisoned the lock
+    fn lock(&self) -> MutexGuard<'_, EvaluationStats<T::Stressor>> {
+        self.stats.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Antifragile> Antifragile for Instrumented<T>
+where
+    T::Stressor: PartialOrd,
+{
+    type Stressor = T::Stressor;
+    type Payoff = T::Payoff;
+
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        let payoff = self.system.payoff(stressor);
+        self.lock().record(stressor);
+        payoff
+    }
+
+    fn twin(r: Self::Payoff) -> Self::Payoff {
+        T::twin(r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Triad, TriadAnalysis, Verified};
+
+    #[test]
+    fn test_documented_evaluation_counts() {
+        let system = Instrumented::new(crate::antifragile!(|x: i64| x * x));
+        let _ = system.classify(5, 2);
+        assert_eq!(system.evaluations(), 3);
+        let _ = system.classify_with_tolerance(5, 2, 1);
+        assert_eq!(system.evaluations(), 6);
+
+        let stats = system.reset();
+        assert_eq!((stats.min_stressor, stats.max_stressor), (Some(3), Some(7)));
+        assert_eq!(system.stats(), EvaluationStats::default());
+
+        let verified = Verified::check(system, 5, 2);
+        assert!(verified.is_antifragile());
+        assert_eq!(verified.inner().evaluations(), 3);
+        assert_eq!(verified.classification(), Triad::Antifragile);
+    }
+
+    #[test]
+    fn test_unordered_stressors_are_counted_only() {
+        let system = Instrumented::new(crate::antifragile!(|x: f64| x));
+        let _ = system.payoff(f64::NAN);
+        let _ = system.payoff(2.0);
+        let _ = system.payoff(-1.0);
+
+        let stats = system.stats();
+        assert_eq!(stats.evaluations, 3);
+        assert_eq!(stats.min_stressor, Some(-1.0));
+        assert_eq!(stats.max_stressor, Some(2.0));
+    }
+}
@@ -0,0 +1,306 @@
+//! # Integer and fixed-point payoffs
+//!
+//! Embedded systems measure payoffs in integer microseconds, counts, or
+//! fixed-point quantities. Integer arithmetic has no rounding, so the
+//! convexity test is exact: [`Robust`](crate::Triad::Robust) means the three
+//! payoffs lie on a straight line, not that they were merely close.
+//!
+//! What integers do have is overflow. [`classify`](crate::TriadAnalysis::classify)
+//! computes `x ± Δ`, `f(x+Δ) + f(x-Δ)`, and `2·f(x)` with the ordinary
+//! operators, which panic in debug builds and wrap in release builds. A
+//! wrapped sum changes sign, so it silently flips the classification.
+//! [`IntegerAnalysis`](crate::integer::IntegerAnalysis) offers two
+//! alternatives:
+//!
+//! | Method | On overflow |
+//! |--------|-------------|
+//! | [`classify_exact`](crate::integer::IntegerAnalysis::classify_exact) | Returns an error naming what overflowed |
+//! | [`classify_saturating`](crate::integer::IntegerAnalysis::classify_saturating) | Clamps to the type's range and classifies anyway |
+//!
+//! Overflow is avoided entirely when every payoff stays within half the
+//! type's range: then neither the sum nor the twin can leave it. Both
+//! methods double the payoff with a checked or saturating `r + r` rather
+//! than calling [`Antifragile::twin`](crate::Antifragile::twin).
+//!
+//! Every primitive integer implements
+//! [`CheckedArithmetic`](crate::integer::CheckedArithmetic); the `fixed`
+//! feature adds the fixed-point types of the `fixed` crate.
+//!
+//! ```rust
+//! use antifragile::{Antifragile, ClassifyError, Triad};
+//! use antifragile::integer::IntegerAnalysis;
+//!
+//! /// p99 latency in microseconds against queue depth
+//! struct Latency;
+//!
+//! impl Antifragile for Latency {
+//!     type Stressor = u32;
+//!     type Payoff = i32;
+//!
+//!     fn payoff(&self, depth: u32) -> i32 {
+//!         let depth = i32::try_from(depth).unwrap_or(i32::MAX);
+//!         50_i32.saturating_add(depth.saturating_mul(depth))
+//!     }
+//! }
+//!
+//! // Latency is a cost: convex means it grows ever faster with depth
+//! assert_eq!(Latency.classify_exact(100, 10), Ok(Triad::Antifragile));
+//!
+//! // Near the top of the range the sum no longer fits in an i32; saturated,
+//! // the sum and the twin both clamp to i32::MAX and the curvature is lost
+//! assert_eq!(Latency.classify_exact(40_000, 10), Err(ClassifyError::Overflow));
+//! assert_eq!(Latency.classify_saturating(40_000, 10), Triad::Robust);
+//!
+//! // A queue depth cannot go below zero
+//! assert!(matches!(
+//!     Latency.classify_exact(0, 1),
+//!     Err(ClassifyError::OutOfRange { .. })
+//! ));
+//! ```
+
+use crate::{Antifragile, ClassifyError, Probe, Triad};
+
+/// Checked and saturating addition and subtraction
+///
+/// Implemented for every primitive integer and, with the `fixed` feature,
+/// for the fixed-point types of the `fixed` crate.
+pub trait CheckedArithmetic: Copy + PartialOrd {
+    /// `self + rhs`, or `None` on overflow
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// `self - rhs`, or `None` on overflow
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    /// `self + rhs`, clamped to the type's range
+    #[must_use]
+    fn saturating_add(self, rhs: Self) -> Self;
+    /// `self - rhs`, clamped to the type's range
+    #[must_use]
+    fn saturating_sub(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_checked_arithmetic {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl CheckedArithmetic for $t {
+                #[inline]
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                #[inline]
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                #[inline]
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$t>::saturating_add(self, rhs)
+                }
+
+                #[inline]
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    <$t>::saturating_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_arithmetic!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+#[cfg(feature = "fixed")]
+macro_rules! impl_checked_arithmetic_fixed {
+    ($($fixed:ident: $bound:ident),* $(,)?) => {
+        $(
+            impl<Frac: fixed::types::extra::$bound> CheckedArithmetic for fixed::$fixed<Frac> {
+                #[inline]
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    Self::checked_add(self, rhs)
+                }
+
+                #[inline]
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    Self::checked_sub(self, rhs)
+                }
+
+                #[inline]
+                fn saturating_add(self, rhs: Self) -> Self {
+                    Self::saturating_add(self, rhs)
+                }
+
+                #[inline]
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    Self::saturating_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "fixed")]
+impl_checked_arithmetic_fixed!(
+    FixedI8: LeEqU8,
+    FixedI16: LeEqU16,
+    FixedI32: LeEqU32,
+    FixedI64: LeEqU64,
+    FixedI128: LeEqU128,
+    FixedU8: LeEqU8,
+    FixedU16: LeEqU16,
+    FixedU32: LeEqU32,
+    FixedU64: LeEqU64,
+    FixedU128: LeEqU128,
+);
+
+/// Extension trait classifying integer and fixed-point systems without
+/// unchecked overflow
+pub trait IntegerAnalysis: Antifragile {
+    /// Classify with checked arithmetic throughout
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClassifyError::OutOfRange`] naming the evaluation whose
+    /// stressor `x ± Δ` overflows, or [`ClassifyError::Overflow`] if
+    /// `f(x+Δ) + f(x-Δ)` or `2·f(x)` does.
+    fn classify_exact(
+        &self,
+        at: Self::Stressor,
+        delta: Self::Stressor,
+    ) -> Result<Triad, ClassifyError>
+    where
+        Self::Stressor: CheckedArithmetic,
+        Self::Payoff: CheckedArithmetic,
+    {
+        let below = at.checked_sub(delta).ok_or(ClassifyError::OutOfRange {
+            probe: Probe::Below,
+        })?;
+        let above = at.checked_add(delta).ok_or(ClassifyError::OutOfRange {
+            probe: Probe::Above,
+        })?;
+        let f_x = self.payoff(at);
+        let sum = self
+            .payoff(above)
+            .checked_add(self.payoff(below))
+            .ok_or(ClassifyError::Overflow)?;
+        let twin = f_x.checked_add(f_x).ok_or(ClassifyError::Overflow)?;
+        Ok(compare(sum, twin))
+    }
+
+    /// Classify with saturating arithmetic throughout
+    ///
+    /// Never fails, but clamping loses information: stressors clamp at the
+    /// ends of their range, making the perturbation lopsided, and when the
+    /// sum and the twin both saturate at the same bound the result is
+    /// [`Robust`](Triad::Robust) whatever the curvature. Prefer
+    /// [`classify_exact`](Self::classify_exact) where an error can be
+    /// handled.
+    fn classify_saturating(&self, at: Self::Stressor, delta: Self::Stressor) -> Triad
+    where
+        Self::Stressor: CheckedArithmetic,
+        Self::Payoff: CheckedArithmetic,
+    {
+        let f_x = self.payoff(at);
+        let sum = self
+            .payoff(at.saturating_add(delta))
+            .saturating_add(self.payoff(at.saturating_sub(delta)));
+        compare(sum, f_x.saturating_add(f_x))
+    }
+}
+
+impl<T: Antifragile + ?Sized> IntegerAnalysis for T {}
+
+/// The classification for `sum` against `twin`
+fn compare<P: CheckedArithmetic>(sum: P, twin: P) -> Triad {
+    if sum > twin {
+        Triad::Antifragile
+    } else if sum < twin {
+        Triad::Fragile
+    } else {
+        Triad::Robust
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TriadAnalysis;
+
+    struct Cube;
+
+    impl Antifragile for Cube {
+        type Stressor = i16;
+        type Payoff = i16;
+        fn payoff(&self, x: i16) -> i16 {
+            x.saturating_mul(x).saturating_mul(x)
+        }
+    }
+
+    #[test]
+    fn test_exact_matches_classify_within_range() {
+        for at in -20..=20 {
+            assert_eq!(Cube.classify_exact(at, 3), Ok(Cube.classify(at, 3)));
+            assert_eq!(Cube.classify_saturating(at, 3), Cube.classify(at, 3));
+        }
+    }
+
+    #[test]
+    fn test_overflow_is_reported_or_clamped() {
+        // 30³ = 27 000 fits in an i16, but 2·27 000 does not
+        assert_eq!(Cube.classify_exact(30, 1), Err(ClassifyError::Overflow));
+        assert_eq!(Cube.classify_saturating(30, 1), Triad::Robust);
+        assert_eq!(
+            Cube.classify_exact(i16::MAX, 1),
+            Err(ClassifyError::OutOfRange {
+                probe: Probe::Above
+            })
+        );
+        assert_eq!(
+            Cube.classify_exact(i16::MIN, 1),
+            Err(ClassifyError::OutOfRange {
+                probe: Probe::Below
+            })
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_out_of_range_names_the_probe() {
+        assert_eq!(
+            ClassifyError::OutOfRange {
+                probe: Probe::Below
+            }
+            .to_string(),
+            "stressor for f(x - delta) is out of range"
+        );
+    }
+
+    #[cfg(feature = "fixed")]
+    #[test]
+    fn test_fixed_point_payoffs() {
+        use fixed::types::I16F16;
+
+        struct Square;
+
+        impl Antifragile for Square {
+            type Stressor = I16F16;
+            type Payoff = I16F16;
+            fn payoff(&self, x: I16F16) -> I16F16 {
+                x.saturating_mul(x)
+            }
+        }
+
+        let half = I16F16::from_num(0.5);
+        assert_eq!(
+            Square.classify_exact(I16F16::ONE, half),
+            Ok(Triad::Antifragile)
+        );
+        assert_eq!(
+            Square.classify_exact(I16F16::from_num(150), half),
+            Err(ClassifyError::Overflow)
+        );
+        assert_eq!(
+            Square.classify_saturating(I16F16::from_num(150), half),
+            Triad::Robust
+        );
+    }
+}
@@ -0,0 +1,357 @@
+//! # Delta ladders: classification across shock scales
+//!
+//! The most common real-world finding is not a single verdict but a change
+//! of verdict with scale: *antifragile to small shocks, fragile to large
+//! ones*. A cache absorbs a burst but falls over under a stampede; a
+//! portfolio gains from wiggles and is ruined by a crash.
+//!
+//! [`delta_ladder`](crate::ladder::delta_ladder) spends an evaluation budget
+//! on a geometric ladder of deltas, runs the convexity test at each rung,
+//! and reports where the verdict changes. The base payoff is evaluated once
+//! and each rung costs two more evaluations, so a budget of `2n + 1` buys
+//! `n` rungs.
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad};
+//! use antifragile::ladder::{LadderConfig, delta_ladder};
+//!
+//! /// Gains from small moves, but large moves hit a hard limit
+//! struct Market;
+//!
+//! impl Antifragile for Market {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, x: f64) -> f64 {
+//!         x * x - 0.01 * x.powi(4)
+//!     }
+//! }
+//!
+//! let config = LadderConfig {
+//!     smallest: 0.1,
+//!     largest: 100.0,
+//!     relative: false,
+//!     ..LadderConfig::default()
+//! };
+//! let ladder = delta_ladder(&Market, 0.0, 17, &config);
+//!
+//! assert_eq!(ladder.rungs.len(), 8);
+//! assert_eq!(ladder.small_shocks(), Some(Triad::Antifragile));
+//! assert_eq!(ladder.large_shocks(), Some(Triad::Fragile));
+//!
+//! // H(Δ) = Δ² - 0.01·Δ⁴ changes sign at Δ = 10
+//! let change = ladder.transitions()[0];
+//! assert!(change.lower < 10.0 && 10.0 < change.upper);
+//! let crossover = ladder.crossover().unwrap();
+//! assert!(change.lower < crossover && crossover < change.upper);
+//! ```
+
+use crate::csv::CsvWriter;
+use crate::{Antifragile, Triad};
+
+/// Configuration for [`delta_ladder`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LadderConfig {
+    /// Smallest delta on the ladder
+    pub smallest: f64,
+    /// Largest delta on the ladder
+    pub largest: f64,
+    /// Whether `smallest` and `largest` are fractions of the base point
+    /// rather than absolute sizes; falls back to absolute at a base point of
+    /// zero
+    pub relative: bool,
+    /// `|H|` at or below which a rung is classified as robust
+    pub tolerance: f64,
+}
+
+impl Default for LadderConfig {
+    /// Deltas from 0.1% to 50% of the base point, tolerance `1e-12`
+    fn default() -> Self {
+        Self {
+            smallest: 0.001,
+            largest: 0.5,
+            relative: true,
+            tolerance: 1e-12,
+        }
+    }
+}
+
+/// The convexity test at one delta
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LadderRung {
+    /// Delta of this rung
+    pub delta: f64,
+    /// Payoff at `at - delta`
+    pub down: f64,
+    /// Payoff at `at + delta`
+    pub up: f64,
+    /// Mean shocked payoff minus the base payoff
+    pub gap: f64,
+    /// Sign of `gap` beyond the tolerance
+    pub classification: Triad,
+}
+
+/// A change of verdict between two neighbouring rungs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LadderTransition {
+    /// Verdict below the change
+    pub from: Triad,
+    /// Verdict above the change
+    pub to: Triad,
+    /// Largest delta with the old verdict
+    pub lower: f64,
+    /// Smallest delta with the new verdict
+    pub upper: f64,
+}
+
+/// Result of [`delta_ladder`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeltaLadder {
+    /// Base point
+    pub at: f64,
+    /// Payoff at the base point
+    pub base: f64,
+    /// One entry per rung, smallest delta first
+    pub rungs: Vec<LadderRung>,
+    /// Payoff evaluations spent
+    pub evaluations: usize,
+}
+
+impl DeltaLadder {
+    /// Verdict at the smallest delta
+    #[must_use]
+    pub fn small_shocks(&self) -> Option<Triad> {
+        self.rungs.first().map(|rung| rung.classification)
+    }
+
+    /// Verdict at the largest delta
+    #[must_use]
+    pub fn large_shocks(&self) -> Option<Triad> {
+        self.rungs.last().map(|rung| rung.classification)
+    }
+
+    /// Every change of verdict between neighbouring rungs, smallest first
+    #[must_use]
+    pub fn transitions(&self) -> Vec<LadderTransition> {
+        self.rungs
+            .windows(2)
+            .filter(|w| w[0].classification != w[1].classification)
+            .map(|w| LadderTransition {
+                from: w[0].classification,
+                to: w[1].classification,
+                lower: w[0].delta,
+                upper: w[1].delta,
+            })
+            .collect()
+    }
+
+    /// One `delta,down,up,gap,classification` row per rung, with a header row
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = CsvWriter::new(&["delta", "down", "up", "gap", "classification"]);
+        for rung in &self.rungs {
+            csv.row(&[
+                rung.delta.to_string(),
+                rung.down.to_string(),
+                rung.up.to_string(),
+                rung.gap.to_string(),
+                format!("{:?}", rung.classification),
+            ]);
+        }
+        csv.finish()
+    }
+
+    /// Delta at which the system first turns from antifragile to fragile
+    ///
+    /// Robust rungs in between are skipped. The crossing is interpolated
+    /// linearly in `log Δ` between the last antifragile rung and the first
+    /// fragile rung after it, where the gap changes sign.
+    #[must_use]
+    pub fn crossover(&self) -> Option<f64> {
+        let mut last_antifragile: Option<&LadderRung> = None;
+        for rung in &self.rungs {
+            match rung.classification {
+                Triad::Antifragile => last_antifragile = Some(rung),
+                Triad::Fragile => {
+                    if let Some(below) = last_antifragile {
+                        let t = below.gap / (below.gap - rung.gap);
+                        let (low, high) = (below.delta.ln(), rung.delta.ln());
+                        return Some((low + t * (high - low)).exp());
+                    }
+                }
+                Triad::Robust => {}
+            }
+        }
+        None
+    }
+}
+
+/// Run the convexity test on `system` at `at` over a geometric ladder of
+/// deltas that fits within `max_evals` payoff evaluations
+///
+/// The ladder has `(max_evals - 1) / 2` rungs spaced evenly in `log Δ` from
+/// [`smallest`](LadderConfig::smallest) to
+/// [`largest`](LadderConfig::largest); a single rung uses the smallest
+/// delta. A budget below three evaluations runs nothing.
+#[must_use]
+pub fn delta_ladder<T>(system: &T, at: f64, max_evals: usize, config: &LadderConfig) -> DeltaLadder
+where
+    T: Antifragile<Stressor = f64, Payoff = f64> + ?Sized,
+{
+    let count = max_evals.saturating_sub(1) / 2;
+    if count == 0 {
+        return DeltaLadder {
+            at,
+            base: f64::NAN,
+            rungs: Vec::new(),
+            evaluations: 0,
+        };
+    }
+
+    let scale = if config.relative && at != 0.0 {
+        at.abs()
+    } else {
+        1.0
+    };
+    let (smallest, largest) = (config.smallest * scale, config.largest * scale);
+    let base = system.payoff(at);
+
+    let rungs = (0..count)
+        .map(|k| {
+            let delta = if count == 1 {
+                smallest
+            } else {
+                #[allow(clippy::cast_precision_loss)]
+                let fraction = k as f64 / (count - 1) as f64;
+                smallest * (largest / smallest).powf(fraction)
+            };
+            let down = system.payoff(at - delta);
+            let up = system.payoff(at + delta);
+            let gap = 0.5 * (down + up) - base;
+            let classification = if gap > config.tolerance {
+                Triad::Antifragile
+            } else if gap < -config.tolerance {
+                Triad::Fragile
+            } else {
+                Triad::Robust
+            };
+            LadderRung {
+                delta,
+                down,
+                up,
+                gap,
+                classification,
+            }
+        })
+        .collect();
+
+    DeltaLadder {
+        at,
+        base,
+        rungs,
+        evaluations: 1 + 2 * count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Payoff<F>(F);
+
+    impl<F: Fn(f64) -> f64> Antifragile for Payoff<F> {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&self, x: f64) -> f64 {
+            (self.0)(x)
+        }
+    }
+
+    #[test]
+    fn test_ladder_is_geometric_and_within_budget() {
+        let ladder = delta_ladder(&Payoff(|x: f64| x * x), 10.0, 10, &LadderConfig::default());
+        assert_eq!(ladder.rungs.len(), 4);
+        assert_eq!(ladder.evaluations, 9);
+        let deltas: Vec<f64> = ladder.rungs.iter().map(|r| r.delta).collect();
+        assert!((deltas[0] - 0.01).abs() < 1e-12);
+        assert!((deltas[3] - 5.0).abs() < 1e-9);
+        let ratio = deltas[1] / deltas[0];
+        assert!(
+            deltas
+                .windows(2)
+                .all(|w| (w[1] / w[0] - ratio).abs() < 1e-9)
+        );
+        assert!(ladder.transitions().is_empty());
+        assert_eq!(ladder.crossover(), None);
+    }
+
+    #[test]
+    fn test_antifragile_to_fragile_across_a_cliff() {
+        // Convex up to ±1, flat from 1 to 3, then falls off a cliff
+        let payoff = Payoff(|x: f64| {
+            let d = x.abs();
+            if d <= 1.0 {
+                d * d
+            } else if d <= 3.0 {
+                1.0
+            } else {
+                1.0 - (d - 3.0) * 10.0
+            }
+        });
+        let config = LadderConfig {
+            smallest: 0.5,
+            largest: 8.0,
+            relative: false,
+            ..LadderConfig::default()
+        };
+        let ladder = delta_ladder(&payoff, 0.0, 11, &config);
+        let verdicts: Vec<Triad> = ladder.rungs.iter().map(|r| r.classification).collect();
+        assert_eq!(
+            verdicts,
+            [
+                Triad::Antifragile,
+                Triad::Antifragile,
+                Triad::Antifragile,
+                Triad::Fragile,
+                Triad::Fragile
+            ]
+        );
+        let transitions = ladder.transitions();
+        assert_eq!(transitions.len(), 1);
+        assert!((transitions[0].lower - 2.0).abs() < 1e-9);
+        assert!((transitions[0].upper - 4.0).abs() < 1e-9);
+        let crossover = ladder.crossover().unwrap();
+        assert!(crossover > 2.0 && crossover < 4.0);
+    }
+
+    #[test]
+    fn test_small_budgets() {
+        let system = Payoff(|x: f64| x * x);
+        let config = LadderConfig::default();
+        let empty = delta_ladder(&system, 1.0, 2, &config);
+        assert!(empty.rungs.is_empty());
+        assert_eq!(empty.evaluations, 0);
+        assert_eq!(empty.small_shocks(), None);
+
+        let single = delta_ladder(&system, 1.0, 3, &config);
+        assert_eq!(single.rungs.len(), 1);
+        assert!((single.rungs[0].delta - 0.001).abs() < 1e-15);
+        assert_eq!(single.large_shocks(), Some(Triad::Antifragile));
+    }
+
+    #[test]
+    fn test_csv_has_one_row_per_rung() {
+        let config = LadderConfig {
+            smallest: 1.0,
+            largest: 2.0,
+            relative: false,
+            ..LadderConfig::default()
+        };
+        let ladder = delta_ladder(&Payoff(|x: f64| -x * x), 0.0, 5, &config);
+        assert_eq!(
+            ladder.to_csv(),
+            "delta,down,up,gap,classification\n1,-1,-1,-1,Fragile\n2,-4,-4,-4,Fragile\n"
+        );
+    }
+}
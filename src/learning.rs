@@ -0,0 +1,408 @@
+//! # Learning systems: antifragility over information arrival
+//!
+//! Some systems get better *because* they are exposed to errors: online
+//! learners, adaptive controllers, teams running post-mortems. For them the
+//! stressor is the amount or noisiness of the experience they receive, and
+//! the payoff is the skill they end up with.
+//!
+//! Skill after training is random, so a single run cannot classify anything.
+//! [`LearningProtocol`](crate::learning::LearningProtocol) runs repeated
+//! trials: each trial builds a fresh [`Learner`](crate::learning::Learner),
+//! feeds it `rounds` batches of experience at the given stress level, and
+//! records its final skill. Trial `i` receives the same random stream at
+//! every stress level (common random numbers), so the second difference
+//! between stress levels is measured per trial and its standard error is
+//! honest. A learner is only called antifragile if the convexity is
+//! significant.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::learning::{Learner, LearningProtocol, Noise};
+//!
+//! // Estimates a constant from noisy observations; `stress` is the noise level
+//! #[derive(Default)]
+//! struct MeanEstimator {
+//!     sum: f64,
+//!     count: f64,
+//! }
+//!
+//! impl Learner for MeanEstimator {
+//!     fn learn(&mut self, stress: f64, noise: &mut Noise) {
+//!         self.sum += 5.0 + stress * noise.normal();
+//!         self.count += 1.0;
+//!     }
+//!
+//!     fn skill(&self) -> f64 {
+//!         let error = self.sum / self.count - 5.0;
+//!         -error * error
+//!     }
+//! }
+//!
+//! let protocol = LearningProtocol::new(MeanEstimator::default).with_trials(400);
+//! let analysis = protocol.analyze(1.0, 0.5);
+//!
+//! // Squared error grows with the square of the noise: noisy data only hurts
+//! assert_eq!(analysis.classification, Triad::Fragile);
+//! ```
+
+use core::f64::consts::FRAC_1_SQRT_2;
+
+use crate::rng::SplitMix64;
+use crate::special::erfc;
+use crate::{Antifragile, Triad, TriadWithConfidence};
+
+/// Source of randomness handed to a [`Learner`]
+///
+/// Deterministic for a given trial, so the same trial sees the same stream
+/// at every stress level.
+#[derive(Debug, Clone)]
+pub struct Noise {
+    rng: SplitMix64,
+}
+
+impl Noise {
+    /// A stream seeded with `seed`; equal seeds give equal streams
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self {
+            rng: SplitMix64::new(seed),
+        }
+    }
+
+    /// Uniform sample from `[0, 1)`
+    pub fn uniform(&mut self) -> f64 {
+        self.rng.next_f64()
+    }
+
+    /// Standard normal sample
+    pub fn normal(&mut self) -> f64 {
+        self.rng.next_normal()
+    }
+
+    /// Poisson sample with mean `rate`
+    ///
+    /// Inverts a single uniform, so the same stream gives counts that never
+    /// decrease as the rate grows.
+    pub fn poisson(&mut self, rate: f64) -> u32 {
+        let uniform = self.uniform();
+        let mut probability = (-rate).exp();
+        let mut cumulative = probability;
+        let mut count = 0;
+        while uniform > cumulative && probability > 0.0 {
+            count += 1;
+            probability *= rate / f64::from(count);
+            cumulative += probability;
+        }
+        count
+    }
+}
+
+/// Lets `rand` distributions draw from the same deterministic stream
+#[cfg(feature = "rand")]
+impl rand::RngCore for Noise {
+    #[allow(clippy::cast_possible_truncation)]
+    fn next_u32(&mut self) -> u32 {
+        (self.rng.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        rand::rand_core::impls::fill_bytes_via_next(self, dst);
+    }
+}
+
+/// A system whose skill changes as it is exposed to experience
+pub trait Learner {
+    /// Take in one batch of experience at stress level `stress`
+    ///
+    /// What stress means is up to the learner: the noise level of its
+    /// inputs, the number of examples in the batch, the rate of injected
+    /// errors.
+    fn learn(&mut self, stress: f64, noise: &mut Noise);
+
+    /// Current skill (higher is better)
+    fn skill(&self) -> f64;
+}
+
+/// Mean and standard error of final skill across trials
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrialSummary {
+    /// Mean final skill
+    pub mean: f64,
+    /// Standard error of the mean
+    pub std_error: f64,
+}
+
+/// Classification of a learner from repeated trials
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LearningAnalysis {
+    /// Antifragile or fragile if the convexity is significant, else robust
+    pub classification: Triad,
+    /// Mean over trials of `s(x+Δ) + s(x-Δ) - 2·s(x)`
+    pub curvature: f64,
+    /// Standard error of `curvature`
+    pub std_error: f64,
+}
+
+impl LearningAnalysis {
+    /// Returns true if the sign of the curvature is significant
+    #[must_use]
+    pub fn is_significant(&self) -> bool {
+        self.classification != Triad::Robust
+    }
+
+    /// The classification with the normal-approximation confidence that the
+    /// curvature is nonzero and the mean curvature as its margin
+    #[must_use]
+    pub fn triad_with_confidence(&self) -> TriadWithConfidence {
+        TriadWithConfidence {
+            triad: self.classification,
+            confidence: normal_confidence(self.curvature, self.std_error),
+            margin: self.curvature,
+        }
+    }
+}
+
+/// Repeated-trial protocol for measuring how a [`Learner`] responds to stress
+#[derive(Debug, Clone, Copy)]
+pub struct LearningProtocol<F> {
+    factory: F,
+    rounds: u32,
+    trials: u32,
+    seed: u64,
+    significance: f64,
+}
+
+impl<F, L> LearningProtocol<F>
+where
+    F: Fn() -> L,
+    L: Learner,
+{
+    /// A protocol that builds each trial's learner with `factory`
+    ///
+    /// Defaults to 100 rounds of experience per trial, 64 trials, and a
+    /// significance threshold of two standard errors.
+    pub const fn new(factory: F) -> Self {
+        Self {
+            factory,
+            rounds: 100,
+            trials: 64,
+            seed: 0x5EED,
+            significance: 2.0,
+        }
+    }
+
+    /// Set the number of batches of experience per trial
+    #[must_use]
+    pub const fn with_rounds(mut self, rounds: u32) -> Self {
+        self.rounds = rounds;
+        self
+    }
+
+    /// Set the number of independent trials
+    #[must_use]
+    pub const fn with_trials(mut self, trials: u32) -> Self {
+        self.trials = trials;
+        self
+    }
+
+    /// Set the base seed; equal seeds give identical results
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set how many standard errors the curvature must clear to count
+    #[must_use]
+    pub const fn with_significance(mut self, standard_errors: f64) -> Self {
+        self.significance = standard_errors;
+        self
+    }
+
+    /// Final skill of every trial at stress level `stress`
+    #[must_use]
+    pub fn trial_skills(&self, stress: f64) -> Vec<f64> {
+        (0..self.trials)
+            .map(|trial| {
+                let mut learner = (self.factory)();
+                let mut noise = Noise::new(self.trial_seed(trial));
+                for _ in 0..self.rounds {
+                    learner.learn(stress, &mut noise);
+                }
+                learner.skill()
+            })
+            .collect()
+    }
+
+    /// Mean and standard error of final skill at stress level `stress`
+    #[must_use]
+    pub fn run(&self, stress: f64) -> TrialSummary {
+        let (mean, std_error) = mean_and_error(&self.trial_skills(stress));
+        TrialSummary { mean, std_error }
+    }
+
+    /// Classify the learner at `at` from paired trials at `at` and `at ± delta`
+    #[must_use]
+    pub fn analyze(&self, at: f64, delta: f64) -> LearningAnalysis {
+        let center = self.trial_skills(at);
+        let up = self.trial_skills(at + delta);
+        let down = self.trial_skills(at - delta);
+        let differences: Vec<f64> = center
+            .iter()
+            .zip(&up)
+            .zip(&down)
+            .map(|((c, u), d)| u + d - 2.0 * c)
+            .collect();
+        let (curvature, std_error) = mean_and_error(&differences);
+
+        let threshold = self.significance * std_error;
+        let classification = if curvature > threshold {
+            Triad::Antifragile
+        } else if curvature < -threshold {
+            Triad::Fragile
+        } else {
+            Triad::Robust
+        };
+        LearningAnalysis {
+            classification,
+            curvature,
+            std_error,
+        }
+    }
+
+    fn trial_seed(&self, trial: u32) -> u64 {
+        self.seed
+            .wrapping_add(u64::from(trial).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+}
+
+impl<F, L> Antifragile for LearningProtocol<F>
+where
+    F: Fn() -> L,
+    L: Learner,
+{
+    type Stressor = f64;
+    /// Mean final skill across trials
+    type Payoff = f64;
+
+    fn payoff(&self, stress: f64) -> f64 {
+        self.run(stress).mean
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn mean_and_error(values: &[f64]) -> (f64, f64) {
+    let count = values.len() as f64;
+    if values.is_empty() {
+        return (f64::NAN, f64::NAN);
+    }
+    let mean = values.iter().sum::<f64>() / count;
+    if values.len() < 2 {
+        return (mean, f64::INFINITY);
+    }
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (count - 1.0);
+    (mean, (variance / count).sqrt())
+}
+
+/// One minus the two-sided normal p-value of `mean` against zero
+pub(crate) fn normal_confidence(mean: f64, std_error: f64) -> f64 {
+    if std_error > 0.0 {
+        1.0 - erfc((mean / std_error).abs() * FRAC_1_SQRT_2)
+    } else if mean == 0.0 {
+        0.0
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Skill is the mean squared size of the surprises seen so far
+    #[derive(Default)]
+    struct SurpriseSeeker {
+        total: f64,
+        seen: f64,
+    }
+
+    impl Learner for SurpriseSeeker {
+        fn learn(&mut self, stress: f64, noise: &mut Noise) {
+            self.total += (stress * noise.normal()).powi(2);
+            self.seen += 1.0;
+        }
+
+        fn skill(&self) -> f64 {
+            self.total / self.seen
+        }
+    }
+
+    #[derive(Default)]
+    struct Estimator {
+        sum: f64,
+        count: f64,
+    }
+
+    impl Learner for Estimator {
+        fn learn(&mut self, stress: f64, noise: &mut Noise) {
+            self.sum += stress * noise.normal();
+            self.count += 1.0;
+        }
+
+        fn skill(&self) -> f64 {
+            -(self.sum / self.count).powi(2)
+        }
+    }
+
+    struct Static;
+
+    impl Learner for Static {
+        fn learn(&mut self, _stress: f64, _noise: &mut Noise) {}
+
+        fn skill(&self) -> f64 {
+            1.0
+        }
+    }
+
+    #[test]
+    fn test_convex_learner_is_antifragile() {
+        let analysis = LearningProtocol::new(SurpriseSeeker::default).analyze(1.0, 0.5);
+        assert_eq!(analysis.classification, Triad::Antifragile);
+        assert!(analysis.is_significant());
+        let reading = analysis.triad_with_confidence();
+        assert_eq!(reading.triad, Triad::Antifragile);
+        // Significant at two standard errors means better than 95%
+        assert!(reading.confidence > 0.95);
+    }
+
+    #[test]
+    fn test_noisy_estimator_is_fragile() {
+        let analysis = LearningProtocol::new(Estimator::default)
+            .with_trials(500)
+            .with_rounds(20)
+            .analyze(1.0, 0.5);
+        assert_eq!(analysis.classification, Triad::Fragile);
+    }
+
+    #[test]
+    fn test_static_learner_is_robust() {
+        let protocol = LearningProtocol::new(|| Static);
+        let analysis = protocol.analyze(1.0, 0.5);
+        assert_eq!(analysis.classification, Triad::Robust);
+        assert!(analysis.curvature.abs() < f64::EPSILON);
+        assert!(analysis.triad_with_confidence().confidence.abs() < f64::EPSILON);
+        assert!((protocol.payoff(3.0) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_trials_are_reproducible() {
+        let protocol = LearningProtocol::new(Estimator::default).with_seed(9);
+        assert_eq!(protocol.trial_skills(2.0), protocol.trial_skills(2.0));
+        let summary = protocol.with_trials(1).run(2.0);
+        assert!(summary.std_error.is_infinite());
+    }
+}
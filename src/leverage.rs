@@ -0,0 +1,296 @@
+//! # Leverage and nonlinearity amplification
+//!
+//! Scaling a payoff by a constant never changes its classification: `k·f` is
+//! exactly as convex as `f`. Leverage becomes dangerous because it is applied
+//! to *capital*. With `k` times the exposure, one unit of capital grows to
+//! `1 + k·f(x)`, and any outcome with `k·f(x) ≤ -1` wipes it out.
+//!
+//! [`Leveraged`](crate::leverage::Leveraged) models this by reporting the
+//! log-growth of capital, `ln(1 + k·f(x))`, with ruin mapped to negative
+//! infinity. The logarithm is concave, so as `k` grows its curvature swamps
+//! whatever convexity the underlying payoff had: even an antifragile position
+//! becomes fragile once it is levered far enough.
+//! [`flip_leverage`](crate::leverage::flip_leverage) finds that point, and
+//! [`leverage_profile`](crate::leverage::leverage_profile) tabulates
+//! classification and tail loss across a range of leverages.
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad, TriadAnalysis};
+//! use antifragile::leverage::{Leveraged, flip_leverage};
+//!
+//! // A convex strategy: loses 5% on a down shock, gains 15% on an up shock
+//! struct Strategy;
+//!
+//! impl Antifragile for Strategy {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, shock: f64) -> f64 {
+//!         0.1 * shock + 0.05 * shock * shock
+//!     }
+//! }
+//!
+//! assert_eq!(Leveraged::new(Strategy, 0.5).classify(0.0, 1.0), Triad::Antifragile);
+//! assert_eq!(Leveraged::new(Strategy, 15.0).classify(0.0, 1.0), Triad::Fragile);
+//!
+//! // (1 - 0.05k)(1 + 0.15k) = 1 at k = 40/3
+//! let flip = flip_leverage(&Strategy, 0.0, 1.0, 19.0).unwrap();
+//! assert!((flip - 40.0 / 3.0).abs() < 1e-9);
+//! ```
+
+use crate::{Antifragile, Triad, TriadAnalysis};
+
+/// Number of bisection steps used by [`flip_leverage`]
+const BISECTION_STEPS: usize = 64;
+
+/// A payoff taken with leverage `k`, reported as the log-growth of capital
+///
+/// The inner payoff is read as a return per unit of exposure. The leveraged
+/// payoff is `ln(1 + k·f(x))`, or negative infinity when `1 + k·f(x) ≤ 0`
+/// (ruin). Points where ruin is reached on both sides and at the centre
+/// classify as [`Triad::Robust`]: there is nothing left to lose.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Leveraged<T> {
+    inner: T,
+    leverage: f64,
+}
+
+impl<T> Leveraged<T> {
+    /// Take `inner` with leverage `leverage`
+    pub const fn new(inner: T, leverage: f64) -> Self {
+        Self { inner, leverage }
+    }
+
+    /// The leverage factor
+    #[must_use]
+    pub const fn leverage(&self) -> f64 {
+        self.leverage
+    }
+
+    /// Get reference to the unlevered system
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Unwrap the unlevered system
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Antifragile<Payoff = f64>> Antifragile for Leveraged<T> {
+    type Stressor = T::Stressor;
+    type Payoff = f64;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> f64 {
+        log_growth(self.leverage, self.inner.payoff(stressor))
+    }
+}
+
+/// Classification and tail loss of a system at one leverage
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LeveragePoint {
+    /// The leverage factor
+    pub leverage: f64,
+    /// Classification of the log-growth payoff
+    pub classification: Triad,
+    /// Largest loss across the three probes, as a fraction of capital
+    ///
+    /// Values of `1.0` or more mean the capital is wiped out.
+    pub tail_loss: f64,
+}
+
+impl LeveragePoint {
+    /// Returns true if the worst probe wipes out the capital
+    #[must_use]
+    pub fn is_ruined(&self) -> bool {
+        self.tail_loss >= 1.0
+    }
+}
+
+/// Classify `system` at each of the given `leverages`
+///
+/// Each point probes `at - delta`, `at`, and `at + delta`, exactly as
+/// [`classify`](TriadAnalysis::classify) does.
+#[must_use]
+pub fn leverage_profile<T>(
+    system: &T,
+    at: T::Stressor,
+    delta: T::Stressor,
+    leverages: &[f64],
+) -> Vec<LeveragePoint>
+where
+    T: Antifragile<Payoff = f64>,
+{
+    let probes = probes(system, at, delta);
+    let worst = probes.into_iter().fold(f64::INFINITY, f64::min);
+
+    leverages
+        .iter()
+        .map(|&leverage| LeveragePoint {
+            leverage,
+            classification: classify_levered(leverage, probes),
+            tail_loss: (-leverage * worst).max(0.0),
+        })
+        .collect()
+}
+
+/// Find the leverage at which an antifragile system turns fragile
+///
+/// Searches `(0, max_leverage]` by bisection and returns the smallest leverage
+/// found to classify as [`Triad::Fragile`]. Returns `None` if the unlevered
+/// system is not antifragile at `at`, or if it is still not fragile at
+/// `max_leverage`.
+#[must_use]
+pub fn flip_leverage<T>(
+    system: &T,
+    at: T::Stressor,
+    delta: T::Stressor,
+    max_leverage: f64,
+) -> Option<f64>
+where
+    T: Antifragile<Payoff = f64>,
+{
+    let probes = probes(system, at, delta);
+    let is_fragile = |leverage| classify_levered(leverage, probes).is_fragile();
+
+    if !system.classify(at, delta).is_antifragile() || !is_fragile(max_leverage) {
+        return None;
+    }
+
+    let (mut lo, mut hi) = (0.0, max_leverage);
+    for _ in 0..BISECTION_STEPS {
+        let mid = 0.5 * (lo + hi);
+        if is_fragile(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    Some(hi)
+}
+
+/// `ln(1 + k·r)`, or negative infinity on ruin
+fn log_growth(leverage: f64, payoff: f64) -> f64 {
+    let levered = leverage * payoff;
+    if levered <= -1.0 {
+        f64::NEG_INFINITY
+    } else {
+        levered.ln_1p()
+    }
+}
+
+/// Classify `ln(1 + k·f)` from the three unlevered probe payoffs
+fn classify_levered(leverage: f64, [minus, centre, plus]: [f64; 3]) -> Triad {
+    let sum = log_growth(leverage, minus) + log_growth(leverage, plus);
+    let twin = 2.0 * log_growth(leverage, centre);
+    if sum > twin {
+        Triad::Antifragile
+    } else if sum < twin {
+        Triad::Fragile
+    } else {
+        Triad::Robust
+    }
+}
+
+fn probes<T: Antifragile<Payoff = f64>>(
+    system: &T,
+    at: T::Stressor,
+    delta: T::Stressor,
+) -> [f64; 3] {
+    [
+        system.payoff(at - delta),
+        system.payoff(at),
+        system.payoff(at + delta),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Long straddle-like payoff: gains from moves in either direction
+    struct Straddle;
+
+    impl Antifragile for Straddle {
+        type Stressor = f64;
+        type Payoff = f64;
+        fn payoff(&self, x: f64) -> f64 {
+            0.2 * x.abs() - 0.05
+        }
+    }
+
+    #[test]
+    fn test_scaling_alone_preserves_classification() {
+        // At low leverage log-growth is nearly linear in the payoff
+        assert_eq!(
+            Leveraged::new(Straddle, 0.01).classify(0.0, 1.0),
+            Triad::Antifragile
+        );
+    }
+
+    /// Convex but skewed: small losses on the way down, larger gains up
+    struct Skewed;
+
+    impl Antifragile for Skewed {
+        type Stressor = f64;
+        type Payoff = f64;
+        fn payoff(&self, x: f64) -> f64 {
+            0.2 * x + 0.05 * x * x
+        }
+    }
+
+    #[test]
+    fn test_flip_point_separates_classifications() {
+        let flip = flip_leverage(&Skewed, 0.0, 1.0, 6.0).unwrap();
+        assert_eq!(
+            Leveraged::new(Skewed, flip * 0.99).classify(0.0, 1.0),
+            Triad::Antifragile
+        );
+        assert_eq!(
+            Leveraged::new(Skewed, flip * 1.01).classify(0.0, 1.0),
+            Triad::Fragile
+        );
+    }
+
+    #[test]
+    fn test_symmetric_gains_never_flip() {
+        // Ruin arrives before the logarithm's concavity can win
+        assert_eq!(flip_leverage(&Straddle, 0.0, 1.0, 100.0), None);
+    }
+
+    #[test]
+    fn test_flip_requires_antifragile_start() {
+        struct Concave;
+        impl Antifragile for Concave {
+            type Stressor = f64;
+            type Payoff = f64;
+            fn payoff(&self, x: f64) -> f64 {
+                -x * x
+            }
+        }
+
+        assert_eq!(flip_leverage(&Concave, 0.0, 1.0, 100.0), None);
+        // Not enough leverage to flip
+        assert_eq!(flip_leverage(&Skewed, 0.0, 1.0, 1.0), None);
+    }
+
+    #[test]
+    fn test_profile_reports_tail_loss_and_ruin() {
+        let profile = leverage_profile(&Straddle, 0.0, 1.0, &[1.0, 10.0, 20.0, 40.0]);
+
+        assert_eq!(profile.len(), 4);
+        assert!((profile[0].tail_loss - 0.05).abs() < 1e-12);
+        assert!(!profile[1].is_ruined());
+        assert!(profile[2].is_ruined());
+        assert!(profile[3].is_ruined());
+        // Ruined at the centre but not at the edges
+        assert_eq!(profile[2].classification, Triad::Antifragile);
+        assert!(
+            profile
+                .windows(2)
+                .all(|pair| pair[0].tail_loss <= pair[1].tail_loss)
+        );
+    }
+}
@@ -22,6 +22,69 @@
 //! | [`Triad`] | Classification enum (Fragile/Robust/Antifragile) |
 //! | [`TriadAnalysis`] | Extension trait with classification methods |
 //! | [`Verified`] | Wrapper that caches classification result |
+//! | [`VerifiedPolicy`] | Staleness policy for a cached classification |
+//! | [`antifragile!`] | Macro building an ad-hoc [`FnSystem`] from a payoff closure |
+//!
+//! ## Analysis Modules
+//!
+//! Heavier analyses live in their own modules:
+//!
+//! | Module | Purpose | Requires |
+//! |--------|---------|----------|
+//! | [`surrogate`] | Budgeted profile sweeps for expensive payoffs | `std` |
+//! | [`cache`] | Result caching keyed by verification context, and memoized payoffs | `std` |
+//! | [`report`] | Incrementally maintained analysis reports | `std` |
+//! | [`whatif`] | Hypothetical payoff patches and their classification delta | `std` |
+//! | [`transforms`] | Floors, caps, collars, and proportional hedges | |
+//! | [`leverage`] | Classification and tail loss as leverage increases | `std` |
+//! | [`vega`] | Long/short volatility exposure across a volatility range | `std` |
+//! | [`robustness`] | Wasserstein sensitivity of outcomes to stressor model error | `std` |
+//! | [`shift`] | Worst-case expected payoff over a Wasserstein ball of stressor distributions | `std` |
+//! | [`compound`] | Terminal-wealth classification under multi-period reinvestment | |
+//! | [`empirical`] | Interpolated systems built from observed telemetry | `std` |
+//! | [`rebalance`] | Barbell rebalancing and volatility-harvesting gains | |
+//! | [`fit`] | Named-family curve fits classified by analytic curvature | `std` |
+//! | [`piecewise`] | Exact kink-by-kink classification of piecewise-linear payoffs | `std` |
+//! | [`strategy`] | Spreads, straddles, condors, and other option structures | `std` |
+//! | [`systems`] | Ready-made models of canonical fragile and antifragile systems | `std` |
+//! | [`quadrant`] | Fourth-quadrant detection: fat tails meeting unbounded concave exposure | `std` |
+//! | [`learning`] | Repeated-trial classification of learners exposed to noisy experience | `std` |
+//! | [`domain`] | Valid stressor ranges that clamp or reject out-of-domain probes | |
+//! | [`multivariate`] | Vector stressors classified by finite-difference Hessian definiteness | |
+//! | [`cost`] | Jensen gap and tail loss in money per period | `std` |
+//! | [`sensitivity`] | `Parameterized` systems and interventions ranked by convexity gained per unit cost | `std` |
+//! | [`monte_carlo`] | Jensen-gap classification under a sampled stressor distribution | `std` |
+//! | [`wavelet`] | Per-timescale classification of observed stressor and payoff series | `std` |
+//! | [`distribution`] | Stressor distributions: empirical resampling and `rand`-backed families | `std` |
+//! | [`tails`] | Hill tail index and max-to-sum diagnostics for fat-tailed losses | `std` |
+//! | [`frequency`] | Gain, phase, and Jensen asymmetry of dynamic systems under sinusoidal stress | `std` |
+//! | [`control`] | PID and custom control loops with disturbance amplitude as the stressor | `std` |
+//! | [`evt`] | Peaks-over-threshold GPD fits to losses with return levels | `std` |
+//! | [`harness`] | Property tests run under escalating stress and classified by pass rate | `std` |
+//! | [`chaos`] | Fault-injection ramps with success-rate and latency payoffs and abort conditions | `std` |
+//! | [`heuristic`] | Taleb–Douady `H` heuristic and acceleration of harm under growing shocks | `std` |
+//! | [`memory`] | Allocator and arena throughput under growing allocation rates and fragmentation | `std` |
+//! | [`bayes`] | Streaming posterior probabilities of each Triad class with configurable priors | `std` |
+//! | [`attribution`] | Decomposition of curvature over a range into the bands that contribute it | `std` |
+//! | [`consensus`] | Sample-weighted fleet verdict from per-replica classifiers, ignoring cold replicas | `std` |
+//! | `render` | Markdown and HTML documents from reports and profile sweeps | `render` |
+//! | [`regime`] | One system's verdicts at named operating points, with staleness tracking | `std` |
+//! | [`ladder`] | Verdicts over a geometric ladder of deltas within an evaluation budget | `std` |
+//! | [`sweep`] | Classification details across operating points, exportable as CSV, dense-grid classification, and two-stressor heatmaps | `std` |
+//! | `arrow` | Arrow record batches and Parquet files from sweeps, ladders, draws, and verdicts | `arrow` |
+//! | [`combinators`] | Sums, scalings, shifts, negations, compositions, envelopes, and weighted portfolios of systems | |
+//! | `numeric` | Payoff adapters taking zero and `f64` conversion from `num-traits` | `num-traits` |
+//! | [`integer`] | Overflow-checked and saturating classification of integer and fixed-point payoffs | |
+//! | `asynchronous` | Systems with asynchronous payoffs and their classification | `async` |
+//! | [`stateful`] | Systems whose payoff changes their state, classified in a documented order | |
+//! | [`path`] | Path-dependent payoffs classified by volatile against smooth stress paths | `std` |
+//! | [`stream`] | Online classification and regime-change detection from streamed observations | `std` |
+//! | [`fallible`] | Systems whose payoff can fail, classified with the first error propagated | |
+//! | [`aggregate`] | Per-system classifications combined by weakest link, majority, or weight, and batches of operating points | `std` |
+//! | `lowercase` | Serde helpers writing `Triad` as `"antifragile"`, `"fragile"`, or `"robust"` | `serde` |
+//! | [`instrument`] | Payoff evaluation counts and probed stressor range of a wrapped system | `std` |
+//! | `telemetry` | Classification rank, curvature, convexity ratio, and evaluation gauges with stable names, through `metrics` or a Prometheus registry | `metrics` or `prometheus` |
+//! | `otel` | Classification rank, convexity, and transitions as OpenTelemetry metrics and span events | `otel` |
 //!
 //! ## Performance Characteristics
 //!
@@ -30,6 +93,11 @@
 //! - `Triad` operations: all constant-time
 //! - `Verified::check()`: one classification + struct creation
 //!
+//! The `benches/` suite measures these paths along with batch and Monte
+//! Carlo classification, and `tests/allocation.rs` fails if the core path
+//! allocates. Wrap a system in `instrument::Instrumented` to count the
+//! evaluations an analysis actually makes.
+//!
 //! ## Quick Start
 //!
 //! ```rust
@@ -136,6 +204,20 @@
 //! |---------|---------|-------------|
 //! | `std` | Yes | Standard library support (disable for `no_std`) |
 //! | `serde` | No | Serialization support for `Triad` and `Verified` |
+//! | `rand` | No | `rand`/`rand_distr` distributions as stressor distributions (implies `std`) |
+//! | `render` | No | Markdown and HTML rendering of reports and profile sweeps (implies `std`) |
+//! | `json` | No | Loading empirical systems from JSON observations (implies `std`) |
+//! | `arrow` | No | Arrow record batches and Parquet files from analysis results (implies `std`) |
+//! | `num-traits` | No | `Numeric` adapter taking zero and `f64` conversion from `num-traits` |
+//! | `libm` | No | Float math without `std`, enabling `classify_stochastic` on `no_std` targets |
+//! | `fixed` | No | Integer analysis helpers for the fixed-point types of the `fixed` crate |
+//! | `async` | No | `AntifragileAsync` trait for payoffs computed by futures |
+//! | `schemars` | No | `JsonSchema` for `Triad`, `Verified`, and report types, for OpenAPI generation (implies `serde`) |
+//! | `rayon` | No | Parallel batch classification with `par_classify_batch` (implies `std`) |
+//! | `metrics` | No | Classification, convexity, and evaluation gauges through the `metrics` facade (implies `std`) |
+//! | `prometheus` | No | Rank, curvature, and convexity ratio gauges registered in a `prometheus` registry under the `telemetry` names (implies `std`) |
+//! | `otel` | No | OpenTelemetry metrics and span events for classification transitions (implies `std`) |
+//! | `tracing` | No | `tracing` events from `classify` (trace), `Verified::check` and Monte Carlo runs (debug), and the online classifiers (trace; regime changes at info) |
 #![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
 #![deny(missing_docs)]
@@ -143,16 +225,268 @@
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+/// Aggregate classification of many systems under a combining policy.
+#[cfg(feature = "std")]
+pub mod aggregate;
+
 /// Core types and traits for antifragility analysis.
 pub mod antifragile;
 
+/// Arrow record batches and Parquet files from analysis results.
+#[cfg(feature = "arrow")]
+pub mod arrow;
+
+/// Asynchronous payoffs: classification of systems whose payoff is a future.
+#[cfg(feature = "async")]
+pub mod asynchronous;
+
+/// Attribution of curvature over a stressor range to the bands that contribute it.
+#[cfg(feature = "std")]
+pub mod attribution;
+
+/// Streaming Bayesian posterior over the Triad from stressor-payoff observations.
+#[cfg(feature = "std")]
+pub mod bayes;
+
+/// Result caching keyed by system identity and verification context.
+#[cfg(feature = "std")]
+pub mod cache;
+
+/// Fault-injection experiments classified by how payoffs respond to the fault rate.
+#[cfg(feature = "std")]
+pub mod chaos;
+
+/// Combinators composing systems: sums, leverage, fixed costs, short positions, pipelines, envelopes, and portfolios.
+pub mod combinators;
+
+/// Multi-period compounding: terminal wealth as a function of per-period volatility.
+pub mod compound;
+
+/// Fleet-level consensus over per-replica Triad probabilities weighted by sample counts.
+#[cfg(feature = "std")]
+pub mod consensus;
+
+/// Closed-loop controllers classified by how tracking responds to disturbance.
+#[cfg(feature = "std")]
+pub mod control;
+
+/// Monetary cost of fragility: Jensen gap and tail loss per period.
+#[cfg(feature = "std")]
+pub mod cost;
+
+#[cfg(feature = "std")]
+mod csv;
+
+/// Stressor distributions for Monte Carlo analyses, with optional `rand` backing.
+#[cfg(feature = "std")]
+pub mod distribution;
+
+/// Stressor domains: clamp or reject probes outside a system's valid range.
+pub mod domain;
+
+/// Systems built from observed `(stressor, payoff)` samples.
+#[cfg(feature = "std")]
+pub mod empirical;
+
+/// Extreme value theory: Generalized Pareto fits to losses and return levels.
+#[cfg(feature = "std")]
+pub mod evt;
+
+/// Systems whose payoff can fail, classified with the error propagated.
+pub mod fallible;
+
+/// Stress-response curve fitting to named families.
+#[cfg(feature = "std")]
+pub mod fit;
+
+/// Frequency-domain transfer analysis: gain, phase, and asymmetry of dynamic systems.
+#[cfg(feature = "std")]
+pub mod frequency;
+
+/// Stress-aware property testing: classify a component by its pass rate under escalating stress.
+#[cfg(feature = "std")]
+pub mod harness;
+
+/// The Taleb–Douady fragility heuristic: harm under a ladder of growing shocks.
+#[cfg(feature = "std")]
+pub mod heuristic;
+
+/// Instrumented payoffs: evaluation counts and the stressor range probed.
+#[cfg(feature = "std")]
+pub mod instrument;
+
+/// Overflow-aware classification of integer and fixed-point payoffs.
+pub mod integer;
+
+/// Delta ladders: classification across shock scales within an evaluation budget.
+#[cfg(feature = "std")]
+pub mod ladder;
+
+/// Learning systems: repeated-trial classification over information arrival.
+#[cfg(feature = "std")]
+pub mod learning;
+
+/// Leverage analysis: how borrowing against a payoff erodes its convexity.
+#[cfg(feature = "std")]
+pub mod leverage;
+
+/// Lowercase serde representation of `Triad` for `#[serde(with = ...)]`.
+#[cfg(feature = "serde")]
+pub mod lowercase;
+
+#[cfg(any(feature = "std", feature = "libm"))]
+mod math;
+
+/// Allocator and arena stress profiles under growing memory pressure.
+#[cfg(feature = "std")]
+pub mod memory;
+
+/// Monte Carlo classification by the Jensen gap under a stressor distribution.
+#[cfg(feature = "std")]
+pub mod monte_carlo;
+
+/// Vector-valued stressors classified by the definiteness of the Hessian.
+pub mod multivariate;
+
+/// Adapters for `num-traits` numerics lacking `Default` or `Into<f64>`.
+#[cfg(feature = "num-traits")]
+pub mod numeric;
+
+/// OpenTelemetry metrics and span events for classifications and their transitions.
+#[cfg(feature = "otel")]
+pub mod otel;
+
+/// Path-dependent payoffs: volatile against smooth stress paths of equal mean.
+#[cfg(feature = "std")]
+pub mod path;
+
+/// Piecewise-linear payoffs classified exactly from their slopes.
+#[cfg(feature = "std")]
+pub mod piecewise;
+
+/// Taleb's quadrant map: where statistical classification can be trusted.
+#[cfg(feature = "std")]
+pub mod quadrant;
+
+#[cfg(feature = "std")]
+mod quadratic;
+
+/// Rebalancing between a safe and a convex asset, and the volatility it harvests.
+pub mod rebalance;
+
+/// Verdicts at named operating points of one system, with staleness tracking.
+#[cfg(feature = "std")]
+pub mod regime;
+
+/// Markdown and HTML rendering of reports and profile sweeps.
+#[cfg(feature = "render")]
+pub mod render;
+
+/// Analysis reports that can be updated incrementally.
+#[cfg(feature = "std")]
+pub mod report;
+
+#[cfg(feature = "std")]
+mod rng;
+
+/// Distributional robustness: how far outcomes move when the stressor model is wrong.
+#[cfg(feature = "std")]
+pub mod robustness;
+
+/// Intervention ranking by the sensitivity of convexity to each design parameter.
+#[cfg(feature = "std")]
+pub mod sensitivity;
+
+/// Wasserstein distribution-shift stress testing of expected payoff.
+#[cfg(feature = "std")]
+pub mod shift;
+
+#[cfg(any(feature = "std", feature = "libm"))]
+mod special;
+
+/// Stateful payoffs: classification of systems that change as they are stressed.
+pub mod stateful;
+
+/// Option strategy composer: spreads, straddles, and condors as exact payoffs.
+#[cfg(feature = "std")]
+pub mod strategy;
+
+/// Streaming classification of observations as they arrive.
+#[cfg(feature = "std")]
+pub mod stream;
+
+/// Surrogate-assisted profile computation for expensive payoffs.
+#[cfg(feature = "std")]
+pub mod surrogate;
+
+/// Profile sweeps: classification details across a series of operating points.
+#[cfg(feature = "std")]
+pub mod sweep;
+
+/// Ready-made models of well-known fragile and antifragile systems.
+#[cfg(feature = "std")]
+pub mod systems;
+
+/// Fat-tail diagnostics: Hill tail index and max-to-sum ratios of payoff samples.
+#[cfg(feature = "std")]
+pub mod tails;
+
+/// Classification gauges with stable names, through `metrics` or a Prometheus registry.
+#[cfg(any(feature = "metrics", feature = "prometheus"))]
+pub mod telemetry;
+
+/// Composable payoff transforms: floors, caps, collars, and hedges.
+pub mod transforms;
+
+/// Vega profiles: expected payoff as a function of stressor volatility.
+#[cfg(feature = "std")]
+pub mod vega;
+
+/// Multi-scale Haar analysis of payoff series with a Triad per timescale.
+#[cfg(feature = "std")]
+pub mod wavelet;
+
+/// What-if analysis: hypothetical payoff patches and their effect on classification.
+#[cfg(feature = "std")]
+pub mod whatif;
+
 pub use antifragile::{
-    Antifragile, InvalidTriadValue, ParseTriadError, Triad, TriadAnalysis, Verified,
+    Antifragile, Classification, ClassificationDetail, ClassifyError, Explanation, FnSystem,
+    Inconclusive, InvalidTriadValue, ParseTriadError, Probe, Triad, TriadAnalysis,
+    TriadWithConfidence, VerificationDiff, Verified, VerifiedOf, VerifiedPolicy,
 };
 
+#[cfg(feature = "std")]
+pub use antifragile::{AuditedVerified, Reverification};
+
+/// Build an ad-hoc system from a payoff closure
+///
+/// The closure's argument must be annotated with the stressor type. The
+/// payoff type is taken from an explicit return type or inferred from the
+/// body. The result is a [`FnSystem`].
+///
+/// ```rust
+/// use antifragile::{Triad, TriadAnalysis, antifragile};
+///
+/// let convex = antifragile!(|x: f64| -> f64 { x.powi(2) });
+/// assert_eq!(convex.classify(10.0, 1.0), Triad::Antifragile);
+///
+/// let counter = antifragile!(|n: i128| 3 * n - 7);
+/// assert_eq!(counter.classify(1_000, 10), Triad::Robust);
+/// ```
+#[macro_export]
+macro_rules! antifragile {
+    (|$x:ident : $stressor:ty| -> $payoff:ty $body:block) => {
+        $crate::FnSystem::new(|$x: $stressor| -> $payoff { $body })
+    };
+    (|$x:ident : $stressor:ty| $body:expr) => {
+        $crate::FnSystem::new(|$x: $stressor| $body)
+    };
+}
+
 /// Common f64-based Antifragile systems
 pub mod prelude {
-    pub use super::{Antifragile, Triad, TriadAnalysis, Verified};
+    pub use super::{Antifragile, Triad, TriadAnalysis, Verified, VerifiedOf};
 
     /// Type alias for f64 stressor/payoff classification results
     pub type ClassifyResult = super::Triad;
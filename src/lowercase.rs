@@ -0,0 +1,129 @@
+//! # Lowercase serde representation of `Triad`
+//!
+//! [`Triad`] serializes as its variant name, `"Antifragile"`, by default.
+//! Systems that consume the JSON often expect the lowercase names that
+//! [`FromStr`](core::str::FromStr) accepts and that `&'static str::from`
+//! produces. Use this module with `#[serde(with = "...")]` on a `Triad`
+//! field, or [`option`](crate::lowercase::option) on an `Option<Triad>`
+//! field. Deserialization is case-insensitive, so data written with the
+//! default representation still reads back.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Status {
+//!     #[serde(with = "antifragile::lowercase")]
+//!     triad: Triad,
+//!     #[serde(with = "antifragile::lowercase::option")]
+//!     previous: Option<Triad>,
+//! }
+//!
+//! let status = Status { triad: Triad::Antifragile, previous: None };
+//! let json = serde_json::to_string(&status).unwrap();
+//! assert_eq!(json, r#"{"triad":"antifragile","previous":null}"#);
+//!
+//! let old: Status = serde_json::from_str(r#"{"triad":"Fragile","previous":"robust"}"#).unwrap();
+//! assert_eq!((old.triad, old.previous), (Triad::Fragile, Some(Triad::Robust)));
+//! ```
+
+use core::fmt;
+
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use serde::{Deserialize, Serializer};
+
+use crate::Triad;
+
+/// Serialize a `Triad` as its lowercase name
+///
+/// # Errors
+///
+/// Whatever error the serializer reports.
+pub fn serialize<S: Serializer>(triad: &Triad, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str((*triad).into())
+}
+
+/// Deserialize a `Triad` from its name in any case
+///
+/// # Errors
+///
+/// If the input is not a string naming a classification.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Triad, D::Error> {
+    deserializer.deserialize_str(TriadName)
+}
+
+/// Lowercase representation of `Option<Triad>`, with `None` as null
+pub mod option {
+    use super::{Deserialize, Deserializer, Serializer, Triad};
+
+    /// Serialize an `Option<Triad>` as a lowercase name or null
+    ///
+    /// # Errors
+    ///
+    /// Whatever error the serializer reports.
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: Serializer>(
+        triad: &Option<Triad>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match triad {
+            Some(triad) => serializer.serialize_some(<&str>::from(*triad)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize an `Option<Triad>` from a name in any case or null
+    ///
+    /// # Errors
+    ///
+    /// If the input is neither null nor a string naming a classification.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Triad>, D::Error> {
+        #[derive(Deserialize)]
+        struct Named(#[serde(with = "super")] Triad);
+
+        Ok(Option::<Named>::deserialize(deserializer)?.map(|Named(triad)| triad))
+    }
+}
+
+/// Visitor parsing a classification name with [`FromStr`](core::str::FromStr)
+struct TriadName;
+
+impl Visitor<'_> for TriadName {
+    type Value = Triad;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"antifragile\", \"fragile\", or \"robust\"")
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<Triad, E> {
+        name.parse()
+            .map_err(|_| E::invalid_value(Unexpected::Str(name), &self))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Wrapped(#[serde(with = "super")] Triad);
+
+    #[test]
+    fn test_roundtrip_and_rejection() {
+        for triad in Triad::ALL {
+            let json = serde_json::to_string(&<&str>::from(triad)).unwrap();
+            let Wrapped(parsed) = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, triad);
+        }
+        let error = serde_json::from_str::<Wrapped>(r#""sideways""#).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("\"antifragile\", \"fragile\", or \"robust\"")
+        );
+        assert!(serde_json::from_str::<Wrapped>("2").is_err());
+    }
+}
@@ -0,0 +1,56 @@
+//! Float functions that work with or without `std`
+//!
+//! `core` has no `sqrt`, `ln`, or `exp`. With `std` these forward to the
+//! inherent `f64` methods; without it they come from `libm`, so the
+//! statistics built on them compile for embedded targets.
+
+/// Square root
+#[inline]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        x.sqrt()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        libm::sqrt(x)
+    }
+}
+
+/// Natural logarithm
+#[inline]
+pub(crate) fn ln(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        x.ln()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        libm::log(x)
+    }
+}
+
+/// Exponential function
+#[inline]
+pub(crate) fn exp(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        x.exp()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        libm::exp(x)
+    }
+}
+
+#[cfg(all(test, feature = "libm"))]
+mod tests {
+    #[test]
+    fn test_libm_agrees_with_std() {
+        for x in [1e-3, 0.5, 1.0, 2.0, 10.0, 1e6] {
+            assert!((libm::sqrt(x) - super::sqrt(x)).abs() <= 1e-15 * x.sqrt());
+            assert!((libm::log(x) - super::ln(x)).abs() <= 1e-15 * x.ln().abs().max(1.0));
+            assert!((libm::exp(-x) - super::exp(-x)).abs() <= 1e-15 * (-x).exp());
+        }
+    }
+}
@@ -0,0 +1,462 @@
+//! # Allocator stress profiles
+//!
+//! An allocator tuned on a quiet benchmark can fall apart under memory
+//! pressure: free space splinters into holes too small to use, allocations
+//! start failing long before the heap is full, and every failure costs a
+//! retry or a crash. Whether that happens gradually or all at once is a
+//! convexity question.
+//!
+//! [`AllocatorHarness`](crate::memory::AllocatorHarness) drives any
+//! [`Arena`](crate::memory::Arena) with a pluggable
+//! [`Workload`](crate::memory::Workload). Each tick, allocations whose
+//! lifetime has ended are freed, then a Poisson number of new requests
+//! arrives, with mean equal to the stressor (the allocation rate), each
+//! drawing its size and lifetime from the workload. Live memory grows with
+//! the rate, so the stressor is memory pressure. The payoff is chosen with a
+//! [`MemoryMetric`](crate::memory::MemoryMetric): allocations served per
+//! tick by default, or the success rate, or wall-clock operations per
+//! second for measuring a real allocator.
+//!
+//! [`FirstFit`](crate::memory::FirstFit) is a simulated first-fit heap for
+//! experimenting with workloads. [`report`](crate::memory::AllocatorHarness::report)
+//! turns a sweep of allocation rates into a
+//! [`ReportSection`](crate::report::ReportSection).
+//!
+//! ```rust
+//! use antifragile::{Triad, TriadAnalysis};
+//! use antifragile::learning::Noise;
+//! use antifragile::memory::{Allocation, AllocatorHarness, FirstFit};
+//!
+//! // Mostly small, short-lived objects with occasional large buffers
+//! let workload = || {
+//!     |noise: &mut Noise| {
+//!         let size = if noise.uniform() < 0.9 { 64 } else { 4096 };
+//!         Allocation::new(size, 50)
+//!     }
+//! };
+//! let harness = AllocatorHarness::new(|| FirstFit::new(256 * 1024), workload);
+//!
+//! // Plenty of headroom: every request is served
+//! assert!((harness.measure(2.0).success_rate - 1.0).abs() < f64::EPSILON);
+//! // Near the memory limit, failures grow faster than the load
+//! assert_eq!(harness.classify(12.0, 4.0), Triad::Fragile);
+//! ```
+
+use std::time::Instant;
+
+use crate::learning::Noise;
+use crate::report::{ReportEntry, ReportSection};
+use crate::{Antifragile, TriadAnalysis};
+
+/// One allocation request produced by a [`Workload`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Allocation {
+    /// Requested size in bytes
+    pub size: usize,
+    /// Ticks until the allocation is freed
+    pub lifetime: u32,
+}
+
+impl Allocation {
+    /// A request for `size` bytes held for `lifetime` ticks
+    #[must_use]
+    pub const fn new(size: usize, lifetime: u32) -> Self {
+        Self { size, lifetime }
+    }
+}
+
+/// An allocator or arena under test
+pub trait Arena {
+    /// Token returned by a successful allocation and passed back to free it
+    type Handle;
+
+    /// Allocate `size` bytes, or `None` if the request cannot be served
+    fn allocate(&mut self, size: usize) -> Option<Self::Handle>;
+
+    /// Release an allocation
+    fn free(&mut self, handle: Self::Handle);
+
+    /// Share of free memory unusable for a large request, in `[0, 1]`
+    ///
+    /// Defaults to zero for arenas that do not track it.
+    fn fragmentation(&self) -> f64 {
+        0.0
+    }
+}
+
+/// A generator of allocation requests
+///
+/// Any `FnMut(&mut Noise) -> Allocation` closure is a workload.
+pub trait Workload {
+    /// The next allocation request
+    fn next(&mut self, noise: &mut Noise) -> Allocation;
+}
+
+impl<F: FnMut(&mut Noise) -> Allocation> Workload for F {
+    fn next(&mut self, noise: &mut Noise) -> Allocation {
+        self(noise)
+    }
+}
+
+/// How an allocation run becomes a payoff
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MemoryMetric {
+    /// Successful allocations per tick
+    #[default]
+    Served,
+    /// Share of requests that succeeded
+    SuccessRate,
+    /// Allocations and frees per second of wall-clock time
+    ///
+    /// Not reproducible between runs; pair it with
+    /// [`classify_stochastic`](crate::TriadAnalysis::classify_stochastic).
+    OpsPerSecond,
+}
+
+/// Results of [`AllocatorHarness::measure`], averaged over all trials
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryStats {
+    /// Allocation rate the run was driven at
+    pub rate: f64,
+    /// Requests per tick
+    pub requested: f64,
+    /// Successful allocations per tick
+    pub served: f64,
+    /// Share of requests that succeeded
+    pub success_rate: f64,
+    /// Highest fragmentation reported by the arena in any trial
+    pub peak_fragmentation: f64,
+    /// Allocations and frees per second of wall-clock time
+    pub ops_per_second: f64,
+}
+
+/// Drives an [`Arena`] with a [`Workload`] at a given allocation rate
+#[derive(Debug, Clone, Copy)]
+pub struct AllocatorHarness<A, W> {
+    arena: A,
+    workload: W,
+    ticks: u32,
+    trials: u32,
+    seed: u64,
+    metric: MemoryMetric,
+}
+
+impl<A, W, AS, WS> AllocatorHarness<A, W>
+where
+    A: Fn() -> AS,
+    W: Fn() -> WS,
+    AS: Arena,
+    WS: Workload,
+{
+    /// A harness building a fresh arena and workload for every trial
+    ///
+    /// Defaults to 8 trials of 1000 ticks and the
+    /// [`Served`](MemoryMetric::Served) metric.
+    pub const fn new(arena: A, workload: W) -> Self {
+        Self {
+            arena,
+            workload,
+            ticks: 1000,
+            trials: 8,
+            seed: 0x5EED,
+            metric: MemoryMetric::Served,
+        }
+    }
+
+    /// Set the number of ticks simulated per trial
+    #[must_use]
+    pub const fn with_ticks(mut self, ticks: u32) -> Self {
+        self.ticks = ticks;
+        self
+    }
+
+    /// Set the number of independent trials
+    #[must_use]
+    pub const fn with_trials(mut self, trials: u32) -> Self {
+        self.trials = trials;
+        self
+    }
+
+    /// Set the base seed; equal seeds give identical results
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set how a run becomes a payoff
+    #[must_use]
+    pub const fn with_metric(mut self, metric: MemoryMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Run every trial at `rate` allocation requests per tick
+    ///
+    /// Every trial sees the same arrivals and requests at every rate up to
+    /// the extra requests a higher rate adds. NaN rates with zero ticks or
+    /// zero trials.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn measure(&self, rate: f64) -> MemoryStats {
+        let (mut requested, mut served, mut operations) = (0_u64, 0_u64, 0_u64);
+        let mut peak_fragmentation: f64 = 0.0;
+        let started = Instant::now();
+
+        for trial in 0..self.trials {
+            let seed = self
+                .seed
+                .wrapping_add(u64::from(trial).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+            let mut arrivals = Noise::new(seed);
+            let mut draws = Noise::new(!seed);
+            let mut arena = (self.arena)();
+            let mut workload = (self.workload)();
+            let mut live: Vec<(u32, AS::Handle)> = Vec::new();
+
+            for tick in 0..self.ticks {
+                let mut index = 0;
+                while index < live.len() {
+                    if live[index].0 <= tick {
+                        let (_, handle) = live.swap_remove(index);
+                        arena.free(handle);
+                        operations += 1;
+                    } else {
+                        index += 1;
+                    }
+                }
+
+                for _ in 0..arrivals.poisson(rate) {
+                    let request = workload.next(&mut draws);
+                    requested += 1;
+                    operations += 1;
+                    if let Some(handle) = arena.allocate(request.size) {
+                        served += 1;
+                        live.push((tick.saturating_add(request.lifetime), handle));
+                    }
+                }
+                peak_fragmentation = peak_fragmentation.max(arena.fragmentation());
+            }
+        }
+
+        let ticks = f64::from(self.trials) * f64::from(self.ticks);
+        MemoryStats {
+            rate,
+            requested: requested as f64 / ticks,
+            served: served as f64 / ticks,
+            success_rate: served as f64 / requested as f64,
+            peak_fragmentation,
+            ops_per_second: operations as f64 / started.elapsed().as_secs_f64(),
+        }
+    }
+
+    /// A report section with one entry per allocation rate in `rates`,
+    /// classified at `rate ± delta` and scored by the payoff at `rate`
+    ///
+    /// Entries whose peak fragmentation exceeds one half carry a warning.
+    #[must_use]
+    pub fn report(&self, title: &str, rates: &[f64], delta: f64) -> ReportSection {
+        rates
+            .iter()
+            .fold(ReportSection::new(title), |section, &rate| {
+                let stats = self.measure(rate);
+                let mut entry = ReportEntry::new(
+                    format!("{rate} allocations/tick"),
+                    self.classify(rate, delta),
+                )
+                .with_score(self.score(&stats));
+                if stats.peak_fragmentation > 0.5 {
+                    entry = entry.with_warning(format!(
+                        "peak fragmentation {:.0}%",
+                        100.0 * stats.peak_fragmentation
+                    ));
+                }
+                section.with_entry(entry)
+            })
+    }
+
+    fn score(&self, stats: &MemoryStats) -> f64 {
+        match self.metric {
+            MemoryMetric::Served => stats.served,
+            MemoryMetric::SuccessRate => stats.success_rate,
+            MemoryMetric::OpsPerSecond => stats.ops_per_second,
+        }
+    }
+}
+
+impl<A, W, AS, WS> Antifragile for AllocatorHarness<A, W>
+where
+    A: Fn() -> AS,
+    W: Fn() -> WS,
+    AS: Arena,
+    WS: Workload,
+{
+    /// Mean allocation requests per tick
+    type Stressor = f64;
+    /// The harness's metric
+    type Payoff = f64;
+
+    fn payoff(&self, rate: f64) -> f64 {
+        self.score(&self.measure(rate))
+    }
+}
+
+/// A simulated heap that serves each request from the first hole large
+/// enough, splitting it, and coalesces neighbouring holes on free
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirstFit {
+    capacity: usize,
+    /// Holes as `(offset, length)`, sorted by offset and never adjacent
+    holes: Vec<(usize, usize)>,
+}
+
+impl FirstFit {
+    /// An empty heap of `capacity` bytes
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            holes: if capacity == 0 {
+                Vec::new()
+            } else {
+                vec![(0, capacity)]
+            },
+        }
+    }
+
+    /// Total size of the heap
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Bytes not currently allocated
+    #[must_use]
+    pub fn free_bytes(&self) -> usize {
+        self.holes.iter().map(|&(_, length)| length).sum()
+    }
+
+    /// Size of the largest request that would currently succeed
+    #[must_use]
+    pub fn largest_hole(&self) -> usize {
+        self.holes
+            .iter()
+            .map(|&(_, length)| length)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl Arena for FirstFit {
+    /// Offset and size of the allocation
+    type Handle = (usize, usize);
+
+    fn allocate(&mut self, size: usize) -> Option<(usize, usize)> {
+        let size = size.max(1);
+        let index = self.holes.iter().position(|&(_, length)| length >= size)?;
+        let (offset, length) = self.holes[index];
+        if length == size {
+            self.holes.remove(index);
+        } else {
+            self.holes[index] = (offset + size, length - size);
+        }
+        Some((offset, size))
+    }
+
+    fn free(&mut self, (offset, size): (usize, usize)) {
+        let index = self.holes.partition_point(|&(start, _)| start < offset);
+        let merges_next = self
+            .holes
+            .get(index)
+            .is_some_and(|&(start, _)| offset + size == start);
+        let merges_previous = index.checked_sub(1).is_some_and(|previous| {
+            let (start, length) = self.holes[previous];
+            start + length == offset
+        });
+        match (merges_previous, merges_next) {
+            (true, true) => {
+                let (_, next_length) = self.holes.remove(index);
+                self.holes[index - 1].1 += size + next_length;
+            }
+            (true, false) => self.holes[index - 1].1 += size,
+            (false, true) => self.holes[index] = (offset, self.holes[index].1 + size),
+            (false, false) => self.holes.insert(index, (offset, size)),
+        }
+    }
+
+    /// `1 - largest hole / free bytes`; zero when nothing is free
+    #[allow(clippy::cast_precision_loss)]
+    fn fragmentation(&self) -> f64 {
+        let free = self.free_bytes();
+        if free == 0 {
+            0.0
+        } else {
+            1.0 - self.largest_hole() as f64 / free as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Triad;
+
+    fn mixed() -> impl FnMut(&mut Noise) -> Allocation {
+        |noise: &mut Noise| {
+            let size = if noise.uniform() < 0.9 { 64 } else { 4096 };
+            Allocation::new(size, 50)
+        }
+    }
+
+    fn uniform() -> impl FnMut(&mut Noise) -> Allocation {
+        |_: &mut Noise| Allocation::new(467, 50)
+    }
+
+    #[test]
+    fn test_first_fit_splits_and_coalesces() {
+        let mut heap = FirstFit::new(100);
+        let a = heap.allocate(30).unwrap();
+        let b = heap.allocate(30).unwrap();
+        let c = heap.allocate(30).unwrap();
+        assert_eq!(heap.allocate(20), None);
+        heap.free(a);
+        heap.free(c);
+        // Two holes of 30 and 40: a 40-byte request fits only the second
+        assert!((heap.fragmentation() - 3.0 / 7.0).abs() < 1e-12);
+        assert_eq!(heap.allocate(40), Some((60, 40)));
+        heap.free((60, 40));
+        heap.free(b);
+        assert_eq!(heap.largest_hole(), 100);
+        assert!(heap.fragmentation().abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_pressure_is_fragile_near_the_limit() {
+        let harness = AllocatorHarness::new(|| FirstFit::new(256 * 1024), mixed);
+        let light = harness.measure(2.0);
+        assert!((light.success_rate - 1.0).abs() < f64::EPSILON);
+        assert!((light.served - 2.0).abs() < 0.2);
+        assert_eq!(harness.classify(12.0, 4.0), Triad::Fragile);
+        let rates = harness.with_metric(MemoryMetric::SuccessRate);
+        assert!(rates.payoff(16.0) < 1.0);
+    }
+
+    #[test]
+    fn test_mixed_sizes_fragment_the_heap() {
+        let heap = || FirstFit::new(256 * 1024);
+        let mixed = AllocatorHarness::new(heap, mixed).measure(12.0);
+        let uniform = AllocatorHarness::new(heap, uniform).measure(12.0);
+        // Same mean size, but holes left by small objects cannot hold large ones
+        assert!(mixed.peak_fragmentation > uniform.peak_fragmentation);
+        assert!(mixed.ops_per_second > 0.0);
+    }
+
+    #[test]
+    fn test_report_section() {
+        let harness = AllocatorHarness::new(|| FirstFit::new(256 * 1024), mixed).with_trials(2);
+        let section = harness.report("first fit", &[2.0, 12.0], 1.0);
+        assert_eq!(section.title(), "first fit");
+        assert_eq!(section.entries().len(), 2);
+        assert_eq!(section.entries()[0].label, "2 allocations/tick");
+        assert!(section.entries().iter().all(|e| e.score.is_some()));
+    }
+}
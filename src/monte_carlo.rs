@@ -0,0 +1,300 @@
+//! # Monte Carlo classification under a stressor distribution
+//!
+//! The three-point test is a local statement about curvature at one
+//! operating point. Taleb's argument is distributional: a system is fragile
+//! if, under the stressors it will actually face, its expected payoff falls
+//! short of the payoff at the expected stressor. That shortfall is the
+//! Jensen gap `E[f(X)] - f(E[X])`.
+//!
+//! [`monte_carlo`](crate::monte_carlo::monte_carlo) draws stressors from a
+//! user-supplied sampler, estimates both sides, and classifies on the sign
+//! of the gap. Because the gap is computed against the *sample* mean, a
+//! linear payoff gives a gap of exactly zero up to rounding, and a payoff
+//! that is convex over the sampled range gives a positive gap for every
+//! sample set: the sign is not a matter of luck, only its size is.
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad};
+//! use antifragile::monte_carlo::{MonteCarloConfig, Noise, monte_carlo};
+//!
+//! struct Call;
+//!
+//! impl Antifragile for Call {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, price: f64) -> f64 {
+//!         (price - 100.0).max(0.0)
+//!     }
+//! }
+//!
+//! // Price ~ Normal(100, 10)
+//! let price = |noise: &mut Noise| 100.0 + 10.0 * noise.normal();
+//! let analysis = monte_carlo(&Call, price, &MonteCarloConfig::default());
+//!
+//! assert_eq!(analysis.classification, Triad::Antifragile);
+//! // E[(X - μ)⁺] = σ / √(2π) ≈ 3.99
+//! assert!((analysis.jensen_gap - 3.99).abs() < 0.2);
+//! ```
+
+use crate::distribution::StressorDistribution;
+pub use crate::learning::Noise;
+use crate::{Antifragile, Triad};
+
+/// Configuration for [`monte_carlo`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonteCarloConfig {
+    /// Number of stressor draws
+    pub samples: usize,
+    /// Seed for the sampler's noise; equal seeds give identical results
+    pub seed: u64,
+    /// Gaps within this distance of zero classify as robust
+    pub tolerance: f64,
+}
+
+impl Default for MonteCarloConfig {
+    /// 10 000 samples and a tolerance of `1e-9`
+    fn default() -> Self {
+        Self {
+            samples: 10_000,
+            seed: 0x5EED,
+            tolerance: 1e-9,
+        }
+    }
+}
+
+/// Result of [`monte_carlo`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JensenAnalysis {
+    /// Sign of the Jensen gap, robust within the tolerance
+    pub classification: Triad,
+    /// Sample mean of the stressor, `E[X]`
+    pub mean_stressor: f64,
+    /// `f(E[X])`
+    pub payoff_at_mean: f64,
+    /// Sample mean of the payoff, `E[f(X)]`
+    pub expected_payoff: f64,
+    /// `E[f(X)] - f(E[X])`
+    pub jensen_gap: f64,
+    /// Standard error of `E[f(X)]`
+    pub std_error: f64,
+    /// Number of draws
+    pub samples: usize,
+}
+
+/// A [`JensenAnalysis`] together with every draw behind it
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonteCarloSamples {
+    /// Result of the run
+    pub analysis: JensenAnalysis,
+    /// Stressors in the order drawn
+    pub stressors: Vec<f64>,
+    /// Payoff at each stressor
+    pub payoffs: Vec<f64>,
+}
+
+/// Classify `system` by its Jensen gap under stressors drawn from `sampler`
+///
+/// `sampler` receives a deterministic [`Noise`] stream seeded from
+/// `config.seed` and returns one stressor per call. Costs `samples + 1`
+/// payoff evaluations. With no samples the result is robust with every
+/// value NaN.
+#[must_use]
+pub fn monte_carlo<T, S>(system: &T, sampler: S, config: &MonteCarloConfig) -> JensenAnalysis
+where
+    T: Antifragile<Stressor = f64, Payoff = f64> + ?Sized,
+    S: FnMut(&mut Noise) -> f64,
+{
+    monte_carlo_samples(system, sampler, config).analysis
+}
+
+/// Like [`monte_carlo`], but keep every stressor and payoff drawn
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn monte_carlo_samples<T, S>(
+    system: &T,
+    mut sampler: S,
+    config: &MonteCarloConfig,
+) -> MonteCarloSamples
+where
+    T: Antifragile<Stressor = f64, Payoff = f64> + ?Sized,
+    S: FnMut(&mut Noise) -> f64,
+{
+    let mut noise = Noise::new(config.seed);
+    let stressors: Vec<f64> = (0..config.samples).map(|_| sampler(&mut noise)).collect();
+    let payoffs: Vec<f64> = stressors.iter().map(|&x| system.payoff(x)).collect();
+
+    let count = config.samples as f64;
+    let mean_stressor = stressors.iter().sum::<f64>() / count;
+    let payoff_at_mean = system.payoff(mean_stressor);
+    let expected_payoff = payoffs.iter().sum::<f64>() / count;
+    let jensen_gap = expected_payoff - payoff_at_mean;
+    let std_error = if config.samples < 2 {
+        f64::NAN
+    } else {
+        let variance = payoffs
+            .iter()
+            .map(|p| (p - expected_payoff).powi(2))
+            .sum::<f64>()
+            / (count - 1.0);
+        (variance / count).sqrt()
+    };
+
+    let classification = if jensen_gap > config.tolerance {
+        Triad::Antifragile
+    } else if jensen_gap < -config.tolerance {
+        Triad::Fragile
+    } else {
+        Triad::Robust
+    };
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        system = core::any::type_name::<T>(),
+        samples = config.samples,
+        seed = config.seed,
+        mean_stressor,
+        jensen_gap,
+        std_error,
+        ?classification,
+        "monte carlo classification"
+    );
+
+    MonteCarloSamples {
+        analysis: JensenAnalysis {
+            classification,
+            mean_stressor,
+            payoff_at_mean,
+            expected_payoff,
+            jensen_gap,
+            std_error,
+            samples: config.samples,
+        },
+        stressors,
+        payoffs,
+    }
+}
+
+/// Classify `system` by its Jensen gap under stressors drawn from
+/// `distribution`
+///
+/// Equivalent to [`monte_carlo`] with the distribution's sampler. The gap is
+/// still measured against the sample mean, not
+/// [`StressorDistribution::mean`], so its sign stays exact for convex and
+/// concave payoffs.
+#[must_use]
+pub fn monte_carlo_distribution<T, D>(
+    system: &T,
+    distribution: &D,
+    config: &MonteCarloConfig,
+) -> JensenAnalysis
+where
+    T: Antifragile<Stressor = f64, Payoff = f64> + ?Sized,
+    D: StressorDistribution + ?Sized,
+{
+    monte_carlo(
+        system,
+        |noise: &mut Noise| distribution.sample(noise),
+        config,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Power(f64);
+
+    impl Antifragile for Power {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&self, x: f64) -> f64 {
+            x.powf(self.0)
+        }
+    }
+
+    fn uniform(noise: &mut Noise) -> f64 {
+        1.0 + 2.0 * noise.uniform()
+    }
+
+    #[test]
+    fn test_jensen_gap_of_square_is_variance() {
+        let analysis = monte_carlo(&Power(2.0), uniform, &MonteCarloConfig::default());
+        assert_eq!(analysis.classification, Triad::Antifragile);
+        // Var(Uniform(1, 3)) = 1/3
+        assert!((analysis.jensen_gap - 1.0 / 3.0).abs() < 0.02);
+        assert!((analysis.mean_stressor - 2.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_concave_and_linear_payoffs() {
+        let config = MonteCarloConfig::default();
+        assert_eq!(
+            monte_carlo(&Power(0.5), uniform, &config).classification,
+            Triad::Fragile
+        );
+        assert_eq!(
+            monte_carlo(&Power(1.0), uniform, &config).classification,
+            Triad::Robust
+        );
+    }
+
+    #[test]
+    fn test_seed_determines_result() {
+        let config = MonteCarloConfig {
+            samples: 100,
+            ..MonteCarloConfig::default()
+        };
+        let a = monte_carlo(&Power(2.0), uniform, &config);
+        let b = monte_carlo(&Power(2.0), uniform, &config);
+        let c = monte_carlo(
+            &Power(2.0),
+            uniform,
+            &MonteCarloConfig { seed: 1, ..config },
+        );
+        assert_eq!(a, b);
+        assert!((a.jensen_gap - c.jensen_gap).abs() > 0.0);
+        assert_eq!(a.samples, 100);
+    }
+
+    #[test]
+    fn test_no_samples() {
+        let config = MonteCarloConfig {
+            samples: 0,
+            ..MonteCarloConfig::default()
+        };
+        let analysis = monte_carlo(&Power(2.0), uniform, &config);
+        assert_eq!(analysis.classification, Triad::Robust);
+        assert!(analysis.jensen_gap.is_nan());
+    }
+
+    #[test]
+    fn test_distribution_matches_sampler() {
+        use crate::distribution::Empirical;
+
+        let config = MonteCarloConfig::default();
+        let distribution = Empirical::new(vec![1.0, 2.0, 3.0]);
+        let analysis = monte_carlo_distribution(&Power(2.0), &distribution, &config);
+        let sampler = |noise: &mut Noise| distribution.sample(noise);
+        assert_eq!(analysis, monte_carlo(&Power(2.0), sampler, &config));
+        // Var({1, 2, 3}) = 2/3
+        assert!((analysis.jensen_gap - 2.0 / 3.0).abs() < 0.03);
+    }
+
+    #[test]
+    fn test_samples_are_kept() {
+        let config = MonteCarloConfig {
+            samples: 50,
+            ..MonteCarloConfig::default()
+        };
+        let run = monte_carlo_samples(&Power(2.0), uniform, &config);
+        assert_eq!(run.analysis, monte_carlo(&Power(2.0), uniform, &config));
+        assert_eq!(run.stressors.len(), 50);
+        assert!(
+            run.stressors
+                .iter()
+                .zip(&run.payoffs)
+                .all(|(x, y)| (x * x - y).abs() < 1e-12)
+        );
+    }
+}
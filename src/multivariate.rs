@@ -0,0 +1,417 @@
+//! # Multi-dimensional stressors
+//!
+//! Real systems are stressed along several axes at once: load, latency, and
+//! error rate; rates and spreads; heat and drought. Projecting these onto a
+//! single scalar loses exactly the interactions that make a system fragile.
+//!
+//! [`AntifragileN`](crate::multivariate::AntifragileN) is the counterpart of
+//! [`Antifragile`](crate::Antifragile) for a stressor vector `[f64; N]`. The
+//! scalar convexity test generalizes to the Hessian of the payoff:
+//!
+//! | Hessian | Meaning | Classification |
+//! |---------|---------|----------------|
+//! | Positive (semi)definite | Convex along every direction | `Some(Antifragile)` |
+//! | Negative (semi)definite | Concave along every direction | `Some(Fragile)` |
+//! | Zero | Linear | `Some(Robust)` |
+//! | Indefinite | Gains from some stress combinations, harmed by others | `None` |
+//!
+//! The Hessian is estimated with central finite differences using a step per
+//! axis, so axes in different units can be probed at their own scale.
+//! Definiteness is invariant under that per-axis scaling, and is determined
+//! by counting the signs of pivots in a symmetric elimination (Sylvester's
+//! law of inertia), which needs no square roots and works without `std`.
+//!
+//! For exactly two stressors, [`CrossConvexity`](crate::multivariate::CrossConvexity)
+//! estimates the mixed partial `∂²f/∂x∂y` on its own and says whether the
+//! stressors amplify or dampen each other, the question that matters when
+//! shocks arrive together.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::multivariate::{
+//!     AntifragileN, CrossConvexity, Definiteness, HessianAnalysis, Interaction,
+//! };
+//!
+//! // Fine under load or latency alone, but the two together compound
+//! struct Service;
+//!
+//! impl AntifragileN<2> for Service {
+//!     fn payoff(&self, [load, latency]: [f64; 2]) -> f64 {
+//!         -load * latency
+//!     }
+//! }
+//!
+//! let hessian = Service.hessian([1.0, 1.0], [0.1, 0.1]);
+//! assert!((hessian[0][1] + 1.0).abs() < 1e-9);
+//! assert_eq!(
+//!     Service.definiteness([1.0, 1.0], [0.1, 0.1], 1e-9),
+//!     Definiteness::Indefinite
+//! );
+//! assert_eq!(Service.classify_hessian([1.0, 1.0], [0.1, 0.1], 1e-9), None);
+//!
+//! // Load and latency amplify each other
+//! assert_eq!(Service.interaction(1.0, 1.0, 0.1, 0.1, 1e-9), Interaction::Amplifying);
+//!
+//! // Each axis on its own looks robust: the scalar projection hides the risk
+//! let along_load = Service.along([1.0, 1.0], [1.0, 0.0]);
+//! assert_eq!(
+//!     antifragile::TriadAnalysis::classify(&along_load, 0.0, 0.1),
+//!     Triad::Robust
+//! );
+//! ```
+
+use crate::antifragile::abs;
+use crate::{Antifragile, Triad};
+
+/// A system whose payoff depends on `N` stressors at once
+pub trait AntifragileN<const N: usize> {
+    /// Compute the payoff for a given stressor vector
+    fn payoff(&self, stressor: [f64; N]) -> f64;
+}
+
+/// Curvature of a payoff across all directions of the stressor space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Definiteness {
+    /// Strictly convex along every direction
+    PositiveDefinite,
+    /// Convex along every direction, flat along some
+    PositiveSemidefinite,
+    /// Strictly concave along every direction
+    NegativeDefinite,
+    /// Concave along every direction, flat along some
+    NegativeSemidefinite,
+    /// Convex along some directions and concave along others
+    Indefinite,
+    /// Flat along every direction
+    Zero,
+}
+
+impl Definiteness {
+    /// The Triad classification, or `None` for a saddle
+    #[must_use]
+    pub const fn triad(self) -> Option<Triad> {
+        match self {
+            Self::PositiveDefinite | Self::PositiveSemidefinite => Some(Triad::Antifragile),
+            Self::NegativeDefinite | Self::NegativeSemidefinite => Some(Triad::Fragile),
+            Self::Zero => Some(Triad::Robust),
+            Self::Indefinite => None,
+        }
+    }
+}
+
+/// Extension trait providing Hessian-based classification
+pub trait HessianAnalysis<const N: usize>: AntifragileN<N> {
+    /// Central finite-difference Hessian at `at`, stepping `delta[i]` along axis `i`
+    fn hessian(&self, at: [f64; N], delta: [f64; N]) -> [[f64; N]; N] {
+        let mut hessian = second_differences(self, at, delta);
+        for (i, row) in hessian.iter_mut().enumerate() {
+            for (j, entry) in row.iter_mut().enumerate() {
+                *entry /= delta[i] * delta[j];
+            }
+        }
+        hessian
+    }
+
+    /// Definiteness of the Hessian at `at`
+    ///
+    /// Pivots within `epsilon` of zero, in payoff units, are treated as zero.
+    fn definiteness(&self, at: [f64; N], delta: [f64; N], epsilon: f64) -> Definiteness {
+        inertia(second_differences(self, at, delta), epsilon)
+    }
+
+    /// Classify on the Triad, or `None` where the Hessian is indefinite
+    fn classify_hessian(&self, at: [f64; N], delta: [f64; N], epsilon: f64) -> Option<Triad> {
+        self.definiteness(at, delta, epsilon).triad()
+    }
+
+    /// The scalar system `t ↦ f(at + t·direction)`
+    ///
+    /// A projection onto one direction, for use with the scalar analyses.
+    fn along(&self, at: [f64; N], direction: [f64; N]) -> Along<'_, Self, N>
+    where
+        Self: Sized,
+    {
+        Along {
+            system: self,
+            at,
+            direction,
+        }
+    }
+}
+
+impl<T: AntifragileN<N> + ?Sized, const N: usize> HessianAnalysis<N> for T {}
+
+/// How two stressors combine, from the sign of the mixed partial derivative
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interaction {
+    /// `∂²f/∂x∂y < 0`: raising one stressor makes the other hurt more (or
+    /// help less), so a joint shock is worse than the two shocks apart
+    Amplifying,
+    /// `∂²f/∂x∂y > 0`: raising one stressor makes the other hurt less (or
+    /// help more), so a joint shock is better than the two shocks apart
+    Dampening,
+    /// The stressors act independently
+    Independent,
+}
+
+/// Extension trait for systems with exactly two stressors
+pub trait CrossConvexity: AntifragileN<2> {
+    /// Central finite-difference estimate of `∂²f/∂x∂y` at `(at_x, at_y)`
+    fn cross_convexity(&self, at_x: f64, at_y: f64, dx: f64, dy: f64) -> f64 {
+        cross_difference(self, [at_x, at_y], (0, dx), (1, dy)) / (dx * dy)
+    }
+
+    /// Classify the interaction of the two stressors at `(at_x, at_y)`
+    ///
+    /// A mixed second difference within `epsilon` of zero, in payoff units,
+    /// counts as [`Interaction::Independent`].
+    fn interaction(&self, at_x: f64, at_y: f64, dx: f64, dy: f64, epsilon: f64) -> Interaction {
+        let cross = cross_difference(self, [at_x, at_y], (0, dx), (1, dy));
+        if cross < -epsilon {
+            Interaction::Amplifying
+        } else if cross > epsilon {
+            Interaction::Dampening
+        } else {
+            Interaction::Independent
+        }
+    }
+}
+
+impl<T: AntifragileN<2> + ?Sized> CrossConvexity for T {}
+
+/// A multi-dimensional system restricted to a line through the stressor space
+#[derive(Debug, Clone, Copy)]
+pub struct Along<'a, T, const N: usize> {
+    system: &'a T,
+    at: [f64; N],
+    direction: [f64; N],
+}
+
+impl<T: AntifragileN<N>, const N: usize> Antifragile for Along<'_, T, N> {
+    /// Distance along the direction from the base point
+    type Stressor = f64;
+    type Payoff = f64;
+
+    fn payoff(&self, t: f64) -> f64 {
+        self.system.payoff(offset(self.at, self.direction, t))
+    }
+}
+
+/// `δᵢ·δⱼ·Hᵢⱼ`: the Hessian scaled by the steps, in payoff units
+pub(crate) fn second_differences<T: AntifragileN<N> + ?Sized, const N: usize>(
+    system: &T,
+    at: [f64; N],
+    delta: [f64; N],
+) -> [[f64; N]; N] {
+    let center = system.payoff(at);
+    let mut result = [[0.0; N]; N];
+    for i in 0..N {
+        let up = system.payoff(step(at, i, delta[i]));
+        let down = system.payoff(step(at, i, -delta[i]));
+        result[i][i] = up + down - 2.0 * center;
+        for j in 0..i {
+            let cross = cross_difference(system, at, (i, delta[i]), (j, delta[j]));
+            result[i][j] = cross;
+            result[j][i] = cross;
+        }
+    }
+    result
+}
+
+/// `δᵢ·δⱼ·∂²f/∂xᵢ∂xⱼ` from the four diagonal corners around `at`
+fn cross_difference<T: AntifragileN<N> + ?Sized, const N: usize>(
+    system: &T,
+    at: [f64; N],
+    (i, di): (usize, f64),
+    (j, dj): (usize, f64),
+) -> f64 {
+    let pp = system.payoff(step(step(at, i, di), j, dj));
+    let pm = system.payoff(step(step(at, i, di), j, -dj));
+    let mp = system.payoff(step(step(at, i, -di), j, dj));
+    let mm = system.payoff(step(step(at, i, -di), j, -dj));
+    (pp - pm - mp + mm) / 4.0
+}
+
+fn step<const N: usize>(mut at: [f64; N], axis: usize, by: f64) -> [f64; N] {
+    at[axis] += by;
+    at
+}
+
+fn offset<const N: usize>(mut at: [f64; N], direction: [f64; N], t: f64) -> [f64; N] {
+    for (x, d) in at.iter_mut().zip(direction) {
+        *x += t * d;
+    }
+    at
+}
+
+/// Count pivot signs of a symmetric matrix by elimination with diagonal pivoting
+pub(crate) fn inertia<const N: usize>(mut matrix: [[f64; N]; N], epsilon: f64) -> Definiteness {
+    let mut remaining = [true; N];
+    let (mut positive, mut negative) = (0, 0);
+
+    for _ in 0..N {
+        let mut pivot = None;
+        for k in (0..N).filter(|&k| remaining[k]) {
+            if pivot.is_none_or(|p: usize| abs(matrix[k][k]) > abs(matrix[p][p])) {
+                pivot = Some(k);
+            }
+        }
+        let Some(p) = pivot else { break };
+        let value = matrix[p][p];
+
+        if abs(value) <= epsilon {
+            // A zero diagonal with a non-zero off-diagonal entry contains a
+            // 2×2 block [[0, a], [a, 0]], which has eigenvalues ±a
+            let coupled = (0..N).any(|i| {
+                remaining[i]
+                    && (0..N).any(|j| remaining[j] && i != j && abs(matrix[i][j]) > epsilon)
+            });
+            if coupled {
+                return Definiteness::Indefinite;
+            }
+            break;
+        }
+
+        if value > 0.0 {
+            positive += 1;
+        } else {
+            negative += 1;
+        }
+        remaining[p] = false;
+        let pivot_row = matrix[p];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            if !remaining[i] {
+                continue;
+            }
+            let factor = row[p] / value;
+            for (j, entry) in row.iter_mut().enumerate() {
+                if remaining[j] {
+                    *entry -= factor * pivot_row[j];
+                }
+            }
+        }
+    }
+
+    match (positive, negative) {
+        (0, 0) => Definiteness::Zero,
+        (_, 0) if positive == N => Definiteness::PositiveDefinite,
+        (_, 0) => Definiteness::PositiveSemidefinite,
+        (0, _) if negative == N => Definiteness::NegativeDefinite,
+        (0, _) => Definiteness::NegativeSemidefinite,
+        _ => Definiteness::Indefinite,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TriadAnalysis;
+
+    /// `xᵀ A x / 2` for a fixed symmetric `A`
+    struct Quadratic([[f64; 3]; 3]);
+
+    impl AntifragileN<3> for Quadratic {
+        fn payoff(&self, x: [f64; 3]) -> f64 {
+            let mut total = 0.0;
+            for i in 0..3 {
+                for j in 0..3 {
+                    total += 0.5 * x[i] * self.0[i][j] * x[j];
+                }
+            }
+            total
+        }
+    }
+
+    const DELTA: [f64; 3] = [0.5, 0.5, 0.5];
+
+    #[test]
+    fn test_hessian_of_quadratic() {
+        let a = [[2.0, 1.0, 0.0], [1.0, 3.0, -1.0], [0.0, -1.0, 4.0]];
+        let hessian = Quadratic(a).hessian([1.0, -2.0, 0.5], [0.1, 0.2, 0.3]);
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((hessian[i][j] - a[i][j]).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_definiteness() {
+        let cases = [
+            (
+                [[2.0, 1.0, 0.0], [1.0, 3.0, -1.0], [0.0, -1.0, 4.0]],
+                Definiteness::PositiveDefinite,
+            ),
+            (
+                [[-2.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, -3.0]],
+                Definiteness::NegativeDefinite,
+            ),
+            (
+                [[1.0, 1.0, 0.0], [1.0, 1.0, 0.0], [0.0, 0.0, 0.0]],
+                Definiteness::PositiveSemidefinite,
+            ),
+            (
+                [[1.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, 1.0]],
+                Definiteness::Indefinite,
+            ),
+            (
+                [[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 0.0]],
+                Definiteness::Indefinite,
+            ),
+            ([[0.0; 3]; 3], Definiteness::Zero),
+        ];
+        for (a, expected) in cases {
+            assert_eq!(Quadratic(a).definiteness([0.0; 3], DELTA, 1e-9), expected);
+        }
+    }
+
+    #[test]
+    fn test_classify_hessian() {
+        let convex = Quadratic([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+        assert_eq!(
+            convex.classify_hessian([0.0; 3], DELTA, 1e-9),
+            Some(Triad::Antifragile)
+        );
+        let saddle = Quadratic([[1.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, 0.0]]);
+        assert_eq!(saddle.classify_hessian([0.0; 3], DELTA, 1e-9), None);
+    }
+
+    struct Pair(fn(f64, f64) -> f64);
+
+    impl AntifragileN<2> for Pair {
+        fn payoff(&self, [x, y]: [f64; 2]) -> f64 {
+            (self.0)(x, y)
+        }
+    }
+
+    #[test]
+    fn test_cross_convexity_and_interaction() {
+        let compounding = Pair(|x, y| -x * y - x * x);
+        assert!((compounding.cross_convexity(1.0, 2.0, 0.1, 0.3) + 1.0).abs() < 1e-9);
+        assert_eq!(
+            compounding.interaction(1.0, 2.0, 0.1, 0.3, 1e-12),
+            Interaction::Amplifying
+        );
+
+        let offsetting = Pair(|x, y| 2.0 * x * y);
+        assert_eq!(
+            offsetting.interaction(0.0, 0.0, 0.5, 0.5, 1e-12),
+            Interaction::Dampening
+        );
+
+        let separable = Pair(|x, y| x * x - y * y * y);
+        assert_eq!(
+            separable.interaction(1.0, 1.0, 0.5, 0.5, 1e-12),
+            Interaction::Independent
+        );
+    }
+
+    #[test]
+    fn test_projection_agrees_with_directional_curvature() {
+        let saddle = Quadratic([[1.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, 0.0]]);
+        let x_axis = saddle.along([0.0; 3], [1.0, 0.0, 0.0]);
+        let y_axis = saddle.along([0.0; 3], [0.0, 1.0, 0.0]);
+        assert_eq!(x_axis.classify(0.0, 0.5), Triad::Antifragile);
+        assert_eq!(y_axis.classify(0.0, 0.5), Triad::Fragile);
+    }
+}
@@ -0,0 +1,223 @@
+//! # Generic numerics via `num-traits`
+//!
+//! [`classify`](crate::TriadAnalysis::classify) needs nothing from a payoff
+//! beyond the [`Antifragile`](crate::Antifragile) bounds: `Copy`, `Add`, and
+//! `PartialOrd`. Other analyses ask for more: `Default` as a zero in
+//! [`classify_detailed`](crate::TriadAnalysis::classify_detailed), and
+//! `Into<f64>` for [`convexity`](crate::TriadAnalysis::convexity) and
+//! [`classify_checked`](crate::TriadAnalysis::classify_checked). Types from
+//! the `num-traits` ecosystem, and primitives such as `i128` that have no
+//! lossless conversion to `f64`, often miss one of those.
+//!
+//! [`Numeric`](crate::numeric::Numeric) adapts any such value, taking its
+//! zero from [`Zero`](num_traits::Zero) and its `f64` from
+//! [`ToPrimitive`](num_traits::ToPrimitive).
+//! [`numeric`](crate::numeric::IntoNumeric::numeric) wraps a whole system so
+//! its payoffs are adapted without touching the model:
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad, TriadAnalysis};
+//! use antifragile::numeric::IntoNumeric;
+//!
+//! /// Requests served, counted exactly
+//! struct Served;
+//!
+//! impl Antifragile for Served {
+//!     type Stressor = i128;
+//!     type Payoff = i128;
+//!
+//!     fn payoff(&self, load: i128) -> i128 {
+//!         load * load
+//!     }
+//! }
+//!
+//! // `i128` has no `Into<f64>`, so the convexity score needs the adapter
+//! let served = Served.numeric();
+//! assert_eq!(served.classify(1_000, 10), Triad::Antifragile);
+//! assert!((served.convexity(1_000, 10) - 1e-4).abs() < 1e-12);
+//! assert_eq!(served.classify_detailed(1_000, 10).difference.0, 200);
+//! ```
+
+use core::fmt::Display;
+use core::ops::{Add, Sub};
+
+use num_traits::{ToPrimitive, Zero};
+
+use crate::Antifragile;
+
+/// A numeric value adapted to the bounds the analyses expect
+///
+/// `Default` is [`Zero::zero`] and the `f64` conversion is
+/// [`ToPrimitive::to_f64`], NaN when the value does not fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Numeric<T>(pub T);
+
+impl<T: Add<Output = T>> Add for Numeric<T> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Numeric<T> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl<T: Zero> Default for Numeric<T> {
+    fn default() -> Self {
+        Self(T::zero())
+    }
+}
+
+impl<T: ToPrimitive> From<Numeric<T>> for f64 {
+    fn from(value: Numeric<T>) -> Self {
+        value.0.to_f64().unwrap_or(Self::NAN)
+    }
+}
+
+impl<T: Display> Display for Numeric<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// A system whose payoffs are wrapped in [`Numeric`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct NumericSystem<T> {
+    inner: T,
+}
+
+impl<T> NumericSystem<T> {
+    /// Adapt the payoffs of `inner`
+    pub const fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    /// Get reference to the wrapped system
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Unwrap the adapted system
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Antifragile> Antifragile for NumericSystem<T> {
+    type Stressor = T::Stressor;
+    type Payoff = Numeric<T::Payoff>;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        Numeric(self.inner.payoff(stressor))
+    }
+
+    #[inline]
+    fn twin(r: Self::Payoff) -> Self::Payoff {
+        Numeric(T::twin(r.0))
+    }
+}
+
+/// Extension trait for adapting a system's payoffs fluently
+pub trait IntoNumeric: Antifragile + Sized {
+    /// Wrap every payoff in [`Numeric`] (see [`NumericSystem`])
+    fn numeric(self) -> NumericSystem<Self> {
+        NumericSystem::new(self)
+    }
+}
+
+impl<T: Antifragile> IntoNumeric for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Classification, Triad, TriadAnalysis};
+
+    /// Money in whole cents, with no `Default` or `Into<f64>` of its own
+    #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+    struct Cents(i64);
+
+    impl Add for Cents {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self {
+            Self(self.0 + rhs.0)
+        }
+    }
+
+    impl Sub for Cents {
+        type Output = Self;
+        fn sub(self, rhs: Self) -> Self {
+            Self(self.0 - rhs.0)
+        }
+    }
+
+    impl Zero for Cents {
+        fn zero() -> Self {
+            Self(0)
+        }
+        fn is_zero(&self) -> bool {
+            self.0 == 0
+        }
+    }
+
+    impl ToPrimitive for Cents {
+        fn to_i64(&self) -> Option<i64> {
+            Some(self.0)
+        }
+        fn to_u64(&self) -> Option<u64> {
+            self.0.to_u64()
+        }
+    }
+
+    struct Fees;
+
+    impl Antifragile for Fees {
+        type Stressor = i64;
+        type Payoff = Cents;
+        fn payoff(&self, trades: i64) -> Cents {
+            Cents(250 * trades - trades * trades)
+        }
+    }
+
+    #[test]
+    fn test_classify_needs_no_default() {
+        assert_eq!(Fees.classify(10, 2), Triad::Fragile);
+        assert_eq!(Fees.classify_with_tolerance(10, 2, Cents(8)), Triad::Robust);
+    }
+
+    #[test]
+    fn test_adapter_supplies_zero_and_f64() {
+        let fees = Fees.numeric();
+        let detail = fees.classify_detailed(10, 2);
+        assert_eq!(detail.difference, Numeric(Cents(8)));
+        assert_eq!(detail.at, Numeric(Cents(2400)));
+        assert_eq!(fees.classify_checked(10, 2, 1.0), Classification::Fragile);
+        assert_eq!(fees.classify_checked(10, 0, 1.0).triad(), None);
+        assert_eq!(fees.inner().payoff(1), Cents(249));
+    }
+
+    #[test]
+    fn test_out_of_range_values_convert_to_nan() {
+        struct Opaque;
+        impl ToPrimitive for Opaque {
+            fn to_i64(&self) -> Option<i64> {
+                None
+            }
+            fn to_u64(&self) -> Option<u64> {
+                None
+            }
+        }
+
+        assert!(f64::from(Numeric(Cents(-3))) < 0.0);
+        assert!(f64::from(Numeric(u128::MAX)) > 3e38);
+        assert!(f64::from(Numeric(Opaque)).is_nan());
+    }
+}
@@ -0,0 +1,310 @@
+//! # OpenTelemetry classification metrics and events
+//!
+//! For observability stacks built on OTLP,
+//! [`Recorder`](crate::otel::Recorder) records a monitored system's
+//! classifications through the [`opentelemetry`](https://docs.rs/opentelemetry)
+//! API, to whichever `MeterProvider` and `TracerProvider` the application
+//! installed. Without them, recording does nothing.
+//!
+//! | Instrument | Kind | Value |
+//! |------------|------|-------|
+//! | `antifragile.classification.rank` | Gauge | 0 fragile, 1 robust, 2 antifragile |
+//! | `antifragile.convexity` | Gauge | Measured convexity, when known |
+//! | `antifragile.classification.transitions` | Counter | Changes of classification |
+//!
+//! Every measurement carries the attributes `antifragile.system` and
+//! `antifragile.classification`, with the lowercase classification name;
+//! transitions also carry `antifragile.classification.previous`. Each
+//! transition is added as an `antifragile.classification.transition` event
+//! to the active span, so it shows up alongside the trace that caused it.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::otel::Recorder;
+//!
+//! let recorder = Recorder::global("checkout");
+//! recorder.record(Triad::Robust, Some(0.01));
+//! // A transition: counted, and added to the active span
+//! recorder.record(Triad::Fragile, Some(-0.8));
+//! assert_eq!(recorder.last(), Some(Triad::Fragile));
+//! ```
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Gauge, Meter};
+use opentelemetry::trace::get_active_span;
+
+use crate::Triad;
+use crate::stream::TriadChange;
+
+/// Attribute naming the monitored system
+pub const SYSTEM: &str = "antifragile.system";
+
+/// Attribute holding the lowercase classification name
+pub const CLASSIFICATION: &str = "antifragile.classification";
+
+/// Attribute holding the classification before a transition
+pub const PREVIOUS_CLASSIFICATION: &str = "antifragile.classification.previous";
+
+/// Attribute holding the convexity on transition events
+pub const CONVEXITY: &str = "antifragile.convexity";
+
+/// Gauge holding [`Triad::rank`] of the latest classification
+pub const RANK_METRIC: &str = "antifragile.classification.rank";
+
+/// Gauge holding the latest convexity
+pub const CONVEXITY_METRIC: &str = "antifragile.convexity";
+
+/// Counter of classification transitions
+pub const TRANSITIONS_METRIC: &str = "antifragile.classification.transitions";
+
+/// Span event recorded on each transition
+pub const TRANSITION_EVENT: &str = "antifragile.classification.transition";
+
+/// Marks that nothing has been recorded yet
+const NONE: u8 = u8::MAX;
+
+/// Records one system's classifications as OpenTelemetry metrics and
+/// transition events
+///
+/// Remembers the last classification recorded, so that a change between
+/// two calls to [`record`](Self::record) counts as a transition.
+#[derive(Debug)]
+pub struct Recorder {
+    system: KeyValue,
+    rank: Gauge<u64>,
+    convexity: Gauge<f64>,
+    transitions: Counter<u64>,
+    last: AtomicU8,
+}
+
+impl Recorder {
+    /// A recorder for `system` with instruments from `meter`
+    #[must_use]
+    pub fn new(meter: &Meter, system: impl Into<String>) -> Self {
+        Self {
+            system: KeyValue::new(SYSTEM, system.into()),
+            rank: meter
+                .u64_gauge(RANK_METRIC)
+                .with_description("Triad rank: 0 fragile, 1 robust, 2 antifragile")
+                .build(),
+            convexity: meter
+                .f64_gauge(CONVEXITY_METRIC)
+                .with_description("Measured convexity of the payoff")
+                .build(),
+            transitions: meter
+                .u64_counter(TRANSITIONS_METRIC)
+                .with_description("Changes of Triad classification")
+                .with_unit("{transition}")
+                .build(),
+            last: AtomicU8::new(NONE),
+        }
+    }
+
+    /// A recorder for `system` using the global meter provider's
+    /// `antifragile` meter
+    #[must_use]
+    pub fn global(system: impl Into<String>) -> Self {
+        Self::new(&opentelemetry::global::meter("antifragile"), system)
+    }
+
+    /// Record a classification and its convexity, if known
+    ///
+    /// Counts a transition, and adds it to the active span, when the
+    /// classification differs from the last one recorded.
+    pub fn record(&self, triad: Triad, convexity: Option<f64>) {
+        let attributes = [self.system.clone(), classification(CLASSIFICATION, triad)];
+        self.rank.record(u64::from(triad.rank()), &attributes);
+        if let Some(convexity) = convexity {
+            self.convexity.record(convexity, &attributes);
+        }
+        if let Some(previous) = self.replace_last(triad) {
+            if previous != triad {
+                self.transition(previous, triad, convexity);
+            }
+        }
+    }
+
+    /// Record a regime change reported by a
+    /// [`ChangeDetector`](crate::stream::ChangeDetector)
+    ///
+    /// Counts the transition even if its earlier regime was never
+    /// recorded here.
+    pub fn record_change(&self, change: &TriadChange) {
+        let attributes = [
+            self.system.clone(),
+            classification(CLASSIFICATION, change.to),
+        ];
+        self.rank.record(u64::from(change.to.rank()), &attributes);
+        self.convexity.record(change.convexity, &attributes);
+        self.replace_last(change.to);
+        self.transition(change.from, change.to, Some(change.convexity));
+    }
+
+    /// The last classification recorded
+    #[must_use]
+    pub fn last(&self) -> Option<Triad> {
+        Triad::ALL
+            .get(usize::from(self.last.load(Ordering::Relaxed)))
+            .copied()
+    }
+
+    fn replace_last(&self, triad: Triad) -> Option<Triad> {
+        let previous = self.last.swap(triad.rank(), Ordering::Relaxed);
+        Triad::ALL.get(usize::from(previous)).copied()
+    }
+
+    fn transition(&self, from: Triad, to: Triad, convexity: Option<f64>) {
+        let mut attributes = vec![
+            self.system.clone(),
+            classification(CLASSIFICATION, to),
+            classification(PREVIOUS_CLASSIFICATION, from),
+        ];
+        self.transitions.add(1, &attributes);
+        if let Some(convexity) = convexity {
+            attributes.push(KeyValue::new(CONVEXITY, convexity));
+        }
+        get_active_span(|span| span.add_event(TRANSITION_EVENT, attributes));
+    }
+}
+
+/// `key` set to the lowercase name of `triad`
+fn classification(key: &'static str, triad: Triad) -> KeyValue {
+    KeyValue::new(key, <&'static str>::from(triad))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    use opentelemetry::metrics::{InstrumentBuilder, InstrumentProvider, SyncInstrument};
+
+    /// `(instrument, value, attributes)` of every measurement
+    type Log = Arc<Mutex<Vec<(String, f64, Vec<KeyValue>)>>>;
+
+    #[derive(Default)]
+    struct Capture(Log);
+
+    struct Instrument {
+        name: String,
+        log: Log,
+    }
+
+    impl SyncInstrument<u64> for Instrument {
+        #[allow(clippy::cast_precision_loss)]
+        fn measure(&self, value: u64, attributes: &[KeyValue]) {
+            self.log
+                .lock()
+                .unwrap()
+                .push((self.name.clone(), value as f64, attributes.to_vec()));
+        }
+    }
+
+    impl SyncInstrument<f64> for Instrument {
+        fn measure(&self, value: f64, attributes: &[KeyValue]) {
+            self.log
+                .lock()
+                .unwrap()
+                .push((self.name.clone(), value, attributes.to_vec()));
+        }
+    }
+
+    impl Capture {
+        fn instrument(&self, name: &str) -> Arc<Instrument> {
+            Arc::new(Instrument {
+                name: name.to_owned(),
+                log: Arc::clone(&self.0),
+            })
+        }
+    }
+
+    impl InstrumentProvider for Capture {
+        fn u64_counter(&self, builder: InstrumentBuilder<'_, Counter<u64>>) -> Counter<u64> {
+            Counter::new(self.instrument(&builder.name))
+        }
+
+        fn u64_gauge(&self, builder: InstrumentBuilder<'_, Gauge<u64>>) -> Gauge<u64> {
+            Gauge::new(self.instrument(&builder.name))
+        }
+
+        fn f64_gauge(&self, builder: InstrumentBuilder<'_, Gauge<f64>>) -> Gauge<f64> {
+            Gauge::new(self.instrument(&builder.name))
+        }
+    }
+
+    fn attribute(attributes: &[KeyValue], key: &str) -> Option<String> {
+        attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == key)
+            .map(|kv| kv.value.to_string())
+    }
+
+    #[test]
+    fn test_transitions_are_counted_once() {
+        let capture = Arc::new(Capture::default());
+        let log = Arc::clone(&capture.0);
+        let recorder = Recorder::new(&Meter::new(capture), "checkout");
+
+        recorder.record(Triad::Robust, None);
+        recorder.record(Triad::Robust, Some(0.0));
+        recorder.record(Triad::Fragile, Some(-0.5));
+
+        let log = log.lock().unwrap();
+        let transitions: Vec<_> = log
+            .iter()
+            .filter(|(name, ..)| name == TRANSITIONS_METRIC)
+            .collect();
+        assert_eq!(transitions.len(), 1);
+        let (_, count, attributes) = transitions[0];
+        assert!((count - 1.0).abs() < f64::EPSILON);
+        assert_eq!(attribute(attributes, SYSTEM).as_deref(), Some("checkout"));
+        assert_eq!(
+            attribute(attributes, CLASSIFICATION).as_deref(),
+            Some("fragile")
+        );
+        assert_eq!(
+            attribute(attributes, PREVIOUS_CLASSIFICATION).as_deref(),
+            Some("robust")
+        );
+
+        let ranks: Vec<f64> = log
+            .iter()
+            .filter(|(name, ..)| name == RANK_METRIC)
+            .map(|&(_, rank, _)| rank)
+            .collect();
+        assert_eq!(ranks, [1.0, 1.0, 0.0]);
+        assert_eq!(
+            log.iter()
+                .filter(|(name, ..)| name == CONVEXITY_METRIC)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_record_change_from_detector() {
+        let capture = Arc::new(Capture::default());
+        let log = Arc::clone(&capture.0);
+        let recorder = Recorder::new(&Meter::new(capture), "search");
+        assert_eq!(recorder.last(), None);
+
+        recorder.record_change(&TriadChange {
+            observation: 40,
+            from: Triad::Antifragile,
+            to: Triad::Fragile,
+            convexity: -1.5,
+        });
+
+        assert_eq!(recorder.last(), Some(Triad::Fragile));
+        let log = log.lock().unwrap();
+        assert!(
+            log.iter()
+                .any(|(name, value, attributes)| name == TRANSITIONS_METRIC
+                    && (value - 1.0).abs() < f64::EPSILON
+                    && attribute(attributes, PREVIOUS_CLASSIFICATION).as_deref()
+                        == Some("antifragile"))
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! # Path-dependent payoffs
+//!
+//! A stop-loss, a margin call, or a circuit breaker responds to the *path*
+//! of stress, not to a single level: the same average load can be harmless
+//! when it arrives smoothly and ruinous when it swings. Such systems
+//! implement [`PathAntifragile`](crate::path::PathAntifragile), whose payoff
+//! takes a slice of stressors.
+//!
+//! The point test compares `f(x+Δ) + f(x-Δ)` with `2·f(x)`.
+//! [`classify_path`](crate::path::PathAnalysis::classify_path) lifts it to
+//! paths: the smooth path holds `x` for every step, and the two volatile
+//! paths zigzag between `x+Δ` and `x-Δ`, one starting up and one starting
+//! down. All three have mean `x`, and starting both ways keeps the verdict
+//! from depending on which swing comes first. A system that is better off
+//! under the zigzags is antifragile to path volatility.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::path::{PathAnalysis, PathAntifragile};
+//!
+//! /// A fund, with returns in percent, liquidated once it falls `limit`
+//! /// points below its running peak
+//! struct StopLoss {
+//!     limit: f64,
+//! }
+//!
+//! impl PathAntifragile for StopLoss {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, returns: &[f64]) -> f64 {
+//!         let (mut wealth, mut peak) = (0.0_f64, 0.0_f64);
+//!         for r in returns {
+//!             wealth += r;
+//!             peak = peak.max(wealth);
+//!             if peak - wealth > self.limit {
+//!                 break;
+//!             }
+//!         }
+//!         wealth
+//!     }
+//! }
+//!
+//! let fund = StopLoss { limit: 5.0 };
+//! // Swings of ±10% around a 1% daily drift trip the stop-loss
+//! assert_eq!(fund.classify_path(1.0, 10.0, 20), Triad::Fragile);
+//! // Swings of ±3% never draw down far enough to matter
+//! assert_eq!(fund.classify_path(1.0, 3.0, 20), Triad::Robust);
+//! ```
+
+use core::ops::{Add, Sub};
+
+use crate::Triad;
+
+/// A system whose payoff depends on the whole path of stress
+///
+/// The path counterpart of [`Antifragile`](crate::Antifragile), with the
+/// same bounds on stressor and payoff.
+pub trait PathAntifragile {
+    /// The type of stressor applied at each step of a path
+    type Stressor: Copy + Add<Output = Self::Stressor> + Sub<Output = Self::Stressor>;
+
+    /// The type of payoff/outcome (must be comparable and additive)
+    type Payoff: Copy + Add<Output = Self::Payoff> + PartialOrd;
+
+    /// The outcome after experiencing `path`, first step first
+    fn payoff(&self, path: &[Self::Stressor]) -> Self::Payoff;
+
+    /// Returns the payoff added to itself (r + r)
+    ///
+    /// See [`Antifragile::twin`](crate::Antifragile::twin).
+    fn twin(r: Self::Payoff) -> Self::Payoff {
+        r + r
+    }
+}
+
+/// Extension trait comparing volatile and smooth stress paths
+pub trait PathAnalysis: PathAntifragile {
+    /// Classify by zigzag paths of `steps` steps around `at`
+    ///
+    /// Compares `f(up-first) + f(down-first)` against `2·f(smooth)`, where
+    /// the smooth path is `at` at every step and the zigzags alternate
+    /// `at ± delta`.
+    fn classify_path(&self, at: Self::Stressor, delta: Self::Stressor, steps: usize) -> Triad {
+        let (up, down) = (at + delta, at - delta);
+        let zigzag = |first, second| -> Vec<Self::Stressor> {
+            (0..steps)
+                .map(|step| if step % 2 == 0 { first } else { second })
+                .collect()
+        };
+        let sum = self.payoff(&zigzag(up, down)) + self.payoff(&zigzag(down, up));
+        let twin = Self::twin(self.payoff(&vec![at; steps]));
+        compare(sum, twin)
+    }
+
+    /// Compare a volatile path against a smooth one
+    ///
+    /// [`Antifragile`](Triad::Antifragile) if the system does better under
+    /// `volatile`. The comparison is only meaningful when both paths have
+    /// the same mean, which is left to the caller.
+    fn compare_paths(&self, volatile: &[Self::Stressor], smooth: &[Self::Stressor]) -> Triad {
+        compare(self.payoff(volatile), self.payoff(smooth))
+    }
+}
+
+impl<T: PathAntifragile + ?Sized> PathAnalysis for T {}
+
+/// The classification for `volatile` against `smooth`
+fn compare<P: Copy + PartialOrd>(volatile: P, smooth: P) -> Triad {
+    if volatile > smooth {
+        Triad::Antifragile
+    } else if volatile < smooth {
+        Triad::Fragile
+    } else {
+        Triad::Robust
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Total stress, insensitive to ordering and volatility
+    struct Total;
+
+    impl PathAntifragile for Total {
+        type Stressor = i64;
+        type Payoff = i64;
+
+        fn payoff(&self, path: &[i64]) -> i64 {
+            path.iter().sum()
+        }
+    }
+
+    /// Rewards every swing: the sum of squared steps
+    struct Energy;
+
+    impl PathAntifragile for Energy {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&self, path: &[f64]) -> f64 {
+            path.iter().map(|x| x * x).sum()
+        }
+    }
+
+    /// Accumulates steps until ruined by the first one below `floor`
+    struct Ruin {
+        floor: i64,
+    }
+
+    impl PathAntifragile for Ruin {
+        type Stressor = i64;
+        type Payoff = i64;
+
+        fn payoff(&self, path: &[i64]) -> i64 {
+            path.iter().take_while(|step| **step >= self.floor).sum()
+        }
+    }
+
+    #[test]
+    fn test_linear_and_convex_paths() {
+        assert_eq!(Total.classify_path(5, 3, 10), Triad::Robust);
+        assert_eq!(Energy.classify_path(1.0, 0.5, 10), Triad::Antifragile);
+        assert_eq!(Energy.classify_path(1.0, 0.5, 0), Triad::Robust);
+    }
+
+    #[test]
+    fn test_order_of_swings_matters() {
+        let ruin = Ruin { floor: 0 };
+        // Down-first is ruined at once, up-first banks one step before ruin
+        assert_eq!(ruin.payoff(&[-1, 3, -1, 3]), 0);
+        assert_eq!(ruin.payoff(&[3, -1, 3, -1]), 3);
+        assert_eq!(ruin.classify_path(1, 2, 4), Triad::Fragile);
+        assert_eq!(ruin.compare_paths(&[3, -1, 3, -1], &[1; 4]), Triad::Fragile);
+        assert_eq!(ruin.compare_paths(&[2, 0, 2, 0], &[1; 4]), Triad::Robust);
+    }
+}
@@ -0,0 +1,374 @@
+//! # Piecewise-linear payoffs
+//!
+//! Tiered pricing, insurance layers, tranches, and option combinations are
+//! all straight lines joined at breakpoints. Their convexity lives entirely
+//! in the kinks: a slope that increases across a breakpoint is convex there,
+//! one that decreases is concave.
+//!
+//! [`PiecewiseLinear`](crate::piecewise::PiecewiseLinear) stores the
+//! breakpoints and classifies from the slopes directly, so there is no
+//! finite-difference step to tune and no rounding in the verdict. The second
+//! difference `f(x+Δ) + f(x-Δ) - 2·f(x)` is the sum over kinks of the slope
+//! change times `max(0, Δ - |k - x|)`, which
+//! [`second_difference`](crate::piecewise::PiecewiseLinear::second_difference)
+//! computes in closed form.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::piecewise::PiecewiseLinear;
+//!
+//! // Volume pricing: $10/unit up to 100 units, $8/unit up to 500, then $5
+//! let revenue = PiecewiseLinear::new([(0.0, 0.0), (100.0, 1000.0), (500.0, 4200.0)])?
+//!     .with_right_slope(5.0);
+//!
+//! assert_eq!(revenue.slopes(), vec![10.0, 10.0, 8.0, 5.0]);
+//! assert_eq!(revenue.classify_exact(100.0, 10.0), Triad::Fragile);
+//! assert_eq!(revenue.classify_exact(300.0, 10.0), Triad::Robust);
+//! assert_eq!(revenue.global_classification(), Some(Triad::Fragile));
+//! # Ok::<(), antifragile::piecewise::PiecewiseError>(())
+//! ```
+
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::{Antifragile, Triad};
+
+/// Error returned when breakpoints do not describe a piecewise-linear function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PiecewiseError {
+    /// Fewer than two breakpoints were supplied
+    TooFewBreakpoints {
+        /// Number of breakpoints supplied
+        found: usize,
+    },
+    /// A breakpoint contained a NaN or infinite value
+    NonFinite {
+        /// Position of the offending breakpoint in the input
+        index: usize,
+    },
+    /// A breakpoint's stressor did not exceed the previous one
+    NotIncreasing {
+        /// Position of the offending breakpoint in the input
+        index: usize,
+    },
+}
+
+impl Display for PiecewiseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooFewBreakpoints { found } => {
+                write!(f, "too few breakpoints: found {found}, need at least 2")
+            }
+            Self::NonFinite { index } => write!(f, "non-finite breakpoint at index {index}"),
+            Self::NotIncreasing { index } => write!(
+                f,
+                "breakpoint at index {index} does not increase the stressor"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PiecewiseError {}
+
+/// A change of slope at a breakpoint
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Kink {
+    /// Stressor value of the breakpoint
+    pub stressor: f64,
+    /// Slope to the right minus slope to the left
+    pub slope_change: f64,
+}
+
+impl Kink {
+    /// Convex kinks are antifragile, concave ones fragile, straight ones robust
+    pub fn classification(&self) -> Triad {
+        if self.slope_change > 0.0 {
+            Triad::Antifragile
+        } else if self.slope_change < 0.0 {
+            Triad::Fragile
+        } else {
+            Triad::Robust
+        }
+    }
+}
+
+/// A continuous payoff made of straight segments joined at breakpoints
+///
+/// Outside the first and last breakpoint the payoff continues in a straight
+/// line, by default with the slope of the adjacent segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiecewiseLinear {
+    stressors: Vec<f64>,
+    payoffs: Vec<f64>,
+    left_slope: f64,
+    right_slope: f64,
+}
+
+impl PiecewiseLinear {
+    /// Build from `(stressor, payoff)` breakpoints in increasing stressor order
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseError::NonFinite`] if any value is NaN or infinite,
+    /// [`PiecewiseError::NotIncreasing`] if the stressors are not strictly
+    /// increasing, and [`PiecewiseError::TooFewBreakpoints`] for fewer than
+    /// two breakpoints.
+    pub fn new(breakpoints: impl IntoIterator<Item = (f64, f64)>) -> Result<Self, PiecewiseError> {
+        let mut stressors: Vec<f64> = Vec::new();
+        let mut payoffs = Vec::new();
+        for (index, (stressor, payoff)) in breakpoints.into_iter().enumerate() {
+            if !stressor.is_finite() || !payoff.is_finite() {
+                return Err(PiecewiseError::NonFinite { index });
+            }
+            if stressors.last().is_some_and(|&last| stressor <= last) {
+                return Err(PiecewiseError::NotIncreasing { index });
+            }
+            stressors.push(stressor);
+            payoffs.push(payoff);
+        }
+
+        let found = stressors.len();
+        if found < 2 {
+            return Err(PiecewiseError::TooFewBreakpoints { found });
+        }
+
+        let left_slope = (payoffs[1] - payoffs[0]) / (stressors[1] - stressors[0]);
+        let right_slope = (payoffs[found - 1] - payoffs[found - 2])
+            / (stressors[found - 1] - stressors[found - 2]);
+        Ok(Self {
+            stressors,
+            payoffs,
+            left_slope,
+            right_slope,
+        })
+    }
+
+    /// Set the slope below the first breakpoint
+    ///
+    /// A slope that differs from the first segment adds a kink there.
+    #[must_use]
+    pub fn with_left_slope(mut self, slope: f64) -> Self {
+        self.left_slope = slope;
+        self
+    }
+
+    /// Set the slope above the last breakpoint
+    ///
+    /// A slope that differs from the last segment adds a kink there.
+    #[must_use]
+    pub fn with_right_slope(mut self, slope: f64) -> Self {
+        self.right_slope = slope;
+        self
+    }
+
+    /// The breakpoints as `(stressor, payoff)` pairs
+    pub fn breakpoints(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.stressors
+            .iter()
+            .copied()
+            .zip(self.payoffs.iter().copied())
+    }
+
+    /// Number of breakpoints
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.stressors.len()
+    }
+
+    /// Always false: construction requires at least two breakpoints
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.stressors.is_empty()
+    }
+
+    /// Slopes from left to right: the left tail, each segment, the right tail
+    #[must_use]
+    pub fn slopes(&self) -> Vec<f64> {
+        let mut slopes = Vec::with_capacity(self.stressors.len() + 1);
+        slopes.push(self.left_slope);
+        slopes.extend(
+            self.stressors
+                .windows(2)
+                .zip(self.payoffs.windows(2))
+                .map(|(x, y)| (y[1] - y[0]) / (x[1] - x[0])),
+        );
+        slopes.push(self.right_slope);
+        slopes
+    }
+
+    /// The slope change at every breakpoint
+    #[must_use]
+    pub fn kinks(&self) -> Vec<Kink> {
+        self.slopes()
+            .windows(2)
+            .zip(&self.stressors)
+            .map(|(pair, &stressor)| Kink {
+                stressor,
+                slope_change: pair[1] - pair[0],
+            })
+            .collect()
+    }
+
+    /// Exact `f(x+Δ) + f(x-Δ) - 2·f(x)`, computed from the kinks
+    ///
+    /// Kinks strictly inside `(x - |Δ|, x + |Δ|)` contribute their slope
+    /// change weighted by their distance from the probe edges.
+    #[must_use]
+    pub fn second_difference(&self, at: f64, delta: f64) -> f64 {
+        let reach = delta.abs();
+        self.kinks()
+            .iter()
+            .map(|kink| kink.slope_change * (reach - (kink.stressor - at).abs()).max(0.0))
+            .sum()
+    }
+
+    /// Classify `at` from the kinks within `delta` of it
+    ///
+    /// Agrees with [`TriadAnalysis::classify`](crate::TriadAnalysis::classify)
+    /// whenever the latter's arithmetic is exact, and is free of rounding
+    /// error otherwise.
+    pub fn classify_exact(&self, at: f64, delta: f64) -> Triad {
+        let curvature = self.second_difference(at, delta);
+        if curvature > 0.0 {
+            Triad::Antifragile
+        } else if curvature < 0.0 {
+            Triad::Fragile
+        } else {
+            Triad::Robust
+        }
+    }
+
+    /// Classification of the payoff as a whole
+    ///
+    /// `Some(Antifragile)` if every kink is convex, `Some(Fragile)` if every
+    /// kink is concave, `Some(Robust)` if the payoff is a single straight
+    /// line, and `None` if it has both convex and concave kinks.
+    #[must_use]
+    pub fn global_classification(&self) -> Option<Triad> {
+        let kinks = self.kinks();
+        let convex = kinks.iter().any(|kink| kink.slope_change > 0.0);
+        let concave = kinks.iter().any(|kink| kink.slope_change < 0.0);
+        match (convex, concave) {
+            (true, true) => None,
+            (true, false) => Some(Triad::Antifragile),
+            (false, true) => Some(Triad::Fragile),
+            (false, false) => Some(Triad::Robust),
+        }
+    }
+
+    /// Evaluate the payoff at `stressor`
+    #[must_use]
+    pub fn evaluate(&self, stressor: f64) -> f64 {
+        // NaN fails both tail checks below and has no segment
+        if stressor.is_nan() {
+            return f64::NAN;
+        }
+        let last = self.stressors.len() - 1;
+        if stressor <= self.stressors[0] {
+            return self.payoffs[0] + self.left_slope * (stressor - self.stressors[0]);
+        }
+        if stressor >= self.stressors[last] {
+            return self.payoffs[last] + self.right_slope * (stressor - self.stressors[last]);
+        }
+        let right = self.stressors.partition_point(|&x| x <= stressor);
+        let (x0, x1) = (self.stressors[right - 1], self.stressors[right]);
+        let (y0, y1) = (self.payoffs[right - 1], self.payoffs[right]);
+        y0 + (y1 - y0) * (stressor - x0) / (x1 - x0)
+    }
+}
+
+impl Antifragile for PiecewiseLinear {
+    type Stressor = f64;
+    type Payoff = f64;
+
+    fn payoff(&self, stressor: f64) -> f64 {
+        self.evaluate(stressor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TriadAnalysis;
+
+    fn call(strike: f64) -> PiecewiseLinear {
+        PiecewiseLinear::new([(strike - 1.0, 0.0), (strike, 0.0)])
+            .unwrap()
+            .with_right_slope(1.0)
+    }
+
+    #[test]
+    fn test_call_is_convex_only_near_strike() {
+        let option = call(100.0);
+        assert!((option.evaluate(90.0)).abs() < f64::EPSILON);
+        assert!((option.evaluate(112.5) - 12.5).abs() < f64::EPSILON);
+        assert_eq!(option.classify_exact(100.0, 5.0), Triad::Antifragile);
+        assert_eq!(option.classify_exact(110.0, 5.0), Triad::Robust);
+        assert_eq!(option.global_classification(), Some(Triad::Antifragile));
+    }
+
+    #[test]
+    fn test_second_difference_is_exact() {
+        let option = call(100.0);
+        // Kink of +1 at 100, probed from 98 with Δ = 5: weight 5 - 2 = 3
+        assert!((option.second_difference(98.0, 5.0) - 3.0).abs() < f64::EPSILON);
+        assert!((option.second_difference(98.0, -5.0) - 3.0).abs() < f64::EPSILON);
+        // A kink exactly at the probe edge contributes nothing
+        assert!(option.second_difference(95.0, 5.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_agrees_with_finite_differences() {
+        // Long a put at 90 and short a call at 110: a risk reversal
+        let payoff = PiecewiseLinear::new([(90.0, 0.0), (110.0, 0.0)])
+            .unwrap()
+            .with_left_slope(-1.0)
+            .with_right_slope(-1.0);
+        for at in [85.0, 90.0, 100.0, 108.0, 110.0, 120.0] {
+            assert_eq!(payoff.classify_exact(at, 4.0), payoff.classify(at, 4.0));
+        }
+        assert_eq!(payoff.global_classification(), None);
+        assert_eq!(payoff.kinks().len(), 2);
+    }
+
+    #[test]
+    fn test_nan_stressor_yields_nan() {
+        let option = call(100.0);
+        assert!(option.evaluate(f64::NAN).is_nan());
+        assert_eq!(option.classify(1.0, f64::NAN), Triad::Robust);
+        assert_eq!(
+            option.try_classify(1.0, f64::NAN),
+            Err(crate::ClassifyError::NotANumber {
+                probe: crate::Probe::Below
+            })
+        );
+    }
+
+    #[test]
+    fn test_default_tails_extend_end_segments() {
+        let line = PiecewiseLinear::new([(0.0, 1.0), (2.0, 5.0)]).unwrap();
+        assert_eq!(line.slopes(), vec![2.0, 2.0, 2.0]);
+        assert!((line.evaluate(-1.0) + 1.0).abs() < f64::EPSILON);
+        assert!((line.evaluate(3.0) - 7.0).abs() < f64::EPSILON);
+        assert_eq!(line.global_classification(), Some(Triad::Robust));
+        assert_eq!(line.breakpoints().count(), line.len());
+    }
+
+    #[test]
+    fn test_invalid_breakpoints() {
+        assert_eq!(
+            PiecewiseLinear::new([(0.0, 0.0)]),
+            Err(PiecewiseError::TooFewBreakpoints { found: 1 })
+        );
+        assert_eq!(
+            PiecewiseLinear::new([(0.0, 0.0), (f64::INFINITY, 1.0)]),
+            Err(PiecewiseError::NonFinite { index: 1 })
+        );
+        assert_eq!(
+            PiecewiseLinear::new([(0.0, 0.0), (1.0, 1.0), (1.0, 2.0)]),
+            Err(PiecewiseError::NotIncreasing { index: 2 })
+        );
+    }
+}
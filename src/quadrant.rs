@@ -0,0 +1,350 @@
+//! # Fourth-quadrant detection
+//!
+//! Taleb's "fourth quadrant" is where statistics stops working: the stressor
+//! is fat-tailed (rare events dominate and are badly estimated from samples)
+//! *and* the exposure to it is complex, here meaning losses that keep
+//! growing, at least linearly, the further out the stressor goes. Outside
+//! that quadrant, a convexity classification from observed data is a
+//! reasonable summary. Inside it, the classification should come with a
+//! warning.
+//!
+//! [`assess_quadrant`](crate::quadrant::assess_quadrant) combines two checks:
+//!
+//! - **Tail fatness.** A Hill estimate of the tail index `α` from the largest
+//!   deviations of the stressor samples around their median. Below
+//!   [`QuadrantConfig::fat_tail_index`](crate::quadrant::QuadrantConfig::fat_tail_index)
+//!   the tail is treated as fat.
+//! - **Unbounded concave exposure.** The payoff is probed beyond the largest
+//!   observed deviation, at one and two times that distance again. If it
+//!   keeps falling without the losses slowing down on either side, nothing
+//!   caps the damage from the next, larger event.
+//!
+//! | | Bounded or convex exposure | Unbounded concave exposure |
+//! |---|---|---|
+//! | **Thin tails** | First | Second |
+//! | **Fat tails** | Third | **Fourth** |
+//!
+//! ```rust
+//! use antifragile::Antifragile;
+//! use antifragile::quadrant::{Quadrant, QuadrantConfig, assess_quadrant};
+//!
+//! // Short volatility: keeps a premium, loses quadratically on big moves
+//! struct ShortStraddle;
+//!
+//! impl Antifragile for ShortStraddle {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, x: f64) -> f64 {
+//!         1.0 - x * x
+//!     }
+//! }
+//!
+//! // Pareto-like market moves with tail index 1.5
+//! let moves: Vec<f64> = (1..=400)
+//!     .map(|i| {
+//!         let u = f64::from(i) / 401.0;
+//!         let size = u.powf(-1.0 / 1.5) - 1.0;
+//!         if i % 2 == 0 { size } else { -size }
+//!     })
+//!     .collect();
+//!
+//! let assessment = assess_quadrant(&ShortStraddle, &moves, &QuadrantConfig::default())?;
+//! assert_eq!(assessment.quadrant, Quadrant::Fourth);
+//! assert!(assessment.warning().is_some());
+//! # Ok::<(), antifragile::quadrant::QuadrantError>(())
+//! ```
+
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::Antifragile;
+use crate::tails::hill_sorted;
+
+/// Warning attached to fourth-quadrant results
+pub const FOURTH_QUADRANT_WARNING: &str = "fourth quadrant: fat-tailed stressor with unbounded \
+     concave exposure; statistical methods are unreliable here";
+
+/// Minimum number of stressor samples [`assess_quadrant`] accepts
+pub const MIN_SAMPLES: usize = 20;
+
+/// Configuration for [`assess_quadrant`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadrantConfig {
+    /// Fraction of samples, taken from the largest deviations, used by the
+    /// Hill estimator (at least five samples are always used)
+    pub tail_fraction: f64,
+    /// Tail index below which the stressor counts as fat-tailed
+    ///
+    /// Below 4 the fourth moment is infinite, so sample variances themselves
+    /// are unstable.
+    pub fat_tail_index: f64,
+}
+
+impl Default for QuadrantConfig {
+    /// Hill estimate from the largest 2% of deviations; fat below `α = 4`
+    fn default() -> Self {
+        Self {
+            tail_fraction: 0.02,
+            fat_tail_index: 4.0,
+        }
+    }
+}
+
+/// Taleb's map of where statistical analysis can be trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Quadrant {
+    /// Thin tails, bounded or convex exposure
+    First,
+    /// Thin tails, unbounded concave exposure
+    Second,
+    /// Fat tails, bounded or convex exposure
+    Third,
+    /// Fat tails, unbounded concave exposure: statistics are unreliable
+    Fourth,
+}
+
+/// Result of [`assess_quadrant`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuadrantAssessment {
+    /// The quadrant the system falls into
+    pub quadrant: Quadrant,
+    /// Hill estimate of the stressor's tail index (lower is fatter)
+    pub tail_index: f64,
+    /// Losses keep growing below the smallest observed stressor
+    pub exposed_below: bool,
+    /// Losses keep growing above the largest observed stressor
+    pub exposed_above: bool,
+}
+
+impl QuadrantAssessment {
+    /// Returns true in the fourth quadrant
+    #[must_use]
+    pub fn is_fourth_quadrant(&self) -> bool {
+        self.quadrant == Quadrant::Fourth
+    }
+
+    /// The warning that results in this quadrant should carry, if any
+    #[must_use]
+    pub fn warning(&self) -> Option<&'static str> {
+        self.is_fourth_quadrant().then_some(FOURTH_QUADRANT_WARNING)
+    }
+}
+
+/// Error returned by [`assess_quadrant`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuadrantError {
+    /// Fewer than [`MIN_SAMPLES`] samples were supplied
+    TooFewSamples {
+        /// Number of samples supplied
+        found: usize,
+    },
+    /// A sample was NaN or infinite
+    NonFinite {
+        /// Position of the offending sample
+        index: usize,
+    },
+    /// Too many samples sit exactly at the median to estimate a tail
+    DegenerateTail,
+}
+
+impl Display for QuadrantError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooFewSamples { found } => write!(
+                f,
+                "too few samples: found {found}, need at least {MIN_SAMPLES}"
+            ),
+            Self::NonFinite { index } => write!(f, "non-finite sample at index {index}"),
+            Self::DegenerateTail => write!(f, "tail deviations are zero; cannot estimate a tail"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for QuadrantError {}
+
+/// Place `system` on Taleb's quadrant map given observed stressor `samples`
+///
+/// # Errors
+///
+/// Returns [`QuadrantError::TooFewSamples`] for fewer than [`MIN_SAMPLES`]
+/// samples, [`QuadrantError::NonFinite`] if a sample is NaN or infinite, and
+/// [`QuadrantError::DegenerateTail`] if the tail deviations are all zero.
+pub fn assess_quadrant<T>(
+    system: &T,
+    samples: &[f64],
+    config: &QuadrantConfig,
+) -> Result<QuadrantAssessment, QuadrantError>
+where
+    T: Antifragile<Stressor = f64, Payoff = f64>,
+{
+    if samples.len() < MIN_SAMPLES {
+        return Err(QuadrantError::TooFewSamples {
+            found: samples.len(),
+        });
+    }
+    if let Some(index) = samples.iter().position(|x| !x.is_finite()) {
+        return Err(QuadrantError::NonFinite { index });
+    }
+
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let median = sorted[sorted.len() / 2];
+    let tail_index = hill_estimate(&sorted, median, config.tail_fraction)?;
+
+    let (lowest, highest) = (sorted[0], sorted[sorted.len() - 1]);
+    let exposed_below = unbounded_loss(system, lowest, lowest - median);
+    let exposed_above = unbounded_loss(system, highest, highest - median);
+
+    let fat = tail_index < config.fat_tail_index;
+    let exposed = exposed_below || exposed_above;
+    let quadrant = match (fat, exposed) {
+        (false, false) => Quadrant::First,
+        (false, true) => Quadrant::Second,
+        (true, false) => Quadrant::Third,
+        (true, true) => Quadrant::Fourth,
+    };
+
+    Ok(QuadrantAssessment {
+        quadrant,
+        tail_index,
+        exposed_below,
+        exposed_above,
+    })
+}
+
+/// Hill estimator of the tail index of `|x - median|`
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn hill_estimate(sorted: &[f64], median: f64, tail_fraction: f64) -> Result<f64, QuadrantError> {
+    let mut deviations: Vec<f64> = sorted.iter().map(|x| (x - median).abs()).collect();
+    deviations.sort_by(|a, b| b.total_cmp(a));
+
+    let tail = ((tail_fraction * deviations.len() as f64) as usize).clamp(5, deviations.len() - 1);
+    hill_sorted(&deviations, tail).ok_or(QuadrantError::DegenerateTail)
+}
+
+/// Does the payoff keep falling, without decelerating, beyond `edge`?
+///
+/// `reach` is the signed distance from the median to the edge.
+fn unbounded_loss<T>(system: &T, edge: f64, reach: f64) -> bool
+where
+    T: Antifragile<Stressor = f64, Payoff = f64>,
+{
+    if reach == 0.0 {
+        return false;
+    }
+    let near = system.payoff(edge);
+    let mid = system.payoff(edge + reach);
+    let far = system.payoff(edge + 2.0 * reach);
+    let first_loss = near - mid;
+    let second_loss = mid - far;
+    first_loss > 0.0 && second_loss >= first_loss
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    struct Quadratic(f64);
+
+    impl Antifragile for Quadratic {
+        type Stressor = f64;
+        type Payoff = f64;
+        fn payoff(&self, x: f64) -> f64 {
+            self.0 * x * x
+        }
+    }
+
+    /// Loses linearly below zero, down to a floor of -1
+    struct Insured;
+
+    impl Antifragile for Insured {
+        type Stressor = f64;
+        type Payoff = f64;
+        fn payoff(&self, x: f64) -> f64 {
+            x.max(-1.0)
+        }
+    }
+
+    fn normal_samples(n: usize) -> Vec<f64> {
+        let mut rng = SplitMix64::new(11);
+        (0..n).map(|_| rng.next_normal()).collect()
+    }
+
+    fn pareto_samples(n: usize, alpha: f64) -> Vec<f64> {
+        let mut rng = SplitMix64::new(13);
+        (0..n)
+            .map(|i| {
+                let size = (1.0 - rng.next_f64()).powf(-1.0 / alpha) - 1.0;
+                if i % 2 == 0 { size } else { -size }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hill_separates_thin_and_fat_tails() {
+        let config = QuadrantConfig::default();
+        let thin = assess_quadrant(&Quadratic(1.0), &normal_samples(5000), &config).unwrap();
+        let fat = assess_quadrant(&Quadratic(1.0), &pareto_samples(5000, 2.0), &config).unwrap();
+        assert!(thin.tail_index > config.fat_tail_index);
+        assert!(fat.tail_index < config.fat_tail_index);
+        assert!((fat.tail_index - 2.0).abs() < 0.6);
+    }
+
+    #[test]
+    fn test_quadrant_map() {
+        let config = QuadrantConfig::default();
+        let thin = normal_samples(5000);
+        let fat = pareto_samples(5000, 2.0);
+
+        let quadrant = |curvature, samples| {
+            assess_quadrant(&Quadratic(curvature), samples, &config)
+                .unwrap()
+                .quadrant
+        };
+        assert_eq!(quadrant(1.0, &thin), Quadrant::First);
+        assert_eq!(quadrant(-1.0, &thin), Quadrant::Second);
+        assert_eq!(quadrant(1.0, &fat), Quadrant::Third);
+        assert_eq!(quadrant(-1.0, &fat), Quadrant::Fourth);
+    }
+
+    #[test]
+    fn test_floored_losses_are_bounded() {
+        let assessment = assess_quadrant(
+            &Insured,
+            &pareto_samples(2000, 1.5),
+            &QuadrantConfig::default(),
+        )
+        .unwrap();
+        assert!(!assessment.exposed_below);
+        assert!(!assessment.exposed_above);
+        assert_eq!(assessment.quadrant, Quadrant::Third);
+        assert_eq!(assessment.warning(), None);
+    }
+
+    #[test]
+    fn test_invalid_samples() {
+        let config = QuadrantConfig::default();
+        assert_eq!(
+            assess_quadrant(&Insured, &[0.0; 10], &config),
+            Err(QuadrantError::TooFewSamples { found: 10 })
+        );
+        let mut samples = normal_samples(30);
+        samples[4] = f64::NAN;
+        assert_eq!(
+            assess_quadrant(&Insured, &samples, &config),
+            Err(QuadrantError::NonFinite { index: 4 })
+        );
+        assert_eq!(
+            assess_quadrant(&Insured, &[1.0; 30], &config),
+            Err(QuadrantError::DegenerateTail)
+        );
+    }
+}
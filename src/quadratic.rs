@@ -0,0 +1,151 @@
+//! Running sums for least-squares quadratic fits
+//!
+//! Shared by the streaming estimators: each observation updates a fixed
+//! set of sums, so memory and per-observation cost are constant, and an
+//! observation can be taken back out by subtracting it again.
+
+/// Curvature, relative to the payoff scale over the stressor variance, that
+/// the fit cannot tell from rounding in the sums
+const ROUNDING: f64 = 1e-9;
+
+/// Sufficient statistics of `y = a + b·x + c·x²` by least squares
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct QuadraticSums {
+    /// First stressor observed; sums are taken about it for conditioning
+    origin: Option<f64>,
+    /// `Σ xᵏ` for `k = 0..=4`
+    powers: [f64; 5],
+    /// `Σ xᵏ·y` for `k = 0..=2`
+    moments: [f64; 3],
+    /// `Σ y²`
+    squares: f64,
+}
+
+impl QuadraticSums {
+    /// Empty sums
+    pub(crate) const fn new() -> Self {
+        Self {
+            origin: None,
+            powers: [0.0; 5],
+            moments: [0.0; 3],
+            squares: 0.0,
+        }
+    }
+
+    /// Add one observation
+    pub(crate) fn add(&mut self, stressor: f64, payoff: f64) {
+        self.update(stressor, payoff, 1.0);
+    }
+
+    /// Take back an observation previously added
+    ///
+    /// Subtraction accumulates rounding error; callers that remove many
+    /// observations should rebuild the sums from time to time.
+    pub(crate) fn remove(&mut self, stressor: f64, payoff: f64) {
+        self.update(stressor, payoff, -1.0);
+    }
+
+    /// Scale the weight of every observation so far by `factor`
+    pub(crate) fn decay(&mut self, factor: f64) {
+        for sum in self.powers.iter_mut().chain(&mut self.moments) {
+            *sum *= factor;
+        }
+        self.squares *= factor;
+    }
+
+    /// Total weight of the observations, their number when unweighted
+    pub(crate) const fn weight(&self) -> f64 {
+        self.powers[0]
+    }
+
+    fn update(&mut self, stressor: f64, payoff: f64, weight: f64) {
+        let x = stressor - *self.origin.get_or_insert(stressor);
+        let mut power = weight;
+        for (k, sum) in self.powers.iter_mut().enumerate() {
+            *sum += power;
+            if let Some(moment) = self.moments.get_mut(k) {
+                *moment += power * payoff;
+            }
+            power *= x;
+        }
+        self.squares += weight * payoff * payoff;
+    }
+
+    /// Number of observations in the sums
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(crate) fn count(&self) -> usize {
+        self.weight().round().max(0.0) as usize
+    }
+
+    /// Least-squares second derivative and its standard error
+    ///
+    /// `noise` is the payoff noise standard deviation, or `None` to
+    /// estimate it from the residuals, treating the weights as frequencies.
+    /// `None` until the curvature is identified: three distinct stressors,
+    /// and a total weight above three when the noise is estimated. A
+    /// curvature within rounding of zero, such as the fit of an exactly
+    /// linear payoff, is reported as zero.
+    pub(crate) fn curvature(&self, noise: Option<f64>) -> Option<(f64, f64)> {
+        let [s0, s1, s2, s3, s4] = self.powers;
+        let matrix = [[s0, s1, s2], [s1, s2, s3], [s2, s3, s4]];
+        let inverse = invert(&matrix)?;
+        let beta: Vec<f64> = inverse
+            .iter()
+            .map(|row| row.iter().zip(&self.moments).map(|(a, b)| a * b).sum())
+            .collect();
+
+        let variance = match noise {
+            Some(sigma) => sigma * sigma,
+            None if s0 > 3.0 => {
+                let fitted: f64 = beta.iter().zip(&self.moments).map(|(b, m)| b * m).sum();
+                (self.squares - fitted).max(0.0) / (s0 - 3.0)
+            }
+            None => return None,
+        };
+        let curvature = 2.0 * beta[2];
+        let curvature = if curvature.abs() <= self.rounding() {
+            0.0
+        } else {
+            curvature
+        };
+        Some((curvature, 2.0 * (variance * inverse[2][2]).sqrt()))
+    }
+
+    /// Largest curvature indistinguishable from rounding: [`ROUNDING`]
+    /// times the root mean square payoff over the stressor variance
+    fn rounding(&self) -> f64 {
+        let [s0, s1, s2, ..] = self.powers;
+        let mean = s1 / s0;
+        let spread = s2 / s0 - mean * mean;
+        ROUNDING * (self.squares / s0).max(0.0).sqrt() / spread
+    }
+}
+
+/// Inverse of a symmetric 3×3 matrix, or `None` if it is singular
+fn invert(m: &[[f64; 3]; 3]) -> Option<[[f64; 3]; 3]> {
+    let cofactor =
+        |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+    let adjugate = [
+        [
+            cofactor(1, 2, 1, 2),
+            -cofactor(0, 2, 1, 2),
+            cofactor(0, 1, 1, 2),
+        ],
+        [
+            -cofactor(1, 2, 0, 2),
+            cofactor(0, 2, 0, 2),
+            -cofactor(0, 1, 0, 2),
+        ],
+        [
+            cofactor(1, 2, 0, 1),
+            -cofactor(0, 2, 0, 1),
+            cofactor(0, 1, 0, 1),
+        ],
+    ];
+    let determinant: f64 = (0..3).map(|c| m[0][c] * adjugate[c][0]).sum();
+    let scale = m.iter().flatten().fold(0.0_f64, |a, v| a.max(v.abs()));
+    if determinant.abs() <= 1e-12 * scale.powi(3) {
+        return None;
+    }
+    Some(adjugate.map(|row| row.map(|v| v / determinant)))
+}
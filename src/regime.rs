@@ -0,0 +1,383 @@
+//! # Verdicts per operating regime
+//!
+//! One system legitimately has different classifications in different
+//! regimes: a service can be antifragile off-peak and fragile during a flash
+//! sale. [`VerifiedMap`](crate::regime::VerifiedMap) carries one system
+//! together with its verdict at each named operating point, so the regimes
+//! travel together instead of as loose [`Verified`](crate::Verified) copies.
+//!
+//! Verdicts are stamped with the map's generation. Mutating the system
+//! through [`system_mut`](crate::regime::VerifiedMap::system_mut) (or calling
+//! [`mark_changed`](crate::regime::VerifiedMap::mark_changed)) starts a new
+//! generation, which makes every existing verdict stale until
+//! [`refresh`](crate::regime::VerifiedMap::refresh) re-runs it.
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad};
+//! use antifragile::regime::VerifiedMap;
+//!
+//! // Capacity that benefits from load until it saturates
+//! struct Service {
+//!     saturation: f64,
+//! }
+//!
+//! impl Antifragile for Service {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, load: f64) -> f64 {
+//!         if load < self.saturation {
+//!             load * load
+//!         } else {
+//!             self.saturation * self.saturation - (load - self.saturation).powi(2)
+//!         }
+//!     }
+//! }
+//!
+//! let mut regimes = VerifiedMap::new(Service { saturation: 50.0 });
+//! regimes.verify("off-peak", 10.0, 1.0);
+//! regimes.verify("flash-sale", 80.0, 1.0);
+//!
+//! assert_eq!(regimes.classification("off-peak"), Some(Triad::Antifragile));
+//! assert_eq!(regimes.classification("flash-sale"), Some(Triad::Fragile));
+//! assert_eq!(regimes.summary().verdict(), Some(Triad::Fragile));
+//!
+//! // Scaling out moves the saturation point: both verdicts need re-running
+//! regimes.system_mut().saturation = 100.0;
+//! assert_eq!(regimes.summary().stale, 2);
+//! assert_eq!(regimes.refresh(), 2);
+//! assert_eq!(regimes.summary().verdict(), Some(Triad::Antifragile));
+//! ```
+
+use core::ops::Sub;
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Antifragile, Triad, TriadAnalysis};
+
+/// The verdict at one named operating point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegimeVerdict<S> {
+    /// The operating point
+    pub at: S,
+    /// The perturbation used
+    pub delta: S,
+    /// Classification at `at`
+    pub classification: Triad,
+    /// Generation of the map when the classification was computed
+    pub generation: u64,
+}
+
+/// Counts over every verdict in a [`VerifiedMap`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RegimeSummary {
+    /// Regimes classified as Fragile
+    pub fragile: usize,
+    /// Regimes classified as Robust
+    pub robust: usize,
+    /// Regimes classified as Antifragile
+    pub antifragile: usize,
+    /// Regimes whose verdict predates the current generation
+    pub stale: usize,
+}
+
+impl RegimeSummary {
+    /// Total number of regimes
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.fragile + self.robust + self.antifragile
+    }
+
+    /// Number of regimes with the given classification
+    #[must_use]
+    pub const fn count(&self, triad: Triad) -> usize {
+        match triad {
+            Triad::Fragile => self.fragile,
+            Triad::Robust => self.robust,
+            Triad::Antifragile => self.antifragile,
+        }
+    }
+
+    /// The overall verdict: a system is only as good as its weakest regime
+    ///
+    /// Stale verdicts are counted as they stand; check [`stale`](Self::stale)
+    /// or [`refresh`](VerifiedMap::refresh) first.
+    #[must_use]
+    pub fn verdict(&self) -> Option<Triad> {
+        Triad::iter().find(|&triad| self.count(triad) > 0)
+    }
+
+    /// Returns true if every regime has the same classification
+    #[must_use]
+    pub fn is_uniform(&self) -> bool {
+        Triad::iter().filter(|&triad| self.count(triad) > 0).count() <= 1
+    }
+}
+
+/// One system with its verdict at each named operating point
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "T: Serialize, T::Stressor: Serialize",
+        deserialize = "T: Deserialize<'de>, T::Stressor: Deserialize<'de>"
+    ))
+)]
+pub struct VerifiedMap<T: Antifragile> {
+    system: T,
+    generation: u64,
+    regimes: BTreeMap<String, RegimeVerdict<T::Stressor>>,
+}
+
+impl<T: Antifragile> VerifiedMap<T>
+where
+    T::Payoff: Sub<Output = T::Payoff> + Default + PartialOrd,
+{
+    /// Wrap a system with no regimes verified yet
+    #[must_use]
+    pub const fn new(system: T) -> Self {
+        Self {
+            system,
+            generation: 0,
+            regimes: BTreeMap::new(),
+        }
+    }
+
+    /// Classify the regime `name` at `at`
+    ///
+    /// Returns the verdict it replaces, if any.
+    pub fn verify(
+        &mut self,
+        name: impl Into<String>,
+        at: T::Stressor,
+        delta: T::Stressor,
+    ) -> Option<RegimeVerdict<T::Stressor>> {
+        let classification = self.system.classify(at, delta);
+        self.regimes.insert(
+            name.into(),
+            RegimeVerdict {
+                at,
+                delta,
+                classification,
+                generation: self.generation,
+            },
+        )
+    }
+
+    /// Re-run every stale verdict at its recorded operating point
+    ///
+    /// Returns the number of regimes re-verified.
+    pub fn refresh(&mut self) -> usize {
+        let mut refreshed = 0;
+        for verdict in self.regimes.values_mut() {
+            if verdict.generation < self.generation {
+                verdict.classification = self.system.classify(verdict.at, verdict.delta);
+                verdict.generation = self.generation;
+                refreshed += 1;
+            }
+        }
+        refreshed
+    }
+
+    /// Returns true if the verdict for `name` still matches a fresh
+    /// classification of the current system, or `None` if there is none
+    #[must_use]
+    pub fn still_holds(&self, name: &str) -> Option<bool> {
+        let verdict = self.regimes.get(name)?;
+        Some(self.system.classify(verdict.at, verdict.delta) == verdict.classification)
+    }
+}
+
+impl<T: Antifragile> VerifiedMap<T> {
+    /// The verdict for `name`
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&RegimeVerdict<T::Stressor>> {
+        self.regimes.get(name)
+    }
+
+    /// The classification for `name`
+    #[must_use]
+    pub fn classification(&self, name: &str) -> Option<Triad> {
+        self.regimes.get(name).map(|verdict| verdict.classification)
+    }
+
+    /// Returns true if the verdict for `name` predates the current
+    /// generation, or `None` if there is none
+    #[must_use]
+    pub fn is_stale(&self, name: &str) -> Option<bool> {
+        self.regimes
+            .get(name)
+            .map(|verdict| verdict.generation < self.generation)
+    }
+
+    /// Names of regimes whose verdict predates the current generation
+    pub fn stale(&self) -> impl Iterator<Item = &str> + '_ {
+        self.regimes
+            .iter()
+            .filter(|(_, verdict)| verdict.generation < self.generation)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Forget the verdict for `name`
+    pub fn remove(&mut self, name: &str) -> Option<RegimeVerdict<T::Stressor>> {
+        self.regimes.remove(name)
+    }
+
+    /// Regimes and their verdicts, ordered by name
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &RegimeVerdict<T::Stressor>)> + '_ {
+        self.regimes
+            .iter()
+            .map(|(name, verdict)| (name.as_str(), verdict))
+    }
+
+    /// Number of regimes
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.regimes.len()
+    }
+
+    /// Returns true if no regime has been verified
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.regimes.is_empty()
+    }
+
+    /// Counts by classification and staleness
+    #[must_use]
+    pub fn summary(&self) -> RegimeSummary {
+        let mut summary = RegimeSummary::default();
+        for verdict in self.regimes.values() {
+            match verdict.classification {
+                Triad::Fragile => summary.fragile += 1,
+                Triad::Robust => summary.robust += 1,
+                Triad::Antifragile => summary.antifragile += 1,
+            }
+            if verdict.generation < self.generation {
+                summary.stale += 1;
+            }
+        }
+        summary
+    }
+
+    /// The current generation
+    #[must_use]
+    pub const fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Record that the system changed, making every verdict stale
+    pub const fn mark_changed(&mut self) {
+        self.generation += 1;
+    }
+
+    /// Get reference to the system
+    #[must_use]
+    pub const fn system(&self) -> &T {
+        &self.system
+    }
+
+    /// Get mutable access to the system, making every verdict stale
+    pub const fn system_mut(&mut self) -> &mut T {
+        self.mark_changed();
+        &mut self.system
+    }
+
+    /// Unwrap the system, discarding the verdicts
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.system
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Scaled {
+        curvature: f64,
+    }
+
+    impl Antifragile for Scaled {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&self, x: f64) -> f64 {
+            self.curvature * (x - 50.0).abs().powi(3) * (x - 50.0).signum()
+        }
+    }
+
+    fn regimes() -> VerifiedMap<Scaled> {
+        let mut map = VerifiedMap::new(Scaled { curvature: 1.0 });
+        map.verify("peak", 80.0, 1.0);
+        map.verify("off-peak", 20.0, 1.0);
+        map.verify("steady", 50.0, 1.0);
+        map
+    }
+
+    #[test]
+    fn test_verdicts_per_regime() {
+        let map = regimes();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.classification("peak"), Some(Triad::Antifragile));
+        assert_eq!(map.classification("off-peak"), Some(Triad::Fragile));
+        assert_eq!(map.classification("steady"), Some(Triad::Robust));
+        assert_eq!(map.classification("missing"), None);
+
+        let names: Vec<&str> = map.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["off-peak", "peak", "steady"]);
+
+        let summary = map.summary();
+        assert_eq!(
+            (summary.fragile, summary.robust, summary.antifragile),
+            (1, 1, 1)
+        );
+        assert_eq!(summary.verdict(), Some(Triad::Fragile));
+        assert!(!summary.is_uniform());
+    }
+
+    #[test]
+    fn test_mutation_marks_verdicts_stale() {
+        let mut map = regimes();
+        assert_eq!(map.stale().count(), 0);
+
+        map.system_mut().curvature = -1.0;
+        assert_eq!(map.generation(), 1);
+        assert_eq!(map.is_stale("peak"), Some(true));
+        assert_eq!(map.still_holds("peak"), Some(false));
+        assert_eq!(map.still_holds("steady"), Some(true));
+
+        // A regime verified after the change is fresh
+        let replaced = map.verify("peak", 80.0, 1.0).unwrap();
+        assert_eq!(replaced.classification, Triad::Antifragile);
+        assert_eq!(map.is_stale("peak"), Some(false));
+        assert_eq!(map.stale().collect::<Vec<_>>(), ["off-peak", "steady"]);
+
+        assert_eq!(map.refresh(), 2);
+        assert_eq!(map.summary().stale, 0);
+        assert_eq!(map.classification("peak"), Some(Triad::Fragile));
+        assert_eq!(map.classification("off-peak"), Some(Triad::Antifragile));
+        assert_eq!(map.refresh(), 0);
+    }
+
+    #[test]
+    fn test_empty_and_uniform_summaries() {
+        let mut map = VerifiedMap::new(Scaled { curvature: 1.0 });
+        assert!(map.is_empty());
+        assert_eq!(map.summary().verdict(), None);
+        assert!(map.summary().is_uniform());
+
+        map.verify("a", 60.0, 1.0);
+        map.verify("b", 70.0, 1.0);
+        assert!(map.summary().is_uniform());
+        assert_eq!(map.summary().count(Triad::Antifragile), 2);
+
+        assert_eq!(map.remove("a").map(|v| v.at), Some(60.0));
+        assert_eq!(map.summary().total(), 1);
+        assert!((map.into_inner().curvature - 1.0).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,475 @@
+//! # Markdown and HTML rendering
+//!
+//! Reports and profile sweeps are usually shared with people, not programs.
+//! [`Render`](crate::render::Render) turns an
+//! [`AnalysisReport`](crate::report::AnalysisReport), a
+//! [`ProfileSweep`](crate::sweep::ProfileSweep), or a
+//! [`SurrogateProfile`](crate::surrogate::SurrogateProfile) into a
+//! [`Document`](crate::render::Document) of headings, paragraphs, and tables,
+//! which is then written out as Markdown or as a standalone HTML page.
+//!
+//! Documents can be combined, so a report and the sweeps behind it can be
+//! published as one page:
+//!
+//! ```rust
+//! use antifragile::Antifragile;
+//! use antifragile::render::Render;
+//! use antifragile::sweep::ProfileSweep;
+//!
+//! struct Convex;
+//!
+//! impl Antifragile for Convex {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, x: f64) -> f64 {
+//!         x * x
+//!     }
+//! }
+//!
+//! let sweep = ProfileSweep::new("convex", &Convex, [1.0, 2.0, 3.0], 0.5).with_precision(2);
+//! let markdown = sweep.to_markdown();
+//!
+//! assert!(markdown.starts_with("# convex\n"));
+//! assert!(markdown.contains("| 2.00 | 2.25 | 4.00 | 6.25 | 8.50 | 8.00 | Antifragile |"));
+//! assert!(sweep.to_html().contains("<td>Antifragile</td>"));
+//! ```
+
+use core::fmt::{self, Display, Formatter};
+
+use crate::Triad;
+use crate::report::{AnalysisReport, ReportEntry};
+use crate::surrogate::{CellSource, SurrogateProfile};
+use crate::sweep::ProfileSweep;
+
+/// A block of a [`Document`]
+#[derive(Debug, Clone, PartialEq)]
+enum Block {
+    Heading {
+        level: usize,
+        text: String,
+    },
+    Paragraph(String),
+    Table {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+}
+
+/// A format-independent document of headings, paragraphs, and tables
+#[derive(Debug, Clone, PartialEq)]
+pub struct Document {
+    title: String,
+    blocks: Vec<Block>,
+}
+
+impl Document {
+    /// Create an empty document
+    #[must_use]
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            blocks: Vec::new(),
+        }
+    }
+
+    /// The document title
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Add a section heading
+    #[must_use]
+    pub fn heading(mut self, text: impl Into<String>) -> Self {
+        self.blocks.push(Block::Heading {
+            level: 2,
+            text: text.into(),
+        });
+        self
+    }
+
+    /// Add a paragraph of plain text
+    #[must_use]
+    pub fn paragraph(mut self, text: impl Into<String>) -> Self {
+        self.blocks.push(Block::Paragraph(text.into()));
+        self
+    }
+
+    /// Add a table; rows shorter than `headers` are padded with empty cells
+    #[must_use]
+    pub fn table<H: Into<String>>(
+        mut self,
+        headers: impl IntoIterator<Item = H>,
+        rows: impl IntoIterator<Item = Vec<String>>,
+    ) -> Self {
+        let headers: Vec<String> = headers.into_iter().map(Into::into).collect();
+        let rows = rows
+            .into_iter()
+            .map(|mut row| {
+                row.resize(headers.len().max(row.len()), String::new());
+                row
+            })
+            .collect();
+        self.blocks.push(Block::Table { headers, rows });
+        self
+    }
+
+    /// Append `other` as a section, nesting its headings one level deeper
+    #[must_use]
+    pub fn append(mut self, other: Self) -> Self {
+        self.blocks.push(Block::Heading {
+            level: 2,
+            text: other.title,
+        });
+        self.blocks
+            .extend(other.blocks.into_iter().map(|block| match block {
+                Block::Heading { level, text } => Block::Heading {
+                    level: level + 1,
+                    text,
+                },
+                other => other,
+            }));
+        self
+    }
+
+    /// Write the document as Markdown
+    #[must_use]
+    pub fn markdown(&self) -> String {
+        Markdown(self).to_string()
+    }
+
+    /// Write the document as a standalone HTML page
+    #[must_use]
+    pub fn html(&self) -> String {
+        Html(self).to_string()
+    }
+}
+
+/// A [`Document`] displayed as Markdown
+struct Markdown<'a>(&'a Document);
+
+impl Display for Markdown<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# {}", markdown_line(&self.0.title))?;
+        for block in &self.0.blocks {
+            writeln!(f)?;
+            match block {
+                Block::Heading { level, text } => {
+                    writeln!(f, "{} {}", "#".repeat((*level).min(6)), markdown_line(text))?;
+                }
+                Block::Paragraph(text) => writeln!(f, "{}", markdown_line(text))?,
+                Block::Table { headers, rows } => {
+                    markdown_row(f, headers)?;
+                    writeln!(f, "|{}", "---|".repeat(headers.len()))?;
+                    for row in rows {
+                        markdown_row(f, row)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A [`Document`] displayed as a standalone HTML page
+struct Html<'a>(&'a Document);
+
+impl Display for Html<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let title = html_escape(&self.0.title);
+        writeln!(
+            f,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">"
+        )?;
+        writeln!(
+            f,
+            "<title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>"
+        )?;
+        for block in &self.0.blocks {
+            match block {
+                Block::Heading { level, text } => {
+                    let level = (*level).min(6);
+                    writeln!(f, "<h{level}>{}</h{level}>", html_escape(text))?;
+                }
+                Block::Paragraph(text) => writeln!(f, "<p>{}</p>", html_escape(text))?,
+                Block::Table { headers, rows } => {
+                    writeln!(f, "<table>\n<thead>")?;
+                    html_row(f, "th", headers)?;
+                    writeln!(f, "</thead>\n<tbody>")?;
+                    for row in rows {
+                        html_row(f, "td", row)?;
+                    }
+                    writeln!(f, "</tbody>\n</table>")?;
+                }
+            }
+        }
+        writeln!(f, "</body>\n</html>")
+    }
+}
+
+/// Types that can be presented as a [`Document`]
+pub trait Render {
+    /// Build the document
+    fn document(&self) -> Document;
+
+    /// Render as Markdown
+    fn to_markdown(&self) -> String {
+        self.document().markdown()
+    }
+
+    /// Render as a standalone HTML page
+    fn to_html(&self) -> String {
+        self.document().html()
+    }
+}
+
+impl<S: Copy + Display, P: Display> Render for ProfileSweep<S, P> {
+    fn document(&self) -> Document {
+        let number = |value: &dyn Display| match self.precision() {
+            Some(digits) => format!("{value:.digits$}"),
+            None => value.to_string(),
+        };
+        let rows = self.points().iter().map(|(at, detail)| {
+            vec![
+                number(at),
+                number(&detail.below),
+                number(&detail.at),
+                number(&detail.above),
+                number(&detail.sum),
+                number(&detail.twin),
+                format!("{:?}", detail.triad),
+            ]
+        });
+        Document::new(self.title())
+            .paragraph(verdict_line(
+                self.verdict(),
+                self.points().len(),
+                "operating points",
+            ))
+            .paragraph(format!("Perturbation Δ = {}.", number(self.delta())))
+            .table(
+                [
+                    "x",
+                    "f(x - Δ)",
+                    "f(x)",
+                    "f(x + Δ)",
+                    "f(x + Δ) + f(x - Δ)",
+                    "2·f(x)",
+                    "Classification",
+                ],
+                rows,
+            )
+    }
+}
+
+impl Render for AnalysisReport {
+    /// One table per section; scores and robustness to three decimals
+    fn document(&self) -> Document {
+        let summary = self.summary();
+        let mut document = Document::new(self.title()).paragraph(verdict_line(
+            summary.verdict(),
+            summary.total(),
+            "entries",
+        ));
+        if summary.total() > 0 {
+            let counts: Vec<String> = Triad::iter()
+                .map(|triad| format!("{} {triad:?}", summary.count(triad)))
+                .collect();
+            document = document.paragraph(format!("{}.", counts.join(", ")));
+        }
+        for section in self.sections() {
+            document = document.heading(section.title()).table(
+                ["Item", "Classification", "Score", "Robustness", "Notes"],
+                section.entries().iter().map(entry_row),
+            );
+        }
+        document
+    }
+}
+
+impl Render for SurrogateProfile {
+    fn document(&self) -> Document {
+        let rows = self.cells().iter().map(|cell| {
+            vec![
+                cell.stressor.to_string(),
+                format!("{:?}", cell.classification),
+                match cell.source {
+                    CellSource::Evaluated => "evaluated".into(),
+                    CellSource::Surrogate => "surrogate".into(),
+                },
+                format!("{:.3e}", cell.uncertainty),
+            ]
+        });
+        let verdict = self.cells().iter().map(|cell| cell.classification).min();
+        Document::new("Surrogate profile")
+            .paragraph(verdict_line(
+                verdict,
+                self.cells().len(),
+                "operating points",
+            ))
+            .paragraph(format!(
+                "{} payoff evaluations; {} of {} points relied on surrogate values.",
+                self.evaluations(),
+                self.surrogate_cells(),
+                self.cells().len()
+            ))
+            .table(["x", "Classification", "Source", "Uncertainty"], rows)
+    }
+}
+
+/// "Verdict: Fragile across 3 entries." or a note that there is nothing to judge
+fn verdict_line(verdict: Option<Triad>, count: usize, noun: &str) -> String {
+    match verdict {
+        Some(verdict) => format!("Verdict: {verdict:?} across {count} {noun}."),
+        None => format!("No verdict: no {noun}."),
+    }
+}
+
+/// A report entry as a table row
+fn entry_row(entry: &ReportEntry) -> Vec<String> {
+    let notes: Vec<&str> = entry
+        .rationale
+        .iter()
+        .chain(&entry.warnings)
+        .map(String::as_str)
+        .collect();
+    vec![
+        entry.label.clone(),
+        format!("{:?}", entry.classification),
+        entry.score.map_or_else(String::new, |s| format!("{s:.3}")),
+        entry
+            .robustness
+            .map_or_else(String::new, |r| format!("{r:.3}")),
+        notes.join("; "),
+    ]
+}
+
+/// Markdown text on one line, with table separators escaped
+fn markdown_line(text: &str) -> String {
+    text.replace(['\n', '\r'], " ").replace('|', "\\|")
+}
+
+fn markdown_row(f: &mut Formatter<'_>, cells: &[String]) -> fmt::Result {
+    write!(f, "|")?;
+    for cell in cells {
+        write!(f, " {} |", markdown_line(cell))?;
+    }
+    writeln!(f)
+}
+
+fn html_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn html_row(f: &mut Formatter<'_>, tag: &str, cells: &[String]) -> fmt::Result {
+    write!(f, "<tr>")?;
+    for cell in cells {
+        write!(f, "<{tag}>{}</{tag}>", html_escape(cell))?;
+    }
+    writeln!(f, "</tr>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Antifragile;
+    use crate::report::ReportSection;
+
+    struct Concave;
+
+    impl Antifragile for Concave {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&self, x: f64) -> f64 {
+            -x * x
+        }
+    }
+
+    fn report() -> AnalysisReport {
+        let mut report = AnalysisReport::new("checkout <prod>");
+        report.push_section(
+            ReportSection::new("latency")
+                .with_entry(ReportEntry::new("p50", Triad::Robust).with_score(0.125))
+                .with_entry(
+                    ReportEntry::new("p99 | tail", Triad::Fragile)
+                        .with_rationale("sum fell short")
+                        .with_warning("fat tails"),
+                ),
+        );
+        report
+    }
+
+    #[test]
+    fn test_report_markdown() {
+        let markdown = report().to_markdown();
+        assert_eq!(
+            markdown,
+            "# checkout <prod>\n\
+             \n\
+             Verdict: Fragile across 2 entries.\n\
+             \n\
+             1 Fragile, 1 Robust, 0 Antifragile.\n\
+             \n\
+             ## latency\n\
+             \n\
+             | Item | Classification | Score | Robustness | Notes |\n\
+             |---|---|---|---|---|\n\
+             | p50 | Robust | 0.125 |  |  |\n\
+             | p99 \\| tail | Fragile |  |  | sum fell short; fat tails |\n"
+        );
+    }
+
+    #[test]
+    fn test_report_html_is_escaped() {
+        let html = report().to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>checkout &lt;prod&gt;</title>"));
+        assert!(html.contains("<h2>latency</h2>"));
+        assert!(html.contains("<tr><td>p99 | tail</td><td>Fragile</td>"));
+        assert!(html.ends_with("</html>\n"));
+    }
+
+    #[test]
+    fn test_sweep_verdict_and_nesting() {
+        let sweep = ProfileSweep::new("concave", &Concave, [1.0, 2.0], 1.0);
+        assert_eq!(sweep.verdict(), Some(Triad::Fragile));
+        assert_eq!(sweep.points().len(), 2);
+
+        let combined = report().document().append(sweep.document());
+        let markdown = combined.markdown();
+        assert!(markdown.contains("\n## concave\n"));
+        assert!(markdown.contains("| 2 | -1 | -4 | -9 | -10 | -8 | Fragile |"));
+
+        let empty = ProfileSweep::new("empty", &Concave, [], 1.0);
+        assert_eq!(empty.verdict(), None);
+        assert!(
+            empty
+                .to_markdown()
+                .contains("No verdict: no operating points.")
+        );
+    }
+
+    #[test]
+    fn test_surrogate_profile() {
+        use crate::surrogate::{SurrogateConfig, surrogate_profile};
+
+        let profile = surrogate_profile(&Concave, 0.0..=4.0, 5, 0.5, &SurrogateConfig::default());
+        let markdown = profile.to_markdown();
+        assert!(markdown.starts_with("# Surrogate profile\n"));
+        assert!(markdown.contains("Verdict: Fragile across 5 operating points."));
+        assert!(markdown.contains("| 2 | Fragile | evaluated |"));
+    }
+}
@@ -0,0 +1,542 @@
+//! # Analysis reports
+//!
+//! An [`AnalysisReport`](crate::report::AnalysisReport) collects classification
+//! results into titled sections and keeps a running
+//! [`ReportSummary`](crate::report::ReportSummary) of them.
+//!
+//! Reports are designed to be maintained incrementally by long-running
+//! services: appending an entry, appending a section, replacing a section, or
+//! aging out old sections each update the summary in time proportional to the
+//! entries touched, never by rescanning the whole report.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::report::{AnalysisReport, ReportEntry};
+//!
+//! let mut report = AnalysisReport::new("checkout-service");
+//!
+//! report.push_entry("window 1", ReportEntry::new("p50 latency", Triad::Robust));
+//! report.push_entry("window 1", ReportEntry::new("throughput", Triad::Antifragile));
+//! assert_eq!(report.summary().verdict(), Some(Triad::Robust));
+//!
+//! // A new observation window arrives
+//! report.push_entry("window 2", ReportEntry::new("p50 latency", Triad::Fragile));
+//! assert_eq!(report.summary().verdict(), Some(Triad::Fragile));
+//!
+//! // Keep only the most recent window
+//! report.retain_recent(1);
+//! assert_eq!(report.summary().total(), 1);
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Triad;
+use crate::quadrant::QuadrantAssessment;
+
+/// A single classified item in a report
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ReportEntry {
+    /// What was classified (a metric, an operating point, a subsystem)
+    pub label: String,
+    /// The classification result
+    pub classification: Triad,
+    /// Optional numeric score accompanying the classification
+    pub score: Option<f64>,
+    /// Optional distributional robustness score in `(0, 1]`, as computed by
+    /// [`robustness_score`](crate::robustness::robustness_score)
+    pub robustness: Option<f64>,
+    /// Caveats readers must see alongside the classification
+    pub warnings: Vec<String>,
+    /// Why the classification came out as it did, such as an
+    /// [`Explanation`](crate::Explanation) rendered to text
+    pub rationale: Option<String>,
+}
+
+impl ReportEntry {
+    /// Create an entry without a score
+    #[must_use]
+    pub fn new(label: impl Into<String>, classification: Triad) -> Self {
+        Self {
+            label: label.into(),
+            classification,
+            score: None,
+            robustness: None,
+            warnings: Vec::new(),
+            rationale: None,
+        }
+    }
+
+    /// Attach a numeric score to the entry
+    #[must_use]
+    pub fn with_score(mut self, score: f64) -> Self {
+        self.score = Some(score);
+        self
+    }
+
+    /// Attach a distributional robustness score to the entry
+    #[must_use]
+    pub fn with_robustness(mut self, robustness: f64) -> Self {
+        self.robustness = Some(robustness);
+        self
+    }
+
+    /// Attach a caveat to the entry
+    #[must_use]
+    pub fn with_warning(mut self, warning: impl Into<String>) -> Self {
+        self.warnings.push(warning.into());
+        self
+    }
+
+    /// Attach the reasoning behind the classification
+    #[must_use]
+    pub fn with_rationale(mut self, rationale: impl Into<String>) -> Self {
+        self.rationale = Some(rationale.into());
+        self
+    }
+
+    /// Attach the fourth-quadrant warning if `assessment` calls for one
+    ///
+    /// See [`assess_quadrant`](crate::quadrant::assess_quadrant).
+    #[must_use]
+    pub fn with_quadrant(self, assessment: &QuadrantAssessment) -> Self {
+        match assessment.warning() {
+            Some(warning) => self.with_warning(warning),
+            None => self,
+        }
+    }
+}
+
+/// A titled group of report entries
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ReportSection {
+    title: String,
+    entries: Vec<ReportEntry>,
+}
+
+impl ReportSection {
+    /// Create an empty section
+    #[must_use]
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add an entry, returning the section for chaining
+    #[must_use]
+    pub fn with_entry(mut self, entry: ReportEntry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// The section title
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The entries in insertion order
+    #[must_use]
+    pub fn entries(&self) -> &[ReportEntry] {
+        &self.entries
+    }
+}
+
+/// Running totals over every entry in a report
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ReportSummary {
+    counts: [usize; 3],
+    scored: usize,
+    score_sum: f64,
+    robustness_count: usize,
+    robustness_sum: f64,
+    warned: usize,
+}
+
+impl ReportSummary {
+    /// Total number of entries
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Number of entries with the given classification
+    #[must_use]
+    pub const fn count(&self, triad: Triad) -> usize {
+        self.counts[triad.rank() as usize]
+    }
+
+    /// The least desirable classification present, if any
+    #[must_use]
+    pub fn worst(&self) -> Option<Triad> {
+        Triad::iter().find(|&triad| self.count(triad) > 0)
+    }
+
+    /// The most desirable classification present, if any
+    #[must_use]
+    pub fn best(&self) -> Option<Triad> {
+        Triad::ALL
+            .into_iter()
+            .rev()
+            .find(|&triad| self.count(triad) > 0)
+    }
+
+    /// The overall verdict: a report is only as good as its weakest entry
+    #[must_use]
+    pub fn verdict(&self) -> Option<Triad> {
+        self.worst()
+    }
+
+    /// Mean of the scores of entries that carry one
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean_score(&self) -> Option<f64> {
+        (self.scored > 0).then(|| self.score_sum / self.scored as f64)
+    }
+
+    /// Mean robustness of entries that carry a robustness score
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean_robustness(&self) -> Option<f64> {
+        (self.robustness_count > 0).then(|| self.robustness_sum / self.robustness_count as f64)
+    }
+
+    /// Number of entries carrying at least one warning
+    #[must_use]
+    pub const fn warned(&self) -> usize {
+        self.warned
+    }
+
+    fn add(&mut self, entry: &ReportEntry) {
+        self.counts[entry.classification.rank() as usize] += 1;
+        if let Some(score) = entry.score {
+            self.scored += 1;
+            self.score_sum += score;
+        }
+        if let Some(robustness) = entry.robustness {
+            self.robustness_count += 1;
+            self.robustness_sum += robustness;
+        }
+        if !entry.warnings.is_empty() {
+            self.warned += 1;
+        }
+    }
+
+    fn remove(&mut self, entry: &ReportEntry) {
+        self.counts[entry.classification.rank() as usize] -= 1;
+        if let Some(score) = entry.score {
+            self.scored -= 1;
+            self.score_sum = if self.scored == 0 {
+                0.0
+            } else {
+                self.score_sum - score
+            };
+        }
+        if let Some(robustness) = entry.robustness {
+            self.robustness_count -= 1;
+            self.robustness_sum = if self.robustness_count == 0 {
+                0.0
+            } else {
+                self.robustness_sum - robustness
+            };
+        }
+        if !entry.warnings.is_empty() {
+            self.warned -= 1;
+        }
+    }
+}
+
+/// A report of classification results, maintained incrementally
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AnalysisReport {
+    title: String,
+    sections: Vec<ReportSection>,
+    summary: ReportSummary,
+    revision: u64,
+}
+
+impl AnalysisReport {
+    /// Create an empty report
+    #[must_use]
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            sections: Vec::new(),
+            summary: ReportSummary::default(),
+            revision: 0,
+        }
+    }
+
+    /// The report title
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The sections in insertion order
+    #[must_use]
+    pub fn sections(&self) -> &[ReportSection] {
+        &self.sections
+    }
+
+    /// Look up a section by title
+    #[must_use]
+    pub fn section(&self, title: &str) -> Option<&ReportSection> {
+        self.sections.iter().find(|section| section.title == title)
+    }
+
+    /// The running summary over all entries
+    #[must_use]
+    pub const fn summary(&self) -> &ReportSummary {
+        &self.summary
+    }
+
+    /// Number of updates applied since the report was created
+    ///
+    /// Useful for consumers that poll the report and only re-render on change.
+    #[must_use]
+    pub const fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Append a section to the end of the report
+    pub fn push_section(&mut self, section: ReportSection) {
+        for entry in &section.entries {
+            self.summary.add(entry);
+        }
+        self.sections.push(section);
+        self.revision += 1;
+    }
+
+    /// Append an entry to the section titled `section`, creating it if needed
+    pub fn push_entry(&mut self, section: &str, entry: ReportEntry) {
+        self.summary.add(&entry);
+        match self.sections.iter_mut().find(|s| s.title == section) {
+            Some(existing) => existing.entries.push(entry),
+            None => self
+                .sections
+                .push(ReportSection::new(section).with_entry(entry)),
+        }
+        self.revision += 1;
+    }
+
+    /// Replace the section with the same title, or append it if absent
+    ///
+    /// Returns the section that was replaced.
+    pub fn replace_section(&mut self, section: ReportSection) -> Option<ReportSection> {
+        for entry in &section.entries {
+            self.summary.add(entry);
+        }
+        self.revision += 1;
+
+        if let Some(index) = self.sections.iter().position(|s| s.title == section.title) {
+            let old = core::mem::replace(&mut self.sections[index], section);
+            for entry in &old.entries {
+                self.summary.remove(entry);
+            }
+            Some(old)
+        } else {
+            self.sections.push(section);
+            None
+        }
+    }
+
+    /// Remove the section titled `title`
+    pub fn remove_section(&mut self, title: &str) -> Option<ReportSection> {
+        let index = self.sections.iter().position(|s| s.title == title)?;
+        let old = self.sections.remove(index);
+        for entry in &old.entries {
+            self.summary.remove(entry);
+        }
+        self.revision += 1;
+        Some(old)
+    }
+
+    /// Keep only the `max_sections` most recently added sections
+    ///
+    /// Returns the number of sections dropped.
+    pub fn retain_recent(&mut self, max_sections: usize) -> usize {
+        let excess = self.sections.len().saturating_sub(max_sections);
+        if excess == 0 {
+            return 0;
+        }
+        for section in self.sections.drain(..excess) {
+            for entry in &section.entries {
+                self.summary.remove(entry);
+            }
+        }
+        self.revision += 1;
+        excess
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recomputed(report: &AnalysisReport) -> ReportSummary {
+        let mut summary = ReportSummary::default();
+        for section in report.sections() {
+            for entry in section.entries() {
+                summary.add(entry);
+            }
+        }
+        summary
+    }
+
+    #[test]
+    fn test_push_entry_creates_and_extends_sections() {
+        let mut report = AnalysisReport::new("service");
+        report.push_entry("latency", ReportEntry::new("p50", Triad::Robust));
+        report.push_entry("latency", ReportEntry::new("p99", Triad::Fragile));
+        report.push_entry("throughput", ReportEntry::new("rps", Triad::Antifragile));
+
+        assert_eq!(report.sections().len(), 2);
+        assert_eq!(report.section("latency").unwrap().entries().len(), 2);
+        assert_eq!(report.summary().total(), 3);
+        assert_eq!(report.summary().worst(), Some(Triad::Fragile));
+        assert_eq!(report.summary().best(), Some(Triad::Antifragile));
+        assert_eq!(report.revision(), 3);
+    }
+
+    #[test]
+    fn test_replace_section_updates_summary() {
+        let mut report = AnalysisReport::new("service");
+        report.push_section(
+            ReportSection::new("window")
+                .with_entry(ReportEntry::new("a", Triad::Fragile).with_score(-1.0))
+                .with_entry(ReportEntry::new("b", Triad::Fragile).with_score(-3.0)),
+        );
+        assert_eq!(report.summary().verdict(), Some(Triad::Fragile));
+
+        let old = report.replace_section(
+            ReportSection::new("window")
+                .with_entry(ReportEntry::new("a", Triad::Antifragile).with_score(2.0)),
+        );
+
+        assert_eq!(old.unwrap().entries().len(), 2);
+        assert_eq!(report.summary().count(Triad::Fragile), 0);
+        assert_eq!(report.summary().verdict(), Some(Triad::Antifragile));
+        assert_eq!(report.summary().mean_score(), Some(2.0));
+        assert_eq!(*report.summary(), recomputed(&report));
+    }
+
+    #[test]
+    fn test_retain_recent_ages_out_sections() {
+        let mut report = AnalysisReport::new("service");
+        for (i, triad) in [Triad::Fragile, Triad::Robust, Triad::Antifragile]
+            .into_iter()
+            .enumerate()
+        {
+            report.push_entry(&format!("window {i}"), ReportEntry::new("x", triad));
+        }
+
+        assert_eq!(report.retain_recent(2), 1);
+        assert_eq!(report.retain_recent(2), 0);
+        assert_eq!(report.sections()[0].title(), "window 1");
+        assert_eq!(report.summary().verdict(), Some(Triad::Robust));
+        assert_eq!(*report.summary(), recomputed(&report));
+    }
+
+    #[test]
+    fn test_remove_section() {
+        let mut report = AnalysisReport::new("service");
+        report.push_entry("a", ReportEntry::new("x", Triad::Fragile).with_score(1.0));
+        assert!(report.remove_section("missing").is_none());
+        assert!(report.remove_section("a").is_some());
+
+        assert_eq!(report.summary().total(), 0);
+        assert_eq!(report.summary().verdict(), None);
+        assert_eq!(report.summary().mean_score(), None);
+    }
+
+    #[test]
+    fn test_robustness_beside_classification() {
+        let mut report = AnalysisReport::new("service");
+        report.push_entry(
+            "load",
+            ReportEntry::new("p50", Triad::Robust).with_robustness(0.8),
+        );
+        report.push_entry(
+            "load",
+            ReportEntry::new("p99", Triad::Fragile).with_robustness(0.2),
+        );
+        report.push_entry("load", ReportEntry::new("errors", Triad::Robust));
+
+        let mean = report.summary().mean_robustness().unwrap();
+        assert!((mean - 0.5).abs() < 1e-12);
+        assert_eq!(
+            report.section("load").unwrap().entries()[2].robustness,
+            None
+        );
+
+        report.remove_section("load");
+        assert_eq!(report.summary().mean_robustness(), None);
+    }
+
+    #[test]
+    fn test_rationale_from_explanation() {
+        use crate::{Antifragile, TriadAnalysis};
+
+        struct Square;
+        impl Antifragile for Square {
+            type Stressor = f64;
+            type Payoff = f64;
+            fn payoff(&self, x: f64) -> f64 {
+                x * x
+            }
+        }
+
+        let explanation = Square.explain(1.0, 1.0);
+        let entry = ReportEntry::new("load", explanation.detail.triad)
+            .with_rationale(format!("{explanation:.1}"));
+        assert_eq!(
+            entry.rationale.as_deref(),
+            Some("f(x+Δ) + f(x-Δ) = 4.0 exceeded 2·f(x) = 2.0 by 2.0, therefore Antifragile")
+        );
+    }
+
+    #[test]
+    fn test_warnings_are_counted() {
+        use crate::quadrant::{Quadrant, QuadrantAssessment};
+
+        let fourth = QuadrantAssessment {
+            quadrant: Quadrant::Fourth,
+            tail_index: 1.5,
+            exposed_below: true,
+            exposed_above: false,
+        };
+        let first = QuadrantAssessment {
+            quadrant: Quadrant::First,
+            ..fourth
+        };
+
+        let mut report = AnalysisReport::new("desk");
+        report.push_entry(
+            "book",
+            ReportEntry::new("short vol", Triad::Fragile).with_quadrant(&fourth),
+        );
+        report.push_entry(
+            "book",
+            ReportEntry::new("long vol", Triad::Antifragile).with_quadrant(&first),
+        );
+        assert_eq!(report.summary().warned(), 1);
+        assert_eq!(
+            report.section("book").unwrap().entries()[0].warnings,
+            vec![crate::quadrant::FOURTH_QUADRANT_WARNING.to_string()]
+        );
+
+        report.retain_recent(0);
+        assert_eq!(report.summary().warned(), 0);
+    }
+}
@@ -0,0 +1,148 @@
+//! Special functions shared by the analytic models
+//!
+//! Accurate to about `1e-7`, which is well below the resolution any
+//! classification in this crate depends on.
+//!
+//! Without `std` only the Student's t test behind
+//! [`classify_stochastic`](crate::TriadAnalysis::classify_stochastic) is used.
+#![cfg_attr(not(feature = "std"), allow(dead_code))]
+
+use core::f64::consts::{FRAC_1_SQRT_2, PI};
+
+use crate::antifragile::abs;
+use crate::math::{exp, ln, sqrt};
+
+/// Complementary error function (Chebyshev fit, fractional error < 1.2e-7)
+pub(crate) fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.5 * abs(x));
+    let poly = -1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let tail = t * exp(-x * x + poly);
+    if x >= 0.0 { tail } else { 2.0 - tail }
+}
+
+/// Standard normal density
+pub(crate) fn normal_pdf(z: f64) -> f64 {
+    exp(-0.5 * z * z) / sqrt(2.0 * PI)
+}
+
+/// Standard normal distribution function
+pub(crate) fn normal_cdf(z: f64) -> f64 {
+    0.5 * erfc(-z * FRAC_1_SQRT_2)
+}
+
+/// Standard normal loss function `E[(Z - z)⁺]`
+pub(crate) fn normal_loss(z: f64) -> f64 {
+    // 1 - Φ(z) computed directly to avoid cancellation in the upper tail
+    normal_pdf(z) - z * 0.5 * erfc(z * FRAC_1_SQRT_2)
+}
+
+/// Natural log of the gamma function for `x >= 0.5` (Lanczos, g = 7)
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 8] = [
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    let x = x - 1.0;
+    let series = COEFFICIENTS
+        .iter()
+        .zip(1_u8..)
+        .fold(0.999_999_999_999_809_9, |sum, (c, i)| {
+            sum + c / (x + f64::from(i))
+        });
+    let t = x + 7.5;
+    0.5 * ln(2.0 * PI) + (x + 0.5) * ln(t) - t + ln(series)
+}
+
+/// Regularized incomplete beta function `I_x(a, b)`
+pub(crate) fn regularized_beta(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front = exp(a * ln(x) + b * ln(1.0 - x) + ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b));
+    // The continued fraction converges fastest below the mean of the distribution
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_fraction(x, a, b) / a
+    } else {
+        1.0 - front * beta_fraction(1.0 - x, b, a) / b
+    }
+}
+
+/// Continued fraction for the incomplete beta function (modified Lentz)
+fn beta_fraction(x: f64, a: f64, b: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let clamp = |v: f64| if abs(v) < TINY { TINY } else { v };
+    let mut upper = 1.0;
+    let mut lower = 1.0 / clamp(1.0 - (a + b) * x / (a + 1.0));
+    let mut result = lower;
+    for m in 1..200_u8 {
+        let m = f64::from(m);
+        let even = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        lower = 1.0 / clamp(1.0 + even * lower);
+        upper = clamp(1.0 + even / upper);
+        result *= lower * upper;
+        let odd = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        lower = 1.0 / clamp(1.0 + odd * lower);
+        upper = clamp(1.0 + odd / upper);
+        let step = lower * upper;
+        result *= step;
+        if abs(step - 1.0) < 1e-12 {
+            break;
+        }
+    }
+    result
+}
+
+/// Two-sided p-value `P(|T| >= |t|)` of Student's t with `df` degrees of freedom
+pub(crate) fn student_t_two_sided(t: f64, df: f64) -> f64 {
+    regularized_beta(df / (df + t * t), 0.5 * df, 0.5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_erfc_known_values() {
+        assert!((erfc(0.0) - 1.0).abs() < 1e-7);
+        assert!((erfc(1.0) - 0.157_299_2).abs() < 1e-7);
+        assert!((erfc(-0.5) + erfc(0.5) - 2.0).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_normal_loss() {
+        // E[Z⁺] = φ(0)
+        assert!((normal_loss(0.0) - normal_pdf(0.0)).abs() < 1e-7);
+        // E[(Z - z)⁺] - E[(z - Z)⁺] = -z, and E[(z - Z)⁺] = loss(-z) by symmetry
+        let z = 0.7;
+        assert!((normal_loss(z) - normal_loss(-z) + z).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_ln_gamma_and_student_t() {
+        assert!((ln_gamma(5.0) - 24.0_f64.ln()).abs() < 1e-10);
+        assert!((ln_gamma(0.5) - PI.sqrt().ln()).abs() < 1e-10);
+        // One degree of freedom is the Cauchy distribution: P(|T| >= 1) = 1/2
+        assert!((student_t_two_sided(1.0, 1.0) - 0.5).abs() < 1e-7);
+        // Two degrees of freedom: P(|T| >= t) = 1 - t / √(2 + t²)
+        assert!((student_t_two_sided(2.0, 2.0) - (1.0 - 2.0 / 6.0_f64.sqrt())).abs() < 1e-7);
+        assert!((student_t_two_sided(0.0, 10.0) - 1.0).abs() < 1e-12);
+        // Many degrees of freedom approach the normal: P(|Z| >= 1.96) ≈ 0.05
+        assert!((student_t_two_sided(1.96, 1e6) - 0.05).abs() < 1e-4);
+    }
+}
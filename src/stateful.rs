@@ -0,0 +1,191 @@
+//! # Stateful payoffs
+//!
+//! Some systems change as they are stressed: an organism adapts to a dose,
+//! a battery drains, a model learns from each load it serves. Their payoff
+//! takes `&mut self`, which [`Antifragile`](crate::Antifragile) cannot
+//! express. [`AntifragileMut`](crate::stateful::AntifragileMut) can, and
+//! [`StatefulAnalysis`](crate::stateful::StatefulAnalysis) classifies it in
+//! one of two ways:
+//!
+//! | Method | State seen by each probe |
+//! |--------|--------------------------|
+//! | [`classify_mut`](crate::stateful::StatefulAnalysis::classify_mut) | Whatever the previous probes left behind |
+//! | [`classify_isolated`](crate::stateful::StatefulAnalysis::classify_isolated) | A fresh clone of the current state |
+//!
+//! ## Evaluation order
+//!
+//! [`classify_mut`](crate::stateful::StatefulAnalysis::classify_mut)
+//! evaluates in a fixed order, the same one
+//! [`classify`](crate::TriadAnalysis::classify) uses:
+//!
+//! 1. `f(x)`
+//! 2. `f(x+Δ)`
+//! 3. `f(x-Δ)`
+//!
+//! With state carried between probes, the verdict describes the system
+//! *including* its response to the earlier probes, which is exactly the
+//! hormetic effect: a dose that strengthens the system improves its
+//! response to the next one.
+//! [`classify_isolated`](crate::stateful::StatefulAnalysis::classify_isolated)
+//! removes that effect and measures the curvature of the current state
+//! alone.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::stateful::{AntifragileMut, StatefulAnalysis};
+//!
+//! /// Each dose is costly now but builds adaptation for the next
+//! #[derive(Clone)]
+//! struct Hormetic {
+//!     adaptation: f64,
+//! }
+//!
+//! impl AntifragileMut for Hormetic {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&mut self, dose: f64) -> f64 {
+//!         let response = self.adaptation * dose - dose * dose;
+//!         self.adaptation += dose;
+//!         response
+//!     }
+//! }
+//!
+//! let mut organism = Hormetic { adaptation: 0.0 };
+//!
+//! // From a fixed state, every dose only harms
+//! assert_eq!(organism.classify_isolated(1.0, 0.5), Triad::Fragile);
+//! assert!(organism.adaptation.abs() < f64::EPSILON);
+//!
+//! // Carried from dose to dose, the stress makes the system stronger
+//! assert_eq!(organism.classify_mut(1.0, 0.5), Triad::Antifragile);
+//! assert!((organism.adaptation - 3.0).abs() < f64::EPSILON);
+//! ```
+
+use core::ops::{Add, Sub};
+
+use crate::Triad;
+
+/// A system whose payoff may change its own state
+///
+/// The stateful counterpart of [`Antifragile`](crate::Antifragile), with the
+/// same bounds on stressor and payoff.
+pub trait AntifragileMut {
+    /// The type of stressor (e.g., volatility, load, perturbation)
+    type Stressor: Copy + Add<Output = Self::Stressor> + Sub<Output = Self::Stressor>;
+
+    /// The type of payoff/outcome (must be comparable and additive)
+    type Payoff: Copy + Add<Output = Self::Payoff> + PartialOrd;
+
+    /// The payoff function, free to learn from or deplete the system
+    fn payoff(&mut self, stressor: Self::Stressor) -> Self::Payoff;
+
+    /// Returns the payoff added to itself (r + r)
+    ///
+    /// See [`Antifragile::twin`](crate::Antifragile::twin).
+    fn twin(r: Self::Payoff) -> Self::Payoff {
+        r + r
+    }
+}
+
+/// Extension trait classifying stateful systems
+pub trait StatefulAnalysis: AntifragileMut {
+    /// Classify, carrying state from one probe to the next
+    ///
+    /// Evaluates `f(x)`, then `f(x+Δ)`, then `f(x-Δ)` on `self`, leaving
+    /// the system in the state the last probe produced (see the
+    /// [module documentation](self#evaluation-order)).
+    fn classify_mut(&mut self, at: Self::Stressor, delta: Self::Stressor) -> Triad {
+        let f_x = self.payoff(at);
+        let f_x_plus = self.payoff(at + delta);
+        let f_x_minus = self.payoff(at - delta);
+        compare(f_x_plus + f_x_minus, Self::twin(f_x))
+    }
+
+    /// Classify with each probe run on its own clone of the current state
+    ///
+    /// The order of evaluation does not matter and `self` is left
+    /// untouched.
+    fn classify_isolated(&self, at: Self::Stressor, delta: Self::Stressor) -> Triad
+    where
+        Self: Clone,
+    {
+        let f_x = self.clone().payoff(at);
+        let f_x_plus = self.clone().payoff(at + delta);
+        let f_x_minus = self.clone().payoff(at - delta);
+        compare(f_x_plus + f_x_minus, Self::twin(f_x))
+    }
+}
+
+impl<T: AntifragileMut + ?Sized> StatefulAnalysis for T {}
+
+/// The classification for `sum` against `twin`
+fn compare<P: Copy + PartialOrd>(sum: P, twin: P) -> Triad {
+    if sum > twin {
+        Triad::Antifragile
+    } else if sum < twin {
+        Triad::Fragile
+    } else {
+        Triad::Robust
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A convex payoff that records every stressor it sees
+    #[cfg(feature = "std")]
+    #[derive(Clone, Default)]
+    struct Recorder {
+        seen: Vec<i32>,
+    }
+
+    #[cfg(feature = "std")]
+    impl AntifragileMut for Recorder {
+        type Stressor = i32;
+        type Payoff = i32;
+
+        fn payoff(&mut self, x: i32) -> i32 {
+            self.seen.push(x);
+            x * x
+        }
+    }
+
+    /// A battery that delivers up to its remaining charge
+    #[derive(Clone)]
+    struct Battery {
+        charge: f64,
+    }
+
+    impl AntifragileMut for Battery {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&mut self, demand: f64) -> f64 {
+            let delivered = demand.min(self.charge);
+            self.charge -= delivered;
+            delivered
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_evaluation_order_is_documented_order() {
+        let mut recorder = Recorder::default();
+        assert_eq!(recorder.classify_mut(10, 3), Triad::Antifragile);
+        assert_eq!(recorder.seen, [10, 13, 7]);
+
+        assert_eq!(recorder.classify_isolated(10, 3), Triad::Antifragile);
+        assert_eq!(recorder.seen.len(), 3);
+    }
+
+    #[test]
+    fn test_depletion_makes_carried_state_fragile() {
+        let mut battery = Battery { charge: 20.0 };
+        assert_eq!(battery.classify_isolated(8.0, 2.0), Triad::Robust);
+        // 8 then 10 drain the battery, leaving 2 of the 6 demanded last
+        assert_eq!(battery.classify_mut(8.0, 2.0), Triad::Fragile);
+        assert!(battery.charge.abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,254 @@
+//! # Option strategy composer
+//!
+//! Standard option structures are piecewise-linear payoffs, so they can be
+//! classified exactly. [`OptionStrategy`](crate::strategy::OptionStrategy)
+//! collects legs (long or short calls and puts) and builds the combined
+//! expiry payoff as a [`PiecewiseLinear`](crate::piecewise::PiecewiseLinear).
+//!
+//! Every option contributes its quantity to the slope change at its strike:
+//! long options add convexity, short options add concavity, whether they are
+//! calls or puts. A structure is antifragile over a region exactly when the
+//! long strikes inside it outweigh the short ones.
+//!
+//! Premiums are not modelled; they shift the payoff by a constant and do not
+//! affect classification.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::strategy::OptionStrategy;
+//!
+//! let straddle = OptionStrategy::straddle(100.0).build()?;
+//! let condor = OptionStrategy::iron_condor(80.0, 90.0, 110.0, 120.0).build()?;
+//!
+//! // Long volatility vs short volatility around the same centre
+//! assert_eq!(straddle.classify_exact(100.0, 15.0), Triad::Antifragile);
+//! assert_eq!(condor.classify_exact(100.0, 15.0), Triad::Fragile);
+//! # Ok::<(), antifragile::piecewise::PiecewiseError>(())
+//! ```
+
+use crate::piecewise::{PiecewiseError, PiecewiseLinear};
+
+/// Whether an option pays on the upside or the downside
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OptionKind {
+    /// Pays `max(0, x - strike)` per unit
+    Call,
+    /// Pays `max(0, strike - x)` per unit
+    Put,
+}
+
+/// A single option position
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptionLeg {
+    /// Call or put
+    pub kind: OptionKind,
+    /// Strike price
+    pub strike: f64,
+    /// Number of contracts; negative for short positions
+    pub quantity: f64,
+}
+
+impl OptionLeg {
+    /// Expiry payoff of the leg at underlying price `x`
+    #[must_use]
+    pub fn payoff(&self, x: f64) -> f64 {
+        let intrinsic = match self.kind {
+            OptionKind::Call => x - self.strike,
+            OptionKind::Put => self.strike - x,
+        };
+        self.quantity * intrinsic.max(0.0)
+    }
+}
+
+/// A combination of option legs on one underlying
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OptionStrategy {
+    legs: Vec<OptionLeg>,
+}
+
+impl OptionStrategy {
+    /// An empty strategy
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { legs: Vec::new() }
+    }
+
+    /// Add a leg
+    #[must_use]
+    pub fn with_leg(mut self, kind: OptionKind, strike: f64, quantity: f64) -> Self {
+        self.legs.push(OptionLeg {
+            kind,
+            strike,
+            quantity,
+        });
+        self
+    }
+
+    /// Buy one call
+    #[must_use]
+    pub fn long_call(self, strike: f64) -> Self {
+        self.with_leg(OptionKind::Call, strike, 1.0)
+    }
+
+    /// Sell one call
+    #[must_use]
+    pub fn short_call(self, strike: f64) -> Self {
+        self.with_leg(OptionKind::Call, strike, -1.0)
+    }
+
+    /// Buy one put
+    #[must_use]
+    pub fn long_put(self, strike: f64) -> Self {
+        self.with_leg(OptionKind::Put, strike, 1.0)
+    }
+
+    /// Sell one put
+    #[must_use]
+    pub fn short_put(self, strike: f64) -> Self {
+        self.with_leg(OptionKind::Put, strike, -1.0)
+    }
+
+    /// Bull call spread: long a call at `low`, short a call at `high`
+    #[must_use]
+    pub fn bull_spread(low: f64, high: f64) -> Self {
+        Self::new().long_call(low).short_call(high)
+    }
+
+    /// Long straddle: long a call and a put at the same strike
+    #[must_use]
+    pub fn straddle(strike: f64) -> Self {
+        Self::new().long_call(strike).long_put(strike)
+    }
+
+    /// Short iron condor: short a put spread and a call spread around the
+    /// current price
+    ///
+    /// Long put at `put_low`, short put at `put_high`, short call at
+    /// `call_low`, long call at `call_high`. Profits while the underlying
+    /// stays between the short strikes; losses are capped by the wings.
+    #[must_use]
+    pub fn iron_condor(put_low: f64, put_high: f64, call_low: f64, call_high: f64) -> Self {
+        Self::new()
+            .long_put(put_low)
+            .short_put(put_high)
+            .short_call(call_low)
+            .long_call(call_high)
+    }
+
+    /// Risk reversal: short a put at `put_strike`, long a call at
+    /// `call_strike`
+    #[must_use]
+    pub fn risk_reversal(put_strike: f64, call_strike: f64) -> Self {
+        Self::new().short_put(put_strike).long_call(call_strike)
+    }
+
+    /// The legs in the order they were added
+    #[must_use]
+    pub fn legs(&self) -> &[OptionLeg] {
+        &self.legs
+    }
+
+    /// Combined expiry payoff at underlying price `x`
+    #[must_use]
+    pub fn payoff(&self, x: f64) -> f64 {
+        self.legs.iter().map(|leg| leg.payoff(x)).sum()
+    }
+
+    /// Build the combined expiry payoff as a piecewise-linear function
+    ///
+    /// Breakpoints sit at each distinct strike. A strategy with fewer than two
+    /// distinct strikes gets an extra, kink-free breakpoint one unit above.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PiecewiseError::NonFinite`] if any strike or quantity is NaN
+    /// or infinite.
+    pub fn build(&self) -> Result<PiecewiseLinear, PiecewiseError> {
+        if let Some(index) = self
+            .legs
+            .iter()
+            .position(|leg| !leg.strike.is_finite() || !leg.quantity.is_finite())
+        {
+            return Err(PiecewiseError::NonFinite { index });
+        }
+
+        let mut strikes: Vec<f64> = self.legs.iter().map(|leg| leg.strike).collect();
+        strikes.sort_by(f64::total_cmp);
+        strikes.dedup();
+        match strikes.as_slice() {
+            [] => strikes.extend([0.0, 1.0]),
+            [only] => strikes.push(only + 1.0),
+            _ => {}
+        }
+
+        // Below every strike only puts pay, above every strike only calls do
+        let left_slope = -self.quantity_of(OptionKind::Put);
+        let right_slope = self.quantity_of(OptionKind::Call);
+        Ok(
+            PiecewiseLinear::new(strikes.into_iter().map(|x| (x, self.payoff(x))))?
+                .with_left_slope(left_slope)
+                .with_right_slope(right_slope),
+        )
+    }
+
+    fn quantity_of(&self, kind: OptionKind) -> f64 {
+        self.legs
+            .iter()
+            .filter(|leg| leg.kind == kind)
+            .map(|leg| leg.quantity)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Triad;
+
+    #[test]
+    fn test_straddle_is_globally_antifragile() {
+        let payoff = OptionStrategy::straddle(100.0).build().unwrap();
+        assert_eq!(payoff.global_classification(), Some(Triad::Antifragile));
+        assert_eq!(payoff.kinks().len(), 2);
+        assert!((payoff.kinks()[0].slope_change - 2.0).abs() < f64::EPSILON);
+        assert!((payoff.evaluate(85.0) - 15.0).abs() < f64::EPSILON);
+        assert!((payoff.evaluate(130.0) - 30.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_bull_spread_is_convex_low_and_concave_high() {
+        let payoff = OptionStrategy::bull_spread(90.0, 110.0).build().unwrap();
+        assert_eq!(payoff.classify_exact(90.0, 5.0), Triad::Antifragile);
+        assert_eq!(payoff.classify_exact(110.0, 5.0), Triad::Fragile);
+        assert_eq!(payoff.classify_exact(100.0, 20.0), Triad::Robust);
+        assert_eq!(payoff.global_classification(), None);
+        assert!((payoff.evaluate(200.0) - 20.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_iron_condor_and_risk_reversal_match_leg_sums() {
+        for strategy in [
+            OptionStrategy::iron_condor(80.0, 90.0, 110.0, 120.0),
+            OptionStrategy::risk_reversal(95.0, 105.0),
+        ] {
+            let payoff = strategy.build().unwrap();
+            for x in [50.0, 82.5, 95.0, 100.0, 113.0, 150.0] {
+                assert!((payoff.evaluate(x) - strategy.payoff(x)).abs() < 1e-12);
+            }
+        }
+        let condor = OptionStrategy::iron_condor(80.0, 90.0, 110.0, 120.0)
+            .build()
+            .unwrap();
+        assert_eq!(condor.slopes(), vec![0.0, 1.0, 0.0, -1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_degenerate_strategies() {
+        let empty = OptionStrategy::new().build().unwrap();
+        assert_eq!(empty.global_classification(), Some(Triad::Robust));
+        assert_eq!(
+            OptionStrategy::new().long_call(f64::NAN).build(),
+            Err(PiecewiseError::NonFinite { index: 0 })
+        );
+    }
+}
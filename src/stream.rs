@@ -0,0 +1,745 @@
+//! # Streaming classification
+//!
+//! A production monitor sees `(stressor, payoff)` observations one at a
+//! time, forever. [`OnlineClassifier`](crate::stream::OnlineClassifier)
+//! folds each one into the sufficient statistics of a least-squares
+//! quadratic fit, so it never buffers observations or re-fits, and reports
+//! the current verdict and curvature estimate at any point.
+//!
+//! The verdict follows the sign of the fitted second derivative once it
+//! clears the significance threshold, two standard errors by default, and
+//! is [`Robust`](crate::Triad::Robust) until then.
+//!
+//! By default every observation counts equally. With
+//! [`with_half_life`](crate::stream::OnlineClassifier::with_half_life) the
+//! fit is exponentially weighted instead, the way ops dashboards weight
+//! telemetry: an observation's weight halves with every `half_life` newer
+//! ones, so recent behaviour dominates without a hard cutoff.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::stream::OnlineClassifier;
+//!
+//! let mut monitor = OnlineClassifier::new();
+//! assert_eq!(monitor.convexity(), None);
+//!
+//! // Throughput against load, flattening out with a little jitter
+//! for i in 0..50 {
+//!     let load = f64::from(i % 10);
+//!     let jitter = if i % 3 == 0 { 0.2 } else { -0.1 };
+//!     monitor.observe(load, 10.0 * load - 0.5 * load * load + jitter);
+//! }
+//! assert_eq!(monitor.triad(), Triad::Fragile);
+//! assert!((monitor.convexity().unwrap() + 1.0).abs() < 0.05);
+//! ```
+//!
+//! ## Rolling windows
+//!
+//! Services change behaviour after a deploy, and an all-time fit keeps
+//! averaging in the old regime.
+//! [`RollingClassifier`](crate::stream::RollingClassifier) fits only the
+//! observations inside a [`Window`](crate::stream::Window): the most recent
+//! `n`, or those within a span of time of the newest. Older observations
+//! age out as new ones arrive.
+//!
+//! ```rust
+//! use std::time::Duration;
+//!
+//! use antifragile::Triad;
+//! use antifragile::stream::{RollingClassifier, Window};
+//!
+//! let mut monitor = RollingClassifier::new(Window::Within(Duration::from_secs(60)));
+//! let curve = |load: f64, sign: f64| sign * load * load;
+//!
+//! // Convex before the deploy at t = 100 s, concave after it
+//! for second in 0..200_u32 {
+//!     let load = f64::from(second % 7);
+//!     let sign = if second < 100 { 1.0 } else { -1.0 };
+//!     monitor.observe_at(Duration::from_secs(second.into()), load, curve(load, sign));
+//!     if second == 99 {
+//!         assert_eq!(monitor.triad(), Triad::Antifragile);
+//!     }
+//! }
+//! assert_eq!(monitor.triad(), Triad::Fragile);
+//! assert_eq!(monitor.observations(), 61);
+//! ```
+//!
+//! ## Change points
+//!
+//! Often the moment a system *becomes* fragile matters more than its
+//! steady-state label. [`ChangeDetector`](crate::stream::ChangeDetector)
+//! runs CUSUM tests on the standardized curvature of a rolling fit and
+//! returns a [`TriadChange`](crate::stream::TriadChange)
+//! when the evidence for another class crosses its threshold.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::stream::{ChangeConfig, ChangeDetector};
+//!
+//! let mut detector = ChangeDetector::new(ChangeConfig::default());
+//! let mut became_fragile = None;
+//! for i in 0..600_u32 {
+//!     let load = f64::from(i % 10);
+//!     let wobble = if i % 2 == 0 { 0.3 } else { -0.3 };
+//!     let curvature = if i < 300 { 0.5 } else { -0.5 };
+//!     if let Some(change) = detector.observe(load, curvature * load * load + wobble) {
+//!         if change.to == Triad::Fragile {
+//!             became_fragile = Some(change.observation);
+//!         }
+//!     }
+//! }
+//! // Reported within about one window of the change at observation 300
+//! assert!(became_fragile.is_some_and(|at| (300..360).contains(&at)));
+//! assert_eq!(detector.regime(), Some(Triad::Fragile));
+//! ```
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::Triad;
+use crate::quadratic::QuadraticSums;
+
+/// Incremental classifier over a stream of `(stressor, payoff)` observations
+///
+/// Each observation costs constant time and memory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnlineClassifier {
+    sums: QuadraticSums,
+    significance: f64,
+    /// Factor applied to earlier weights before each observation
+    decay: f64,
+    observed: usize,
+}
+
+impl OnlineClassifier {
+    /// An empty classifier with a significance threshold of two standard
+    /// errors
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            sums: QuadraticSums::new(),
+            significance: 2.0,
+            decay: 1.0,
+            observed: 0,
+        }
+    }
+
+    /// Set how many standard errors the curvature must clear to count
+    #[must_use]
+    pub const fn with_significance(mut self, standard_errors: f64) -> Self {
+        self.significance = standard_errors;
+        self
+    }
+
+    /// Weight observations exponentially, halving an observation's weight
+    /// with every `half_life` observations after it
+    ///
+    /// The total weight settles near `1.44 × half_life`, which acts as the
+    /// sample size of the fit, so a half-life of a few observations never
+    /// identifies the curvature. A half-life that is not positive turns
+    /// weighting off.
+    #[must_use]
+    pub fn with_half_life(mut self, half_life: f64) -> Self {
+        self.decay = if half_life > 0.0 {
+            0.5_f64.powf(half_life.recip())
+        } else {
+            1.0
+        };
+        self
+    }
+
+    /// Add one observation
+    ///
+    /// Observations with a NaN or infinite value are ignored, so one bad
+    /// reading cannot poison the running fit.
+    pub fn observe(&mut self, stressor: f64, payoff: f64) {
+        if stressor.is_finite() && payoff.is_finite() {
+            if self.decay < 1.0 {
+                self.sums.decay(self.decay);
+            }
+            self.sums.add(stressor, payoff);
+            self.observed += 1;
+        }
+    }
+
+    /// Add every observation of `observations`
+    pub fn observe_all(&mut self, observations: impl IntoIterator<Item = (f64, f64)>) {
+        for (stressor, payoff) in observations {
+            self.observe(stressor, payoff);
+        }
+    }
+
+    /// Number of observations folded in so far
+    #[must_use]
+    pub const fn observations(&self) -> usize {
+        self.observed
+    }
+
+    /// Total weight of the observations: their number when unweighted,
+    /// less once older ones have decayed
+    #[must_use]
+    pub const fn effective_observations(&self) -> f64 {
+        self.sums.weight()
+    }
+
+    /// Fitted second derivative and its standard error
+    ///
+    /// `None` until the curvature is identified: at least four observations
+    /// at three or more distinct stressors.
+    #[must_use]
+    pub fn curvature(&self) -> Option<(f64, f64)> {
+        self.sums.curvature(None)
+    }
+
+    /// Fitted second derivative, if identified
+    #[must_use]
+    pub fn convexity(&self) -> Option<f64> {
+        self.curvature().map(|(curvature, _)| curvature)
+    }
+
+    /// Current classification
+    ///
+    /// [`Robust`](Triad::Robust) until the curvature is identified and
+    /// clears the significance threshold.
+    pub fn triad(&self) -> Triad {
+        verdict(self.curvature(), self.significance)
+    }
+}
+
+impl Default for OnlineClassifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Which observations a [`RollingClassifier`] keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Window {
+    /// The most recent `n` observations
+    Last(usize),
+    /// Observations no older than this, measured back from the newest
+    /// timestamp seen
+    Within(Duration),
+}
+
+/// Streaming classifier over a rolling window of recent observations
+///
+/// Observations carry a timestamp: a [`Duration`] since any epoch the
+/// caller chooses, such as the start of the process or the Unix epoch.
+/// Each one costs amortized constant time; memory grows with the window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollingClassifier {
+    window: Window,
+    significance: f64,
+    /// Newest timestamp seen
+    latest: Duration,
+    /// `(timestamp, stressor, payoff)`, oldest first
+    kept: VecDeque<(Duration, f64, f64)>,
+    sums: QuadraticSums,
+    /// Removals since the sums were last rebuilt from `kept`
+    removed: usize,
+}
+
+impl RollingClassifier {
+    /// An empty classifier over `window`, with a significance threshold of
+    /// two standard errors
+    #[must_use]
+    pub const fn new(window: Window) -> Self {
+        Self {
+            window,
+            significance: 2.0,
+            latest: Duration::ZERO,
+            kept: VecDeque::new(),
+            sums: QuadraticSums::new(),
+            removed: 0,
+        }
+    }
+
+    /// Set how many standard errors the curvature must clear to count
+    #[must_use]
+    pub const fn with_significance(mut self, standard_errors: f64) -> Self {
+        self.significance = standard_errors;
+        self
+    }
+
+    /// The window observations are kept for
+    #[must_use]
+    pub const fn window(&self) -> Window {
+        self.window
+    }
+
+    /// Add one observation, timestamped like the newest so far
+    ///
+    /// Convenient for [`Window::Last`], where timestamps do not matter.
+    pub fn observe(&mut self, stressor: f64, payoff: f64) {
+        self.observe_at(self.latest, stressor, payoff);
+    }
+
+    /// Add every observation of `observations`, as [`observe`](Self::observe)
+    pub fn observe_all(&mut self, observations: impl IntoIterator<Item = (f64, f64)>) {
+        for (stressor, payoff) in observations {
+            self.observe(stressor, payoff);
+        }
+    }
+
+    /// Add one observation made at `timestamp`, then age out whatever the
+    /// window no longer covers
+    ///
+    /// Timestamps are expected in non-decreasing order; one earlier than
+    /// the newest so far is treated as arriving with it. Observations with
+    /// a NaN or infinite value are ignored.
+    pub fn observe_at(&mut self, timestamp: Duration, stressor: f64, payoff: f64) {
+        if !stressor.is_finite() || !payoff.is_finite() {
+            return;
+        }
+        self.latest = self.latest.max(timestamp);
+        self.kept.push_back((self.latest, stressor, payoff));
+        self.sums.add(stressor, payoff);
+        self.evict();
+    }
+
+    /// Age out observations older than the window as of `now`, without
+    /// adding one
+    ///
+    /// Only affects [`Window::Within`]; lets a quiet stream forget a regime
+    /// that is no longer being observed.
+    pub fn expire(&mut self, now: Duration) {
+        self.latest = self.latest.max(now);
+        self.evict();
+    }
+
+    /// Number of observations inside the window
+    #[must_use]
+    pub fn observations(&self) -> usize {
+        self.kept.len()
+    }
+
+    /// Fitted second derivative over the window and its standard error
+    ///
+    /// `None` until the curvature is identified: at least four observations
+    /// at three or more distinct stressors inside the window.
+    #[must_use]
+    pub fn curvature(&self) -> Option<(f64, f64)> {
+        self.sums.curvature(None)
+    }
+
+    /// Fitted second derivative over the window, if identified
+    #[must_use]
+    pub fn convexity(&self) -> Option<f64> {
+        self.curvature().map(|(curvature, _)| curvature)
+    }
+
+    /// Classification of the observations inside the window
+    ///
+    /// [`Robust`](Triad::Robust) until the curvature is identified and
+    /// clears the significance threshold.
+    pub fn triad(&self) -> Triad {
+        verdict(self.curvature(), self.significance)
+    }
+
+    fn evict(&mut self) {
+        loop {
+            let expired = match (self.window, self.kept.front()) {
+                (Window::Last(n), Some(_)) => self.kept.len() > n,
+                (Window::Within(span), Some((at, _, _))) => self.latest.saturating_sub(*at) > span,
+                (_, None) => false,
+            };
+            if !expired {
+                break;
+            }
+            if let Some((_, stressor, payoff)) = self.kept.pop_front() {
+                self.sums.remove(stressor, payoff);
+                self.removed += 1;
+            }
+        }
+        // Subtraction drifts; rebuild once as many observations have left
+        // as remain, keeping the cost amortized constant
+        if self.removed > self.kept.len() {
+            self.sums = QuadraticSums::new();
+            for (_, stressor, payoff) in &self.kept {
+                self.sums.add(*stressor, *payoff);
+            }
+            self.removed = 0;
+        }
+    }
+}
+
+/// Sensitivity of a [`ChangeDetector`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChangeConfig {
+    /// Number of most recent observations in the fit being watched
+    pub window: usize,
+    /// CUSUM reference value `k`, in standard errors: the curvature must
+    /// exceed it to count as evidence of a curved regime, and stay within
+    /// it to count as evidence of a robust one
+    pub reference: f64,
+    /// CUSUM decision threshold `h`: lower detects sooner, with more false
+    /// alarms
+    pub threshold: f64,
+}
+
+impl Default for ChangeConfig {
+    /// Window of 50, reference `2.0`, threshold `50.0`
+    fn default() -> Self {
+        Self {
+            window: 50,
+            reference: 2.0,
+            threshold: 50.0,
+        }
+    }
+}
+
+/// A change of classification regime found by [`ChangeDetector`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriadChange {
+    /// One-based number of the observation that triggered the detection
+    pub observation: usize,
+    /// The regime before the change
+    pub from: Triad,
+    /// The regime after it
+    pub to: Triad,
+    /// The fitted second derivative when the change was detected
+    pub convexity: f64,
+}
+
+/// CUSUM change-point detector on the online curvature estimate
+///
+/// Watches `z = κ / se`, the fitted second derivative of a
+/// [`RollingClassifier`] in units of its standard error. Scores are
+/// clamped to ±10 so an exact fit cannot swamp the test. One cumulative sum
+/// per class gathers evidence against the current regime:
+///
+/// | Class | Increment |
+/// |-------|-----------|
+/// | Antifragile | `z - k` |
+/// | Fragile | `-z - k` |
+/// | Robust | `k - abs(z)` |
+///
+/// Each sum is floored at zero, and when one other than the current
+/// regime's exceeds the threshold `h` the regime changes and every sum
+/// restarts.
+///
+/// A hard window forgets the old regime completely once `window`
+/// observations have passed, so changes are typically reported within
+/// about one window. While the window straddles a change it fits neither
+/// regime well, so a flip between the curved classes may pass through
+/// [`Robust`](Triad::Robust) on the way.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeDetector {
+    config: ChangeConfig,
+    classifier: RollingClassifier,
+    observed: usize,
+    regime: Option<Triad>,
+    /// Evidence for Fragile, Robust, and Antifragile, in rank order
+    evidence: [f64; 3],
+}
+
+impl ChangeDetector {
+    /// A detector with no regime yet
+    #[must_use]
+    pub fn new(config: ChangeConfig) -> Self {
+        Self {
+            config,
+            classifier: RollingClassifier::new(Window::Last(config.window))
+                .with_significance(config.reference),
+            observed: 0,
+            regime: None,
+            evidence: [0.0; 3],
+        }
+    }
+
+    /// The current regime, once one has been established
+    #[must_use]
+    pub const fn regime(&self) -> Option<Triad> {
+        self.regime
+    }
+
+    /// The rolling classifier being watched
+    #[must_use]
+    pub const fn classifier(&self) -> &RollingClassifier {
+        &self.classifier
+    }
+
+    /// Number of observations seen
+    #[must_use]
+    pub const fn observations(&self) -> usize {
+        self.observed
+    }
+
+    /// Add one observation, returning the regime change it reveals, if any
+    ///
+    /// The first regime to reach the threshold is established without
+    /// being reported.
+    pub fn observe(&mut self, stressor: f64, payoff: f64) -> Option<TriadChange> {
+        if !stressor.is_finite() || !payoff.is_finite() {
+            return None;
+        }
+        self.observed += 1;
+        self.classifier.observe(stressor, payoff);
+        let (convexity, std_error) = self.classifier.curvature()?;
+        // A convexity within rounding of zero is reported as exactly zero,
+        // and scores zero even when the fit is noiseless
+        let score = if convexity == 0.0 {
+            0.0
+        } else if std_error > 0.0 {
+            (convexity / std_error).clamp(-10.0, 10.0)
+        } else {
+            10.0 * convexity.signum()
+        };
+
+        let k = self.config.reference;
+        let increments = [-score - k, k - score.abs(), score - k];
+        for (sum, increment) in self.evidence.iter_mut().zip(increments) {
+            *sum = (*sum + increment).max(0.0);
+        }
+        if let Some(regime) = self.regime {
+            self.evidence[usize::from(regime.rank())] = 0.0;
+        }
+
+        let (rank, strongest) =
+            self.evidence
+                .iter()
+                .copied()
+                .enumerate()
+                .fold(
+                    (0, 0.0),
+                    |best, (rank, sum)| if sum > best.1 { (rank, sum) } else { best },
+                );
+        if strongest <= self.config.threshold {
+            return None;
+        }
+        self.evidence = [0.0; 3];
+        let to = Triad::ALL[rank];
+        let Some(from) = self.regime.replace(to) else {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(observation = self.observed, regime = ?to, convexity, "regime established");
+            return None;
+        };
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            observation = self.observed,
+            ?from,
+            ?to,
+            convexity,
+            "regime changed"
+        );
+        Some(TriadChange {
+            observation: self.observed,
+            from,
+            to,
+            convexity,
+        })
+    }
+}
+
+/// The Triad for a fitted curvature at `significance` standard errors
+fn verdict(curvature: Option<(f64, f64)>, significance: f64) -> Triad {
+    let triad = match curvature {
+        Some((curvature, std_error)) if curvature > significance * std_error => Triad::Antifragile,
+        Some((curvature, std_error)) if -curvature > significance * std_error => Triad::Fragile,
+        _ => Triad::Robust,
+    };
+    #[cfg(feature = "tracing")]
+    tracing::trace!(
+        convexity = curvature.map(|(curvature, _)| curvature),
+        std_error = curvature.map(|(_, std_error)| std_error),
+        significance,
+        ?triad,
+        "online classification"
+    );
+    triad
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    fn noisy(curve: impl Fn(f64) -> f64, n: u32, seed: u64) -> Vec<(f64, f64)> {
+        let mut rng = SplitMix64::new(seed);
+        (0..n)
+            .map(|i| {
+                let x = f64::from(i % 20) / 2.0;
+                (x, curve(x) + rng.next_normal())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_robust_until_identified() {
+        let mut classifier = OnlineClassifier::default();
+        classifier.observe_all([(1.0, 1.0), (2.0, 4.0), (3.0, 9.0)]);
+        assert_eq!(classifier.observations(), 3);
+        assert_eq!(classifier.curvature(), None);
+        assert_eq!(classifier.triad(), Triad::Robust);
+
+        classifier.observe(4.0, 16.0);
+        assert!((classifier.convexity().unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(classifier.triad(), Triad::Antifragile);
+    }
+
+    #[test]
+    fn test_verdict_sharpens_with_data() {
+        let data = noisy(|x| 0.05 * x * x, 400, 3);
+        let mut classifier = OnlineClassifier::new();
+        classifier.observe_all(data[..10].iter().copied());
+        assert_eq!(classifier.triad(), Triad::Robust);
+        classifier.observe_all(data[10..].iter().copied());
+        assert_eq!(classifier.triad(), Triad::Antifragile);
+        let (curvature, std_error) = classifier.curvature().unwrap();
+        assert!((curvature - 0.1).abs() < 3.0 * std_error);
+
+        let mut linear = OnlineClassifier::new();
+        linear.observe_all(noisy(|x| 3.0 - x, 400, 4));
+        assert_eq!(linear.triad(), Triad::Robust);
+    }
+
+    #[test]
+    fn test_noiseless_straight_lines_are_robust() {
+        for (slope, intercept) in [(3.7, 0.0), (0.0, 5.0)] {
+            let mut classifier = OnlineClassifier::new();
+            for i in 0..500 {
+                let x = f64::from(i % 10);
+                classifier.observe(x, slope * x + intercept);
+                assert_eq!(classifier.triad(), Triad::Robust, "slope {slope} at {i}");
+            }
+            assert_eq!(classifier.convexity(), Some(0.0));
+        }
+    }
+
+    #[test]
+    fn test_significance_and_bad_readings() {
+        let data = noisy(|x| -0.02 * x * x, 200, 5);
+        let mut strict = OnlineClassifier::new().with_significance(50.0);
+        strict.observe_all(data.iter().copied());
+        strict.observe(f64::NAN, 1.0);
+        strict.observe(1.0, f64::INFINITY);
+        assert_eq!(strict.observations(), 200);
+        assert_eq!(strict.triad(), Triad::Robust);
+
+        let mut lenient = strict.clone().with_significance(1.0);
+        lenient.observe(0.0, 0.0);
+        assert_eq!(lenient.triad(), Triad::Fragile);
+    }
+
+    #[test]
+    fn test_half_life_favours_recent_regime() {
+        let mut weighted = OnlineClassifier::new().with_half_life(50.0);
+        let mut unweighted = OnlineClassifier::new();
+        for data in [
+            noisy(|x| 0.05 * x * x, 1_000, 9),
+            noisy(|x| -0.05 * x * x, 200, 10),
+        ] {
+            weighted.observe_all(data.iter().copied());
+            unweighted.observe_all(data);
+        }
+        assert_eq!(weighted.observations(), 1_200);
+        assert!((weighted.effective_observations() - 50.0 / core::f64::consts::LN_2).abs() < 1.0);
+        assert_eq!(weighted.triad(), Triad::Fragile);
+        assert_eq!(unweighted.triad(), Triad::Antifragile);
+
+        let unset = OnlineClassifier::new().with_half_life(0.0);
+        assert_eq!(unset, OnlineClassifier::new());
+    }
+
+    #[test]
+    fn test_detector_reports_each_regime_change() {
+        let mut detector = ChangeDetector::new(ChangeConfig::default());
+        let mut changes = Vec::new();
+        for (curve, seed) in [(0.0, 11), (0.2, 12), (-0.2, 13)] {
+            for (x, y) in noisy(|x| curve * x * x, 600, seed) {
+                changes.extend(detector.observe(x, y));
+            }
+        }
+        assert_eq!(detector.observations(), 1_800);
+        let first = changes.first().unwrap();
+        assert_eq!((first.from, first.to), (Triad::Robust, Triad::Antifragile));
+        assert!((600..700).contains(&first.observation));
+        let last = changes.last().unwrap();
+        assert_eq!(last.to, Triad::Fragile);
+        assert!((1_200..1_300).contains(&last.observation));
+        assert!(last.convexity < 0.0);
+        assert!(changes.len() <= 3);
+    }
+
+    #[test]
+    fn test_steady_stream_raises_no_change() {
+        let mut detector = ChangeDetector::new(ChangeConfig::default());
+        assert_eq!(detector.regime(), None);
+        let changes = noisy(|x| 0.2 * x * x, 3_000, 14)
+            .into_iter()
+            .filter_map(|(x, y)| detector.observe(x, y))
+            .count();
+        assert_eq!(changes, 0);
+        assert_eq!(detector.regime(), Some(Triad::Antifragile));
+
+        let mut flat = ChangeDetector::new(ChangeConfig::default());
+        for (x, y) in noisy(|x| 1.0 - x, 3_000, 15) {
+            assert_eq!(flat.observe(x, y), None);
+        }
+        assert_eq!(flat.regime(), Some(Triad::Robust));
+
+        let mut linear = ChangeDetector::new(ChangeConfig::default());
+        for i in 0..3_000 {
+            let x = f64::from(i % 10);
+            assert_eq!(linear.observe(x, 3.7 * x), None, "at {i}");
+        }
+        assert_eq!(linear.regime(), Some(Triad::Robust));
+    }
+
+    #[test]
+    fn test_last_window_forgets_old_regime() {
+        let mut rolling = RollingClassifier::new(Window::Last(100));
+        rolling.observe_all(noisy(|x| 0.05 * x * x, 300, 6));
+        assert_eq!(rolling.triad(), Triad::Antifragile);
+        rolling.observe_all(noisy(|x| -0.05 * x * x, 300, 7));
+        assert_eq!(rolling.observations(), 100);
+        assert_eq!(rolling.triad(), Triad::Fragile);
+        assert_eq!(rolling.window(), Window::Last(100));
+    }
+
+    #[test]
+    fn test_window_of_straight_line_is_robust() {
+        let mut rolling = RollingClassifier::new(Window::Last(50));
+        for i in 0..2_000 {
+            let x = f64::from(i % 17);
+            rolling.observe(x, 1.3 * x + 100.0);
+            assert_eq!(rolling.triad(), Triad::Robust, "at {i}");
+        }
+        assert_eq!(rolling.convexity(), Some(0.0));
+    }
+
+    #[test]
+    fn test_window_matches_a_fresh_fit() {
+        let data = noisy(|x| 0.1 * x * x - x, 1_000, 8);
+        let mut rolling = RollingClassifier::new(Window::Last(50));
+        rolling.observe_all(data.iter().copied());
+        let mut fresh = OnlineClassifier::new();
+        fresh.observe_all(data[950..].iter().copied());
+        let (rolled, fitted) = (rolling.curvature().unwrap(), fresh.curvature().unwrap());
+        assert!((rolled.0 - fitted.0).abs() < 1e-9);
+        assert!((rolled.1 - fitted.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_time_window_expires() {
+        let mut rolling = RollingClassifier::new(Window::Within(Duration::from_secs(10)));
+        for second in 0..30_u32 {
+            let x = f64::from(second % 5);
+            rolling.observe_at(Duration::from_secs(second.into()), x, x * x);
+        }
+        assert_eq!(rolling.observations(), 11);
+        assert_eq!(rolling.triad(), Triad::Antifragile);
+
+        // Late arrivals join the newest timestamp
+        rolling.observe_at(Duration::from_secs(5), 1.0, 1.0);
+        assert_eq!(rolling.observations(), 12);
+
+        rolling.expire(Duration::from_secs(35));
+        assert_eq!(rolling.observations(), 6);
+        rolling.expire(Duration::from_secs(60));
+        assert_eq!(rolling.observations(), 0);
+        assert_eq!(rolling.curvature(), None);
+        assert_eq!(rolling.triad(), Triad::Robust);
+    }
+}
@@ -0,0 +1,452 @@
+//! # Surrogate-assisted profile computation
+//!
+//! Sweeping the convexity test across a dense grid costs three payoff
+//! evaluations per grid point. When the payoff is expensive (a simulator run,
+//! a load test, a remote call) that quickly becomes infeasible.
+//!
+//! [`surrogate_profile`](crate::surrogate::surrogate_profile) evaluates the true payoff at a small set of knots,
+//! interpolates between them with a local cubic surrogate, and only spends
+//! further evaluations where the surrogate is uncertain. Uncertainty at a
+//! probe is estimated as the disagreement between the local cubic and the
+//! local quadratic interpolant: both are exact for quadratic payoffs, and they
+//! diverge where the payoff bends in ways the knots have not captured yet.
+//!
+//! Every cell of the resulting [`SurrogateProfile`](crate::surrogate::SurrogateProfile) records whether it was
+//! classified from true evaluations only or from surrogate values.
+//!
+//! ```rust
+//! use antifragile::Antifragile;
+//! use antifragile::surrogate::{SurrogateConfig, surrogate_profile};
+//!
+//! struct Expensive;
+//!
+//! impl Antifragile for Expensive {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, x: f64) -> f64 {
+//!         x * x
+//!     }
+//! }
+//!
+//! let config = SurrogateConfig { budget: 8, ..SurrogateConfig::default() };
+//! let profile = surrogate_profile(&Expensive, 0.0..=10.0, 50, 0.5, &config);
+//!
+//! assert!(profile.evaluations() <= 8);
+//! assert!(profile.cells().iter().all(|cell| cell.classification.is_antifragile()));
+//! ```
+
+use core::ops::RangeInclusive;
+
+use crate::csv::CsvWriter;
+use crate::{Antifragile, Triad};
+
+/// Configuration for [`surrogate_profile`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurrogateConfig {
+    /// Maximum number of true payoff evaluations (at least four are always spent
+    /// when the sweep needs more probes than the budget covers)
+    pub budget: usize,
+    /// Surrogate uncertainty below which no further evaluations are spent
+    pub tolerance: f64,
+}
+
+impl Default for SurrogateConfig {
+    /// A budget of 32 evaluations and a tolerance of `1e-9`
+    fn default() -> Self {
+        Self {
+            budget: 32,
+            tolerance: 1e-9,
+        }
+    }
+}
+
+/// Where the payoff values behind a profile cell came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellSource {
+    /// All three probes of the convexity test were evaluated on the true payoff
+    Evaluated,
+    /// At least one probe was interpolated from the surrogate
+    Surrogate,
+}
+
+/// One operating point of a [`SurrogateProfile`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurrogateCell {
+    /// The operating point
+    pub stressor: f64,
+    /// Classification at this operating point
+    pub classification: Triad,
+    /// Whether the classification used surrogate values
+    pub source: CellSource,
+    /// Combined surrogate uncertainty of the three probes (zero when evaluated)
+    pub uncertainty: f64,
+}
+
+/// Result of a surrogate-assisted profile sweep
+#[derive(Debug, Clone, PartialEq)]
+pub struct SurrogateProfile {
+    cells: Vec<SurrogateCell>,
+    evaluations: usize,
+}
+
+impl SurrogateProfile {
+    /// The classified operating points, in ascending stressor order
+    #[must_use]
+    pub fn cells(&self) -> &[SurrogateCell] {
+        &self.cells
+    }
+
+    /// Number of true payoff evaluations spent
+    #[must_use]
+    pub const fn evaluations(&self) -> usize {
+        self.evaluations
+    }
+
+    /// Number of cells classified purely from true evaluations
+    #[must_use]
+    pub fn evaluated_cells(&self) -> usize {
+        self.cells
+            .iter()
+            .filter(|cell| cell.source == CellSource::Evaluated)
+            .count()
+    }
+
+    /// Number of cells that relied on surrogate values
+    #[must_use]
+    pub fn surrogate_cells(&self) -> usize {
+        self.cells.len() - self.evaluated_cells()
+    }
+
+    /// Consume the profile, returning its cells
+    #[must_use]
+    pub fn into_cells(self) -> Vec<SurrogateCell> {
+        self.cells
+    }
+
+    /// One `stressor,classification,source,uncertainty` row per cell, with
+    /// a header row
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = CsvWriter::new(&["stressor", "classification", "source", "uncertainty"]);
+        for cell in &self.cells {
+            let source = match cell.source {
+                CellSource::Evaluated => "evaluated",
+                CellSource::Surrogate => "surrogate",
+            };
+            csv.row(&[
+                cell.stressor.to_string(),
+                format!("{:?}", cell.classification),
+                source.to_string(),
+                cell.uncertainty.to_string(),
+            ]);
+        }
+        csv.finish()
+    }
+}
+
+/// Classify `steps` evenly spaced operating points across `range` under an
+/// evaluation budget
+///
+/// When the budget covers every probe the sweep needs, the result is
+/// identical to calling [`classify`](crate::TriadAnalysis::classify) at each
+/// point. Otherwise, cells whose surrogate sum lies within the surrogate
+/// uncertainty of `2·f(x)` are reported as [`Triad::Robust`], mirroring
+/// [`classify_with_tolerance`](crate::TriadAnalysis::classify_with_tolerance).
+#[must_use]
+pub fn surrogate_profile<T>(
+    system: &T,
+    range: RangeInclusive<f64>,
+    steps: usize,
+    delta: f64,
+    config: &SurrogateConfig,
+) -> SurrogateProfile
+where
+    T: Antifragile<Stressor = f64, Payoff = f64> + ?Sized,
+{
+    let points = grid(&range, steps);
+
+    let mut probes: Vec<f64> = points
+        .iter()
+        .flat_map(|&x| [x - delta, x, x + delta])
+        .collect();
+    probes.sort_by(f64::total_cmp);
+    probes.dedup();
+
+    let mut knots = Knots::default();
+
+    // A cubic surrogate needs at least four knots
+    let budget = config.budget.max(4);
+
+    if probes.len() <= budget {
+        for &p in &probes {
+            knots.insert(p, system.payoff(p));
+        }
+    } else {
+        let seeds = (budget / 4).max(4);
+        for i in 0..seeds {
+            let p = probes[i * (probes.len() - 1) / (seeds - 1)];
+            if !knots.contains(p) {
+                knots.insert(p, system.payoff(p));
+            }
+        }
+
+        while knots.len() < budget {
+            let candidate = probes
+                .iter()
+                .filter(|&&p| !knots.contains(p))
+                .map(|&p| (p, knots.uncertainty(p)))
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+
+            match candidate {
+                Some((p, u)) if u > config.tolerance => knots.insert(p, system.payoff(p)),
+                _ => break,
+            }
+        }
+    }
+
+    let cells = points
+        .into_iter()
+        .map(|x| {
+            let (f_minus, u_minus) = knots.value(x - delta);
+            let (f_x, u_x) = knots.value(x);
+            let (f_plus, u_plus) = knots.value(x + delta);
+
+            let sum = f_plus + f_minus;
+            let twin_f_x = T::twin(f_x);
+            let uncertainty = u_minus + u_plus + 2.0 * u_x;
+
+            let source = if knots.all_known(&[x - delta, x, x + delta]) {
+                CellSource::Evaluated
+            } else {
+                CellSource::Surrogate
+            };
+
+            let classification =
+                if source == CellSource::Surrogate && (sum - twin_f_x).abs() <= uncertainty {
+                    Triad::Robust
+                } else if sum > twin_f_x {
+                    Triad::Antifragile
+                } else if sum < twin_f_x {
+                    Triad::Fragile
+                } else {
+                    Triad::Robust
+                };
+
+            SurrogateCell {
+                stressor: x,
+                classification,
+                source,
+                uncertainty,
+            }
+        })
+        .collect();
+
+    SurrogateProfile {
+        cells,
+        evaluations: knots.len(),
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn grid(range: &RangeInclusive<f64>, steps: usize) -> Vec<f64> {
+    let (start, end) = (*range.start(), *range.end());
+    match steps {
+        0 => Vec::new(),
+        1 => vec![start],
+        _ => {
+            let step = (end - start) / (steps - 1) as f64;
+            (0..steps).map(|i| start + step * i as f64).collect()
+        }
+    }
+}
+
+/// Sorted `(stressor, payoff)` pairs evaluated on the true payoff
+#[derive(Default)]
+struct Knots {
+    points: Vec<(f64, f64)>,
+}
+
+impl Knots {
+    fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    fn position(&self, x: f64) -> Result<usize, usize> {
+        self.points.binary_search_by(|(k, _)| k.total_cmp(&x))
+    }
+
+    fn contains(&self, x: f64) -> bool {
+        self.position(x).is_ok()
+    }
+
+    fn all_known(&self, xs: &[f64]) -> bool {
+        xs.iter().all(|&x| self.contains(x))
+    }
+
+    fn insert(&mut self, x: f64, y: f64) {
+        if let Err(i) = self.position(x) {
+            self.points.insert(i, (x, y));
+        }
+    }
+
+    /// Payoff value at `x` and its uncertainty: exact for knots, surrogate otherwise
+    fn value(&self, x: f64) -> (f64, f64) {
+        match self.position(x) {
+            Ok(i) => (self.points[i].1, 0.0),
+            Err(_) => (self.interpolate(x, 4), self.uncertainty(x)),
+        }
+    }
+
+    fn uncertainty(&self, x: f64) -> f64 {
+        if self.points.len() < 4 {
+            return f64::INFINITY;
+        }
+        (self.interpolate(x, 4) - self.interpolate(x, 3)).abs()
+    }
+
+    /// Lagrange interpolation through the `order` knots nearest to `x`
+    fn interpolate(&self, x: f64, order: usize) -> f64 {
+        let n = self.points.len();
+        let order = order.min(n);
+        if order == 0 {
+            return 0.0;
+        }
+
+        let (Ok(idx) | Err(idx)) = self.position(x);
+        let mut lo = idx.saturating_sub(order / 2);
+        lo = lo.min(n - order);
+        // For odd orders, shift the window towards the nearer neighbour
+        if order % 2 == 1 && lo > 0 && lo + order < n {
+            let left = (x - self.points[lo - 1].0).abs();
+            let right = (self.points[lo + order].0 - x).abs();
+            if left < right {
+                lo -= 1;
+            }
+        }
+
+        let window = &self.points[lo..lo + order];
+        window
+            .iter()
+            .enumerate()
+            .map(|(i, &(xi, yi))| {
+                let basis: f64 = window
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, &(xj, _))| (x - xj) / (xi - xj))
+                    .product();
+                yi * basis
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TriadAnalysis;
+    use core::cell::Cell;
+
+    struct Counted<F> {
+        f: F,
+        calls: Cell<usize>,
+    }
+
+    impl<F: Fn(f64) -> f64> Antifragile for Counted<F> {
+        type Stressor = f64;
+        type Payoff = f64;
+        fn payoff(&self, x: f64) -> f64 {
+            self.calls.set(self.calls.get() + 1);
+            (self.f)(x)
+        }
+    }
+
+    fn counted<F: Fn(f64) -> f64>(f: F) -> Counted<F> {
+        Counted {
+            f,
+            calls: Cell::new(0),
+        }
+    }
+
+    #[test]
+    fn test_quadratic_needs_only_seed_evaluations() {
+        let system = counted(|x| x * x);
+        let config = SurrogateConfig {
+            budget: 16,
+            ..SurrogateConfig::default()
+        };
+        let profile = surrogate_profile(&system, 0.0..=10.0, 100, 0.5, &config);
+
+        assert_eq!(profile.cells().len(), 100);
+        assert_eq!(profile.evaluations(), system.calls.get());
+        assert!(profile.evaluations() <= 16);
+        assert!(profile.surrogate_cells() > 0);
+        assert!(
+            profile
+                .cells()
+                .iter()
+                .all(|cell| cell.classification == Triad::Antifragile)
+        );
+    }
+
+    #[test]
+    fn test_exact_mode_matches_classify() {
+        let system = counted(|x: f64| x.sin());
+        let config = SurrogateConfig {
+            budget: 1000,
+            ..SurrogateConfig::default()
+        };
+        let profile = surrogate_profile(&system, 0.0..=6.0, 13, 0.5, &config);
+
+        assert_eq!(profile.surrogate_cells(), 0);
+        for cell in profile.cells() {
+            assert_eq!(cell.source, CellSource::Evaluated);
+            assert_eq!(cell.classification, system.classify(cell.stressor, 0.5));
+        }
+    }
+
+    #[test]
+    fn test_refinement_respects_budget() {
+        let system = counted(|x: f64| (3.0 * x).sin() + 0.1 * x * x);
+        let config = SurrogateConfig {
+            budget: 24,
+            tolerance: 0.0,
+        };
+        let profile = surrogate_profile(&system, -3.0..=3.0, 200, 0.05, &config);
+
+        assert_eq!(profile.evaluations(), 24);
+        assert_eq!(system.calls.get(), 24);
+    }
+
+    #[test]
+    fn test_refined_profile_finds_sign_change() {
+        // Cubic: concave below zero, convex above
+        let system = counted(|x| x * x * x);
+        let config = SurrogateConfig {
+            budget: 12,
+            ..SurrogateConfig::default()
+        };
+        let profile = surrogate_profile(&system, -5.0..=5.0, 21, 0.5, &config);
+
+        let first = profile.cells().first().unwrap();
+        let last = profile.cells().last().unwrap();
+        assert_eq!(first.classification, Triad::Fragile);
+        assert_eq!(last.classification, Triad::Antifragile);
+    }
+
+    #[test]
+    fn test_empty_and_single_step() {
+        let system = counted(|x| x);
+        let config = SurrogateConfig::default();
+        assert!(
+            surrogate_profile(&system, 0.0..=1.0, 0, 0.1, &config)
+                .cells()
+                .is_empty()
+        );
+
+        let single = surrogate_profile(&system, 2.0..=5.0, 1, 0.1, &config);
+        assert_eq!(single.cells().len(), 1);
+        assert!((single.cells()[0].stressor - 2.0).abs() < f64::EPSILON);
+    }
+}
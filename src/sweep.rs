@@ -0,0 +1,441 @@
+//! # Profile sweeps
+//!
+//! A [`ProfileSweep`](crate::sweep::ProfileSweep) classifies one system at
+//! a series of operating points and keeps every intermediate value of each
+//! test, so the payoff curve and its verdicts can be inspected, rendered, or
+//! exported together.
+//!
+//! [`to_csv`](crate::sweep::ProfileSweep::to_csv) writes one
+//! `stressor,payoff,classification` row per operating point for spreadsheets
+//! and notebooks:
+//!
+//! ```rust
+//! use antifragile::Antifragile;
+//! use antifragile::sweep::ProfileSweep;
+//!
+//! struct Convex;
+//!
+//! impl Antifragile for Convex {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, x: f64) -> f64 {
+//!         x * x
+//!     }
+//! }
+//!
+//! let sweep = ProfileSweep::new("convex", &Convex, [1.0, 2.0], 0.5);
+//! assert_eq!(
+//!     sweep.to_csv(),
+//!     "stressor,payoff,classification\n1,1,Antifragile\n2,4,Antifragile\n"
+//! );
+//! ```
+//!
+//! ## Dense grids
+//!
+//! For heatmaps and fine profiles over `f64` systems,
+//! [`classify_grid`](crate::sweep::classify_grid) classifies every point of a
+//! uniform grid with the grid step as the perturbation. Neighbouring points
+//! share their probes, so it evaluates the payoff once per grid point
+//! instead of three times, and compares them in fixed-width chunks the
+//! compiler can vectorize.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::sweep::classify_grid;
+//!
+//! let cubic = antifragile::antifragile!(|x: f64| x.powi(3));
+//! let triads = classify_grid(&cubic, -1.0, 0.5, 5);
+//! assert_eq!(
+//!     triads,
+//!     [Triad::Fragile, Triad::Fragile, Triad::Robust, Triad::Antifragile, Triad::Antifragile]
+//! );
+//! ```
+//!
+//! ## Heatmaps of two stressors
+//!
+//! [`classify_grid_2d`](crate::sweep::classify_grid_2d) classifies a
+//! two-stressor [`AntifragileN<2>`](crate::multivariate::AntifragileN) system
+//! at every `(x, y)` of a grid by the definiteness of its Hessian, and
+//! records a signed convexity for colouring: the second difference along the
+//! least convex direction. It turns negative wherever some combination of
+//! the two stresses hurts, which is where the envelope flips fragile.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::multivariate::AntifragileN;
+//! use antifragile::sweep::classify_grid_2d;
+//!
+//! /// Convex in load until memory pressure passes 2, concave beyond
+//! struct Service;
+//!
+//! impl AntifragileN<2> for Service {
+//!     fn payoff(&self, [load, memory]: [f64; 2]) -> f64 {
+//!         (2.0 - memory) * load * load + memory * memory
+//!     }
+//! }
+//!
+//! let heatmap = classify_grid_2d(&Service, &[0.0, 1.0], &[1.0, 3.0], [0.1, 0.1], 1e-12);
+//! assert_eq!(heatmap.cell(0, 0).classification, Some(Triad::Antifragile));
+//! // At memory pressure 3 the load axis is concave and memory convex
+//! assert_eq!(heatmap.cell(1, 1).classification, None);
+//! assert!(heatmap.cell(1, 1).convexity < 0.0);
+//! assert!(heatmap.to_csv().starts_with("x,y,classification,convexity\n"));
+//! ```
+
+use core::fmt::Display;
+use core::ops::Sub;
+
+use crate::csv::CsvWriter;
+use crate::multivariate::{AntifragileN, inertia, second_differences};
+use crate::{Antifragile, ClassificationDetail, Triad, TriadAnalysis};
+
+/// Classifications at a series of operating points of one system
+///
+/// Every point keeps its full [`ClassificationDetail`], so the payoff values
+/// behind each verdict travel with it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileSweep<S, P> {
+    title: String,
+    delta: S,
+    points: Vec<(S, ClassificationDetail<P>)>,
+    precision: Option<usize>,
+}
+
+impl<S: Copy, P> ProfileSweep<S, P> {
+    /// Classify `system` at each of `points`, perturbed by `delta`
+    pub fn new<T>(
+        title: impl Into<String>,
+        system: &T,
+        points: impl IntoIterator<Item = S>,
+        delta: S,
+    ) -> Self
+    where
+        T: Antifragile<Stressor = S, Payoff = P>,
+        P: Sub<Output = P> + Default + PartialOrd,
+    {
+        Self {
+            title: title.into(),
+            delta,
+            points: points
+                .into_iter()
+                .map(|at| (at, system.classify_detailed(at, delta)))
+                .collect(),
+            precision: None,
+        }
+    }
+
+    /// Write numbers with `digits` digits after the decimal point
+    #[must_use]
+    pub const fn with_precision(mut self, digits: usize) -> Self {
+        self.precision = Some(digits);
+        self
+    }
+
+    /// The sweep title
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The perturbation used at every point
+    #[must_use]
+    pub const fn delta(&self) -> &S {
+        &self.delta
+    }
+
+    /// Digits after the decimal point, if set
+    #[must_use]
+    pub const fn precision(&self) -> Option<usize> {
+        self.precision
+    }
+
+    /// The operating points and their details, in the order given
+    #[must_use]
+    pub fn points(&self) -> &[(S, ClassificationDetail<P>)] {
+        &self.points
+    }
+
+    /// The least desirable classification in the sweep, if any
+    #[must_use]
+    pub fn verdict(&self) -> Option<Triad> {
+        self.points.iter().map(|(_, detail)| detail.triad).min()
+    }
+}
+
+impl<S: Display, P: Display> ProfileSweep<S, P> {
+    /// One `stressor,payoff,classification` row per operating point, with a
+    /// header row
+    ///
+    /// The payoff is `f(x)` at the operating point; numbers honour
+    /// [`with_precision`](Self::with_precision).
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let number = |value: &dyn Display| match self.precision {
+            Some(digits) => format!("{value:.digits$}"),
+            None => value.to_string(),
+        };
+        let mut csv = CsvWriter::new(&["stressor", "payoff", "classification"]);
+        for (at, detail) in &self.points {
+            csv.row(&[
+                number(at),
+                number(&detail.at),
+                format!("{:?}", detail.triad),
+            ]);
+        }
+        csv.finish()
+    }
+}
+
+/// Classify `system` at `points` operating points `start + i·step`, with
+/// the perturbation fixed to `step`
+///
+/// Evaluates the payoff once at each of the `points + 2` grid nodes from
+/// `start - step` to `start + points·step`, then compares `f(x+Δ) + f(x-Δ)`
+/// with `2·f(x)` exactly as [`classify`](TriadAnalysis::classify) does. The
+/// probes are the grid nodes themselves, so Δ is always `step`, and a result
+/// can differ from `classify(start + i·step, step)` only where rounding the
+/// probe stressors differently changes the verdict. For another
+/// perturbation, use
+/// [`classify_batch`](crate::aggregate::BatchAnalysis::classify_batch).
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn classify_grid<T>(system: &T, start: f64, step: f64, points: usize) -> Vec<Triad>
+where
+    T: Antifragile<Stressor = f64, Payoff = f64> + ?Sized,
+{
+    /// Points compared per chunk
+    const LANES: usize = 4;
+
+    if points == 0 {
+        return Vec::new();
+    }
+    let payoffs: Vec<f64> = (0..points + 2)
+        .map(|node| system.payoff(start + (node as f64 - 1.0) * step))
+        .collect();
+
+    // Three shifted views of the same payoffs, compared a fixed-width chunk
+    // at a time: the gaps of a chunk are computed branch-free, so they
+    // vectorize, and only then mapped to a Triad
+    let mut below = payoffs[..points].chunks_exact(LANES);
+    let mut center = payoffs[1..=points].chunks_exact(LANES);
+    let mut above = payoffs[2..].chunks_exact(LANES);
+    let mut triads = Vec::with_capacity(points);
+    for ((below, center), above) in (&mut below).zip(&mut center).zip(&mut above) {
+        let gaps: [f64; LANES] =
+            core::array::from_fn(|lane| above[lane] + below[lane] - T::twin(center[lane]));
+        triads.extend(gaps.map(triad_of_gap));
+    }
+    let tail = below
+        .remainder()
+        .iter()
+        .zip(center.remainder())
+        .zip(above.remainder());
+    for ((&f_x_minus, &f_x), &f_x_plus) in tail {
+        triads.push(triad_of_gap(f_x_plus + f_x_minus - T::twin(f_x)));
+    }
+    triads
+}
+
+/// The Triad for the sign of `f(x+Δ) + f(x-Δ) - 2·f(x)`
+///
+/// Two distinct floats never subtract to zero, so the sign agrees with
+/// comparing the sum and twin directly; a `NaN` gap, from a `NaN` payoff or
+/// equal infinities, is Robust as the comparison would be.
+fn triad_of_gap(gap: f64) -> Triad {
+    if gap > 0.0 {
+        Triad::Antifragile
+    } else if gap < 0.0 {
+        Triad::Fragile
+    } else {
+        Triad::Robust
+    }
+}
+
+/// One point of a [`Heatmap`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeatmapCell {
+    /// The first stressor
+    pub x: f64,
+    /// The second stressor
+    pub y: f64,
+    /// The Triad from the Hessian's definiteness, `None` at a saddle
+    pub classification: Option<Triad>,
+    /// The second difference along the least convex direction, in payoff
+    /// units: the smaller eigenvalue of the step-scaled Hessian
+    pub convexity: f64,
+}
+
+/// Classifications over a grid of two stressors, from [`classify_grid_2d`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heatmap {
+    width: usize,
+    cells: Vec<HeatmapCell>,
+}
+
+impl Heatmap {
+    /// Number of `x` values, the width of each row
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of `y` values, the number of rows
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.cells.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    /// Every cell, row by row: all `x` values for the first `y`, then the
+    /// next
+    #[must_use]
+    pub fn cells(&self) -> &[HeatmapCell] {
+        &self.cells
+    }
+
+    /// The cell at the `column`-th `x` value and the `row`-th `y` value
+    ///
+    /// # Panics
+    ///
+    /// If `column` or `row` is outside the grid.
+    #[must_use]
+    pub fn cell(&self, column: usize, row: usize) -> &HeatmapCell {
+        assert!(column < self.width, "column {column} outside the grid");
+        &self.cells[row * self.width + column]
+    }
+
+    /// One `x,y,classification,convexity` row per cell, with a header row
+    ///
+    /// Saddles are written as `Indefinite`.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = CsvWriter::new(&["x", "y", "classification", "convexity"]);
+        for cell in &self.cells {
+            let classification = cell
+                .classification
+                .map_or_else(|| "Indefinite".into(), |triad| format!("{triad:?}"));
+            csv.row(&[
+                cell.x.to_string(),
+                cell.y.to_string(),
+                classification,
+                cell.convexity.to_string(),
+            ]);
+        }
+        csv.finish()
+    }
+}
+
+/// Classify a two-stressor system at every `(x, y)` of `xs` × `ys`
+///
+/// Each cell costs the nine payoff evaluations of the finite-difference
+/// Hessian with steps `delta`; pivots within `epsilon` of zero, in payoff
+/// units, count as flat, as in
+/// [`definiteness`](crate::multivariate::HessianAnalysis::definiteness).
+#[must_use]
+pub fn classify_grid_2d<T>(
+    system: &T,
+    xs: &[f64],
+    ys: &[f64],
+    delta: [f64; 2],
+    epsilon: f64,
+) -> Heatmap
+where
+    T: AntifragileN<2> + ?Sized,
+{
+    let cells = ys
+        .iter()
+        .flat_map(|&y| xs.iter().map(move |&x| (x, y)))
+        .map(|(x, y)| {
+            let scaled = second_differences(system, [x, y], delta);
+            let [[a, b], [_, c]] = scaled;
+            let half_gap = ((a - c) / 2.0).hypot(b);
+            HeatmapCell {
+                x,
+                y,
+                classification: inertia(scaled, epsilon).triad(),
+                convexity: f64::midpoint(a, c) - half_gap,
+            }
+        })
+        .collect();
+    Heatmap {
+        width: xs.len(),
+        cells,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Concave;
+
+    impl Antifragile for Concave {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&self, x: f64) -> f64 {
+            x.sqrt()
+        }
+    }
+
+    #[test]
+    fn test_sweep_keeps_details() {
+        let sweep = ProfileSweep::new("sqrt", &Concave, [1.0, 4.0], 0.5);
+        assert_eq!(sweep.title(), "sqrt");
+        assert_eq!(sweep.points().len(), 2);
+        assert_eq!(sweep.points()[1].1, Concave.classify_detailed(4.0, 0.5));
+        assert_eq!(sweep.verdict(), Some(Triad::Fragile));
+    }
+
+    #[test]
+    fn test_grid_matches_pointwise_classification() {
+        let wave = crate::antifragile!(|x: f64| (x * 0.75).sin() * x);
+        // Dyadic grid, so every node is exact and the probes coincide
+        let (start, step) = (-8.0, 0.125);
+        let triads = classify_grid(&wave, start, step, 129);
+        for (i, triad) in triads.into_iter().enumerate() {
+            let at = start + f64::from(u8::try_from(i).unwrap()) * step;
+            assert_eq!(triad, wave.classify(at, step), "at {at}");
+        }
+        assert!(classify_grid(&Concave, 1.0, 0.5, 0).is_empty());
+
+        // Infinite and NaN payoffs in both the chunks and the tail
+        let reciprocal = crate::antifragile!(|x: f64| 1.0 / x);
+        let triads = classify_grid(&reciprocal, -3.0, 1.0, 7);
+        for (i, triad) in triads.into_iter().enumerate() {
+            let at = -3.0 + f64::from(u8::try_from(i).unwrap());
+            assert_eq!(triad, reciprocal.classify(at, 1.0), "at {at}");
+        }
+    }
+
+    #[test]
+    fn test_heatmap_layout_and_convexity() {
+        struct Bowl;
+
+        impl AntifragileN<2> for Bowl {
+            fn payoff(&self, [x, y]: [f64; 2]) -> f64 {
+                x * x + 3.0 * y * y
+            }
+        }
+
+        let heatmap = classify_grid_2d(&Bowl, &[0.0, 1.0, 2.0], &[5.0, 6.0], [1.0, 1.0], 1e-9);
+        assert_eq!((heatmap.width(), heatmap.height()), (3, 2));
+        let cell = heatmap.cell(2, 1);
+        assert!((cell.x - 2.0).abs() < f64::EPSILON && (cell.y - 6.0).abs() < f64::EPSILON);
+        assert_eq!(cell.classification, Some(Triad::Antifragile));
+        // Second differences are 2 along x and 6 along y; x is least convex
+        assert!((cell.convexity - 2.0).abs() < 1e-9);
+
+        let empty = classify_grid_2d(&Bowl, &[], &[1.0], [1.0, 1.0], 1e-9);
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+    }
+
+    #[test]
+    fn test_csv_honours_precision() {
+        let sweep = ProfileSweep::new("sqrt", &Concave, [2.0, 9.0], 0.5).with_precision(3);
+        assert_eq!(
+            sweep.to_csv(),
+            "stressor,payoff,classification\n2.000,1.414,Fragile\n9.000,3.000,Fragile\n"
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! # Battery arbitrage dispatch
+//!
+//! A grid battery charges when power is cheap and discharges when it is
+//! expensive. Each cycle buys energy at the off-peak price `Y` and sells
+//! `η` of it back at the peak price `X`, paying a degradation cost `c` per
+//! unit charged. The operator only cycles when that pays, so each cycle is
+//! worth `max(0, η·X - Y - c)` per unit: a spread option on power prices.
+//!
+//! [`BatteryDispatch`] takes the standard deviation `σ` of both prices as
+//! the stressor and returns expected revenue over a number of cycles, with
+//! `X` and `Y` independent and normally distributed around their means. The
+//! option to stay idle makes revenue convex in `σ`: calm markets earn little,
+//! volatile ones pay for the battery. A battery that must cycle regardless
+//! ([`BatteryDispatch::with_must_run`]) loses the option and is merely robust
+//! to volatility, since its expected revenue no longer depends on `σ`.
+//!
+//! ```rust
+//! use antifragile::{Triad, TriadAnalysis};
+//! use antifragile::systems::battery::{Battery, BatteryDispatch};
+//!
+//! // 100 MWh / 25 MW, 85% round trip, 10 per MWh of wear
+//! let battery = Battery::new(100.0, 25.0, 0.85).with_degradation_cost(10.0);
+//! // Peak 60, off-peak 40 on average, one cycle a day for a year
+//! let dispatch = BatteryDispatch::new(battery, 60.0, 40.0).with_cycles(365);
+//!
+//! assert_eq!(dispatch.classify(15.0, 5.0), Triad::Antifragile);
+//! assert!(dispatch.expected_revenue(30.0) > 2.0 * dispatch.expected_revenue(5.0));
+//! ```
+
+use crate::Antifragile;
+use crate::special::normal_loss;
+
+/// Physical parameters of a battery
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Battery {
+    /// Usable energy per cycle (e.g. megawatt-hours)
+    pub energy: f64,
+    /// Maximum charge and discharge rate (e.g. megawatts)
+    pub power: f64,
+    /// Round-trip efficiency in `(0, 1]`
+    pub efficiency: f64,
+    /// Wear cost per unit of energy charged
+    pub degradation_cost: f64,
+}
+
+impl Battery {
+    /// A battery with no degradation cost
+    #[must_use]
+    pub const fn new(energy: f64, power: f64, efficiency: f64) -> Self {
+        Self {
+            energy,
+            power,
+            efficiency,
+            degradation_cost: 0.0,
+        }
+    }
+
+    /// Set the wear cost per unit of energy charged
+    #[must_use]
+    pub const fn with_degradation_cost(mut self, degradation_cost: f64) -> Self {
+        self.degradation_cost = degradation_cost;
+        self
+    }
+}
+
+/// Arbitrage revenue of a battery as a function of price volatility
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatteryDispatch {
+    battery: Battery,
+    peak_price: f64,
+    offpeak_price: f64,
+    window_hours: f64,
+    cycles: u32,
+    must_run: bool,
+}
+
+impl BatteryDispatch {
+    /// Dispatch `battery` against mean peak and off-peak prices, one cycle
+    /// with four-hour charge and discharge windows
+    #[must_use]
+    pub const fn new(battery: Battery, peak_price: f64, offpeak_price: f64) -> Self {
+        Self {
+            battery,
+            peak_price,
+            offpeak_price,
+            window_hours: 4.0,
+            cycles: 1,
+            must_run: false,
+        }
+    }
+
+    /// Set the length of the charge and discharge windows
+    #[must_use]
+    pub const fn with_window_hours(mut self, window_hours: f64) -> Self {
+        self.window_hours = window_hours;
+        self
+    }
+
+    /// Set the number of cycles revenue is summed over
+    #[must_use]
+    pub const fn with_cycles(mut self, cycles: u32) -> Self {
+        self.cycles = cycles;
+        self
+    }
+
+    /// Force a full cycle every period, even when it loses money
+    #[must_use]
+    pub const fn with_must_run(mut self, must_run: bool) -> Self {
+        self.must_run = must_run;
+        self
+    }
+
+    /// The battery being dispatched
+    #[must_use]
+    pub const fn battery(&self) -> &Battery {
+        &self.battery
+    }
+
+    /// Energy charged per cycle: capacity, unless power limits it first
+    #[must_use]
+    pub fn energy_per_cycle(&self) -> f64 {
+        self.battery
+            .energy
+            .min(self.battery.power * self.window_hours)
+    }
+
+    /// Revenue of one cycle at realized prices
+    #[must_use]
+    pub fn cycle_revenue(&self, peak_price: f64, offpeak_price: f64) -> f64 {
+        let margin = self.margin(peak_price, offpeak_price);
+        let margin = if self.must_run {
+            margin
+        } else {
+            margin.max(0.0)
+        };
+        self.energy_per_cycle() * margin
+    }
+
+    /// Expected revenue over all cycles when both prices have standard
+    /// deviation `sigma`
+    #[must_use]
+    pub fn expected_revenue(&self, sigma: f64) -> f64 {
+        let mean = self.margin(self.peak_price, self.offpeak_price);
+        let spread = sigma.abs() * (1.0 + self.battery.efficiency.powi(2)).sqrt();
+        let expected_margin = if self.must_run {
+            mean
+        } else if spread == 0.0 {
+            mean.max(0.0)
+        } else {
+            spread * normal_loss(-mean / spread)
+        };
+        f64::from(self.cycles) * self.energy_per_cycle() * expected_margin
+    }
+
+    /// Margin per unit charged: `η·peak - offpeak - degradation`
+    fn margin(&self, peak_price: f64, offpeak_price: f64) -> f64 {
+        self.battery.efficiency * peak_price - offpeak_price - self.battery.degradation_cost
+    }
+}
+
+impl Antifragile for BatteryDispatch {
+    /// Standard deviation of peak and off-peak prices
+    type Stressor = f64;
+    /// Expected arbitrage revenue
+    type Payoff = f64;
+
+    fn payoff(&self, sigma: f64) -> f64 {
+        self.expected_revenue(sigma)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Triad, TriadAnalysis};
+
+    fn dispatch() -> BatteryDispatch {
+        let battery = Battery::new(100.0, 25.0, 0.8).with_degradation_cost(5.0);
+        BatteryDispatch::new(battery, 60.0, 40.0)
+    }
+
+    #[test]
+    fn test_calm_market_revenue() {
+        // 0.8 · 60 - 40 - 5 = 3 per MWh over 100 MWh
+        assert!((dispatch().expected_revenue(0.0) - 300.0).abs() < 1e-9);
+        // An unprofitable spread leaves the battery idle
+        assert!(dispatch().cycle_revenue(50.0, 40.0).abs() < f64::EPSILON);
+        assert!((dispatch().with_must_run(true).cycle_revenue(50.0, 40.0) + 500.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_optionality_is_antifragile() {
+        for sigma in [2.0, 10.0, 40.0] {
+            assert_eq!(dispatch().classify(sigma, 1.0), Triad::Antifragile);
+        }
+    }
+
+    #[test]
+    fn test_must_run_is_robust() {
+        let forced = dispatch().with_must_run(true);
+        assert_eq!(forced.classify(10.0, 5.0), Triad::Robust);
+        assert!((forced.expected_revenue(25.0) - forced.expected_revenue(0.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_power_limits_energy_per_cycle() {
+        let short_window = dispatch().with_window_hours(2.0).with_cycles(10);
+        assert!((short_window.energy_per_cycle() - 50.0).abs() < f64::EPSILON);
+        assert!((short_window.expected_revenue(0.0) - 1500.0).abs() < 1e-9);
+    }
+}
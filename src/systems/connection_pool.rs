@@ -0,0 +1,601 @@
+//! # Database connection pools
+//!
+//! A connection pool sits between many concurrent requests and a database
+//! with a fixed number of cores. Too small a pool queues requests that the
+//! database could have served; too large a pool lets every request in at
+//! once, so each query runs slower, runs into its timeout, and the work
+//! already done is thrown away. Retries then put the same work back in.
+//! Which failure dominates decides whether throughput bends gently or
+//! collapses as concurrency grows.
+//!
+//! [`ConnectionPool`] simulates the loop in one-millisecond ticks. Each of
+//! `concurrency` clients thinks for an exponential time, then asks for a
+//! connection. Waiting longer than the acquire timeout, or running a query
+//! longer than the query timeout, fails the attempt; failed attempts are
+//! retried with exponential backoff up to the retry limit. Active queries
+//! share the database cores equally, and every query beyond the core count
+//! slows the database down further through contention. The stressor is the number of clients,
+//! rounded to a whole number; the payoff is successful queries per second.
+//! Each client draws from its own random stream, so adding clients leaves
+//! the others' draws unchanged.
+//!
+//! [`rank_pools`] compares configurations by classification and margin.
+//! For a real database, [`AsyncQueryHarness`] runs a user-supplied async
+//! query from many concurrent clients inside whatever runtime awaits it.
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad, TriadAnalysis};
+//! use antifragile::systems::connection_pool::ConnectionPool;
+//!
+//! let sized = ConnectionPool::new(8);
+//! let oversized = ConnectionPool::new(128);
+//!
+//! // Near saturation the oversized pool starts timing out its own queries
+//! assert_eq!(oversized.classify(40.0, 20.0), Triad::Fragile);
+//! assert!(oversized.convexity(40.0, 20.0) < sized.convexity(40.0, 20.0));
+//! assert!(oversized.payoff(80.0) < sized.payoff(80.0));
+//! ```
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::time::Instant;
+
+use crate::learning::Noise;
+use crate::{Antifragile, Triad, TriadAnalysis};
+
+/// Averages over all trials of a [`ConnectionPool`] simulation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueryStats {
+    /// Successful queries per second
+    pub queries_per_second: f64,
+    /// Requests per second that failed after exhausting their retries
+    pub failures_per_second: f64,
+    /// Attempts per second abandoned at the query timeout, wasting work
+    pub timeouts_per_second: f64,
+    /// Mean milliseconds from request to success, including retries
+    pub mean_latency: f64,
+}
+
+/// A connection pool in front of a database with a fixed number of cores
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConnectionPool {
+    size: u32,
+    database_cores: f64,
+    contention: f64,
+    query_time: f64,
+    think_time: f64,
+    acquire_timeout: f64,
+    query_timeout: f64,
+    retries: u32,
+    backoff: f64,
+    duration: u32,
+    trials: u32,
+    seed: u64,
+}
+
+impl ConnectionPool {
+    /// A pool of `size` connections
+    ///
+    /// Defaults: 8 database cores losing 2% of their speed per extra active
+    /// query, 5 ms of query work and 20 ms of think
+    /// time on average, a 100 ms acquire timeout, a 200 ms query timeout, 2
+    /// retries with 10 ms initial backoff, and 4 trials of 5 seconds.
+    #[must_use]
+    pub const fn new(size: u32) -> Self {
+        Self {
+            size,
+            database_cores: 8.0,
+            contention: 0.02,
+            query_time: 5.0,
+            think_time: 20.0,
+            acquire_timeout: 100.0,
+            query_timeout: 200.0,
+            retries: 2,
+            backoff: 10.0,
+            duration: 5000,
+            trials: 4,
+            seed: 0x5EED,
+        }
+    }
+
+    /// Set the number of queries the database runs at full speed at once
+    #[must_use]
+    pub const fn with_database_cores(mut self, cores: f64) -> Self {
+        self.database_cores = cores;
+        self
+    }
+
+    /// Set the share of speed the database loses for each active query
+    /// beyond its cores, from lock and cache contention
+    #[must_use]
+    pub const fn with_contention(mut self, contention: f64) -> Self {
+        self.contention = contention;
+        self
+    }
+
+    /// Set the mean milliseconds of work per query and of think time
+    /// between a client's requests
+    #[must_use]
+    pub const fn with_times(mut self, query_time: f64, think_time: f64) -> Self {
+        self.query_time = query_time;
+        self.think_time = think_time;
+        self
+    }
+
+    /// Set the milliseconds an attempt may wait for a connection and may
+    /// run on one before it fails
+    #[must_use]
+    pub const fn with_timeouts(mut self, acquire: f64, query: f64) -> Self {
+        self.acquire_timeout = acquire;
+        self.query_timeout = query;
+        self
+    }
+
+    /// Set how often a failed request is retried, and the backoff before
+    /// the first retry in milliseconds; each further retry waits twice as
+    /// long
+    #[must_use]
+    pub const fn with_retries(mut self, retries: u32, backoff: f64) -> Self {
+        self.retries = retries;
+        self.backoff = backoff;
+        self
+    }
+
+    /// Set the milliseconds simulated per trial
+    #[must_use]
+    pub const fn with_duration(mut self, milliseconds: u32) -> Self {
+        self.duration = milliseconds;
+        self
+    }
+
+    /// Set the number of independent trials
+    #[must_use]
+    pub const fn with_trials(mut self, trials: u32) -> Self {
+        self.trials = trials;
+        self
+    }
+
+    /// Set the base seed; equal seeds give identical results
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Number of connections
+    #[must_use]
+    pub const fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Simulate `concurrency` clients, rounded to a whole number
+    ///
+    /// NaN rates with zero duration or zero trials.
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    pub fn simulate(&self, concurrency: f64) -> QueryStats {
+        let clients = concurrency.round().max(0.0) as u64;
+        let (mut successes, mut failures, mut timeouts) = (0_u64, 0_u64, 0_u64);
+        let mut latency = 0.0;
+
+        for trial in 0..self.trials {
+            let base = self
+                .seed
+                .wrapping_add(u64::from(trial).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+            let mut streams: Vec<Noise> = (0..clients)
+                .map(|client| Noise::new(base ^ client.wrapping_mul(0xD1B5_4A32_D192_ED03)))
+                .collect();
+            let mut states: Vec<Client> = streams
+                .iter_mut()
+                .map(|noise| Client::Thinking {
+                    until: exponential(self.think_time, noise),
+                })
+                .collect();
+
+            for tick in 0..self.duration {
+                let now = f64::from(tick);
+                let active = states
+                    .iter()
+                    .filter(|s| matches!(s, Client::Querying { .. }))
+                    .count();
+                let excess = (active as f64 - self.database_cores).max(0.0);
+                let speed = (self.database_cores / active as f64).min(1.0)
+                    / (1.0 + self.contention * excess);
+
+                for (state, noise) in states.iter_mut().zip(&mut streams) {
+                    let failed = match *state {
+                        Client::Querying {
+                            work,
+                            since,
+                            attempt,
+                            started,
+                        } => {
+                            if work <= speed {
+                                successes += 1;
+                                latency += now + 1.0 - started;
+                                *state = Client::Thinking {
+                                    until: now + 1.0 + exponential(self.think_time, noise),
+                                };
+                                None
+                            } else if now + 1.0 - since > self.query_timeout {
+                                timeouts += 1;
+                                Some((attempt, started))
+                            } else {
+                                *state = Client::Querying {
+                                    work: work - speed,
+                                    since,
+                                    attempt,
+                                    started,
+                                };
+                                None
+                            }
+                        }
+                        Client::Waiting {
+                            since,
+                            attempt,
+                            started,
+                        } if now - since > self.acquire_timeout => Some((attempt, started)),
+                        Client::Thinking { until } if until <= now => {
+                            *state = Client::Waiting {
+                                since: now,
+                                attempt: 0,
+                                started: now,
+                            };
+                            None
+                        }
+                        Client::Backoff {
+                            until,
+                            attempt,
+                            started,
+                        } if until <= now => {
+                            *state = Client::Waiting {
+                                since: now,
+                                attempt,
+                                started,
+                            };
+                            None
+                        }
+                        _ => None,
+                    };
+                    if let Some((attempt, started)) = failed {
+                        if attempt >= self.retries {
+                            failures += 1;
+                        }
+                        *state = self.after_failure(attempt, started, now, noise);
+                    }
+                }
+
+                self.admit(&mut states, &mut streams, now);
+            }
+        }
+
+        let seconds = f64::from(self.trials) * f64::from(self.duration) / 1000.0;
+        QueryStats {
+            queries_per_second: successes as f64 / seconds,
+            failures_per_second: failures as f64 / seconds,
+            timeouts_per_second: timeouts as f64 / seconds,
+            mean_latency: latency / successes as f64,
+        }
+    }
+
+    /// Next state of a client whose acquire or query just timed out
+    fn after_failure(&self, attempt: u32, started: f64, now: f64, noise: &mut Noise) -> Client {
+        if attempt < self.retries {
+            Client::Backoff {
+                until: now
+                    + self.backoff * 2.0_f64.powi(i32::try_from(attempt).unwrap_or(i32::MAX)),
+                attempt: attempt + 1,
+                started,
+            }
+        } else {
+            Client::Thinking {
+                until: now + exponential(self.think_time, noise),
+            }
+        }
+    }
+
+    /// Hand free connections to waiting clients in order
+    fn admit(&self, states: &mut [Client], streams: &mut [Noise], now: f64) {
+        let busy = states
+            .iter()
+            .filter(|s| matches!(s, Client::Querying { .. }))
+            .count();
+        let mut free = (self.size as usize).saturating_sub(busy);
+        for (state, noise) in states.iter_mut().zip(streams) {
+            if free == 0 {
+                break;
+            }
+            if let Client::Waiting {
+                attempt, started, ..
+            } = *state
+            {
+                *state = Client::Querying {
+                    work: exponential(self.query_time, noise),
+                    since: now,
+                    attempt,
+                    started,
+                };
+                free -= 1;
+            }
+        }
+    }
+}
+
+impl Default for ConnectionPool {
+    /// A pool with one connection per database core
+    fn default() -> Self {
+        Self::new(8)
+    }
+}
+
+impl Antifragile for ConnectionPool {
+    /// Number of concurrent clients
+    type Stressor = f64;
+    /// Successful queries per second
+    type Payoff = f64;
+
+    fn payoff(&self, concurrency: f64) -> f64 {
+        self.simulate(concurrency).queries_per_second
+    }
+}
+
+/// A pool configuration evaluated by [`rank_pools`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolComparison {
+    /// The configuration
+    pub pool: ConnectionPool,
+    /// Successful queries per second at the expected concurrency
+    pub queries_per_second: f64,
+    /// Classification of the throughput response
+    pub classification: Triad,
+    /// Relative second difference of throughput, as in
+    /// [`convexity`](crate::TriadAnalysis::convexity): its sign is the
+    /// classification and its size how decisive it is
+    pub margin: f64,
+}
+
+/// Evaluate each of `pools` at `concurrency ± delta`, largest margin first
+#[must_use]
+pub fn rank_pools(pools: &[ConnectionPool], concurrency: f64, delta: f64) -> Vec<PoolComparison> {
+    let mut comparisons: Vec<PoolComparison> = pools
+        .iter()
+        .map(|&pool| PoolComparison {
+            pool,
+            queries_per_second: pool.payoff(concurrency),
+            classification: pool.classify(concurrency, delta),
+            margin: pool.convexity(concurrency, delta),
+        })
+        .collect();
+    comparisons.sort_by(|a, b| b.margin.total_cmp(&a.margin));
+    comparisons
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Client {
+    Thinking {
+        until: f64,
+    },
+    Waiting {
+        since: f64,
+        attempt: u32,
+        started: f64,
+    },
+    Querying {
+        work: f64,
+        since: f64,
+        attempt: u32,
+        started: f64,
+    },
+    Backoff {
+        until: f64,
+        attempt: u32,
+        started: f64,
+    },
+}
+
+fn exponential(mean: f64, noise: &mut Noise) -> f64 {
+    -mean * (1.0 - noise.uniform()).ln()
+}
+
+/// Results of [`AsyncQueryHarness::measure`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AsyncStats {
+    /// Number of concurrent clients
+    pub concurrency: usize,
+    /// Queries that reported success
+    pub succeeded: u64,
+    /// Queries that reported failure
+    pub failed: u64,
+    /// Successful queries per second of wall-clock time
+    pub queries_per_second: f64,
+}
+
+/// Load generator for a real, asynchronous database client
+///
+/// `query` is called with the client index and returns a future resolving
+/// to whether the query succeeded. [`measure`](Self::measure) runs
+/// `concurrency` clients side by side, each issuing its queries one after
+/// another, and is itself a future: await it from the runtime the queries
+/// need. Wall-clock results vary between runs, so repeat measurements or
+/// use [`classify_stochastic`](crate::TriadAnalysis::classify_stochastic)
+/// on the collected rates.
+#[derive(Debug, Clone, Copy)]
+pub struct AsyncQueryHarness<Q> {
+    query: Q,
+    queries_per_client: u32,
+}
+
+impl<Q, F> AsyncQueryHarness<Q>
+where
+    Q: Fn(usize) -> F,
+    F: Future<Output = bool>,
+{
+    /// A harness issuing 100 queries per client
+    pub const fn new(query: Q) -> Self {
+        Self {
+            query,
+            queries_per_client: 100,
+        }
+    }
+
+    /// Set the number of queries each client issues
+    #[must_use]
+    pub const fn with_queries_per_client(mut self, queries: u32) -> Self {
+        self.queries_per_client = queries;
+        self
+    }
+
+    /// Run `concurrency` clients to completion
+    #[allow(clippy::cast_precision_loss)]
+    pub async fn measure(&self, concurrency: usize) -> AsyncStats {
+        let started = Instant::now();
+        let clients = (0..concurrency)
+            .map(|client| {
+                Box::pin(async move {
+                    let mut succeeded = 0_u64;
+                    for _ in 0..self.queries_per_client {
+                        if (self.query)(client).await {
+                            succeeded += 1;
+                        }
+                    }
+                    succeeded
+                }) as Pin<Box<dyn Future<Output = u64> + '_>>
+            })
+            .collect();
+        let succeeded: u64 = JoinAll::new(clients).await.iter().sum();
+        let total = concurrency as u64 * u64::from(self.queries_per_client);
+        AsyncStats {
+            concurrency,
+            succeeded,
+            failed: total - succeeded,
+            queries_per_second: succeeded as f64 / started.elapsed().as_secs_f64(),
+        }
+    }
+
+    /// [`measure`](Self::measure) at `at` and `at ± delta` clients, and the
+    /// classification of successful queries per second
+    pub async fn classify(&self, at: usize, delta: usize) -> (Triad, [AsyncStats; 3]) {
+        let below = self.measure(at.saturating_sub(delta)).await;
+        let center = self.measure(at).await;
+        let above = self.measure(at + delta).await;
+        let margin =
+            above.queries_per_second + below.queries_per_second - 2.0 * center.queries_per_second;
+        let triad = if margin > 0.0 {
+            Triad::Antifragile
+        } else if margin < 0.0 {
+            Triad::Fragile
+        } else {
+            Triad::Robust
+        };
+        (triad, [below, center, above])
+    }
+}
+
+/// Polls every future until all have finished, collecting their outputs
+struct JoinAll<'a, T> {
+    pending: Vec<Option<Pin<Box<dyn Future<Output = T> + 'a>>>>,
+    outputs: Vec<Option<T>>,
+}
+
+impl<'a, T> JoinAll<'a, T> {
+    fn new(futures: Vec<Pin<Box<dyn Future<Output = T> + 'a>>>) -> Self {
+        Self {
+            outputs: futures.iter().map(|_| None).collect(),
+            pending: futures.into_iter().map(Some).collect(),
+        }
+    }
+}
+
+// Outputs are never pinned, only moved out once ready
+impl<T> Unpin for JoinAll<'_, T> {}
+
+impl<T> Future for JoinAll<'_, T> {
+    type Output = Vec<T>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Vec<T>> {
+        let this = self.get_mut();
+        for (slot, output) in this.pending.iter_mut().zip(&mut this.outputs) {
+            if let Some(future) = slot {
+                if let Poll::Ready(value) = future.as_mut().poll(context) {
+                    *output = Some(value);
+                    *slot = None;
+                }
+            }
+        }
+        if this.pending.iter().all(Option::is_none) {
+            Poll::Ready(this.outputs.iter_mut().filter_map(Option::take).collect())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::task::Waker;
+
+    /// Drives a future whose leaves are always ready
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let mut context = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn test_light_load_succeeds() {
+        let pool = ConnectionPool::default();
+        let stats = pool.simulate(4.0);
+        // Each client cycles every 25 ms on average: 40 queries per second
+        assert!((stats.queries_per_second - 160.0).abs() < 20.0);
+        assert!(stats.failures_per_second.abs() < f64::EPSILON);
+        assert!(stats.mean_latency < 10.0);
+        assert!(pool.simulate(0.0).queries_per_second.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_oversized_pool_collapses() {
+        let oversized = ConnectionPool::new(128);
+        let stats = oversized.simulate(120.0);
+        assert!(stats.timeouts_per_second > 0.0);
+        assert!(
+            stats.queries_per_second < ConnectionPool::new(8).simulate(120.0).queries_per_second
+        );
+        assert_eq!(oversized.classify(40.0, 20.0), Triad::Fragile);
+    }
+
+    #[test]
+    fn test_rank_pools_prefers_sized_pool() {
+        let sized = ConnectionPool::new(8);
+        let oversized = ConnectionPool::new(128);
+        let ranking = rank_pools(&[oversized, sized], 40.0, 20.0);
+        assert_eq!(ranking[0].pool, sized);
+        assert!(ranking[0].margin > ranking[1].margin);
+        // Without retries failures surface immediately
+        let strict = sized.with_retries(0, 0.0).with_timeouts(1.0, 200.0);
+        assert!(strict.simulate(120.0).failures_per_second > 0.0);
+    }
+
+    #[test]
+    fn test_async_harness_counts_outcomes() {
+        let harness = AsyncQueryHarness::new(|client: usize| async move { client % 4 != 0 })
+            .with_queries_per_client(10);
+        let stats = block_on(harness.measure(8));
+        assert_eq!(stats.succeeded, 60);
+        assert_eq!(stats.failed, 20);
+        let (_, [below, center, above]) = block_on(harness.classify(8, 4));
+        assert_eq!(
+            (below.concurrency, center.concurrency, above.concurrency),
+            (4, 8, 12)
+        );
+    }
+}
@@ -0,0 +1,396 @@
+//! # Thread-pool and executor sizing
+//!
+//! A task executor is a bank of workers in front of a queue. Under light
+//! load every task starts at once; as arrivals approach the pool's capacity
+//! tasks start to wait, and what happens beyond capacity is decided by the
+//! queue bound and the rejection policy. An unbounded queue accepts
+//! everything and lets waits grow without limit, so once tasks carry a
+//! deadline the on-time throughput collapses: the pool stays busy finishing
+//! work nobody is waiting for any more. A bounded queue sheds the excess
+//! instead and keeps serving at capacity.
+//!
+//! [`ThreadPool`] simulates such an executor tick by tick. Each tick a
+//! Poisson number of tasks arrives, with mean equal to the stressor; each
+//! task needs an exponentially distributed amount of work. Busy workers
+//! share the available cores, so a pool larger than the machine gains no
+//! speed. The payoff is the number of tasks completed within the deadline
+//! per tick. Every trial draws the same random numbers at every arrival rate
+//! (common random numbers), so the second difference compares like with
+//! like.
+//!
+//! [`compare_pools`] ranks candidate configurations by the convexity of
+//! their throughput around an expected load, so a runtime tuner can pick
+//! the one that degrades most gracefully.
+//!
+//! ```rust
+//! use antifragile::{Triad, TriadAnalysis};
+//! use antifragile::systems::executor::ThreadPool;
+//!
+//! // Four workers, unbounded queue, tasks useless after 20 ticks
+//! let unbounded = ThreadPool::new(4).with_queue_bound(None).with_deadline(Some(20.0));
+//! let bounded = unbounded.with_queue_bound(Some(16));
+//!
+//! // Approaching capacity, the unbounded pool's on-time throughput falls off a cliff
+//! assert_eq!(unbounded.classify(3.5, 0.5), Triad::Fragile);
+//! assert!(unbounded.simulate(6.0).throughput < 0.5 * bounded.simulate(6.0).throughput);
+//! ```
+
+use std::collections::VecDeque;
+
+use crate::learning::Noise;
+use crate::{Antifragile, Triad, TriadAnalysis};
+
+/// What a full pool does with a newly submitted task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RejectionPolicy {
+    /// Reject the new task
+    #[default]
+    Abort,
+    /// Drop the oldest queued task to make room for the new one
+    DiscardOldest,
+    /// Run the new task on the submitting thread, which accepts nothing
+    /// else until it finishes
+    CallerRuns,
+}
+
+/// Averages per tick over all trials of a [`ThreadPool`] simulation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolStats {
+    /// Tasks completed within the deadline
+    pub throughput: f64,
+    /// Tasks completed after the deadline
+    pub late: f64,
+    /// Tasks rejected or discarded
+    pub rejected: f64,
+    /// Mean time from arrival to completion of completed tasks
+    pub mean_latency: f64,
+}
+
+/// A fixed-size worker pool whose task arrival rate is the stressor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThreadPool {
+    workers: u32,
+    cores: u32,
+    queue_bound: Option<u32>,
+    policy: RejectionPolicy,
+    service_time: f64,
+    deadline: Option<f64>,
+    ticks: u32,
+    trials: u32,
+    seed: u64,
+}
+
+impl ThreadPool {
+    /// A pool of `workers` workers
+    ///
+    /// Defaults: one core per worker, a queue of 64 tasks, the
+    /// [`Abort`](RejectionPolicy::Abort) policy, one tick of work per task
+    /// on average, no deadline, and 8 trials of 2000 ticks.
+    #[must_use]
+    pub const fn new(workers: u32) -> Self {
+        Self {
+            workers,
+            cores: workers,
+            queue_bound: Some(64),
+            policy: RejectionPolicy::Abort,
+            service_time: 1.0,
+            deadline: None,
+            ticks: 2000,
+            trials: 8,
+            seed: 0x5EED,
+        }
+    }
+
+    /// Set the number of cores the busy workers share
+    #[must_use]
+    pub const fn with_cores(mut self, cores: u32) -> Self {
+        self.cores = cores;
+        self
+    }
+
+    /// Set how many tasks may wait for a worker; `None` for unbounded
+    #[must_use]
+    pub const fn with_queue_bound(mut self, bound: Option<u32>) -> Self {
+        self.queue_bound = bound;
+        self
+    }
+
+    /// Set what happens to a task submitted to a full pool
+    #[must_use]
+    pub const fn with_policy(mut self, policy: RejectionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set the mean ticks of work a task needs on a dedicated core
+    #[must_use]
+    pub const fn with_service_time(mut self, ticks: f64) -> Self {
+        self.service_time = ticks;
+        self
+    }
+
+    /// Set the latency beyond which a completed task no longer counts
+    #[must_use]
+    pub const fn with_deadline(mut self, deadline: Option<f64>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Set the number of ticks simulated per trial
+    #[must_use]
+    pub const fn with_ticks(mut self, ticks: u32) -> Self {
+        self.ticks = ticks;
+        self
+    }
+
+    /// Set the number of independent trials
+    #[must_use]
+    pub const fn with_trials(mut self, trials: u32) -> Self {
+        self.trials = trials;
+        self
+    }
+
+    /// Set the base seed; equal seeds give identical results
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Number of workers
+    #[must_use]
+    pub const fn workers(&self) -> u32 {
+        self.workers
+    }
+
+    /// Tasks per tick the pool completes when every worker is busy
+    #[must_use]
+    pub fn capacity(&self) -> f64 {
+        f64::from(self.workers.min(self.cores)) / self.service_time
+    }
+
+    /// Simulate the pool with `arrival_rate` tasks arriving per tick on
+    /// average
+    ///
+    /// NaN throughput with zero ticks or zero trials.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn simulate(&self, arrival_rate: f64) -> PoolStats {
+        let mut tally = Tally::default();
+        for trial in 0..self.trials {
+            let seed = self
+                .seed
+                .wrapping_add(u64::from(trial).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+            // Separate streams keep each task's size fixed as the rate changes
+            let mut arrivals = Noise::new(seed);
+            let mut sizes = Noise::new(!seed);
+            let mut running: Vec<Task> = Vec::with_capacity(self.workers as usize);
+            let mut queue: VecDeque<Task> = VecDeque::new();
+            let mut caller_free_at = 0.0;
+
+            for tick in 0..self.ticks {
+                let now = f64::from(tick);
+                for _ in 0..arrivals.poisson(arrival_rate) {
+                    let task = Task {
+                        arrival: now,
+                        work: -self.service_time * (1.0 - sizes.uniform()).ln(),
+                    };
+                    if running.len() < self.workers as usize {
+                        running.push(task);
+                    } else if self
+                        .queue_bound
+                        .is_none_or(|bound| queue.len() < bound as usize)
+                    {
+                        queue.push_back(task);
+                    } else {
+                        match self.policy {
+                            RejectionPolicy::DiscardOldest => {
+                                tally.rejected += 1;
+                                if queue.pop_front().is_some() {
+                                    queue.push_back(task);
+                                }
+                            }
+                            RejectionPolicy::CallerRuns if now >= caller_free_at => {
+                                caller_free_at = now + task.work;
+                                tally.complete(task.work, self.deadline);
+                            }
+                            RejectionPolicy::Abort | RejectionPolicy::CallerRuns => {
+                                tally.rejected += 1;
+                            }
+                        }
+                    }
+                }
+
+                // A worker that finishes mid-tick starts on the next queued
+                // task with the rest of its share of the tick
+                let share = (f64::from(self.cores) / running.len() as f64).min(1.0);
+                let mut slot = 0;
+                while slot < running.len() && share > 0.0 {
+                    let mut budget = share;
+                    loop {
+                        let task = &mut running[slot];
+                        if task.work > budget {
+                            task.work -= budget;
+                            slot += 1;
+                            break;
+                        }
+                        budget -= task.work;
+                        let finished = now + 1.0 - budget / share;
+                        tally.complete(finished - task.arrival, self.deadline);
+                        if let Some(next) = queue.pop_front() {
+                            running[slot] = next;
+                        } else {
+                            running.swap_remove(slot);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        let ticks = f64::from(self.trials) * f64::from(self.ticks);
+        let completed = (tally.on_time + tally.late) as f64;
+        PoolStats {
+            throughput: tally.on_time as f64 / ticks,
+            late: tally.late as f64 / ticks,
+            rejected: tally.rejected as f64 / ticks,
+            mean_latency: tally.latency / completed,
+        }
+    }
+}
+
+impl Default for ThreadPool {
+    /// A pool of four workers
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl Antifragile for ThreadPool {
+    /// Mean tasks arriving per tick
+    type Stressor = f64;
+    /// Tasks completed within the deadline per tick
+    type Payoff = f64;
+
+    fn payoff(&self, arrival_rate: f64) -> f64 {
+        self.simulate(arrival_rate).throughput
+    }
+}
+
+/// A pool configuration evaluated by [`compare_pools`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolRanking {
+    /// The configuration
+    pub pool: ThreadPool,
+    /// On-time throughput at the expected load
+    pub throughput: f64,
+    /// Relative second difference of throughput around the expected load, as
+    /// in [`convexity`](crate::TriadAnalysis::convexity)
+    pub convexity: f64,
+    /// Classification of the throughput response
+    pub classification: Triad,
+}
+
+/// Evaluate each of `pools` at `load ± delta`, most convex first
+#[must_use]
+pub fn compare_pools(pools: &[ThreadPool], load: f64, delta: f64) -> Vec<PoolRanking> {
+    let mut rankings: Vec<PoolRanking> = pools
+        .iter()
+        .map(|&pool| PoolRanking {
+            pool,
+            throughput: pool.payoff(load),
+            convexity: pool.convexity(load, delta),
+            classification: pool.classify(load, delta),
+        })
+        .collect();
+    rankings.sort_by(|a, b| b.convexity.total_cmp(&a.convexity));
+    rankings
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Task {
+    arrival: f64,
+    work: f64,
+}
+
+#[derive(Debug, Default)]
+struct Tally {
+    on_time: u64,
+    late: u64,
+    rejected: u64,
+    latency: f64,
+}
+
+impl Tally {
+    fn complete(&mut self, latency: f64, deadline: Option<f64>) {
+        self.latency += latency;
+        if deadline.is_none_or(|limit| latency <= limit) {
+            self.on_time += 1;
+        } else {
+            self.late += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_light_load_is_served_in_full() {
+        let pool = ThreadPool::new(4);
+        let stats = pool.simulate(1.0);
+        assert!((stats.throughput - 1.0).abs() < 0.05);
+        assert!(stats.rejected.abs() < f64::EPSILON);
+        assert!(stats.mean_latency < 2.0);
+        assert!((pool.capacity() - 4.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_unbounded_queue_collapses_past_capacity() {
+        let pool = ThreadPool::new(4)
+            .with_queue_bound(None)
+            .with_deadline(Some(20.0));
+        assert_eq!(pool.classify(3.5, 0.5), Triad::Fragile);
+        let overloaded = pool.simulate(6.0);
+        assert!(overloaded.throughput < 1.0);
+        assert!(overloaded.late > 3.0);
+    }
+
+    #[test]
+    fn test_rejection_policies_hold_capacity() {
+        let bounded = ThreadPool::new(4).with_deadline(Some(100.0));
+        for policy in [
+            RejectionPolicy::Abort,
+            RejectionPolicy::DiscardOldest,
+            RejectionPolicy::CallerRuns,
+        ] {
+            let stats = bounded.with_policy(policy).simulate(8.0);
+            assert!(stats.throughput > 3.5, "{policy:?}: {stats:?}");
+            assert!(stats.rejected > 3.0, "{policy:?}: {stats:?}");
+        }
+        // More workers than cores buys no throughput
+        let oversubscribed = ThreadPool::new(16).with_cores(4);
+        assert!((oversubscribed.capacity() - 4.0).abs() < f64::EPSILON);
+        assert!(oversubscribed.simulate(8.0).throughput < 4.2);
+    }
+
+    #[test]
+    fn test_compare_pools_prefers_graceful_degradation() {
+        let unbounded = ThreadPool::new(4)
+            .with_queue_bound(None)
+            .with_deadline(Some(20.0));
+        let bounded = unbounded.with_queue_bound(Some(16));
+        let rankings = compare_pools(&[unbounded, bounded], 3.5, 0.5);
+        assert_eq!(rankings[0].pool, bounded);
+        assert!(rankings[0].convexity > rankings[1].convexity);
+        assert_eq!(rankings[1].classification, Triad::Fragile);
+        assert!(
+            ThreadPool::new(4)
+                .with_ticks(0)
+                .simulate(1.0)
+                .throughput
+                .is_nan()
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! # Adaptive immune defense
+//!
+//! Hormesis as a stateful system: small attacks train the defense, large
+//! ones overwhelm it. Each exposure delivers a dose drawn from an
+//! exponential distribution whose mean is the stressor. Against the current
+//! defensive capacity `C`:
+//!
+//! 1. Doses below the activation threshold are cleared without a response.
+//! 2. Doses above it but within `overwhelm · C` are fought off, and capacity
+//!    grows by `adaptation · (dose - activation)`: the system learns.
+//! 3. Doses beyond `overwhelm · C` get through, causing damage equal to the
+//!    excess over `C`, with no learning.
+//!
+//! Between exposures, capacity wanes back towards its baseline. The payoff
+//! is capacity minus accumulated damage.
+//!
+//! The activation threshold makes learning option-like, so mild exposure is
+//! antifragile; the overwhelm threshold makes damage concave, so heavy
+//! exposure is fragile. Because the outcome depends on the order of doses,
+//! [`ImmuneSystem`] is a [`Learner`] and is classified from repeated trials
+//! with [`ImmuneSystem::protocol`].
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::systems::immune::ImmuneSystem;
+//!
+//! let protocol = ImmuneSystem::default().protocol();
+//!
+//! assert_eq!(protocol.analyze(0.4, 0.2).classification, Triad::Antifragile);
+//! assert_eq!(protocol.analyze(3.0, 1.0).classification, Triad::Fragile);
+//! ```
+
+use crate::learning::{Learner, LearningProtocol, Noise};
+
+/// A defense that adapts to the attacks it survives
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImmuneSystem {
+    baseline: f64,
+    capacity: f64,
+    damage: f64,
+    activation: f64,
+    adaptation: f64,
+    overwhelm: f64,
+    waning: f64,
+}
+
+impl ImmuneSystem {
+    /// A naive system with the given baseline capacity and default response
+    ///
+    /// Defaults: activation threshold `0.5`, adaptation `0.1`, overwhelmed
+    /// beyond `2 ×` capacity, and `1%` of acquired capacity lost per round.
+    #[must_use]
+    pub const fn new(baseline: f64) -> Self {
+        Self {
+            baseline,
+            capacity: baseline,
+            damage: 0.0,
+            activation: 0.5,
+            adaptation: 0.1,
+            overwhelm: 2.0,
+            waning: 0.01,
+        }
+    }
+
+    /// Set the smallest dose that triggers a response
+    #[must_use]
+    pub const fn with_activation(mut self, activation: f64) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /// Set capacity gained per unit of dose above the activation threshold
+    #[must_use]
+    pub const fn with_adaptation(mut self, adaptation: f64) -> Self {
+        self.adaptation = adaptation;
+        self
+    }
+
+    /// Set the multiple of capacity beyond which a dose overwhelms the defense
+    #[must_use]
+    pub const fn with_overwhelm(mut self, overwhelm: f64) -> Self {
+        self.overwhelm = overwhelm;
+        self
+    }
+
+    /// Set the fraction of acquired capacity lost after each exposure
+    #[must_use]
+    pub const fn with_waning(mut self, waning: f64) -> Self {
+        self.waning = waning;
+        self
+    }
+
+    /// Current defensive capacity
+    #[must_use]
+    pub const fn capacity(&self) -> f64 {
+        self.capacity
+    }
+
+    /// Damage accumulated from overwhelming doses
+    #[must_use]
+    pub const fn damage(&self) -> f64 {
+        self.damage
+    }
+
+    /// Respond to a single dose
+    pub fn expose(&mut self, dose: f64) {
+        if dose > self.overwhelm * self.capacity {
+            self.damage += dose - self.capacity;
+        } else if dose > self.activation {
+            self.capacity += self.adaptation * (dose - self.activation);
+        }
+        self.capacity -= self.waning * (self.capacity - self.baseline);
+    }
+
+    /// Repeated-trial protocol starting every trial from this state
+    #[must_use]
+    pub fn protocol(self) -> LearningProtocol<impl Fn() -> Self> {
+        LearningProtocol::new(move || self)
+    }
+}
+
+impl Default for ImmuneSystem {
+    /// A naive system with baseline capacity `1.0`
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl Learner for ImmuneSystem {
+    /// `stress` is the mean dose of an exponentially distributed attack
+    fn learn(&mut self, stress: f64, noise: &mut Noise) {
+        let dose = -stress * (1.0 - noise.uniform()).ln();
+        self.expose(dose);
+    }
+
+    /// Capacity minus accumulated damage
+    fn skill(&self) -> f64 {
+        self.capacity - self.damage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Triad;
+
+    #[test]
+    fn test_single_exposures() {
+        let mut system = ImmuneSystem::default().with_waning(0.0);
+        system.expose(0.3);
+        assert!((system.capacity() - 1.0).abs() < f64::EPSILON);
+        system.expose(1.5);
+        assert!((system.capacity() - 1.1).abs() < 1e-12);
+        system.expose(3.0);
+        assert!((system.damage() - 1.9).abs() < 1e-12);
+        assert!((system.skill() + 0.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_mild_exposure_is_hormetic() {
+        let analysis = ImmuneSystem::default().protocol().analyze(0.4, 0.2);
+        assert_eq!(analysis.classification, Triad::Antifragile);
+    }
+
+    #[test]
+    fn test_heavy_exposure_overwhelms() {
+        let analysis = ImmuneSystem::default().protocol().analyze(3.0, 1.0);
+        assert_eq!(analysis.classification, Triad::Fragile);
+    }
+
+    #[test]
+    fn test_no_exposure_keeps_baseline() {
+        let summary = ImmuneSystem::new(2.0).protocol().run(0.0);
+        assert!((summary.mean - 2.0).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,227 @@
+//! # Newsvendor inventory model
+//!
+//! A retailer orders `Q` units before demand `D` is known. Unsold units cost
+//! a holding (or disposal) charge, unmet demand costs a stockout penalty on
+//! top of the lost sale. The payoff is expected profit, and the stressor is
+//! the standard deviation `σ` of normally distributed demand.
+//!
+//! Profit is concave in demand (sales are capped at what was ordered), so
+//! by Jensen's inequality a plain newsvendor is fragile to demand
+//! volatility: every extra unit of `σ` costs more than the last.
+//!
+//! [`FlexibleCapacity`] adds a real option: after demand is known, up to
+//! `units` more can be produced at `unit_cost` and sold at `price`. Flexible
+//! capacity spreads the concavity over two smaller kinks, so volatility hurts
+//! less. If peak units fetch enough (surge or spot pricing) that their margin
+//! exceeds what an idle unit saves, the kink at `Q` turns convex and, for
+//! moderate volatility, the business becomes antifragile to demand swings.
+//!
+//! ```rust
+//! use antifragile::{Triad, TriadAnalysis};
+//! use antifragile::systems::inventory::{FlexibleCapacity, Newsvendor};
+//!
+//! // 100 units expected; sell at 10, buy at 6, 1 to hold, 2 penalty per stockout
+//! let shop = Newsvendor::new(100.0, 110.0, 10.0, 6.0)
+//!     .with_holding_cost(1.0)
+//!     .with_stockout_cost(2.0);
+//! assert_eq!(shop.classify(20.0, 5.0), Triad::Fragile);
+//!
+//! // Peak capacity of 60 units at a cost of 4, sold at surge price 20
+//! let surge = shop.with_flexible_capacity(FlexibleCapacity::new(60.0, 4.0).with_price(20.0));
+//! assert_eq!(surge.classify(20.0, 5.0), Triad::Antifragile);
+//! ```
+//!
+//! Normal demand can be negative; keep `σ` well below the mean demand for
+//! the model to be meaningful.
+
+use crate::Antifragile;
+use crate::special::normal_loss;
+
+/// Capacity that can be used after demand is known
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlexibleCapacity {
+    /// Maximum number of extra units
+    pub units: f64,
+    /// Cost per extra unit
+    pub unit_cost: f64,
+    /// Selling price per extra unit; `None` uses the regular price
+    pub price: Option<f64>,
+}
+
+impl FlexibleCapacity {
+    /// Up to `units` extra units at `unit_cost` each, sold at the regular price
+    #[must_use]
+    pub const fn new(units: f64, unit_cost: f64) -> Self {
+        Self {
+            units,
+            unit_cost,
+            price: None,
+        }
+    }
+
+    /// Sell units served from flexible capacity at `price`
+    #[must_use]
+    pub const fn with_price(mut self, price: f64) -> Self {
+        self.price = Some(price);
+        self
+    }
+}
+
+/// Single-period inventory decision under normally distributed demand
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Newsvendor {
+    mean_demand: f64,
+    order_quantity: f64,
+    price: f64,
+    unit_cost: f64,
+    holding_cost: f64,
+    stockout_cost: f64,
+    flexible: Option<FlexibleCapacity>,
+}
+
+impl Newsvendor {
+    /// Order `order_quantity` units at `unit_cost` to sell at `price` against
+    /// demand with mean `mean_demand`, with no holding or stockout costs
+    #[must_use]
+    pub const fn new(mean_demand: f64, order_quantity: f64, price: f64, unit_cost: f64) -> Self {
+        Self {
+            mean_demand,
+            order_quantity,
+            price,
+            unit_cost,
+            holding_cost: 0.0,
+            stockout_cost: 0.0,
+            flexible: None,
+        }
+    }
+
+    /// Set the cost per unsold unit (negative for a salvage value)
+    #[must_use]
+    pub const fn with_holding_cost(mut self, holding_cost: f64) -> Self {
+        self.holding_cost = holding_cost;
+        self
+    }
+
+    /// Set the penalty per unit of unmet demand, on top of the lost sale
+    #[must_use]
+    pub const fn with_stockout_cost(mut self, stockout_cost: f64) -> Self {
+        self.stockout_cost = stockout_cost;
+        self
+    }
+
+    /// Add flexible capacity usable after demand is known
+    #[must_use]
+    pub const fn with_flexible_capacity(mut self, flexible: FlexibleCapacity) -> Self {
+        self.flexible = Some(flexible);
+        self
+    }
+
+    /// Set the order quantity
+    #[must_use]
+    pub const fn with_order_quantity(mut self, order_quantity: f64) -> Self {
+        self.order_quantity = order_quantity;
+        self
+    }
+
+    /// Units ordered ahead of demand
+    #[must_use]
+    pub const fn order_quantity(&self) -> f64 {
+        self.order_quantity
+    }
+
+    /// Expected units left unsold at demand volatility `sigma`
+    #[must_use]
+    pub fn expected_leftover(&self, sigma: f64) -> f64 {
+        self.order_quantity - self.mean_demand + self.expected_excess(self.order_quantity, sigma)
+    }
+
+    /// Expected units of demand left unmet, after flexible capacity
+    #[must_use]
+    pub fn expected_shortfall(&self, sigma: f64) -> f64 {
+        self.expected_excess(self.order_quantity + self.flexible_units(), sigma)
+    }
+
+    /// Expected profit at demand volatility `sigma`
+    #[must_use]
+    pub fn expected_profit(&self, sigma: f64) -> f64 {
+        let beyond_order = self.expected_excess(self.order_quantity, sigma);
+        let unmet = self.expected_shortfall(sigma);
+        let flexible_margin = self.flexible.map_or(0.0, |flex| {
+            flex.price.unwrap_or(self.price) - flex.unit_cost
+        });
+
+        self.price * (self.mean_demand - beyond_order)
+            - self.unit_cost * self.order_quantity
+            - self.holding_cost * self.expected_leftover(sigma)
+            + flexible_margin * (beyond_order - unmet)
+            - self.stockout_cost * unmet
+    }
+
+    fn flexible_units(&self) -> f64 {
+        self.flexible.map_or(0.0, |flex| flex.units)
+    }
+
+    /// `E[(D - level)⁺]` for normal demand
+    fn expected_excess(&self, level: f64, sigma: f64) -> f64 {
+        let sigma = sigma.abs();
+        if sigma == 0.0 {
+            return (self.mean_demand - level).max(0.0);
+        }
+        sigma * normal_loss((level - self.mean_demand) / sigma)
+    }
+}
+
+impl Antifragile for Newsvendor {
+    /// Standard deviation of demand
+    type Stressor = f64;
+    /// Expected profit
+    type Payoff = f64;
+
+    fn payoff(&self, sigma: f64) -> f64 {
+        self.expected_profit(sigma)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Triad, TriadAnalysis};
+
+    fn shop() -> Newsvendor {
+        Newsvendor::new(100.0, 110.0, 10.0, 6.0)
+            .with_holding_cost(1.0)
+            .with_stockout_cost(2.0)
+    }
+
+    #[test]
+    fn test_deterministic_demand() {
+        // Sell 100, buy 110, hold 10
+        assert!((shop().expected_profit(0.0) - (1000.0 - 660.0 - 10.0)).abs() < 1e-9);
+        assert!((shop().expected_leftover(0.0) - 10.0).abs() < 1e-9);
+        assert!(shop().expected_shortfall(0.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_plain_newsvendor_is_fragile() {
+        for sigma in [5.0, 20.0, 40.0] {
+            assert_eq!(shop().classify(sigma, 2.0), Triad::Fragile);
+        }
+    }
+
+    #[test]
+    fn test_flexible_capacity_softens_volatility() {
+        let flexible = shop().with_flexible_capacity(FlexibleCapacity::new(30.0, 7.0));
+        let plain_loss = shop().expected_profit(0.0) - shop().expected_profit(30.0);
+        let flexible_loss = flexible.expected_profit(0.0) - flexible.expected_profit(30.0);
+        assert!(flexible_loss < plain_loss);
+        assert!(flexible.expected_shortfall(30.0) < shop().expected_shortfall(30.0));
+    }
+
+    #[test]
+    fn test_surge_priced_capacity_is_antifragile() {
+        let surge =
+            shop().with_flexible_capacity(FlexibleCapacity::new(80.0, 4.0).with_price(20.0));
+        assert_eq!(surge.classify(15.0, 5.0), Triad::Antifragile);
+        assert!(surge.expected_profit(15.0) > surge.expected_profit(0.0));
+    }
+}
@@ -0,0 +1,26 @@
+//! # Library of ready-made systems
+//!
+//! Parameterized models of well-known fragile and antifragile systems. Each
+//! implements [`Antifragile`](crate::Antifragile), so it can be classified,
+//! profiled, and fed to any of the analysis modules as-is, or used as a
+//! template for modelling a similar system.
+//!
+//! | Module | System | Typical classification |
+//! |--------|--------|------------------------|
+//! | [`tranche`](crate::systems::tranche) | Tranched pooled-loss securities | Senior tranches fragile |
+//! | [`population`](crate::systems::population) | Units under selection and replacement | Fragile units, antifragile population |
+//! | [`inventory`](crate::systems::inventory) | Newsvendor ordering with optional flexible capacity | Fragile to demand volatility unless capacity is priced for peaks |
+//! | [`battery`](crate::systems::battery) | Grid battery arbitrage against price volatility | Antifragile while it may stay idle |
+//! | [`immune`](crate::systems::immune) | Adaptive defense trained by small attacks | Antifragile to mild exposure, fragile once overwhelmed |
+//! | [`connection_pool`](crate::systems::connection_pool) | Database connection pools with timeouts and retries | Oversized pools collapse as concurrency grows |
+//! | [`executor`](crate::systems::executor) | Thread pools with bounded queues and rejection policies | Unbounded queues with deadlines collapse past capacity |
+//! | [`network`](crate::systems::network) | Node and edge percolation of supply networks | Chains convex, redundant meshes fragile near threshold |
+
+pub mod battery;
+pub mod connection_pool;
+pub mod executor;
+pub mod immune;
+pub mod inventory;
+pub mod network;
+pub mod population;
+pub mod tranche;
@@ -0,0 +1,254 @@
+//! # Population selection model
+//!
+//! Taleb's layered argument: the antifragility of a system often *requires*
+//! the fragility of its parts. Individual organisms, restaurants, or startups
+//! are harmed by every shock; the population they belong to improves,
+//! because shocks remove the weakest units and the survivors' traits spread.
+//!
+//! [`Population`] holds units with a stress tolerance each. One generation
+//! at stress `s`:
+//!
+//! 1. Units with tolerance at or below `s` fail.
+//! 2. Each failed unit is replaced by an offspring that inherits the mean
+//!    tolerance of the survivors, plus `overcompensation · s`: the
+//!    population's response to the shock overshoots what the shock required.
+//! 3. If nobody survives, the population is extinct.
+//!
+//! Each unit, as an [`Individual`], has fitness `1 - (s / tolerance)²`,
+//! which reaches zero at its tolerance: concave, so fragile. The population's payoff is its mean
+//! tolerance after the generation, which is convex in `s` as long as some
+//! units survive. Both levels can be classified side by side with
+//! [`Population::analyze`].
+//!
+//! ```rust
+//! use antifragile::{Triad, TriadAnalysis};
+//! use antifragile::systems::population::Population;
+//!
+//! let population = Population::uniform(100, 1.0, 3.0).with_overcompensation(0.2);
+//! let layers = population.analyze(2.0, 0.5);
+//!
+//! assert!(layers.all_individuals_fragile());
+//! assert_eq!(layers.population, Triad::Antifragile);
+//!
+//! // Stress beyond every unit's tolerance wipes the population out
+//! assert_eq!(population.survivors(3.5), 0);
+//! ```
+
+use crate::{Antifragile, Triad, TriadAnalysis};
+
+/// One unit of a population
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Individual {
+    /// Stress at which the unit fails
+    pub tolerance: f64,
+}
+
+impl Antifragile for Individual {
+    type Stressor = f64;
+    /// Fitness `1 - (s / tolerance)²`, negative once the unit has failed
+    type Payoff = f64;
+
+    fn payoff(&self, stress: f64) -> f64 {
+        1.0 - (stress / self.tolerance).powi(2)
+    }
+}
+
+/// Classifications of a population and of each of its units
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayeredClassification {
+    /// Classification of each unit, in population order
+    pub individuals: Vec<Triad>,
+    /// Classification of the population's mean tolerance after selection
+    pub population: Triad,
+}
+
+impl LayeredClassification {
+    /// Returns true if every unit is fragile
+    #[must_use]
+    pub fn all_individuals_fragile(&self) -> bool {
+        self.individuals.iter().all(|t| *t == Triad::Fragile)
+    }
+
+    /// Returns true if the population gains from the stress that harms its
+    /// units: Taleb's layered antifragility
+    #[must_use]
+    pub fn is_layered(&self) -> bool {
+        self.population == Triad::Antifragile && self.all_individuals_fragile()
+    }
+}
+
+/// A population of units under selection
+#[derive(Debug, Clone, PartialEq)]
+pub struct Population {
+    tolerances: Vec<f64>,
+    overcompensation: f64,
+}
+
+impl Population {
+    /// A population with the given unit tolerances and no overcompensation
+    #[must_use]
+    pub fn new(tolerances: impl IntoIterator<Item = f64>) -> Self {
+        Self {
+            tolerances: tolerances.into_iter().collect(),
+            overcompensation: 0.0,
+        }
+    }
+
+    /// `size` units with tolerances evenly spread from `min` to `max`
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn uniform(size: usize, min: f64, max: f64) -> Self {
+        let tolerances: Vec<f64> = match size {
+            0 => Vec::new(),
+            1 => vec![min],
+            _ => {
+                let step = (max - min) / (size - 1) as f64;
+                (0..size).map(|i| min + step * i as f64).collect()
+            }
+        };
+        Self::new(tolerances)
+    }
+
+    /// Set the extra tolerance per unit of stress that replacements gain
+    #[must_use]
+    pub fn with_overcompensation(mut self, overcompensation: f64) -> Self {
+        self.overcompensation = overcompensation;
+        self
+    }
+
+    /// Tolerance of each unit
+    #[must_use]
+    pub fn tolerances(&self) -> &[f64] {
+        &self.tolerances
+    }
+
+    /// Each unit as an [`Individual`]
+    pub fn individuals(&self) -> impl Iterator<Item = Individual> + '_ {
+        self.tolerances
+            .iter()
+            .map(|&tolerance| Individual { tolerance })
+    }
+
+    /// Number of units
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tolerances.len()
+    }
+
+    /// Returns true if the population has no units
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tolerances.is_empty()
+    }
+
+    /// Mean tolerance, or zero for an empty population
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean_tolerance(&self) -> f64 {
+        if self.tolerances.is_empty() {
+            0.0
+        } else {
+            self.tolerances.iter().sum::<f64>() / self.tolerances.len() as f64
+        }
+    }
+
+    /// Number of units that survive stress `stress`
+    #[must_use]
+    pub fn survivors(&self, stress: f64) -> usize {
+        self.tolerances.iter().filter(|&&t| t > stress).count()
+    }
+
+    /// The population after one generation of selection at `stress`
+    ///
+    /// Returns an empty population on extinction.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn next_generation(&self, stress: f64) -> Self {
+        let survivors: Vec<f64> = self
+            .tolerances
+            .iter()
+            .copied()
+            .filter(|&t| t > stress)
+            .collect();
+        if survivors.is_empty() {
+            return Self {
+                tolerances: Vec::new(),
+                overcompensation: self.overcompensation,
+            };
+        }
+
+        let inherited =
+            survivors.iter().sum::<f64>() / survivors.len() as f64 + self.overcompensation * stress;
+        let mut tolerances = survivors;
+        tolerances.resize(self.tolerances.len(), inherited);
+        Self {
+            tolerances,
+            overcompensation: self.overcompensation,
+        }
+    }
+
+    /// Classify every unit and the population at `at`
+    #[must_use]
+    pub fn analyze(&self, at: f64, delta: f64) -> LayeredClassification {
+        LayeredClassification {
+            individuals: self
+                .individuals()
+                .map(|unit| unit.classify(at, delta))
+                .collect(),
+            population: self.classify(at, delta),
+        }
+    }
+}
+
+impl Antifragile for Population {
+    type Stressor = f64;
+    /// Mean tolerance after one generation of selection (zero on extinction)
+    type Payoff = f64;
+
+    fn payoff(&self, stress: f64) -> f64 {
+        self.next_generation(stress).mean_tolerance()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_individuals_are_fragile() {
+        let unit = Individual { tolerance: 4.0 };
+        assert_eq!(unit.classify(2.0, 1.0), Triad::Fragile);
+        assert!(unit.payoff(4.0).abs() < f64::EPSILON);
+        assert!(unit.payoff(5.0) < 0.0);
+    }
+
+    #[test]
+    fn test_selection_with_overcompensation_is_layered() {
+        // Tolerances at 1.1, 1.2, ..., 3.0 keep probes off the tolerance grid
+        let population =
+            Population::new((11..=30).map(|i| f64::from(i) / 10.0)).with_overcompensation(0.3);
+        let layers = population.analyze(2.05, 0.5);
+        assert!(layers.is_layered());
+    }
+
+    #[test]
+    fn test_selection_alone_is_close_to_linear() {
+        // Without overcompensation, the mean of a uniform survivor set moves
+        // linearly with the cut-off
+        let population = Population::uniform(1001, 1.0, 3.0);
+        let convexity = population.convexity(2.0, 0.5);
+        assert!(convexity.abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_generations_and_extinction() {
+        let population = Population::uniform(10, 1.0, 2.0).with_overcompensation(0.5);
+        let next = population.next_generation(1.5);
+        assert_eq!(next.len(), 10);
+        assert!(next.mean_tolerance() > population.mean_tolerance());
+
+        let extinct = population.next_generation(2.0);
+        assert!(extinct.is_empty());
+        assert!(population.payoff(2.0).abs() < f64::EPSILON);
+    }
+}
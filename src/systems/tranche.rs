@@ -0,0 +1,252 @@
+//! # Tranched pooled-loss model
+//!
+//! Securitization pools many loans and slices the pool's losses into
+//! tranches. A tranche with attachment `a` and detachment `d` absorbs pool
+//! losses between `a` and `d`: the equity tranche (`a = 0`) takes the first
+//! losses, the senior tranches only lose once everything below them is gone.
+//!
+//! Slicing does not remove any risk, it redistributes curvature. Senior
+//! tranches look safe because they are untouched in normal conditions, but
+//! their expected loss is *convex* in the default rate: each extra point of
+//! defaults costs more than the last. Their value is therefore concave, and
+//! they are fragile to exactly the stress that was supposed to be remote.
+//! The equity tranche, already written down in bad states, has the opposite
+//! shape near its detachment point.
+//!
+//! [`TrancheExposure`] implements [`Antifragile`] with the pool's default
+//! probability as the stressor and the expected fraction of tranche notional
+//! that survives as the payoff. Defaults are modelled as independent, so the
+//! number of defaults in a pool of `n` names is binomial.
+//!
+//! ```rust
+//! use antifragile::{Triad, TriadAnalysis};
+//! use antifragile::systems::tranche::{LossPool, Tranche};
+//!
+//! // 100 loans, 60% of notional lost on default
+//! let pool = LossPool::new(100, 0.6);
+//! let senior = pool.tranche(Tranche::SENIOR);
+//!
+//! // Senior notes are fragile to a rising default rate
+//! assert_eq!(senior.classify(0.15, 0.05), Triad::Fragile);
+//! ```
+
+use crate::Antifragile;
+
+/// A slice of pool losses between an attachment and detachment point
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tranche {
+    /// Pool loss fraction at which the tranche starts to lose
+    pub attachment: f64,
+    /// Pool loss fraction at which the tranche is wiped out
+    pub detachment: f64,
+}
+
+impl Tranche {
+    /// First-loss tranche: 0–3% of pool losses
+    pub const EQUITY: Self = Self::new(0.0, 0.03);
+    /// 3–7% of pool losses
+    pub const MEZZANINE: Self = Self::new(0.03, 0.07);
+    /// 7–15% of pool losses
+    pub const SENIOR: Self = Self::new(0.07, 0.15);
+    /// 15–100% of pool losses
+    pub const SUPER_SENIOR: Self = Self::new(0.15, 1.0);
+
+    /// A tranche absorbing pool losses between `attachment` and `detachment`
+    ///
+    /// Both are fractions of pool notional with
+    /// `0 ≤ attachment < detachment ≤ 1`.
+    #[must_use]
+    pub const fn new(attachment: f64, detachment: f64) -> Self {
+        Self {
+            attachment,
+            detachment,
+        }
+    }
+
+    /// Width of the tranche as a fraction of pool notional
+    #[must_use]
+    pub fn width(&self) -> f64 {
+        self.detachment - self.attachment
+    }
+
+    /// Fraction of tranche notional lost when the pool loses `pool_loss`
+    #[must_use]
+    pub fn loss(&self, pool_loss: f64) -> f64 {
+        let absorbed = (pool_loss - self.attachment).clamp(0.0, self.width());
+        absorbed / self.width()
+    }
+}
+
+/// A homogeneous pool of independent loans
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LossPool {
+    names: u32,
+    loss_given_default: f64,
+}
+
+impl LossPool {
+    /// A pool of `names` equally sized loans, each losing
+    /// `loss_given_default` of its notional on default
+    #[must_use]
+    pub const fn new(names: u32, loss_given_default: f64) -> Self {
+        Self {
+            names,
+            loss_given_default,
+        }
+    }
+
+    /// Number of loans in the pool
+    #[must_use]
+    pub const fn names(&self) -> u32 {
+        self.names
+    }
+
+    /// Fraction of a loan's notional lost on default
+    #[must_use]
+    pub const fn loss_given_default(&self) -> f64 {
+        self.loss_given_default
+    }
+
+    /// Distribution of pool loss as `(loss fraction, probability)` pairs
+    ///
+    /// `default_probability` is clamped to `[0, 1]`.
+    #[must_use]
+    pub fn loss_distribution(&self, default_probability: f64) -> Vec<(f64, f64)> {
+        let p = default_probability.clamp(0.0, 1.0);
+        let n = self.names;
+        let loss_per_default = if n == 0 {
+            0.0
+        } else {
+            self.loss_given_default / f64::from(n)
+        };
+
+        let mut distribution = Vec::with_capacity(n as usize + 1);
+        if p >= 1.0 {
+            distribution.extend((0..n).map(|k| (loss_per_default * f64::from(k), 0.0)));
+            distribution.push((loss_per_default * f64::from(n), 1.0));
+            return distribution;
+        }
+
+        // Binomial probabilities by the ratio recurrence
+        let odds = p / (1.0 - p);
+        let mut probability = (1.0 - p).powi(i32::try_from(n).unwrap_or(i32::MAX));
+        for k in 0..=n {
+            distribution.push((loss_per_default * f64::from(k), probability));
+            probability *= odds * f64::from(n - k) / f64::from(k + 1);
+        }
+        distribution
+    }
+
+    /// Expected fraction of `tranche` notional lost at `default_probability`
+    #[must_use]
+    pub fn expected_tranche_loss(&self, tranche: Tranche, default_probability: f64) -> f64 {
+        self.loss_distribution(default_probability)
+            .into_iter()
+            .map(|(loss, probability)| probability * tranche.loss(loss))
+            .sum()
+    }
+
+    /// Exposure to one tranche of this pool
+    #[must_use]
+    pub const fn tranche(&self, tranche: Tranche) -> TrancheExposure {
+        TrancheExposure {
+            pool: *self,
+            tranche,
+        }
+    }
+}
+
+/// A holding of one tranche, as a function of the pool default probability
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrancheExposure {
+    pool: LossPool,
+    tranche: Tranche,
+}
+
+impl TrancheExposure {
+    /// The underlying pool
+    #[must_use]
+    pub const fn pool(&self) -> &LossPool {
+        &self.pool
+    }
+
+    /// The tranche held
+    #[must_use]
+    pub const fn tranche(&self) -> Tranche {
+        self.tranche
+    }
+}
+
+impl Antifragile for TrancheExposure {
+    /// Default probability of each loan
+    type Stressor = f64;
+    /// Expected fraction of tranche notional that survives
+    type Payoff = f64;
+
+    fn payoff(&self, default_probability: f64) -> f64 {
+        1.0 - self
+            .pool
+            .expected_tranche_loss(self.tranche, default_probability)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Triad, TriadAnalysis};
+
+    #[test]
+    fn test_tranche_loss_is_clamped() {
+        let tranche = Tranche::new(0.1, 0.3);
+        assert!(tranche.loss(0.05).abs() < f64::EPSILON);
+        assert!((tranche.loss(0.2) - 0.5).abs() < 1e-12);
+        assert!((tranche.loss(0.9) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_loss_distribution_is_binomial() {
+        let pool = LossPool::new(4, 0.5);
+        let distribution = pool.loss_distribution(0.5);
+        let total: f64 = distribution.iter().map(|(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+        // C(4, 2) / 16 = 0.375 at two defaults, i.e. a 25% pool loss
+        assert!((distribution[2].0 - 0.25).abs() < f64::EPSILON);
+        assert!((distribution[2].1 - 0.375).abs() < 1e-12);
+        // Stressors outside [0, 1] are clamped
+        assert!((pool.loss_distribution(-0.1)[0].1 - 1.0).abs() < f64::EPSILON);
+        assert!((pool.loss_distribution(1.5)[4].1 - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_capital_structure_conserves_expected_loss() {
+        let pool = LossPool::new(50, 0.6);
+        let structure = [
+            Tranche::EQUITY,
+            Tranche::MEZZANINE,
+            Tranche::SENIOR,
+            Tranche::SUPER_SENIOR,
+        ];
+        let p = 0.08;
+        let tranched: f64 = structure
+            .iter()
+            .map(|t| t.width() * pool.expected_tranche_loss(*t, p))
+            .sum();
+        // Slicing redistributes loss but never removes it
+        assert!((tranched - p * 0.6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_securitization_concentrates_concavity() {
+        let pool = LossPool::new(100, 0.6);
+        // Senior notes lose convexly as defaults rise: fragile
+        assert_eq!(
+            pool.tranche(Tranche::SENIOR).classify(0.15, 0.05),
+            Triad::Fragile
+        );
+        // Equity is already mostly gone and its losses saturate
+        assert_eq!(
+            pool.tranche(Tranche::EQUITY).classify(0.05, 0.03),
+            Triad::Antifragile
+        );
+    }
+}
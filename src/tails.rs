@@ -0,0 +1,376 @@
+//! # Fat-tail diagnostics
+//!
+//! A convexity test looks at three nearby points; it cannot see what happens
+//! once in a thousand periods. A system can pass as robust on every probe
+//! and still carry losses drawn from a distribution so fat-tailed that its
+//! variance, or even its mean, does not exist. Tail diagnostics look at the
+//! payoffs themselves.
+//!
+//! [`tail_report`](crate::tails::tail_report) splits payoff samples at their
+//! median into losses and gains and, for each side, reports:
+//!
+//! - **Tail index.** The [`hill`](crate::tails::hill) estimate of `α` from
+//!   the largest deviations, with its asymptotic standard error `α/√k`.
+//!   Moments of order `α` and above are infinite: below 2 the variance is
+//!   undefined, below 1 the mean.
+//! - **Max-to-sum ratios.** `max|x|ᵖ / Σ|x|ᵖ` for `p = 1..4`. The ratio
+//!   tends to zero as samples accumulate if and only if the `p`-th moment is
+//!   finite, so a ratio that stays large flags a moment that sample
+//!   statistics cannot be trusted for.
+//!
+//! [`payoff_tails`](crate::tails::payoff_tails) does the same for a system
+//! evaluated at observed stressor samples.
+//!
+//! ```rust
+//! use antifragile::tails::{TailConfig, tail_report};
+//!
+//! // Steady small gains, with Pareto-sized blowups one period in four
+//! let payoffs: Vec<f64> = (1..=2000)
+//!     .map(|i| {
+//!         let u = f64::from(i) / 2001.0;
+//!         if i % 4 == 0 { 1.0 - u.powf(-1.0 / 1.5) } else { 1.0 + 0.01 * u }
+//!     })
+//!     .collect();
+//!
+//! let report = tail_report(&payoffs, &TailConfig::default())?;
+//! assert!(report.fat_losses);
+//! assert!(report.losses.is_some_and(|t| (t.index - 1.5).abs() < 0.3));
+//! # Ok::<(), antifragile::tails::TailError>(())
+//! ```
+
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::Antifragile;
+
+/// Minimum number of payoff samples [`tail_report`] accepts
+pub const MIN_SAMPLES: usize = 20;
+
+/// Configuration for [`tail_report`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TailConfig {
+    /// Fraction of one side's deviations used by the Hill estimator (at
+    /// least five are always used)
+    pub tail_fraction: f64,
+    /// Tail index below which a side counts as fat-tailed
+    pub fat_tail_index: f64,
+}
+
+impl Default for TailConfig {
+    /// Hill estimate from the largest 5% of deviations; fat below `α = 4`
+    fn default() -> Self {
+        Self {
+            tail_fraction: 0.05,
+            fat_tail_index: 4.0,
+        }
+    }
+}
+
+/// Hill estimate for one side of the payoff distribution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TailEstimate {
+    /// Estimated tail index `α` (lower is fatter)
+    pub index: f64,
+    /// Asymptotic standard error of `index`, `α/√k`
+    pub std_error: f64,
+    /// Number of order statistics `k` above the threshold
+    pub exceedances: usize,
+    /// Deviation from the median at which the tail starts
+    pub threshold: f64,
+    /// `max|x|ᵖ / Σ|x|ᵖ` over the side's deviations for `p = 1, 2, 3, 4`
+    pub max_to_sum: [f64; 4],
+}
+
+impl TailEstimate {
+    /// Returns true if the `p`-th moment is estimated to be infinite
+    #[must_use]
+    pub fn moment_is_infinite(&self, p: f64) -> bool {
+        self.index <= p
+    }
+}
+
+/// Result of [`tail_report`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TailReport {
+    /// Median payoff, the point deviations are measured from
+    pub median: f64,
+    /// Tail of shortfalls below the median, if there are enough of them
+    pub losses: Option<TailEstimate>,
+    /// Tail of excesses above the median, if there are enough of them
+    pub gains: Option<TailEstimate>,
+    /// Sample excess kurtosis of the payoffs (zero for a normal)
+    pub excess_kurtosis: f64,
+    /// Whether the loss tail index is below the configured threshold
+    pub fat_losses: bool,
+    /// Whether the gain tail index is below the configured threshold
+    pub fat_gains: bool,
+}
+
+/// Error returned by [`tail_report`] and [`payoff_tails`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TailError {
+    /// Fewer than [`MIN_SAMPLES`] samples were supplied
+    TooFewSamples {
+        /// Number of samples supplied
+        found: usize,
+    },
+    /// A payoff was NaN or infinite
+    NonFinite {
+        /// Position of the offending sample
+        index: usize,
+    },
+}
+
+impl Display for TailError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooFewSamples { found } => write!(
+                f,
+                "too few samples: found {found}, need at least {MIN_SAMPLES}"
+            ),
+            Self::NonFinite { index } => write!(f, "non-finite payoff at index {index}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for TailError {}
+
+/// Hill estimate of the tail index from the `k` largest of `magnitudes`
+///
+/// The threshold is the `(k + 1)`-th largest magnitude. Returns `None`
+/// unless `1 <= k < magnitudes.len()`, the threshold is positive, and the
+/// top `k` are not all equal to it.
+#[must_use]
+pub fn hill(magnitudes: &[f64], k: usize) -> Option<f64> {
+    let mut sorted = magnitudes.to_vec();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    hill_sorted(&sorted, k)
+}
+
+/// Hill estimates for every usable `k`, as `(k, α)` pairs
+///
+/// A Hill plot: the estimate should settle on a plateau over a range of
+/// `k` when the tail is genuinely power-law.
+#[must_use]
+pub fn hill_plot(magnitudes: &[f64]) -> Vec<(usize, f64)> {
+    let mut sorted = magnitudes.to_vec();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    (1..sorted.len())
+        .filter_map(|k| hill_sorted(&sorted, k).map(|alpha| (k, alpha)))
+        .collect()
+}
+
+/// Tail diagnostics for both sides of `payoffs`
+///
+/// A side with fewer than six non-zero deviations from the median has no
+/// estimate.
+///
+/// # Errors
+///
+/// Returns [`TailError::TooFewSamples`] for fewer than [`MIN_SAMPLES`]
+/// payoffs and [`TailError::NonFinite`] if one is NaN or infinite.
+#[allow(clippy::cast_precision_loss)]
+pub fn tail_report(payoffs: &[f64], config: &TailConfig) -> Result<TailReport, TailError> {
+    if payoffs.len() < MIN_SAMPLES {
+        return Err(TailError::TooFewSamples {
+            found: payoffs.len(),
+        });
+    }
+    if let Some(index) = payoffs.iter().position(|x| !x.is_finite()) {
+        return Err(TailError::NonFinite { index });
+    }
+
+    let mut sorted = payoffs.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let median = sorted[sorted.len() / 2];
+
+    let losses: Vec<f64> = sorted
+        .iter()
+        .filter(|&&x| x < median)
+        .map(|x| median - x)
+        .collect();
+    let gains: Vec<f64> = sorted
+        .iter()
+        .rev()
+        .filter(|&&x| x > median)
+        .map(|x| x - median)
+        .collect();
+    let losses = side_estimate(&losses, config.tail_fraction);
+    let gains = side_estimate(&gains, config.tail_fraction);
+
+    let n = payoffs.len() as f64;
+    let mean = payoffs.iter().sum::<f64>() / n;
+    let m2 = payoffs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+    let m4 = payoffs.iter().map(|x| (x - mean).powi(4)).sum::<f64>() / n;
+    let excess_kurtosis = if m2 > 0.0 { m4 / (m2 * m2) - 3.0 } else { 0.0 };
+
+    let fat = |side: Option<TailEstimate>| side.is_some_and(|t| t.index < config.fat_tail_index);
+    Ok(TailReport {
+        median,
+        losses,
+        gains,
+        excess_kurtosis,
+        fat_losses: fat(losses),
+        fat_gains: fat(gains),
+    })
+}
+
+/// Tail diagnostics for the payoffs of `system` at observed `stressors`
+///
+/// A system that looks robust around its operating point can still turn
+/// fat-tailed stressors into fat-tailed losses; this evaluates the payoff at
+/// every sample and passes the results to [`tail_report`].
+///
+/// # Errors
+///
+/// As [`tail_report`], with indices referring to `stressors`.
+pub fn payoff_tails<T>(
+    system: &T,
+    stressors: &[f64],
+    config: &TailConfig,
+) -> Result<TailReport, TailError>
+where
+    T: Antifragile<Stressor = f64, Payoff = f64> + ?Sized,
+{
+    let payoffs: Vec<f64> = stressors.iter().map(|&x| system.payoff(x)).collect();
+    tail_report(&payoffs, config)
+}
+
+/// Hill estimate from magnitudes sorted in descending order
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn hill_sorted(sorted: &[f64], k: usize) -> Option<f64> {
+    if k == 0 || k >= sorted.len() {
+        return None;
+    }
+    let threshold = sorted[k];
+    if threshold <= 0.0 {
+        return None;
+    }
+    let log_excess: f64 = sorted[..k].iter().map(|d| (d / threshold).ln()).sum();
+    (log_excess > 0.0).then(|| k as f64 / log_excess)
+}
+
+/// Estimate for one side from its deviations in descending order
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn side_estimate(deviations: &[f64], tail_fraction: f64) -> Option<TailEstimate> {
+    if deviations.len() < 6 {
+        return None;
+    }
+    let k = ((tail_fraction * deviations.len() as f64) as usize).clamp(5, deviations.len() - 1);
+    let index = hill_sorted(deviations, k)?;
+
+    let mut max_to_sum = [0.0; 4];
+    for (p, ratio) in (1..=4).zip(&mut max_to_sum) {
+        let powers = deviations.iter().map(|d| d.powi(p));
+        let sum: f64 = powers.clone().sum();
+        *ratio = powers.fold(0.0, f64::max) / sum;
+    }
+
+    Some(TailEstimate {
+        index,
+        std_error: index / (k as f64).sqrt(),
+        exceedances: k,
+        threshold: deviations[k],
+        max_to_sum,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    fn pareto(n: usize, alpha: f64, seed: u64) -> Vec<f64> {
+        let mut rng = SplitMix64::new(seed);
+        (0..n)
+            .map(|_| (1.0 - rng.next_f64()).powf(-1.0 / alpha))
+            .collect()
+    }
+
+    #[test]
+    fn test_hill_recovers_pareto_index() {
+        let samples = pareto(20_000, 2.5, 3);
+        let alpha = hill(&samples, 1000);
+        assert!(alpha.is_some_and(|a| (a - 2.5).abs() < 0.2));
+
+        let plot = hill_plot(&samples[..200]);
+        assert_eq!(plot.len(), 199);
+        assert!(hill(&samples, 0).is_none());
+        assert!(hill(&[1.0; 10], 3).is_none());
+    }
+
+    #[test]
+    fn test_robust_looking_system_with_fat_losses() {
+        // Flat for ordinary moves, then a cliff: three-point probes near zero
+        // see nothing
+        struct Cliff;
+
+        impl Antifragile for Cliff {
+            type Stressor = f64;
+            type Payoff = f64;
+
+            fn payoff(&self, x: f64) -> f64 {
+                if x > 3.0 { 3.0 - x } else { 0.01 * x }
+            }
+        }
+
+        let mut rng = SplitMix64::new(5);
+        let stressors: Vec<f64> = (0..5000)
+            .map(|_| {
+                let u = 1.0 - rng.next_f64();
+                u.powf(-1.0 / 1.2)
+            })
+            .collect();
+
+        let report = payoff_tails(&Cliff, &stressors, &TailConfig::default()).unwrap();
+        assert!(report.fat_losses);
+        assert!(!report.fat_gains);
+        assert!(report.losses.is_some_and(|t| t.moment_is_infinite(2.0)));
+        assert!(report.excess_kurtosis > 10.0);
+    }
+
+    #[test]
+    fn test_thin_tails_and_max_to_sum() {
+        let mut rng = SplitMix64::new(9);
+        let normal: Vec<f64> = (0..20_000).map(|_| rng.next_normal()).collect();
+        let report = tail_report(&normal, &TailConfig::default()).unwrap();
+        assert!(!report.fat_losses && !report.fat_gains);
+        assert!(report.excess_kurtosis.abs() < 0.2);
+        // Every moment exists: the largest term is a vanishing share
+        let losses = report.losses.unwrap();
+        assert!(losses.max_to_sum.iter().all(|&r| r < 0.05));
+
+        let fat: Vec<f64> = pareto(20_000, 1.5, 7).iter().map(|x| -x).collect();
+        let losses = tail_report(&fat, &TailConfig::default())
+            .unwrap()
+            .losses
+            .unwrap();
+        // Moments above α = 1.5 are infinite: a single loss carries a
+        // growing share of the sum
+        assert!(losses.max_to_sum.windows(2).all(|w| w[0] < w[1]));
+        assert!(losses.max_to_sum[3] > 0.25);
+    }
+
+    #[test]
+    fn test_invalid_samples() {
+        let config = TailConfig::default();
+        assert_eq!(
+            tail_report(&[0.0; 5], &config),
+            Err(TailError::TooFewSamples { found: 5 })
+        );
+        let mut payoffs = vec![1.0; 30];
+        payoffs[7] = f64::INFINITY;
+        assert_eq!(
+            tail_report(&payoffs, &config),
+            Err(TailError::NonFinite { index: 7 })
+        );
+        let flat = tail_report(&[1.0; 30], &config).unwrap();
+        assert!(flat.losses.is_none() && !flat.fat_losses);
+    }
+}
@@ -0,0 +1,412 @@
+//! # Classification gauges with stable names
+//!
+//! Dashboards want a monitored system's classification next to its latency
+//! and error rate. This module publishes it as gauges under fixed names,
+//! each with a `system` label naming the monitored system, so dashboards
+//! and alerts work across services:
+//!
+//! | Gauge | Value |
+//! |-------|-------|
+//! | `antifragile_classification_rank` | [`Triad::rank`](crate::Triad::rank): 0 fragile, 1 robust, 2 antifragile |
+//! | `antifragile_curvature` | Fitted second derivative of a streaming classifier, once identified |
+//! | `antifragile_convexity_ratio` | [`TriadAnalysis::convexity`](crate::TriadAnalysis::convexity): the relative second difference |
+//! | `antifragile_observations` | Observations the classification rests on |
+//! | `antifragile_payoff_evaluations` | Payoff evaluations of an `Instrumented` system |
+//!
+//! Publishing is explicit, so the caller decides how often, typically on a
+//! timer or every few hundred observations.
+//!
+//! ## Through the `metrics` facade
+//!
+//! With the `metrics` feature, `PublishMetrics` writes the current state
+//! of the streaming classifiers and of
+//! [`Instrumented`](crate::instrument::Instrumented) systems to whichever
+//! [`metrics`](https://docs.rs/metrics) recorder the application installed
+//! (Prometheus, StatsD, ...). Without a recorder, publishing does nothing.
+//!
+//! ```rust
+//! # #[cfg(feature = "metrics")] {
+//! use antifragile::stream::OnlineClassifier;
+//! use antifragile::telemetry::PublishMetrics;
+//!
+//! let mut monitor = OnlineClassifier::new();
+//! for load in 0..50 {
+//!     let load = f64::from(load);
+//!     monitor.observe(load, 10.0 * load - 0.5 * load * load);
+//! }
+//! monitor.publish_metrics("checkout");
+//! # }
+//! ```
+//!
+//! ## Straight to a Prometheus registry
+//!
+//! With the `prometheus` feature, `PrometheusGauges` registers the rank,
+//! curvature, and convexity ratio gauges in a [`prometheus`](https://docs.rs/prometheus)
+//! `Registry`, for services that expose one directly.
+//!
+//! ```rust
+//! # #[cfg(feature = "prometheus")] {
+//! use antifragile::Triad;
+//! use antifragile::telemetry::PrometheusGauges;
+//!
+//! let registry = prometheus::Registry::new();
+//! let gauges = PrometheusGauges::register(&registry).unwrap();
+//! gauges.set("checkout", Triad::Fragile, Some(-1.0));
+//!
+//! let families = registry.gather();
+//! assert_eq!(families[0].name(), "antifragile_classification_rank");
+//! # }
+//! ```
+
+#[cfg(feature = "metrics")]
+use crate::Antifragile;
+use crate::Triad;
+#[cfg(feature = "metrics")]
+use crate::instrument::Instrumented;
+#[cfg(feature = "metrics")]
+use crate::stream::{ChangeDetector, OnlineClassifier, RollingClassifier};
+
+/// Gauge holding [`Triad::rank`] of the current classification
+pub const CLASSIFICATION_RANK: &str = "antifragile_classification_rank";
+
+/// Gauge holding the fitted second derivative of a streaming classifier
+pub const CURVATURE: &str = "antifragile_curvature";
+
+/// Gauge holding [`TriadAnalysis::convexity`](crate::TriadAnalysis::convexity),
+/// the second difference relative to the payoff
+pub const CONVEXITY_RATIO: &str = "antifragile_convexity_ratio";
+
+/// Gauge holding the number of observations behind the classification
+pub const OBSERVATIONS: &str = "antifragile_observations";
+
+/// Gauge holding the number of payoff evaluations
+pub const PAYOFF_EVALUATIONS: &str = "antifragile_payoff_evaluations";
+
+/// Label naming the monitored system on every gauge
+pub const SYSTEM_LABEL: &str = "system";
+
+/// Publish a classification, and its convexity ratio if known, for
+/// `system`
+///
+/// For classifications made outside the streaming classifiers, with
+/// `convexity` from [`TriadAnalysis::convexity`](crate::TriadAnalysis::convexity).
+/// When it is `None` the convexity ratio gauge keeps its last value.
+#[cfg(feature = "metrics")]
+pub fn publish_classification(system: &str, triad: Triad, convexity: Option<f64>) {
+    publish_rank(system, triad);
+    if let Some(convexity) = convexity {
+        metrics::gauge!(CONVEXITY_RATIO, SYSTEM_LABEL => system.to_owned()).set(convexity);
+    }
+}
+
+/// Publish the classification and fitted curvature of a streaming
+/// classifier
+#[cfg(feature = "metrics")]
+fn publish_fit(system: &str, triad: Triad, curvature: Option<f64>) {
+    publish_rank(system, triad);
+    if let Some(curvature) = curvature {
+        metrics::gauge!(CURVATURE, SYSTEM_LABEL => system.to_owned()).set(curvature);
+    }
+}
+
+#[cfg(feature = "metrics")]
+fn publish_rank(system: &str, triad: Triad) {
+    metrics::gauge!(CLASSIFICATION_RANK, SYSTEM_LABEL => system.to_owned())
+        .set(f64::from(triad.rank()));
+}
+
+/// Sources of classification gauges
+#[cfg(feature = "metrics")]
+pub trait PublishMetrics {
+    /// Publish the current state as gauges labelled with `system`
+    fn publish_metrics(&self, system: &str);
+}
+
+#[cfg(feature = "metrics")]
+impl PublishMetrics for OnlineClassifier {
+    /// Classification rank, curvature, and observation count
+    #[allow(clippy::cast_precision_loss)]
+    fn publish_metrics(&self, system: &str) {
+        publish_fit(system, self.triad(), self.convexity());
+        metrics::gauge!(OBSERVATIONS, SYSTEM_LABEL => system.to_owned())
+            .set(self.observations() as f64);
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl PublishMetrics for RollingClassifier {
+    /// Classification rank, curvature, and observations inside the window
+    #[allow(clippy::cast_precision_loss)]
+    fn publish_metrics(&self, system: &str) {
+        publish_fit(system, self.triad(), self.convexity());
+        metrics::gauge!(OBSERVATIONS, SYSTEM_LABEL => system.to_owned())
+            .set(self.observations() as f64);
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl PublishMetrics for ChangeDetector {
+    /// The established regime's rank, the watched classifier's curvature,
+    /// and the observations seen
+    ///
+    /// Publishes nothing about the classification until a regime is
+    /// established.
+    #[allow(clippy::cast_precision_loss)]
+    fn publish_metrics(&self, system: &str) {
+        if let Some(regime) = self.regime() {
+            publish_fit(system, regime, self.classifier().convexity());
+        }
+        metrics::gauge!(OBSERVATIONS, SYSTEM_LABEL => system.to_owned())
+            .set(self.observations() as f64);
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl<T: Antifragile> PublishMetrics for Instrumented<T>
+where
+    T::Stressor: PartialOrd,
+{
+    /// Payoff evaluations recorded so far
+    #[allow(clippy::cast_precision_loss)]
+    fn publish_metrics(&self, system: &str) {
+        metrics::gauge!(PAYOFF_EVALUATIONS, SYSTEM_LABEL => system.to_owned())
+            .set(self.evaluations() as f64);
+    }
+}
+
+/// Help text of the Prometheus rank gauge
+#[cfg(feature = "prometheus")]
+const RANK_HELP: &str = "Triad rank: 0 fragile, 1 robust, 2 antifragile";
+
+/// Help text of the Prometheus curvature gauge
+#[cfg(feature = "prometheus")]
+const CURVATURE_HELP: &str = "Fitted second derivative of the payoff";
+
+/// Help text of the Prometheus convexity ratio gauge
+#[cfg(feature = "prometheus")]
+const CONVEXITY_RATIO_HELP: &str = "Second difference of the payoff relative to its level";
+
+/// Rank, curvature, and convexity ratio gauges registered in a Prometheus
+/// registry
+///
+/// The Prometheus counterpart of `PublishMetrics`, under the same names
+/// and label. Cloning shares the underlying gauges.
+#[cfg(feature = "prometheus")]
+#[derive(Debug, Clone)]
+pub struct PrometheusGauges {
+    rank: prometheus::GaugeVec,
+    curvature: prometheus::GaugeVec,
+    convexity_ratio: prometheus::GaugeVec,
+}
+
+#[cfg(feature = "prometheus")]
+impl PrometheusGauges {
+    /// Create the gauges and register them in `registry`
+    ///
+    /// # Errors
+    ///
+    /// If `registry` already holds gauges with these names. Nothing is left
+    /// registered then.
+    pub fn register(registry: &prometheus::Registry) -> prometheus::Result<Self> {
+        let gauge = |name: &str, help: &str| {
+            prometheus::GaugeVec::new(prometheus::Opts::new(name, help), &[SYSTEM_LABEL])
+        };
+        let gauges = Self {
+            rank: gauge(CLASSIFICATION_RANK, RANK_HELP)?,
+            curvature: gauge(CURVATURE, CURVATURE_HELP)?,
+            convexity_ratio: gauge(CONVEXITY_RATIO, CONVEXITY_RATIO_HELP)?,
+        };
+        let all = [&gauges.rank, &gauges.curvature, &gauges.convexity_ratio];
+        for (registered, gauge) in all.iter().enumerate() {
+            if let Err(error) = registry.register(Box::new((*gauge).clone())) {
+                // Roll back, so that registering again can succeed
+                for gauge in &all[..registered] {
+                    let _ = registry.unregister(Box::new((*gauge).clone()));
+                }
+                return Err(error);
+            }
+        }
+        Ok(gauges)
+    }
+
+    /// Set the classification of `system`, and its convexity ratio if known
+    ///
+    /// `convexity` is from
+    /// [`TriadAnalysis::convexity`](crate::TriadAnalysis::convexity); when it
+    /// is `None` the convexity ratio gauge keeps its last value.
+    pub fn set(&self, system: &str, triad: Triad, convexity: Option<f64>) {
+        self.set_rank(system, triad);
+        if let Some(convexity) = convexity {
+            self.convexity_ratio
+                .with_label_values(&[system])
+                .set(convexity);
+        }
+    }
+
+    /// Set the classification of `system` from a streaming classifier, and
+    /// its fitted curvature if identified
+    ///
+    /// When `curvature` is `None` the curvature gauge keeps its last value.
+    pub fn set_fit(&self, system: &str, triad: Triad, curvature: Option<f64>) {
+        self.set_rank(system, triad);
+        if let Some(curvature) = curvature {
+            self.curvature.with_label_values(&[system]).set(curvature);
+        }
+    }
+
+    /// Stop exporting `system`, such as when it is decommissioned
+    pub fn remove(&self, system: &str) {
+        // Absent series are not an error worth reporting
+        let _ = self.rank.remove_label_values(&[system]);
+        let _ = self.curvature.remove_label_values(&[system]);
+        let _ = self.convexity_ratio.remove_label_values(&[system]);
+    }
+
+    fn set_rank(&self, system: &str, triad: Triad) {
+        self.rank
+            .with_label_values(&[system])
+            .set(f64::from(triad.rank()));
+    }
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use super::*;
+    use crate::TriadAnalysis;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use metrics::{
+        Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+
+    /// Keeps every gauge by name and `system` label
+    #[derive(Default)]
+    struct Gauges(Mutex<HashMap<(String, String), Arc<AtomicU64>>>);
+
+    impl Gauges {
+        fn get(&self, name: &str, system: &str) -> Option<f64> {
+            let gauges = self.0.lock().unwrap();
+            let gauge = gauges.get(&(name.to_owned(), system.to_owned()))?;
+            Some(f64::from_bits(gauge.load(Ordering::Relaxed)))
+        }
+    }
+
+    impl Recorder for Gauges {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, _: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::noop()
+        }
+
+        fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+            let system = key
+                .labels()
+                .find(|label| label.key() == SYSTEM_LABEL)
+                .map(|label| label.value().to_owned())
+                .unwrap_or_default();
+            let gauge = Arc::clone(
+                self.0
+                    .lock()
+                    .unwrap()
+                    .entry((key.name().to_owned(), system))
+                    .or_default(),
+            );
+            Gauge::from_arc(gauge)
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn test_online_classifier_gauges() {
+        let mut monitor = OnlineClassifier::new();
+        for load in 0..50 {
+            let load = f64::from(load);
+            monitor.observe(load, 10.0 * load - 0.5 * load * load);
+        }
+
+        let gauges = Gauges::default();
+        metrics::with_local_recorder(&gauges, || monitor.publish_metrics("checkout"));
+
+        assert_eq!(gauges.get(CLASSIFICATION_RANK, "checkout"), Some(0.0));
+        assert!((gauges.get(CURVATURE, "checkout").unwrap() + 1.0).abs() < 1e-6);
+        assert_eq!(gauges.get(CONVEXITY_RATIO, "checkout"), None);
+        assert_eq!(gauges.get(OBSERVATIONS, "checkout"), Some(50.0));
+        assert_eq!(gauges.get(CLASSIFICATION_RANK, "search"), None);
+    }
+
+    #[test]
+    fn test_unidentified_and_instrumented_gauges() {
+        let detector = ChangeDetector::new(crate::stream::ChangeConfig::default());
+        let system = Instrumented::new(crate::antifragile!(|x: f64| x * x));
+        let _ = system.classify(10.0, 1.0);
+
+        let gauges = Gauges::default();
+        metrics::with_local_recorder(&gauges, || {
+            detector.publish_metrics("quiet");
+            system.publish_metrics("pricing");
+        });
+
+        assert_eq!(gauges.get(CLASSIFICATION_RANK, "quiet"), None);
+        assert_eq!(gauges.get(OBSERVATIONS, "quiet"), Some(0.0));
+        assert_eq!(gauges.get(PAYOFF_EVALUATIONS, "pricing"), Some(3.0));
+    }
+}
+
+#[cfg(all(test, feature = "prometheus"))]
+mod prometheus_tests {
+    use super::*;
+    use prometheus::{Encoder, Registry, TextEncoder};
+
+    fn exposition(registry: &Registry) -> String {
+        let mut text = Vec::new();
+        TextEncoder::new()
+            .encode(&registry.gather(), &mut text)
+            .unwrap();
+        String::from_utf8(text).unwrap()
+    }
+
+    #[test]
+    fn test_gauges_use_the_shared_names() {
+        let registry = Registry::new();
+        let gauges = PrometheusGauges::register(&registry).unwrap();
+        gauges.set("checkout", Triad::Antifragile, Some(0.25));
+        gauges.set("search", Triad::Robust, None);
+        gauges.set_fit("stream", Triad::Fragile, Some(-1.5));
+
+        let text = exposition(&registry);
+        assert!(text.contains("antifragile_classification_rank{system=\"checkout\"} 2"));
+        assert!(text.contains("antifragile_classification_rank{system=\"search\"} 1"));
+        assert!(text.contains("antifragile_convexity_ratio{system=\"checkout\"} 0.25"));
+        assert!(!text.contains("antifragile_convexity_ratio{system=\"search\"}"));
+        assert!(text.contains("antifragile_curvature{system=\"stream\"} -1.5"));
+        assert!(!text.contains("antifragile_curvature{system=\"checkout\"}"));
+
+        gauges.remove("checkout");
+        gauges.remove("unknown");
+        assert!(!exposition(&registry).contains("checkout"));
+
+        assert!(PrometheusGauges::register(&registry).is_err());
+    }
+
+    #[test]
+    fn test_failed_registration_leaves_nothing_behind() {
+        let registry = Registry::new();
+        let taken = prometheus::GaugeVec::new(
+            prometheus::Opts::new(CONVEXITY_RATIO, CONVEXITY_RATIO_HELP),
+            &[SYSTEM_LABEL],
+        )
+        .unwrap();
+        registry.register(Box::new(taken.clone())).unwrap();
+        assert!(PrometheusGauges::register(&registry).is_err());
+
+        registry.unregister(Box::new(taken)).unwrap();
+        assert!(PrometheusGauges::register(&registry).is_ok());
+    }
+}
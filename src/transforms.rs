@@ -0,0 +1,307 @@
+//! # Downside-capping and hedging transforms
+//!
+//! Ready-made payoff transforms for experimenting with fragility-reduction
+//! structures. Each transform wraps a system and implements [`Antifragile`](crate::Antifragile)
+//! itself, so transforms compose and can be classified like any other system.
+//!
+//! | Transform | Payoff | Effect on convexity |
+//! |-----------|--------|---------------------|
+//! | [`Floored`](crate::transforms::Floored) | `max(f(x), floor)` | Adds convexity where the floor binds (a long put) |
+//! | [`Capped`](crate::transforms::Capped) | `min(f(x), cap)` | Adds concavity where the cap binds (a short call) |
+//! | [`Collared`](crate::transforms::Collared) | `min(max(f(x), floor), cap)` | Convex at the floor, concave at the cap |
+//! | [`ProportionalHedge`](crate::transforms::ProportionalHedge) | `(1 - ratio)·f(x)` | Scales curvature; preserves its sign for `ratio < 1` |
+//!
+//! Away from the points where a floor or cap binds, the transforms leave the
+//! local classification unchanged.
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad, TriadAnalysis};
+//! use antifragile::transforms::PayoffTransform;
+//!
+//! struct Linear;
+//!
+//! impl Antifragile for Linear {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, x: f64) -> f64 {
+//!         x
+//!     }
+//! }
+//!
+//! let collared = Linear.collared(-5.0, 5.0);
+//! assert_eq!(collared.classify(-5.0, 1.0), Triad::Antifragile);
+//! assert_eq!(collared.classify(0.0, 1.0), Triad::Robust);
+//! assert_eq!(collared.classify(5.0, 1.0), Triad::Fragile);
+//! ```
+
+use crate::Antifragile;
+
+/// Payoff bounded from below: `max(f(x), floor)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Floored<T: Antifragile> {
+    inner: T,
+    floor: T::Payoff,
+}
+
+/// Payoff bounded from above: `min(f(x), cap)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capped<T: Antifragile> {
+    inner: T,
+    cap: T::Payoff,
+}
+
+/// Payoff bounded from both sides: `min(max(f(x), floor), cap)`
+///
+/// The floor is applied first, so if `floor > cap` the payoff is always `cap`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Collared<T: Antifragile> {
+    inner: T,
+    floor: T::Payoff,
+    cap: T::Payoff,
+}
+
+/// A fraction of the position hedged away: `(1 - ratio)·f(x)`
+///
+/// A ratio of `0.0` keeps the full exposure and `1.0` removes it entirely
+/// (the result is [`Robust`](crate::Triad::Robust)). Ratios above `1.0`
+/// over-hedge and flip the sign of the curvature.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProportionalHedge<T> {
+    inner: T,
+    ratio: f64,
+}
+
+impl<T: Antifragile> Floored<T> {
+    /// Wrap `inner` with a payoff floor
+    pub const fn new(inner: T, floor: T::Payoff) -> Self {
+        Self { inner, floor }
+    }
+
+    /// Get reference to the wrapped system
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Unwrap the transformed system
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Antifragile> Capped<T> {
+    /// Wrap `inner` with a payoff cap
+    pub const fn new(inner: T, cap: T::Payoff) -> Self {
+        Self { inner, cap }
+    }
+
+    /// Get reference to the wrapped system
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Unwrap the transformed system
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Antifragile> Collared<T> {
+    /// Wrap `inner` with a payoff floor and cap
+    pub const fn new(inner: T, floor: T::Payoff, cap: T::Payoff) -> Self {
+        Self { inner, floor, cap }
+    }
+
+    /// Get reference to the wrapped system
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Unwrap the transformed system
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T> ProportionalHedge<T> {
+    /// Hedge away `ratio` of the position held in `inner`
+    pub const fn new(inner: T, ratio: f64) -> Self {
+        Self { inner, ratio }
+    }
+
+    /// The hedged fraction
+    #[must_use]
+    pub const fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// Get reference to the wrapped system
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Unwrap the transformed system
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Antifragile> Antifragile for Floored<T> {
+    type Stressor = T::Stressor;
+    type Payoff = T::Payoff;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        let payoff = self.inner.payoff(stressor);
+        if payoff < self.floor {
+            self.floor
+        } else {
+            payoff
+        }
+    }
+}
+
+impl<T: Antifragile> Antifragile for Capped<T> {
+    type Stressor = T::Stressor;
+    type Payoff = T::Payoff;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        let payoff = self.inner.payoff(stressor);
+        if payoff > self.cap { self.cap } else { payoff }
+    }
+}
+
+impl<T: Antifragile> Antifragile for Collared<T> {
+    type Stressor = T::Stressor;
+    type Payoff = T::Payoff;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> Self::Payoff {
+        let payoff = self.inner.payoff(stressor);
+        let floored = if payoff < self.floor {
+            self.floor
+        } else {
+            payoff
+        };
+        if floored > self.cap {
+            self.cap
+        } else {
+            floored
+        }
+    }
+}
+
+impl<T: Antifragile<Payoff = f64>> Antifragile for ProportionalHedge<T> {
+    type Stressor = T::Stressor;
+    type Payoff = f64;
+
+    #[inline]
+    fn payoff(&self, stressor: Self::Stressor) -> f64 {
+        (1.0 - self.ratio) * self.inner.payoff(stressor)
+    }
+}
+
+/// Extension trait for applying payoff transforms fluently
+pub trait PayoffTransform: Antifragile + Sized {
+    /// Bound the payoff from below (see [`Floored`])
+    fn floored(self, floor: Self::Payoff) -> Floored<Self> {
+        Floored::new(self, floor)
+    }
+
+    /// Bound the payoff from above (see [`Capped`])
+    fn capped(self, cap: Self::Payoff) -> Capped<Self> {
+        Capped::new(self, cap)
+    }
+
+    /// Bound the payoff from both sides (see [`Collared`])
+    fn collared(self, floor: Self::Payoff, cap: Self::Payoff) -> Collared<Self> {
+        Collared::new(self, floor, cap)
+    }
+
+    /// Hedge away a fraction of the position (see [`ProportionalHedge`])
+    fn hedged(self, ratio: f64) -> ProportionalHedge<Self>
+    where
+        Self: Antifragile<Payoff = f64>,
+    {
+        ProportionalHedge::new(self, ratio)
+    }
+}
+
+impl<T: Antifragile> PayoffTransform for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Triad, TriadAnalysis};
+
+    struct Linear;
+
+    impl Antifragile for Linear {
+        type Stressor = f64;
+        type Payoff = f64;
+        fn payoff(&self, x: f64) -> f64 {
+            x
+        }
+    }
+
+    struct Concave;
+
+    impl Antifragile for Concave {
+        type Stressor = f64;
+        type Payoff = f64;
+        fn payoff(&self, x: f64) -> f64 {
+            -x * x
+        }
+    }
+
+    struct Counter;
+
+    impl Antifragile for Counter {
+        type Stressor = i64;
+        type Payoff = i64;
+        fn payoff(&self, x: i64) -> i64 {
+            3 * x
+        }
+    }
+
+    #[test]
+    fn test_floor_adds_convexity_where_it_binds() {
+        let floored = Linear.floored(0.0);
+        assert_eq!(floored.classify(0.0, 1.0), Triad::Antifragile);
+        assert_eq!(floored.classify(10.0, 1.0), Triad::Robust);
+        assert_eq!(floored.classify(-10.0, 1.0), Triad::Robust);
+    }
+
+    #[test]
+    fn test_cap_adds_concavity_where_it_binds() {
+        let capped = Linear.capped(0.0);
+        assert_eq!(capped.classify(0.0, 1.0), Triad::Fragile);
+        assert_eq!(capped.classify(10.0, 1.0), Triad::Robust);
+    }
+
+    #[test]
+    fn test_collar_with_inverted_bounds_is_constant() {
+        let collared = Linear.collared(5.0, -5.0);
+        assert!((collared.payoff(100.0) - -5.0).abs() < f64::EPSILON);
+        assert!((collared.payoff(-100.0) - -5.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_proportional_hedge_scales_curvature() {
+        assert_eq!(Concave.hedged(0.5).classify(1.0, 1.0), Triad::Fragile);
+        assert_eq!(Concave.hedged(1.0).classify(1.0, 1.0), Triad::Robust);
+        assert_eq!(Concave.hedged(1.5).classify(1.0, 1.0), Triad::Antifragile);
+        assert!((Concave.hedged(0.25).ratio() - 0.25).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_transforms_compose_on_integer_payoffs() {
+        let structured = Counter.floored(-30).capped(30);
+        assert_eq!(structured.payoff(100), 30);
+        assert_eq!(structured.payoff(-100), -30);
+        assert_eq!(structured.classify(-10, 1), Triad::Antifragile);
+        assert_eq!(structured.classify(10, 1), Triad::Fragile);
+        assert_eq!(structured.inner().inner().payoff(1), 3);
+    }
+}
@@ -0,0 +1,337 @@
+//! # Vega profile: sensitivity to stressor volatility
+//!
+//! A point [`Triad`](crate::Triad) says whether a system gains or loses from
+//! a single perturbation of fixed size. Finance users usually want the
+//! broader question: *as the stressor becomes more volatile, does the
+//! expected payoff go up or down?* That is the system's vega.
+//!
+//! [`vega_profile`](crate::vega::vega_profile) draws the stressor as
+//! `at + σ·Z` with `Z` standard normal, estimates the expected payoff by
+//! Monte Carlo for each volatility `σ` across a range, and reports the slope
+//! `∂E[f]/∂σ` at each point. Every volatility reuses the same normal draws
+//! (common random numbers) in antithetic pairs `(Z, -Z)`, so differences
+//! between neighbouring points are nearly free of sampling noise and linear
+//! payoffs come out exactly vega-neutral.
+//!
+//! ```rust
+//! use antifragile::Antifragile;
+//! use antifragile::vega::{VegaConfig, VolExposure, vega_profile};
+//!
+//! struct Straddle;
+//!
+//! impl Antifragile for Straddle {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, price: f64) -> f64 {
+//!         (price - 100.0).abs()
+//!     }
+//! }
+//!
+//! let profile = vega_profile(&Straddle, 100.0, 1.0..=20.0, &VegaConfig::default());
+//! assert_eq!(profile.exposure(), VolExposure::Long);
+//! ```
+
+use core::ops::RangeInclusive;
+
+use crate::Antifragile;
+use crate::rng::SplitMix64;
+
+/// Configuration for [`vega_profile`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VegaConfig {
+    /// Number of volatility levels sampled across the range
+    pub steps: usize,
+    /// Antithetic sample pairs drawn per volatility level
+    pub samples: usize,
+    /// Seed for the normal draws; equal seeds give identical profiles
+    pub seed: u64,
+    /// Absolute vega below which the system is considered vega-neutral
+    pub tolerance: f64,
+}
+
+impl Default for VegaConfig {
+    /// 21 volatility levels, 2048 antithetic pairs, and a tolerance of `1e-9`
+    fn default() -> Self {
+        Self {
+            steps: 21,
+            samples: 2048,
+            seed: 0x5EED,
+            tolerance: 1e-9,
+        }
+    }
+}
+
+/// Direction of a system's exposure to stressor volatility
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VolExposure {
+    /// Expected payoff rises with volatility
+    Long,
+    /// Expected payoff is insensitive to volatility
+    Neutral,
+    /// Expected payoff falls with volatility
+    Short,
+    /// The direction changes across the range
+    Mixed,
+}
+
+/// One volatility level of a [`VegaProfile`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VegaPoint {
+    /// Stressor volatility `σ`
+    pub volatility: f64,
+    /// Monte Carlo estimate of `E[f(at + σ·Z)]`
+    pub expected_payoff: f64,
+    /// Standard error of the expected payoff estimate
+    pub std_error: f64,
+    /// Estimated `∂E[f]/∂σ` (central difference, one-sided at the ends)
+    pub vega: f64,
+    /// Direction of the exposure at this volatility
+    pub exposure: VolExposure,
+}
+
+/// Expected payoff and vega across a range of stressor volatilities
+#[derive(Debug, Clone, PartialEq)]
+pub struct VegaProfile {
+    points: Vec<VegaPoint>,
+}
+
+impl VegaProfile {
+    /// The volatility levels, in ascending order
+    #[must_use]
+    pub fn points(&self) -> &[VegaPoint] {
+        &self.points
+    }
+
+    /// Overall exposure across the whole range
+    ///
+    /// Neutral points do not break a long or short run; a profile that is
+    /// long in some places and short in others is [`VolExposure::Mixed`].
+    #[must_use]
+    pub fn exposure(&self) -> VolExposure {
+        let long = self.points.iter().any(|p| p.exposure == VolExposure::Long);
+        let short = self.points.iter().any(|p| p.exposure == VolExposure::Short);
+        match (long, short) {
+            (true, true) => VolExposure::Mixed,
+            (true, false) => VolExposure::Long,
+            (false, true) => VolExposure::Short,
+            (false, false) => VolExposure::Neutral,
+        }
+    }
+
+    /// Volatilities at which the exposure switches between long and short
+    ///
+    /// Each crossing is linearly interpolated between the two neighbouring
+    /// levels whose vegas have opposite signs.
+    #[must_use]
+    pub fn crossovers(&self) -> Vec<f64> {
+        let signed: Vec<&VegaPoint> = self
+            .points
+            .iter()
+            .filter(|p| matches!(p.exposure, VolExposure::Long | VolExposure::Short))
+            .collect();
+        signed
+            .windows(2)
+            .filter(|pair| pair[0].exposure != pair[1].exposure)
+            .map(|pair| {
+                let (a, b) = (pair[0], pair[1]);
+                a.volatility + (b.volatility - a.volatility) * a.vega / (a.vega - b.vega)
+            })
+            .collect()
+    }
+
+    /// Consume the profile, returning its points
+    #[must_use]
+    pub fn into_points(self) -> Vec<VegaPoint> {
+        self.points
+    }
+}
+
+/// Estimate the expected payoff and vega of `system` around `at` across
+/// `vol_range`
+///
+/// Costs `2 · samples · steps` payoff evaluations.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn vega_profile<T>(
+    system: &T,
+    at: f64,
+    vol_range: RangeInclusive<f64>,
+    config: &VegaConfig,
+) -> VegaProfile
+where
+    T: Antifragile<Stressor = f64, Payoff = f64> + ?Sized,
+{
+    let mut rng = SplitMix64::new(config.seed);
+    let draws: Vec<f64> = (0..config.samples).map(|_| rng.next_normal()).collect();
+
+    let (start, end) = (*vol_range.start(), *vol_range.end());
+    let volatilities: Vec<f64> = match config.steps {
+        0 => Vec::new(),
+        1 => vec![start],
+        steps => {
+            let step = (end - start) / (steps - 1) as f64;
+            (0..steps).map(|i| start + step * i as f64).collect()
+        }
+    };
+
+    let estimates: Vec<(f64, f64)> = volatilities
+        .iter()
+        .map(|&sigma| {
+            let pairs: Vec<f64> = draws
+                .iter()
+                .map(|&z| 0.5 * (system.payoff(at + sigma * z) + system.payoff(at - sigma * z)))
+                .collect();
+            mean_and_std_error(&pairs)
+        })
+        .collect();
+
+    let n = volatilities.len();
+    let points = (0..n)
+        .map(|i| {
+            let vega = if n < 2 {
+                0.0
+            } else {
+                let (lo, hi) = (i.saturating_sub(1), (i + 1).min(n - 1));
+                (estimates[hi].0 - estimates[lo].0) / (volatilities[hi] - volatilities[lo])
+            };
+            let exposure = if vega > config.tolerance {
+                VolExposure::Long
+            } else if vega < -config.tolerance {
+                VolExposure::Short
+            } else {
+                VolExposure::Neutral
+            };
+            VegaPoint {
+                volatility: volatilities[i],
+                expected_payoff: estimates[i].0,
+                std_error: estimates[i].1,
+                vega,
+                exposure,
+            }
+        })
+        .collect();
+
+    VegaProfile { points }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn mean_and_std_error(values: &[f64]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0.0, 0.0);
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    if values.len() < 2 {
+        return (mean, 0.0);
+    }
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, (variance / n).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Linear;
+
+    impl Antifragile for Linear {
+        type Stressor = f64;
+        type Payoff = f64;
+        fn payoff(&self, x: f64) -> f64 {
+            3.0 * x - 1.0
+        }
+    }
+
+    struct Quadratic;
+
+    impl Antifragile for Quadratic {
+        type Stressor = f64;
+        type Payoff = f64;
+        fn payoff(&self, x: f64) -> f64 {
+            x * x
+        }
+    }
+
+    #[test]
+    fn test_linear_payoff_is_vega_neutral() {
+        let profile = vega_profile(&Linear, 5.0, 0.0..=10.0, &VegaConfig::default());
+        assert_eq!(profile.exposure(), VolExposure::Neutral);
+        assert!(profile.crossovers().is_empty());
+        for point in profile.points() {
+            assert!((point.expected_payoff - 14.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_quadratic_vega_matches_theory() {
+        // E[(x + σZ)²] = x² + σ², so vega = 2σ
+        let config = VegaConfig {
+            samples: 20_000,
+            ..VegaConfig::default()
+        };
+        let profile = vega_profile(&Quadratic, 0.0, 1.0..=3.0, &config);
+        let middle = profile.points()[10];
+        assert!((middle.volatility - 2.0).abs() < 1e-12);
+        assert!((middle.vega - 4.0).abs() < 0.1);
+        assert!((middle.expected_payoff - 4.0).abs() < 0.1);
+
+        let short = vega_profile(&Rev(Quadratic), 0.0, 1.0..=3.0, &config);
+        assert_eq!(short.exposure(), VolExposure::Short);
+    }
+
+    struct Rev<T>(T);
+
+    impl<T: Antifragile<Stressor = f64, Payoff = f64>> Antifragile for Rev<T> {
+        type Stressor = f64;
+        type Payoff = f64;
+        fn payoff(&self, x: f64) -> f64 {
+            -self.0.payoff(x)
+        }
+    }
+
+    #[test]
+    fn test_mixed_exposure_has_crossover() {
+        // Convex for small moves, capped for large ones: long vol at low σ,
+        // short vol once the cap starts to bind often
+        struct CappedStraddle;
+        impl Antifragile for CappedStraddle {
+            type Stressor = f64;
+            type Payoff = f64;
+            fn payoff(&self, x: f64) -> f64 {
+                let move_size = x.abs();
+                if move_size < 1.0 {
+                    move_size
+                } else {
+                    (2.0 - move_size).max(-5.0)
+                }
+            }
+        }
+
+        let config = VegaConfig {
+            samples: 8192,
+            ..VegaConfig::default()
+        };
+        let profile = vega_profile(&CappedStraddle, 0.0, 0.1..=4.0, &config);
+        assert_eq!(profile.exposure(), VolExposure::Mixed);
+        assert_eq!(profile.crossovers().len(), 1);
+    }
+
+    #[test]
+    fn test_deterministic_for_seed() {
+        let config = VegaConfig::default();
+        let a = vega_profile(&Quadratic, 1.0, 0.5..=1.5, &config);
+        let b = vega_profile(&Quadratic, 1.0, 0.5..=1.5, &config);
+        assert_eq!(a, b);
+        assert!(
+            vega_profile(
+                &Quadratic,
+                1.0,
+                0.5..=1.5,
+                &VegaConfig { steps: 0, ..config }
+            )
+            .points()
+            .is_empty()
+        );
+    }
+}
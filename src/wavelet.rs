@@ -0,0 +1,391 @@
+//! # Multi-scale analysis of payoff series
+//!
+//! A system can thrive on fast noise and still be broken by slow drift:
+//! a trend-following strategy, a muscle, an inventory buffer. A single
+//! convexity test blends every timescale together. [`multiscale`](crate::wavelet::multiscale)
+//! separates them using a Haar decomposition of an observed stressor series
+//! and the payoffs recorded alongside it.
+//!
+//! At level `k` the series is cut into blocks of `2 · 2ᵏ` observations. In
+//! each block, the Haar detail `d = (mean of second half - mean of first
+//! half) / 2` is the stressor fluctuation at timescale `2ᵏ`. With `m` the
+//! block-mean stressor and `v` the variance within the two halves (the
+//! fluctuations at finer timescales), the block-mean payoff is regressed on
+//!
+//! ```text
+//! mean payoff = α + β·m + δ·m² + γ·d² + η·v
+//! ```
+//!
+//! so that `γ` measures the effect of fluctuating at exactly this timescale,
+//! separately from where the block sits and from faster noise. For an instantaneous payoff `f`,
+//! `γ ≈ f''/2`: blocks that fluctuated more at that timescale earned more if
+//! `f` is convex and less if it is concave. The sign of `γ`, when it is
+//! significant, is the Triad at that scale.
+//!
+//! ```rust
+//! use antifragile::Triad;
+//! use antifragile::wavelet::{WaveletConfig, multiscale};
+//!
+//! // An irregular stressor series from the logistic map
+//! let stressors: Vec<f64> = (0..256)
+//!     .scan(0.3, |x, _| {
+//!         *x = 3.9 * *x * (1.0 - *x);
+//!         Some(*x)
+//!     })
+//!     .collect();
+//!
+//! // Payoff x²: convex at every timescale
+//! let payoffs: Vec<f64> = stressors.iter().map(|x| x * x).collect();
+//!
+//! let scales = multiscale(&stressors, &payoffs, &WaveletConfig::default())?;
+//! assert!(scales.iter().all(|s| s.classification == Triad::Antifragile));
+//! # Ok::<(), antifragile::wavelet::WaveletError>(())
+//! ```
+
+use core::fmt::Display;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::Triad;
+
+/// Configuration for [`multiscale`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaveletConfig {
+    /// Fewest blocks a level needs to be analysed
+    pub min_blocks: usize,
+    /// Standard errors `γ` must clear to be significant
+    pub significance: f64,
+    /// Effects `γ · mean(d²)` at or below this size, in payoff units, are robust
+    pub tolerance: f64,
+}
+
+impl Default for WaveletConfig {
+    /// At least 8 blocks, two standard errors, and a tolerance of `1e-12`
+    fn default() -> Self {
+        Self {
+            min_blocks: 8,
+            significance: 2.0,
+            tolerance: 1e-12,
+        }
+    }
+}
+
+/// Error returned by [`multiscale`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaveletError {
+    /// The stressor and payoff series have different lengths
+    LengthMismatch {
+        /// Length of the stressor series
+        stressors: usize,
+        /// Length of the payoff series
+        payoffs: usize,
+    },
+    /// The series is too short for even the finest level
+    TooShort {
+        /// Number of observations
+        found: usize,
+        /// Number of observations needed
+        needed: usize,
+    },
+    /// An observation was NaN or infinite
+    NonFinite {
+        /// Position of the offending observation
+        index: usize,
+    },
+}
+
+impl Display for WaveletError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::LengthMismatch { stressors, payoffs } => write!(
+                f,
+                "series lengths differ: {stressors} stressors, {payoffs} payoffs"
+            ),
+            Self::TooShort { found, needed } => write!(
+                f,
+                "series too short: found {found} observations, need at least {needed}"
+            ),
+            Self::NonFinite { index } => write!(f, "non-finite observation at index {index}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for WaveletError {}
+
+/// Classification at one timescale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleAnalysis {
+    /// Timescale in observations (`2ᵏ`)
+    pub scale: usize,
+    /// Number of blocks regressed
+    pub blocks: usize,
+    /// Estimated `γ`, the payoff gained per unit of squared fluctuation
+    pub curvature: f64,
+    /// Standard error of `curvature`
+    pub std_error: f64,
+    /// Sign of `curvature` if significant, else robust
+    pub classification: Triad,
+}
+
+/// Classify the payoff's response to stressor fluctuations at each
+/// dyadic timescale, finest first
+///
+/// Observations beyond the last whole block of a level are ignored at that
+/// level.
+///
+/// # Errors
+///
+/// Returns [`WaveletError::LengthMismatch`] if the series differ in length,
+/// [`WaveletError::NonFinite`] for NaN or infinite observations, and
+/// [`WaveletError::TooShort`] if there are fewer than `2 · min_blocks`
+/// observations (at least six blocks are always needed).
+#[allow(clippy::cast_precision_loss)]
+pub fn multiscale(
+    stressors: &[f64],
+    payoffs: &[f64],
+    config: &WaveletConfig,
+) -> Result<Vec<ScaleAnalysis>, WaveletError> {
+    if stressors.len() != payoffs.len() {
+        return Err(WaveletError::LengthMismatch {
+            stressors: stressors.len(),
+            payoffs: payoffs.len(),
+        });
+    }
+    if let Some(index) = stressors
+        .iter()
+        .zip(payoffs)
+        .position(|(x, y)| !x.is_finite() || !y.is_finite())
+    {
+        return Err(WaveletError::NonFinite { index });
+    }
+    let min_blocks = config.min_blocks.max(6);
+    if stressors.len() < 2 * min_blocks {
+        return Err(WaveletError::TooShort {
+            found: stressors.len(),
+            needed: 2 * min_blocks,
+        });
+    }
+
+    let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+    let mut scales = Vec::new();
+    let mut scale = 1;
+    while stressors.len() / (2 * scale) >= min_blocks {
+        let rows: Vec<[f64; 4]> = stressors
+            .chunks_exact(2 * scale)
+            .zip(payoffs.chunks_exact(2 * scale))
+            .map(|(x, y)| {
+                let (first, second) = x.split_at(scale);
+                let (m1, m2) = (mean(first), mean(second));
+                let detail = 0.5 * (m2 - m1);
+                let within = (first.iter().map(|v| (v - m1).powi(2)).sum::<f64>()
+                    + second.iter().map(|v| (v - m2).powi(2)).sum::<f64>())
+                    / x.len() as f64;
+                [mean(x), detail * detail, within, mean(y)]
+            })
+            .collect();
+        scales.push(analyze_level(scale, &rows, config));
+        scale *= 2;
+    }
+    Ok(scales)
+}
+
+/// Regress block-mean payoff on `[1, m, m², d², v]`
+///
+/// `v` is dropped at the finest level, where each half is a single
+/// observation and has no variance.
+#[allow(clippy::cast_precision_loss)]
+fn analyze_level(scale: usize, rows: &[[f64; 4]], config: &WaveletConfig) -> ScaleAnalysis {
+    let columns = if scale == 1 { 4 } else { 5 };
+    let regressors = |&[x, d2, v, _]: &[f64; 4]| [1.0, x, x * x, d2, v];
+
+    let mut gram = vec![vec![0.0; columns]; columns];
+    let mut moment = vec![0.0; columns];
+    for row in rows {
+        let r = regressors(row);
+        for i in 0..columns {
+            for j in 0..columns {
+                gram[i][j] += r[i] * r[j];
+            }
+            moment[i] += r[i] * row[3];
+        }
+    }
+
+    let robust = |curvature: f64, std_error: f64| ScaleAnalysis {
+        scale,
+        blocks: rows.len(),
+        curvature,
+        std_error,
+        classification: Triad::Robust,
+    };
+    // Without fluctuations (or with perfectly collinear ones) γ is unidentified
+    let Some(inverse) = invert(gram) else {
+        return robust(0.0, f64::INFINITY);
+    };
+
+    let beta: Vec<f64> = inverse
+        .iter()
+        .map(|row| row.iter().zip(&moment).map(|(a, b)| a * b).sum())
+        .collect();
+    let rss: f64 = rows
+        .iter()
+        .map(|row| {
+            let fitted: f64 = regressors(row).iter().zip(&beta).map(|(r, b)| r * b).sum();
+            (row[3] - fitted).powi(2)
+        })
+        .sum();
+    let dof = (rows.len() - columns) as f64;
+    let curvature = beta[3];
+    let std_error = (rss / dof * inverse[3][3]).max(0.0).sqrt();
+
+    let mean_d2 = rows.iter().map(|row| row[1]).sum::<f64>() / rows.len() as f64;
+    if (curvature * mean_d2).abs() <= config.tolerance
+        || curvature.abs() <= config.significance * std_error
+    {
+        return robust(curvature, std_error);
+    }
+    ScaleAnalysis {
+        classification: if curvature > 0.0 {
+            Triad::Antifragile
+        } else {
+            Triad::Fragile
+        },
+        ..robust(curvature, std_error)
+    }
+}
+
+/// Inverse by Gauss–Jordan elimination with partial pivoting, or `None` if
+/// the matrix is numerically singular
+fn invert(mut m: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+    let n = m.len();
+    let scale = m.iter().flatten().map(|v| v.abs()).fold(0.0, f64::max);
+    let mut inverse: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&a, &b| m[a][col].abs().total_cmp(&m[b][col].abs()))?;
+        if m[pivot][col].abs() <= 1e-12 * scale {
+            return None;
+        }
+        m.swap(col, pivot);
+        inverse.swap(col, pivot);
+
+        let value = m[col][col];
+        let pivot_row: Vec<f64> = m[col].iter().map(|v| v / value).collect();
+        let pivot_inverse: Vec<f64> = inverse[col].iter().map(|v| v / value).collect();
+        for (i, (row, inverse_row)) in m.iter_mut().zip(inverse.iter_mut()).enumerate() {
+            let factor = row[col];
+            if i == col || factor == 0.0 {
+                continue;
+            }
+            for (entry, p) in row.iter_mut().zip(&pivot_row) {
+                *entry -= factor * p;
+            }
+            for (entry, p) in inverse_row.iter_mut().zip(&pivot_inverse) {
+                *entry -= factor * p;
+            }
+        }
+        m[col] = pivot_row;
+        inverse[col] = pivot_inverse;
+    }
+    Some(inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    fn noisy_walk(len: usize, seed: u64) -> Vec<f64> {
+        let mut rng = SplitMix64::new(seed);
+        let mut level = 0.0;
+        (0..len)
+            .map(|_| {
+                level += 0.05 * rng.next_normal();
+                level + rng.next_normal()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_instantaneous_payoffs() {
+        let x = noisy_walk(1024, 1);
+        let concave: Vec<f64> = x.iter().map(|v| -v * v).collect();
+        let linear: Vec<f64> = x.iter().map(|v| 3.0 * v - 1.0).collect();
+
+        let config = WaveletConfig::default();
+        let scales = multiscale(&x, &concave, &config).unwrap_or_default();
+        assert!(!scales.is_empty());
+        assert!(scales.iter().all(|s| s.classification == Triad::Fragile));
+        // γ ≈ f''/2 = -1 at the finest scale
+        assert!((scales[0].curvature + 1.0).abs() < 0.2);
+
+        let scales = multiscale(&x, &linear, &config).unwrap_or_default();
+        assert!(scales.iter().all(|s| s.classification == Triad::Robust));
+    }
+
+    #[test]
+    fn test_fast_noise_helps_slow_drift_hurts() {
+        // Profits from each step's move but pays for displacement over a
+        // long horizon: convex in fast noise, concave in slow drift
+        let mut rng = SplitMix64::new(2);
+        let mut level = 0.0;
+        let x: Vec<f64> = (0..8192)
+            .map(|_| {
+                level += 0.3 * rng.next_normal();
+                level + rng.next_normal()
+            })
+            .collect();
+        let payoffs: Vec<f64> = (0..x.len())
+            .map(|t| {
+                let step = x[t] - x[t.saturating_sub(1)];
+                let drift = x[t] - x[t.saturating_sub(64)];
+                step * step - 0.25 * drift * drift
+            })
+            .collect();
+
+        let scales = multiscale(&x, &payoffs, &WaveletConfig::default()).unwrap_or_default();
+        assert_eq!(scales[0].classification, Triad::Antifragile);
+        // Blocks matching the 64-step horizon see the drift penalty
+        let horizon = scales.iter().find(|s| s.scale == 64);
+        assert_eq!(horizon.map(|s| s.classification), Some(Triad::Fragile));
+    }
+
+    #[test]
+    fn test_errors() {
+        let config = WaveletConfig::default();
+        assert_eq!(
+            multiscale(&[1.0; 20], &[1.0; 19], &config),
+            Err(WaveletError::LengthMismatch {
+                stressors: 20,
+                payoffs: 19
+            })
+        );
+        assert_eq!(
+            multiscale(&[1.0; 10], &[1.0; 10], &config),
+            Err(WaveletError::TooShort {
+                found: 10,
+                needed: 16
+            })
+        );
+        let mut x = [1.0; 20];
+        x[4] = f64::NAN;
+        assert_eq!(
+            multiscale(&x, &[1.0; 20], &config),
+            Err(WaveletError::NonFinite { index: 4 })
+        );
+    }
+
+    #[test]
+    fn test_levels_and_constant_series() {
+        let x = [2.0; 64];
+        let scales = multiscale(&x, &[5.0; 64], &WaveletConfig::default()).unwrap_or_default();
+        // Blocks of 2, 4, and 8 leave at least 8 blocks each
+        assert_eq!(
+            scales.iter().map(|s| s.scale).collect::<Vec<_>>(),
+            vec![1, 2, 4]
+        );
+        assert!(scales.iter().all(|s| s.classification == Triad::Robust));
+    }
+}
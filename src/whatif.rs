@@ -0,0 +1,261 @@
+//! # Comparative what-if analysis
+//!
+//! [`WhatIf`](crate::whatif::WhatIf) overlays hypothetical modifications on an
+//! existing system's payoff without touching the system itself, and reports
+//! how the classification changes. It answers questions such as "what would
+//! buying this insurance do to our fragility?".
+//!
+//! Patches are applied to the payoff in the order they were added:
+//!
+//! - **downside cap** (`max(p, -max_loss)`): limits losses, adding convexity
+//!   at the cap
+//! - **upside cap** (`min(p, limit)`): gives up gains, adding concavity at the
+//!   cap
+//! - **hedge** (`p + g(x)`): adds the payoff of another position
+//!
+//! ```rust
+//! use antifragile::{Antifragile, Triad};
+//! use antifragile::whatif::WhatIf;
+//!
+//! // Selling insurance: keep the premium, pay out on large claims
+//! struct Underwriter;
+//!
+//! impl Antifragile for Underwriter {
+//!     type Stressor = f64;
+//!     type Payoff = f64;
+//!
+//!     fn payoff(&self, claims: f64) -> f64 {
+//!         10.0 - (claims - 5.0).max(0.0)
+//!     }
+//! }
+//!
+//! // What if we buy reinsurance covering claims above 5?
+//! let scenario = WhatIf::new(Underwriter).hedge(|claims| (claims - 5.0).max(0.0) - 1.0);
+//! let outcome = scenario.compare(5.0, 1.0);
+//!
+//! assert_eq!(outcome.baseline, Triad::Fragile);
+//! assert_eq!(outcome.patched, Triad::Robust);
+//! assert!(outcome.improved());
+//! ```
+
+use crate::{Antifragile, Triad, TriadAnalysis};
+
+/// A hypothetical modification of a payoff
+pub enum Patch<S> {
+    /// Losses are limited: the payoff never drops below the value
+    Floor(f64),
+    /// Gains are limited: the payoff never exceeds the value
+    Cap(f64),
+    /// The payoff of an additional position is added
+    Hedge(Box<dyn Fn(S) -> f64>),
+}
+
+impl<S> core::fmt::Debug for Patch<S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Floor(floor) => f.debug_tuple("Floor").field(floor).finish(),
+            Self::Cap(cap) => f.debug_tuple("Cap").field(cap).finish(),
+            Self::Hedge(_) => f.write_str("Hedge(..)"),
+        }
+    }
+}
+
+/// A system with hypothetical patches applied to its payoff
+#[derive(Debug)]
+pub struct WhatIf<T: Antifragile> {
+    inner: T,
+    patches: Vec<Patch<T::Stressor>>,
+}
+
+/// The classification of a system before and after its what-if patches
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WhatIfOutcome {
+    /// Classification of the unmodified system
+    pub baseline: Triad,
+    /// Classification with the patches applied
+    pub patched: Triad,
+    /// `f(x+δ) + f(x-δ) - 2·f(x)` of the unmodified system
+    pub baseline_gap: f64,
+    /// `f(x+δ) + f(x-δ) - 2·f(x)` with the patches applied
+    pub patched_gap: f64,
+}
+
+impl WhatIfOutcome {
+    /// Returns true if the patches changed the classification
+    #[must_use]
+    pub fn changed(&self) -> bool {
+        self.baseline != self.patched
+    }
+
+    /// Returns true if the patches moved the classification up the Triad
+    #[must_use]
+    pub fn improved(&self) -> bool {
+        self.patched > self.baseline
+    }
+
+    /// Change in the convexity gap caused by the patches
+    ///
+    /// Positive values mean the patches added convexity, even when the
+    /// classification itself did not change.
+    #[must_use]
+    pub fn gap_change(&self) -> f64 {
+        self.patched_gap - self.baseline_gap
+    }
+}
+
+impl<T: Antifragile<Payoff = f64>> WhatIf<T> {
+    /// Start a what-if scenario with no patches
+    #[must_use]
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            patches: Vec::new(),
+        }
+    }
+
+    /// Limit losses to `max_loss`: the payoff never drops below `-max_loss`
+    #[must_use]
+    pub fn cap_downside(self, max_loss: f64) -> Self {
+        self.patch(Patch::Floor(-max_loss))
+    }
+
+    /// Limit gains: the payoff never exceeds `limit`
+    #[must_use]
+    pub fn cap_upside(self, limit: f64) -> Self {
+        self.patch(Patch::Cap(limit))
+    }
+
+    /// Add the payoff `hedge(x)` of an additional position
+    #[must_use]
+    pub fn hedge(self, hedge: impl Fn(T::Stressor) -> f64 + 'static) -> Self {
+        self.patch(Patch::Hedge(Box::new(hedge)))
+    }
+
+    /// Append an arbitrary patch
+    #[must_use]
+    pub fn patch(mut self, patch: Patch<T::Stressor>) -> Self {
+        self.patches.push(patch);
+        self
+    }
+
+    /// The patches in application order
+    #[must_use]
+    pub fn patches(&self) -> &[Patch<T::Stressor>] {
+        &self.patches
+    }
+
+    /// Get reference to the unmodified system
+    #[must_use]
+    pub const fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Discard the patches, returning the unmodified system
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Classify the system with and without the patches
+    #[must_use]
+    pub fn compare(&self, at: T::Stressor, delta: T::Stressor) -> WhatIfOutcome {
+        WhatIfOutcome {
+            baseline: self.inner.classify(at, delta),
+            patched: self.classify(at, delta),
+            baseline_gap: gap(&self.inner, at, delta),
+            patched_gap: gap(self, at, delta),
+        }
+    }
+}
+
+impl<T: Antifragile<Payoff = f64>> Antifragile for WhatIf<T> {
+    type Stressor = T::Stressor;
+    type Payoff = f64;
+
+    fn payoff(&self, stressor: Self::Stressor) -> f64 {
+        self.patches
+            .iter()
+            .fold(self.inner.payoff(stressor), |payoff, patch| match patch {
+                Patch::Floor(floor) => payoff.max(*floor),
+                Patch::Cap(cap) => payoff.min(*cap),
+                Patch::Hedge(hedge) => payoff + hedge(stressor),
+            })
+    }
+}
+
+fn gap<T: Antifragile<Payoff = f64> + ?Sized>(
+    system: &T,
+    at: T::Stressor,
+    delta: T::Stressor,
+) -> f64 {
+    system.payoff(at + delta) + system.payoff(at - delta) - T::twin(system.payoff(at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ShortPut {
+        strike: f64,
+    }
+
+    impl Antifragile for ShortPut {
+        type Stressor = f64;
+        type Payoff = f64;
+        fn payoff(&self, x: f64) -> f64 {
+            1.0 - (self.strike - x).max(0.0)
+        }
+    }
+
+    #[test]
+    fn test_no_patches_is_identity() {
+        let scenario = WhatIf::new(ShortPut { strike: 100.0 });
+        let outcome = scenario.compare(100.0, 5.0);
+        assert!(!outcome.changed());
+        assert!(outcome.gap_change().abs() < f64::EPSILON);
+        assert!((scenario.payoff(90.0) - -9.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_downside_cap_adds_convexity() {
+        // Losses beyond 3 are insured away, which kinks the payoff at 96
+        let scenario = WhatIf::new(ShortPut { strike: 100.0 }).cap_downside(3.0);
+        let outcome = scenario.compare(96.0, 2.0);
+
+        assert_eq!(outcome.baseline, Triad::Robust);
+        assert_eq!(outcome.patched, Triad::Antifragile);
+        assert!(outcome.improved());
+        assert!(outcome.gap_change() > 0.0);
+    }
+
+    #[test]
+    fn test_upside_cap_adds_concavity() {
+        struct Linear;
+        impl Antifragile for Linear {
+            type Stressor = f64;
+            type Payoff = f64;
+            fn payoff(&self, x: f64) -> f64 {
+                x
+            }
+        }
+
+        let outcome = WhatIf::new(Linear).cap_upside(10.0).compare(10.0, 1.0);
+        assert_eq!(outcome.baseline, Triad::Robust);
+        assert_eq!(outcome.patched, Triad::Fragile);
+        assert!(!outcome.improved());
+    }
+
+    #[test]
+    fn test_patches_apply_in_order() {
+        let floor_then_hedge = WhatIf::new(ShortPut { strike: 100.0 })
+            .cap_downside(3.0)
+            .hedge(|_| -10.0);
+        let hedge_then_floor = WhatIf::new(ShortPut { strike: 100.0 })
+            .hedge(|_| -10.0)
+            .cap_downside(3.0);
+
+        assert!((floor_then_hedge.payoff(100.0) - -9.0).abs() < f64::EPSILON);
+        assert!((hedge_then_floor.payoff(100.0) - -3.0).abs() < f64::EPSILON);
+        assert_eq!(floor_then_hedge.patches().len(), 2);
+    }
+}
@@ -0,0 +1,63 @@
+//! Enforces the "no heap allocations in the core path" guarantee
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use antifragile::{Antifragile, Triad, TriadAnalysis, Verified};
+
+/// Counts allocations made by the current thread
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+// SAFETY: forwards every call unchanged to the system allocator
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        // SAFETY: the caller upholds `GlobalAlloc::alloc`'s contract
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: `ptr` was allocated by `System` through `alloc` above
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Number of allocations `f` makes on this thread
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+struct Quadratic;
+
+impl Antifragile for Quadratic {
+    type Stressor = f64;
+    type Payoff = f64;
+
+    fn payoff(&self, x: f64) -> f64 {
+        x * x
+    }
+}
+
+#[test]
+fn test_core_path_does_not_allocate() {
+    let count = allocations(|| {
+        assert_eq!(Quadratic.classify(10.0, 1.0), Triad::Antifragile);
+        assert_eq!(
+            Quadratic.classify_with_tolerance(10.0, 1.0, 1e-9),
+            Triad::Antifragile
+        );
+        let verified = Verified::check(Quadratic, 10.0, 1.0);
+        assert!(verified.is_antifragile());
+        assert_eq!(Triad::worst_of(Triad::ALL), Some(Triad::Fragile));
+    });
+    assert_eq!(count, 0);
+}
@@ -123,6 +123,92 @@ fn test_serde_triad_roundtrip() {
     }
 }
 
+#[cfg(all(feature = "schemars", feature = "std"))]
+#[test]
+fn test_json_schema_matches_serde_representation() {
+    let schema = serde_json::to_value(schemars::schema_for!(Triad)).unwrap();
+    let names: Vec<_> = schema["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|variant| variant["const"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["Fragile", "Robust", "Antifragile"]);
+
+    let schema = serde_json::to_value(schemars::schema_for!(Verified<f64, f64>)).unwrap();
+    let properties = schema["properties"].as_object().unwrap();
+    for field in ["inner", "classification", "at", "delta", "policy"] {
+        assert!(properties.contains_key(field), "missing {field}");
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_triad_with_confidence_roundtrip() {
+    let reading = antifragile::TriadWithConfidence {
+        triad: Triad::Fragile,
+        confidence: 0.97,
+        margin: -1.5,
+    };
+    let json = serde_json::to_string(&reading).unwrap();
+    let parsed: antifragile::TriadWithConfidence = serde_json::from_str(&json).unwrap();
+    assert_eq!(reading, parsed);
+}
+
+#[cfg(all(feature = "serde", feature = "std"))]
+#[test]
+fn test_serde_verified_reads_0_0_1_json() {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Linear;
+
+    impl Antifragile for Linear {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&self, x: f64) -> f64 {
+            x
+        }
+    }
+
+    // Written before the operating point, delta and timestamp were recorded
+    let json = r#"{"inner":null,"classification":"Robust"}"#;
+    let verified: antifragile::VerifiedOf<Linear> = serde_json::from_str(json).unwrap();
+    assert_eq!(verified.classification(), Triad::Robust);
+    assert!(verified.at().abs() < f64::EPSILON);
+    assert_eq!(verified.timestamp(), std::time::SystemTime::UNIX_EPOCH);
+}
+
+#[cfg(all(feature = "serde", feature = "std"))]
+#[test]
+fn test_serde_verified_map_roundtrip() {
+    use antifragile::regime::VerifiedMap;
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Quadratic {
+        scale: f64,
+    }
+
+    impl Antifragile for Quadratic {
+        type Stressor = f64;
+        type Payoff = f64;
+
+        fn payoff(&self, x: f64) -> f64 {
+            self.scale * x * x
+        }
+    }
+
+    let mut regimes = VerifiedMap::new(Quadratic { scale: 2.0 });
+    regimes.verify("peak", 10.0, 1.0);
+    regimes.mark_changed();
+    regimes.verify("off-peak", 1.0, 0.5);
+
+    let json = serde_json::to_string(&regimes).unwrap();
+    let parsed: VerifiedMap<Quadratic> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, regimes);
+    assert_eq!(parsed.is_stale("peak"), Some(true));
+    assert_eq!(parsed.classification("off-peak"), Some(Triad::Antifragile));
+}
+
 #[cfg(feature = "serde")]
 #[test]
 fn test_serde_verified_roundtrip() {
@@ -144,8 +230,10 @@ fn test_serde_verified_roundtrip() {
     let verified = Verified::check(system, 10.0, 1.0);
 
     let json = serde_json::to_string(&verified).unwrap();
-    let parsed: Verified<SerializableSystem> = serde_json::from_str(&json).unwrap();
+    let parsed: antifragile::VerifiedOf<SerializableSystem> = serde_json::from_str(&json).unwrap();
 
     assert_eq!(parsed.classification(), Triad::Antifragile);
     assert!((parsed.inner().multiplier - 2.0).abs() < f64::EPSILON);
+    assert!((parsed.at() - 10.0).abs() < f64::EPSILON);
+    assert!((parsed.delta() - 1.0).abs() < f64::EPSILON);
 }
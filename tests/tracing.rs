@@ -0,0 +1,125 @@
+//! Events emitted under the `tracing` feature
+
+#![cfg(all(feature = "tracing", feature = "std"))]
+
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+use antifragile::monte_carlo::{MonteCarloConfig, monte_carlo};
+use antifragile::stream::OnlineClassifier;
+use antifragile::{Antifragile, Triad, TriadAnalysis, Verified};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Collects every event as `message field=value ...`
+#[derive(Clone, Default)]
+struct Recorder {
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+struct Line(String);
+
+impl Visit for Line {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.0.insert_str(0, &format!("{value:?}"));
+        } else {
+            self.0.push_str(&format!(" {}={value:?}", field.name()));
+        }
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut line = Line(String::new());
+        event.record(&mut line);
+        self.events.lock().unwrap().push(line.0);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+struct Quadratic;
+
+impl Antifragile for Quadratic {
+    type Stressor = f64;
+    type Payoff = f64;
+
+    fn payoff(&self, x: f64) -> f64 {
+        x * x
+    }
+}
+
+#[test]
+fn test_decisions_are_traced() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        assert_eq!(Quadratic.classify(10.0, 1.0), Triad::Antifragile);
+        let _ = Verified::check(Quadratic, 10.0, 1.0);
+        let _ = monte_carlo(
+            &Quadratic,
+            |noise| 1.0 + noise.uniform(),
+            &MonteCarloConfig::default(),
+        );
+        let _ = OnlineClassifier::new().triad();
+    });
+
+    let events = recorder.events.lock().unwrap();
+    let quadratic = "system=\"tracing::Quadratic\"";
+    assert_eq!(
+        events[0],
+        format!("classified {quadratic} triad=Antifragile")
+    );
+    assert_eq!(
+        events[2],
+        format!("verified {quadratic} classification=Antifragile")
+    );
+    assert!(events[3].starts_with(&format!("monte carlo classification {quadratic} samples=")));
+    assert!(events[3].ends_with("classification=Antifragile"));
+    assert_eq!(
+        events[4],
+        "online classification significance=2.0 triad=Robust"
+    );
+}
+
+#[test]
+fn test_stressors_need_not_be_debug() {
+    /// A stressor without `Debug`: enabling tracing must not require one
+    #[derive(Clone, Copy)]
+    struct Load(i64);
+
+    impl std::ops::Add for Load {
+        type Output = Self;
+
+        fn add(self, other: Self) -> Self {
+            Self(self.0 + other.0)
+        }
+    }
+
+    impl std::ops::Sub for Load {
+        type Output = Self;
+
+        fn sub(self, other: Self) -> Self {
+            Self(self.0 - other.0)
+        }
+    }
+
+    let system = antifragile::antifragile!(|load: Load| load.0 * load.0);
+    assert_eq!(system.classify(Load(5), Load(1)), Triad::Antifragile);
+    assert!(Verified::check(system, Load(5), Load(1)).is_antifragile());
+}